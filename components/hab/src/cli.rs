@@ -22,6 +22,7 @@ use protocol;
 use regex::Regex;
 use url::Url;
 
+use command;
 use feat;
 
 pub fn get() -> App<'static, 'static> {
@@ -70,7 +71,8 @@ pub fn get() -> App<'static, 'static> {
                 (@arg PKG_IDENT: +required +takes_value
                     "A package identifier (ex: core/redis, core/busybox-static/1.42.2)")
                 (@arg REMOTE_SUP: --("remote-sup") -r +takes_value
-                    "Address to a remote Supervisor's Control Gateway [default: 127.0.0.1:9632]")
+                    "Address to a remote Supervisor's Control Gateway, or unix:<path> of its Unix \
+                        domain socket [default: 127.0.0.1:9632]")
             )
         )
         (@subcommand file =>
@@ -87,7 +89,8 @@ pub fn get() -> App<'static, 'static> {
                 (@arg FILE: +required {file_exists} "Path to local file on disk")
                 (@arg USER: -u --user +takes_value "Name of the user key")
                 (@arg REMOTE_SUP: --("remote-sup") -r +takes_value
-                    "Address to a remote Supervisor's Control Gateway [default: 127.0.0.1:9632]")
+                    "Address to a remote Supervisor's Control Gateway, or unix:<path> of its Unix \
+                        domain socket [default: 127.0.0.1:9632]")
             )
         )
         (@subcommand bldr =>
@@ -552,7 +555,12 @@ pub fn get() -> App<'static, 'static> {
                     (@arg ORG: "The service organization")
                 )
             )
+            (subcommand: sub_svc_apply().aliases(&["ap", "app", "appl"]))
+            (subcommand: sub_svc_hooks().aliases(&["ho", "hoo", "hook"]))
             (subcommand: sub_svc_load().aliases(&["l", "lo", "loa"]))
+            (subcommand: sub_svc_logs().aliases(&["log"]))
+            (subcommand: sub_svc_restart().aliases(&["rest", "resta", "restar"]))
+            (subcommand: sub_svc_signal().aliases(&["sig", "sign", "signa"]))
             (subcommand: sub_svc_start().aliases(&["star"]))
             (subcommand: sub_svc_status().aliases(&["stat", "statu"]))
             (subcommand: sub_svc_stop().aliases(&["sto"]))
@@ -563,7 +571,8 @@ pub fn get() -> App<'static, 'static> {
                 (@arg PKG_IDENT: +required +takes_value
                     "A Habitat package identifier (ex: core/redis)")
                 (@arg REMOTE_SUP: --("remote-sup") -r +takes_value
-                    "Address to a remote Supervisor's Control Gateway [default: 127.0.0.1:9632]")
+                    "Address to a remote Supervisor's Control Gateway, or unix:<path> of its Unix \
+                        domain socket [default: 127.0.0.1:9632]")
             )
         )
         (@subcommand studio =>
@@ -574,13 +583,24 @@ pub fn get() -> App<'static, 'static> {
             (about: "Commands relating to the Habitat Supervisor")
             (aliases: &["su"])
             (@setting ArgRequiredElseHelp)
+            (@subcommand audit =>
+                (about: "Display the audit log of requests made to a Supervisor's Control Gateway \
+                    as JSON lines")
+                (aliases: &["a", "au", "aud", "audi"])
+                (@arg TAIL: --tail -n +takes_value {valid_numeric::<u64>}
+                    "Number of most recent entries to display [default: all]")
+                (@arg REMOTE_SUP: --("remote-sup") -r +takes_value
+                    "Address to a remote Supervisor's Control Gateway, or unix:<path> of its Unix \
+                        domain socket [default: 127.0.0.1:9632]")
+            )
             (@subcommand depart =>
                 (about: "Depart a Supervisor from the gossip ring; kicking and banning the target \
                     from joining again with the same member-id")
                 (aliases: &["d", "de", "dep", "depa", "depart"])
                 (@arg MEMBER_ID: +required +takes_value "The member-id of the Supervisor to depart")
                 (@arg REMOTE_SUP: --("remote-sup") -r +takes_value
-                    "Address to a remote Supervisor's Control Gateway [default: 127.0.0.1:9632]")
+                    "Address to a remote Supervisor's Control Gateway, or unix:<path> of its Unix \
+                        domain socket [default: 127.0.0.1:9632]")
             )
             (@subcommand secret =>
                 (about: "Commands relating to a Habitat Supervisor's Contorl Gateway secret")
@@ -589,6 +609,29 @@ pub fn get() -> App<'static, 'static> {
                     (about: "Generate a secret key to use as a Supervisor's Control Gateway secret")
                     (aliases: &["g", "gen"])
                 )
+                (@subcommand add =>
+                    (about: "Create a named token granting a role on the local Supervisor's \
+                        Control Gateway and display its secret")
+                    (@arg NAME: +required +takes_value "Name of the token")
+                    (@arg ROLE: --role +takes_value {valid_ctl_role}
+                        "Role granted by the token: read-only, operator, or admin \
+                        [default: read-only]")
+                    (@arg OVERRIDE_NAME: --("override-name") +takes_value
+                        "The name of the Supervisor if launching more than one [default: default]")
+                )
+                (@subcommand list =>
+                    (about: "List the named tokens of the local Supervisor's Control Gateway")
+                    (aliases: &["l", "ls"])
+                    (@arg OVERRIDE_NAME: --("override-name") +takes_value
+                        "The name of the Supervisor if launching more than one [default: default]")
+                )
+                (@subcommand remove =>
+                    (about: "Remove a named token from the local Supervisor's Control Gateway")
+                    (aliases: &["rm"])
+                    (@arg NAME: +required +takes_value "Name of the token")
+                    (@arg OVERRIDE_NAME: --("override-name") +takes_value
+                        "The name of the Supervisor if launching more than one [default: default]")
+                )
             )
             (subcommand: sub_svc_status().aliases(&["stat", "statu"]))
         )
@@ -756,7 +799,62 @@ fn sub_config_apply() -> App<'static, 'static> {
             "Path to local file on disk (ex: /tmp/config.toml, default: <stdin>)")
         (@arg USER: -u --user +takes_value "Name of a user key to use for encryption")
         (@arg REMOTE_SUP: --("remote-sup") -r +takes_value
-            "Address to a remote Supervisor's Control Gateway [default: 127.0.0.1:9632]")
+            "Address to a remote Supervisor's Control Gateway, or unix:<path> of its Unix \
+                domain socket [default: 127.0.0.1:9632]")
+    )
+}
+
+fn sub_svc_hooks() -> App<'static, 'static> {
+    clap_app!(@subcommand hooks =>
+        (about: "Display the recent runs of the hooks of a loaded Habitat service.")
+        (@arg PKG_IDENT: +required +takes_value
+            "A Habitat package identifier (ex: core/redis)")
+        (@arg HOOK: --hook +takes_value
+            "Only display the runs of the named hook (ex: reconfigure)")
+        (@arg REMOTE_SUP: --("remote-sup") -r +takes_value
+            "Address to a remote Supervisor's Control Gateway, or unix:<path> of its Unix \
+                domain socket [default: 127.0.0.1:9632]")
+    )
+}
+
+fn sub_svc_logs() -> App<'static, 'static> {
+    clap_app!(@subcommand logs =>
+        (about: "Display the output of a loaded Habitat service.")
+        (@arg PKG_IDENT: +required +takes_value
+            "A Habitat package identifier (ex: core/redis)")
+        (@arg TAIL: --tail -n +takes_value {valid_numeric::<u64>}
+            "Only display the last N lines of output")
+        (@arg FOLLOW: --follow -f "Continue displaying output as the service writes it")
+        (@arg SINCE: --since +takes_value {valid_duration}
+            "Only display output written within the given duration (ex: 30s, 10m, 2h)")
+        (@arg REMOTE_SUP: --("remote-sup") -r +takes_value
+            "Address to a remote Supervisor's Control Gateway, or unix:<path> of its Unix \
+                domain socket [default: 127.0.0.1:9632]")
+    )
+}
+
+fn sub_svc_restart() -> App<'static, 'static> {
+    clap_app!(@subcommand restart =>
+        (about: "Restart a running Habitat service without changing whether it is started or \
+            stopped.")
+        (@arg PKG_IDENT: +required +takes_value
+            "A Habitat package identifier (ex: core/redis)")
+        (@arg REMOTE_SUP: --("remote-sup") -r +takes_value
+            "Address to a remote Supervisor's Control Gateway, or unix:<path> of its Unix \
+                domain socket [default: 127.0.0.1:9632]")
+    )
+}
+
+fn sub_svc_signal() -> App<'static, 'static> {
+    clap_app!(@subcommand signal =>
+        (about: "Send a signal to a running Habitat service.")
+        (@arg PKG_IDENT: +required +takes_value
+            "A Habitat package identifier (ex: core/redis)")
+        (@arg SIGNAL: +required +takes_value
+            "The signal to send (ex: HUP, USR1, USR2, TERM)")
+        (@arg REMOTE_SUP: --("remote-sup") -r +takes_value
+            "Address to a remote Supervisor's Control Gateway, or unix:<path> of its Unix \
+                domain socket [default: 127.0.0.1:9632]")
     )
 }
 
@@ -766,7 +864,8 @@ fn sub_svc_start() -> App<'static, 'static> {
         (@arg PKG_IDENT: +required +takes_value
             "A Habitat package identifier (ex: core/redis)")
         (@arg REMOTE_SUP: --("remote-sup") -r +takes_value
-            "Address to a remote Supervisor's Control Gateway [default: 127.0.0.1:9632]")
+            "Address to a remote Supervisor's Control Gateway, or unix:<path> of its Unix \
+                domain socket [default: 127.0.0.1:9632]")
     )
 }
 
@@ -776,8 +875,11 @@ fn sub_svc_status() -> App<'static, 'static> {
     clap_app!(@subcommand status =>
         (about: "Query the status of Habitat services.")
         (@arg PKG_IDENT: +takes_value "A Habitat package identifier (ex: core/redis)")
+        (@arg FORMAT: --format -f +takes_value {valid_status_format}
+            "Output format, one of json, yaml or table [default: table]")
         (@arg REMOTE_SUP: --("remote-sup") -r +takes_value
-        "Address to a remote Supervisor's Control Gateway [default: 127.0.0.1:9632]")
+        "Address to a remote Supervisor's Control Gateway, or unix:<path> of its Unix \
+            domain socket [default: 127.0.0.1:9632]")
     )
 }
 
@@ -787,7 +889,22 @@ fn sub_svc_stop() -> App<'static, 'static> {
         (@arg PKG_IDENT: +required +takes_value
             "A Habitat package identifier (ex: core/redis)")
         (@arg REMOTE_SUP: --("remote-sup") -r +takes_value
-            "Address to a remote Supervisor's Control Gateway [default: 127.0.0.1:9632]")
+            "Address to a remote Supervisor's Control Gateway, or unix:<path> of its Unix \
+                domain socket [default: 127.0.0.1:9632]")
+    )
+}
+
+fn sub_svc_apply() -> App<'static, 'static> {
+    clap_app!(@subcommand apply =>
+        (about: "Load, update, and optionally unload services so that the services loaded by a \
+            Supervisor match those listed in a TOML manifest")
+        (@arg FILE: --file -f +required +takes_value {file_exists}
+            "Path to a TOML manifest listing each service as a [[service]] table")
+        (@arg DRY_RUN: --("dry-run") "Display the changes which would be made without making them")
+        (@arg UNLOAD: --unload "Unload loaded services which aren't listed in the manifest")
+        (@arg REMOTE_SUP: --("remote-sup") -r +takes_value
+            "Address to a remote Supervisor's Control Gateway, or unix:<path> of its Unix \
+                domain socket [default: 127.0.0.1:9632]")
     )
 }
 
@@ -821,8 +938,40 @@ fn sub_svc_load() -> App<'static, 'static> {
               startup until all binds are present. [default: strict] [values: relaxed, strict]")
         (@arg FORCE: --force -f "Load or reload an already loaded service. If the service \
             was previously loaded and running this operation will also restart the service")
+        (@arg HEALTH_CHECK_INTERVAL: --("health-check-interval") +takes_value {valid_duration}
+            "How often to run the health check hook, such as 30s or 5m [default: 30s]")
+        (@arg HEALTH_CHECK_TIMEOUT: --("health-check-timeout") +takes_value {valid_duration}
+            "Kill the health check hook and report critical if it runs for longer than this; \
+            [default: no timeout]")
+        (@arg HEALTH_CHECK_SUCCESS_THRESHOLD: --("health-check-success-threshold") +takes_value
+            {valid_numeric::<u32>}
+            "Consecutive passing checks needed before a failing service is reported healthy \
+            [default: 1]")
+        (@arg HEALTH_CHECK_FAILURE_THRESHOLD: --("health-check-failure-threshold") +takes_value
+            {valid_numeric::<u32>}
+            "Consecutive failing checks needed before a healthy service is reported failing \
+            [default: 1]")
+        (@arg SHUTDOWN_SIGNAL: --("shutdown-signal") +takes_value
+            "The signal asking the service to stop, such as INT or QUIT [default: TERM]")
+        (@arg SHUTDOWN_TIMEOUT: --("shutdown-timeout") +takes_value {valid_duration}
+            "How long to wait for the service to stop before killing it, such as 30s or 5m \
+            [default: 8s]")
+        (@arg RESTART_POLICY: --("restart-policy") +takes_value {valid_restart_policy}
+            "When to restart the service after it exits; [default: always] \
+            [values: always, on-failure, never]")
+        (@arg RESTART_BACKOFF_BASE: --("restart-backoff-base") +takes_value {valid_duration}
+            "How long to wait before restarting the service the first time it exits, doubled \
+            with each further exit in the restart window [default: 1s]")
+        (@arg RESTART_BACKOFF_MAX: --("restart-backoff-max") +takes_value {valid_duration}
+            "The longest to wait before restarting the service [default: 60s]")
+        (@arg MAX_RESTARTS: --("max-restarts") +takes_value {valid_numeric::<u32>}
+            "Restarts allowed in the restart window before the service is left down as \
+            crash-looping, or 0 for no limit [default: 10]")
+        (@arg RESTART_WINDOW: --("restart-window") +takes_value {valid_duration}
+            "The window over which restarts are counted, such as 10m [default: 10m]")
         (@arg REMOTE_SUP: --("remote-sup") -r +takes_value
-            "Address to a remote Supervisor's Control Gateway [default: 127.0.0.1:9632]")
+            "Address to a remote Supervisor's Control Gateway, or unix:<path> of its Unix \
+                domain socket [default: 127.0.0.1:9632]")
     )
 }
 
@@ -856,9 +1005,39 @@ fn sub_svc_load() -> App<'static, 'static> {
               startup until all binds are present. [default: strict] [values: relaxed, strict]")
         (@arg FORCE: --force -f "Load or reload an already loaded service. If the service \
             was previously loaded and running this operation will also restart the service")
+        (@arg HEALTH_CHECK_INTERVAL: --("health-check-interval") +takes_value {valid_duration}
+            "How often to run the health check hook, such as 30s or 5m [default: 30s]")
+        (@arg HEALTH_CHECK_TIMEOUT: --("health-check-timeout") +takes_value {valid_duration}
+            "Kill the health check hook and report critical if it runs for longer than this; \
+            [default: no timeout]")
+        (@arg HEALTH_CHECK_SUCCESS_THRESHOLD: --("health-check-success-threshold") +takes_value
+            {valid_numeric::<u32>}
+            "Consecutive passing checks needed before a failing service is reported healthy \
+            [default: 1]")
+        (@arg HEALTH_CHECK_FAILURE_THRESHOLD: --("health-check-failure-threshold") +takes_value
+            {valid_numeric::<u32>}
+            "Consecutive failing checks needed before a healthy service is reported failing \
+            [default: 1]")
+        (@arg SHUTDOWN_TIMEOUT: --("shutdown-timeout") +takes_value {valid_duration}
+            "How long to wait for the service to stop before killing it, such as 30s or 5m \
+            [default: 8s]")
+        (@arg RESTART_POLICY: --("restart-policy") +takes_value {valid_restart_policy}
+            "When to restart the service after it exits; [default: always] \
+            [values: always, on-failure, never]")
+        (@arg RESTART_BACKOFF_BASE: --("restart-backoff-base") +takes_value {valid_duration}
+            "How long to wait before restarting the service the first time it exits, doubled \
+            with each further exit in the restart window [default: 1s]")
+        (@arg RESTART_BACKOFF_MAX: --("restart-backoff-max") +takes_value {valid_duration}
+            "The longest to wait before restarting the service [default: 60s]")
+        (@arg MAX_RESTARTS: --("max-restarts") +takes_value {valid_numeric::<u32>}
+            "Restarts allowed in the restart window before the service is left down as \
+            crash-looping, or 0 for no limit [default: 10]")
+        (@arg RESTART_WINDOW: --("restart-window") +takes_value {valid_duration}
+            "The window over which restarts are counted, such as 10m [default: 10m]")
         (@arg PASSWORD: --password +takes_value "Password of the service user")
         (@arg REMOTE_SUP: --("remote-sup") -r +takes_value
-            "Address to a remote Supervisor's Control Gateway [default: 127.0.0.1:9632]")
+            "Address to a remote Supervisor's Control Gateway, or unix:<path> of its Unix \
+                domain socket [default: 127.0.0.1:9632]")
     )
 }
 
@@ -918,6 +1097,34 @@ fn valid_numeric<T: FromStr>(val: String) -> result::Result<(), String> {
     }
 }
 
+fn valid_duration(val: String) -> result::Result<(), String> {
+    match command::service::parse_duration(&val) {
+        Some(_) => Ok(()),
+        None => Err(format!("'{}' is not a valid duration", &val)),
+    }
+}
+
+fn valid_ctl_role(val: String) -> result::Result<(), String> {
+    match protocol::token::CtlRole::from_str(&val) {
+        Ok(_) => Ok(()),
+        Err(_) => Err(format!("Role: '{}' is not valid", &val)),
+    }
+}
+
+fn valid_status_format(val: String) -> result::Result<(), String> {
+    match command::service::status::StatusFormat::from_str(&val) {
+        Ok(_) => Ok(()),
+        Err(_) => Err(format!("Status format: '{}' is not valid", &val)),
+    }
+}
+
+fn valid_restart_policy(val: String) -> result::Result<(), String> {
+    match protocol::types::RestartPolicy::from_str(&val) {
+        Ok(_) => Ok(()),
+        Err(_) => Err(format!("Restart policy: '{}' is not valid", &val)),
+    }
+}
+
 fn valid_topology(val: String) -> result::Result<(), String> {
     match protocol::types::Topology::from_str(&val) {
         Ok(_) => Ok(()),
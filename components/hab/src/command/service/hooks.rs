@@ -0,0 +1,72 @@
+// Copyright (c) 2018 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Output of the recent runs of a service's hooks, used by `hab svc hooks`.
+
+use std::io::{self, Write};
+
+use chrono::{TimeZone, Utc};
+use protocol::ctl::SvcHookRun;
+
+/// Write a description of a run of a hook, followed by the end of its output, to `out`.
+pub fn print_run<W>(mut out: W, run: &SvcHookRun) -> io::Result<()>
+where
+    W: Write,
+{
+    write!(
+        out,
+        "{} started at {}, ran for {}ms",
+        run.hook,
+        Utc.timestamp(run.started_at, 0).to_rfc3339(),
+        run.duration_ms
+    )?;
+    match run.exit_code {
+        Some(code) => writeln!(out, ", exited with {}", code)?,
+        None if run.timed_out.unwrap_or(false) => writeln!(out, ", killed for timing out")?,
+        None => writeln!(out, ", exited without a code")?,
+    }
+    for &(name, output) in &[("stdout", &run.stdout), ("stderr", &run.stderr)] {
+        let output = match *output {
+            Some(ref output) if !output.is_empty() => output,
+            _ => continue,
+        };
+        writeln!(out, "  {}:", name)?;
+        for line in output.lines() {
+            writeln!(out, "    {}", line)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn print_timed_out_run() {
+        let mut run = SvcHookRun::default();
+        run.hook = "reconfigure".to_string();
+        run.started_at = 1519905600;
+        run.duration_ms = 60000;
+        run.timed_out = Some(true);
+        run.stderr = Some("waiting for lock\nstill waiting\n".to_string());
+        let mut out = vec![];
+        print_run(&mut out, &run).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "reconfigure started at 2018-03-01T12:00:00+00:00, ran for 60000ms, killed for \
+             timing out\n  stderr:\n    waiting for lock\n    still waiting\n"
+        );
+    }
+}
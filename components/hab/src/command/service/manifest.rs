@@ -0,0 +1,180 @@
+// Copyright (c) 2018 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A TOML manifest listing the services a Supervisor should run, used by `hab svc apply`.
+//!
+//! ```toml
+//! [[service]]
+//! ident = "core/redis"
+//! group = "cache"
+//! topology = "leader"
+//! strategy = "rolling"
+//! channel = "stable"
+//!
+//! [[service]]
+//! ident = "acme/web"
+//! binds = ["cache:redis.cache"]
+//! binding_mode = "relaxed"
+//! ```
+
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use std::str::FromStr;
+
+use hcore::package::PackageIdent;
+use protocol;
+use protocol::types::{BindingMode, ServiceBind, Topology, UpdateStrategy};
+use toml;
+
+use error::{Error, Result};
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ServiceManifest {
+    #[serde(default)]
+    pub service: Vec<ManifestService>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ManifestService {
+    pub ident: String,
+    pub group: Option<String>,
+    pub topology: Option<String>,
+    pub strategy: Option<String>,
+    pub binds: Option<Vec<String>>,
+    pub channel: Option<String>,
+    pub binding_mode: Option<String>,
+}
+
+impl ServiceManifest {
+    pub fn from_file<T>(path: T) -> Result<Self>
+    where
+        T: AsRef<Path>,
+    {
+        let mut content = String::new();
+        File::open(path)?.read_to_string(&mut content)?;
+        Self::from_str(&content)
+    }
+
+    /// Build a load request for each listed service.
+    pub fn svc_loads(&self) -> Result<Vec<protocol::ctl::SvcLoad>> {
+        let mut names = HashSet::new();
+        let mut loads = Vec::with_capacity(self.service.len());
+        for service in self.service.iter() {
+            let msg = service.svc_load()?;
+            let name = msg.ident.as_ref().map(|i| i.name.clone()).unwrap_or_default();
+            if !names.insert(name.clone()) {
+                return Err(Error::ServiceManifest(format!(
+                    "the {} service is listed more than once",
+                    name
+                )));
+            }
+            loads.push(msg);
+        }
+        Ok(loads)
+    }
+}
+
+impl FromStr for ServiceManifest {
+    type Err = Error;
+
+    fn from_str(toml: &str) -> Result<Self> {
+        toml::from_str(toml).map_err(|e| Error::ServiceManifest(e.to_string()))
+    }
+}
+
+impl ManifestService {
+    fn svc_load(&self) -> Result<protocol::ctl::SvcLoad> {
+        let mut msg = protocol::ctl::SvcLoad::default();
+        let ident = PackageIdent::from_str(&self.ident)?;
+        msg.ident = Some(ident.into());
+        msg.group = self.group.clone();
+        msg.bldr_channel = self.channel.clone();
+        if let Some(ref topology) = self.topology {
+            msg.topology = Some(Topology::from_str(topology)? as i32);
+        }
+        if let Some(ref strategy) = self.strategy {
+            msg.update_strategy = Some(UpdateStrategy::from_str(strategy)? as i32);
+        }
+        if let Some(ref binding_mode) = self.binding_mode {
+            msg.binding_mode = Some(BindingMode::from_str(binding_mode)? as i32);
+        }
+        if let Some(ref binds) = self.binds {
+            let mut list = protocol::ctl::ServiceBindList::default();
+            for bind in binds {
+                list.binds.push(ServiceBind::from_str(bind)?);
+            }
+            msg.binds = Some(list);
+        }
+        Ok(msg)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn manifest_svc_loads() {
+        let manifest = ServiceManifest::from_str(
+            r#"
+            [[service]]
+            ident = "core/redis"
+            group = "cache"
+            topology = "leader"
+            strategy = "rolling"
+
+            [[service]]
+            ident = "acme/web/1.0.0"
+            binds = ["cache:redis.cache"]
+            binding_mode = "relaxed"
+            channel = "unstable"
+            "#,
+        ).unwrap();
+        let loads = manifest.svc_loads().unwrap();
+        assert_eq!(loads.len(), 2);
+
+        assert_eq!(loads[0].ident.as_ref().unwrap().name, "redis");
+        assert_eq!(loads[0].group, Some("cache".to_string()));
+        assert_eq!(loads[0].topology, Some(Topology::Leader as i32));
+        assert_eq!(loads[0].update_strategy, Some(UpdateStrategy::Rolling as i32));
+        assert!(loads[0].binds.is_none());
+
+        assert_eq!(loads[1].ident.as_ref().unwrap().version, Some("1.0.0".to_string()));
+        assert_eq!(loads[1].binds.as_ref().unwrap().binds.len(), 1);
+        assert_eq!(loads[1].binding_mode, Some(BindingMode::Relaxed as i32));
+        assert_eq!(loads[1].bldr_channel, Some("unstable".to_string()));
+    }
+
+    #[test]
+    fn manifest_invalid() {
+        assert!(ServiceManifest::from_str("[[service]]\ngroup = \"cache\"").is_err());
+        assert!(
+            ServiceManifest::from_str("[[service]]\nident = \"core/redis\"\nbogus = 1").is_err()
+        );
+
+        let manifest =
+            ServiceManifest::from_str("[[service]]\nident = \"core/redis\"\ntopology = \"mesh\"")
+                .unwrap();
+        assert!(manifest.svc_loads().is_err());
+
+        let manifest = ServiceManifest::from_str(
+            "[[service]]\nident = \"core/redis\"\n[[service]]\nident = \"other/redis\"",
+        ).unwrap();
+        assert!(manifest.svc_loads().is_err());
+    }
+}
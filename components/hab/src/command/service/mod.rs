@@ -12,4 +12,42 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod hooks;
 pub mod key;
+pub mod manifest;
+pub mod status;
+
+/// Parse a duration given either as a number of seconds or as a number followed by one of the
+/// units `s`, `m`, `h` or `d` (ex: `90`, `10m`, `2h`). Returns the duration in seconds.
+pub fn parse_duration(val: &str) -> Option<u64> {
+    let (num, multiplier) = match val.chars().last() {
+        Some('s') => (&val[..val.len() - 1], 1),
+        Some('m') => (&val[..val.len() - 1], 60),
+        Some('h') => (&val[..val.len() - 1], 60 * 60),
+        Some('d') => (&val[..val.len() - 1], 60 * 60 * 24),
+        _ => (val, 1),
+    };
+    num.parse::<u64>().ok().and_then(|n| n.checked_mul(multiplier))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_duration_units() {
+        assert_eq!(parse_duration("90"), Some(90));
+        assert_eq!(parse_duration("30s"), Some(30));
+        assert_eq!(parse_duration("10m"), Some(600));
+        assert_eq!(parse_duration("2h"), Some(7200));
+        assert_eq!(parse_duration("1d"), Some(86400));
+    }
+
+    #[test]
+    fn parse_duration_invalid() {
+        assert_eq!(parse_duration(""), None);
+        assert_eq!(parse_duration("m"), None);
+        assert_eq!(parse_duration("10x"), None);
+        assert_eq!(parse_duration("-5m"), None);
+    }
+}
@@ -0,0 +1,284 @@
+// Copyright (c) 2018 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Output of the service statuses reported by a Supervisor, used by `hab svc status`.
+//!
+//! Statuses are either printed as a table for people to read, or as a JSON or YAML list for
+//! tooling to parse. The field names of the structured formats match those of the Supervisor's
+//! HTTP gateway.
+
+use std::io::{self, Write};
+use std::str::FromStr;
+
+use protocol::types::{service_status, ProcessState, ProcessStats, ServiceStatus};
+use serde_json;
+use serde_yaml;
+use tabwriter::TabWriter;
+
+use error::{Error, Result};
+
+const TABLE_HEADER: &'static [&'static str] = &[
+    "package",
+    "type",
+    "desired",
+    "state",
+    "uptime (s)",
+    "pid",
+    "group",
+];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StatusFormat {
+    Json,
+    Yaml,
+    Table,
+}
+
+impl FromStr for StatusFormat {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value {
+            "json" => Ok(StatusFormat::Json),
+            "yaml" => Ok(StatusFormat::Yaml),
+            "table" => Ok(StatusFormat::Table),
+            _ => Err(Error::ArgumentError("Status format must be json, yaml or table")),
+        }
+    }
+}
+
+/// A service's status as written by the structured formats. Fields which aren't reported by the
+/// Supervisor, such as those unknown to older releases, are written as null.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct StatusOutput {
+    pub ident: String,
+    pub service_group: String,
+    pub composite: Option<String>,
+    pub desired_state: Option<String>,
+    pub state: String,
+    pub pid: Option<u32>,
+    pub elapsed: Option<i64>,
+    pub restart_count: Option<u32>,
+    pub last_exit_code: Option<i32>,
+    pub crash_looping: Option<bool>,
+    pub stats: Option<StatsOutput>,
+    pub health_check: Option<String>,
+    pub update_state: Option<String>,
+    pub election_role: Option<String>,
+    pub unsatisfied_binds: Vec<String>,
+}
+
+/// Resources used by a service's processes, as written by the structured formats.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct StatsOutput {
+    pub cpu_usec: Option<u64>,
+    pub rss_bytes: Option<u64>,
+    pub fds: Option<u64>,
+    pub threads: Option<u64>,
+    pub processes: Option<u64>,
+}
+
+impl From<ProcessStats> for StatsOutput {
+    fn from(stats: ProcessStats) -> Self {
+        StatsOutput {
+            cpu_usec: stats.cpu_usec,
+            rss_bytes: stats.rss_bytes,
+            fds: stats.fds,
+            threads: stats.threads,
+            processes: stats.processes,
+        }
+    }
+}
+
+impl From<ServiceStatus> for StatusOutput {
+    fn from(status: ServiceStatus) -> Self {
+        let process = status.process.unwrap_or_default();
+        StatusOutput {
+            ident: status.ident.to_string(),
+            service_group: status.service_group.to_string(),
+            composite: status.composite,
+            desired_state: status
+                .desired_state
+                .and_then(service_status::DesiredState::from_i32)
+                .map(|s| s.to_string()),
+            state: ProcessState::from_i32(process.state)
+                .unwrap_or_default()
+                .to_string(),
+            pid: process.pid,
+            elapsed: process.elapsed,
+            restart_count: process.restart_count,
+            last_exit_code: process.last_exit_code,
+            crash_looping: process.crash_looping,
+            stats: process.stats.map(StatsOutput::from),
+            health_check: status
+                .health_check
+                .and_then(service_status::HealthCheck::from_i32)
+                .map(|s| s.to_string()),
+            update_state: status
+                .update_state
+                .and_then(service_status::UpdateState::from_i32)
+                .map(|s| s.to_string()),
+            election_role: status
+                .election_role
+                .and_then(service_status::ElectionRole::from_i32)
+                .map(|s| s.to_string()),
+            unsatisfied_binds: status.unsatisfied_binds,
+        }
+    }
+}
+
+/// Write the given statuses to `out` in the requested format.
+pub fn print<W>(out: W, format: StatusFormat, statuses: Vec<ServiceStatus>) -> Result<()>
+where
+    W: Write,
+{
+    let statuses: Vec<StatusOutput> = statuses.into_iter().map(StatusOutput::from).collect();
+    match format {
+        StatusFormat::Json => print_json(out, &statuses),
+        StatusFormat::Yaml => print_yaml(out, &statuses),
+        StatusFormat::Table => print_table(out, &statuses),
+    }
+}
+
+fn print_json<W>(mut out: W, statuses: &[StatusOutput]) -> Result<()>
+where
+    W: Write,
+{
+    serde_json::to_writer_pretty(&mut out, statuses)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    out.write_all(b"\n")?;
+    Ok(())
+}
+
+fn print_yaml<W>(mut out: W, statuses: &[StatusOutput]) -> Result<()>
+where
+    W: Write,
+{
+    serde_yaml::to_writer(&mut out, statuses)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    out.write_all(b"\n")?;
+    Ok(())
+}
+
+fn print_table<W>(out: W, statuses: &[StatusOutput]) -> Result<()>
+where
+    W: Write,
+{
+    let mut out = TabWriter::new(out);
+    if statuses.is_empty() {
+        writeln!(out, "No services loaded.")?;
+        return Ok(out.flush()?);
+    }
+    writeln!(out, "{}", TABLE_HEADER.join("\t"))?;
+    for status in statuses {
+        // A crash-looping process is down, and stays down until the service is restarted.
+        let state = if status.crash_looping == Some(true) {
+            "crash-looping"
+        } else {
+            status.state.as_str()
+        };
+        writeln!(
+            out,
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            status.ident,
+            status.composite.as_ref().map_or("standalone", |c| c.as_str()),
+            status.desired_state.as_ref().map_or("<unknown>", |s| s.as_str()),
+            state,
+            status.elapsed.map_or("<none>".to_string(), |e| e.to_string()),
+            status.pid.map_or("<none>".to_string(), |p| p.to_string()),
+            status.service_group,
+        )?;
+    }
+    Ok(out.flush()?)
+}
+
+#[cfg(test)]
+mod test {
+    use protocol::types::{PackageIdent, ProcessStatus, ServiceGroup};
+
+    use super::*;
+
+    fn status() -> ServiceStatus {
+        let mut ident = PackageIdent::default();
+        ident.origin = "core".to_string();
+        ident.name = "redis".to_string();
+        let mut service_group = ServiceGroup::default();
+        service_group.service = "redis".to_string();
+        service_group.group = "default".to_string();
+        let mut process = ProcessStatus::default();
+        process.state = ProcessState::Up as i32;
+        process.pid = Some(42);
+        process.restart_count = Some(3);
+        let mut stats = ProcessStats::default();
+        stats.rss_bytes = Some(4096);
+        process.stats = Some(stats);
+        let mut status = ServiceStatus::default();
+        status.ident = ident;
+        status.service_group = service_group;
+        status.process = Some(process);
+        status.health_check = Some(service_status::HealthCheck::Critical as i32);
+        status.desired_state = Some(service_status::DesiredState::Up as i32);
+        status.unsatisfied_binds = vec!["cache:redis.default".to_string()];
+        status
+    }
+
+    #[test]
+    fn status_output_from_status() {
+        let output = StatusOutput::from(status());
+        assert_eq!(output.ident, "core/redis");
+        assert_eq!(output.service_group, "redis.default");
+        assert_eq!(output.state, "up");
+        assert_eq!(output.desired_state, Some("up".to_string()));
+        assert_eq!(output.health_check, Some("critical".to_string()));
+        assert_eq!(output.restart_count, Some(3));
+        assert!(output.update_state.is_none());
+
+        let mut status = status();
+        status.update_state = Some(service_status::UpdateState::Aborted as i32);
+        let output = StatusOutput::from(status);
+        assert_eq!(output.update_state, Some("aborted".to_string()));
+    }
+
+    #[test]
+    fn print_json_list() {
+        let mut out = vec![];
+        print(&mut out, StatusFormat::Json, vec![status()]).unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(value[0]["pid"], 42);
+        assert_eq!(value[0]["unsatisfied_binds"][0], "cache:redis.default");
+        assert!(value[0]["last_exit_code"].is_null());
+        assert!(value[0]["crash_looping"].is_null());
+        assert_eq!(value[0]["stats"]["rss_bytes"], 4096);
+        assert!(value[0]["stats"]["fds"].is_null());
+
+        let mut out = vec![];
+        print(&mut out, StatusFormat::Json, vec![]).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap().trim(), "[]");
+    }
+
+    #[test]
+    fn print_table_crash_looping() {
+        let mut status = status();
+        {
+            let process = status.process.as_mut().unwrap();
+            process.state = ProcessState::Down as i32;
+            process.pid = None;
+            process.crash_looping = Some(true);
+        }
+        let mut out = vec![];
+        print(&mut out, StatusFormat::Table, vec![status]).unwrap();
+        let table = String::from_utf8(out).unwrap();
+        assert!(table.lines().nth(1).unwrap().contains("crash-looping"));
+    }
+}
@@ -30,6 +30,7 @@ pub struct Config {
     pub auth_token: Option<String>,
     pub origin: Option<String>,
     pub ctl_secret: Option<String>,
+    pub ctl_ca_cert: Option<String>,
 }
 
 impl ConfigFile for Config {
@@ -42,6 +43,7 @@ impl Default for Config {
             auth_token: None,
             origin: None,
             ctl_secret: None,
+            ctl_ca_cert: None,
         }
     }
 }
@@ -67,6 +67,7 @@ pub enum Error {
     RemoteSupResolutionError(String, io::Error),
     RootRequired,
     ScheduleStatus(depot_client::Error),
+    ServiceManifest(String),
     SubcommandNotSupported(String),
     UnsupportedExportFormat(String),
     TomlDeserializeError(toml::de::Error),
@@ -157,6 +158,7 @@ impl fmt::Display for Error {
                 "Root or administrator permissions required to complete operation".to_string()
             }
             Error::ScheduleStatus(ref e) => format!("Failed to retrieve job group status: {:?}", e),
+            Error::ServiceManifest(ref e) => format!("Invalid service manifest, {}", e),
             Error::SubcommandNotSupported(ref e) => {
                 format!("Subcommand `{}' not supported on this operating system", e)
             }
@@ -219,6 +221,7 @@ impl error::Error for Error {
                 "Root or administrator permissions required to complete operation"
             }
             Error::ScheduleStatus(ref err) => err.description(),
+            Error::ServiceManifest(_) => "Invalid service manifest",
             Error::SubcommandNotSupported(_) => "Subcommand not supported on this operating system",
             Error::UnsupportedExportFormat(_) => "Unsupported export format",
             Error::TomlDeserializeError(_) => "Can't deserialize TOML",
@@ -45,6 +45,7 @@ extern crate serde;
 #[macro_use]
 extern crate serde_derive;
 extern crate serde_json;
+extern crate serde_yaml;
 extern crate tabwriter;
 extern crate tar;
 #[cfg(test)]
@@ -65,6 +66,7 @@ pub mod scaffolding;
 pub const PRODUCT: &'static str = "hab";
 pub const VERSION: &'static str = include_str!(concat!(env!("OUT_DIR"), "/VERSION"));
 pub const CTL_SECRET_ENVVAR: &'static str = "HAB_CTL_SECRET";
+pub const CTL_CA_CERT_ENVVAR: &'static str = "HAB_CTL_CA_CERT";
 pub const ORIGIN_ENVVAR: &'static str = "HAB_ORIGIN";
 
 pub use hcore::AUTH_TOKEN_ENVVAR;
@@ -37,10 +37,11 @@ extern crate tabwriter;
 
 use std::env;
 use std::ffi::OsString;
+use std::fmt;
 use std::fs::File;
 use std::io::prelude::*;
 use std::io::{self, Read};
-use std::net::{SocketAddr, ToSocketAddrs};
+use std::net::{IpAddr, SocketAddr, ToSocketAddrs};
 use std::path::{Path, PathBuf};
 use std::process;
 use std::result;
@@ -65,18 +66,22 @@ use hcore::url::{bldr_url_from_env, default_bldr_url};
 use protocol::codec::*;
 use protocol::ctl::ServiceBindList;
 use protocol::net::ErrCode;
+use protocol::token::{self, CtlRole, CtlToken};
 use protocol::types::*;
-use sup_client::{SrvClient, SrvClientError};
+use sup_client::{SrvClient, SrvClientError, TlsConfig};
 use tabwriter::TabWriter;
 
 use hab::analytics;
 use hab::cli;
 use hab::command;
+use hab::command::service::manifest::ServiceManifest;
+use hab::command::service::status::StatusFormat;
 use hab::config::{self, Config};
 use hab::error::{Error, Result};
 use hab::feat;
 use hab::scaffolding;
-use hab::{AUTH_TOKEN_ENVVAR, CTL_SECRET_ENVVAR, ORIGIN_ENVVAR, PRODUCT, VERSION};
+use hab::{AUTH_TOKEN_ENVVAR, CTL_CA_CERT_ENVVAR, CTL_SECRET_ENVVAR, ORIGIN_ENVVAR, PRODUCT,
+          VERSION};
 
 /// Makes the --org CLI param optional when this env var is set
 const HABITAT_ORG_ENVVAR: &'static str = "HAB_ORG";
@@ -84,10 +89,6 @@ const HABITAT_ORG_ENVVAR: &'static str = "HAB_ORG";
 const HABITAT_USER_ENVVAR: &'static str = "HAB_USER";
 
 lazy_static! {
-    static ref STATUS_HEADER: Vec<&'static str> = {
-        vec!["package", "type", "state", "uptime (s)", "pid", "group"]
-    };
-
     /// The default filesystem root path to base all commands from. This is lazily generated on
     /// first call and reflects on the presence and value of the environment variable keyed as
     /// `FS_ROOT_ENVVAR`.
@@ -228,7 +229,12 @@ fn start(ui: &mut UI) -> Result<()> {
                 ("generate", Some(sc)) => sub_service_key_generate(ui, sc)?,
                 _ => unreachable!(),
             },
+            ("apply", Some(m)) => sub_svc_apply(m)?,
+            ("hooks", Some(m)) => sub_svc_hooks(m)?,
             ("load", Some(m)) => sub_svc_load(m)?,
+            ("logs", Some(m)) => sub_svc_logs(m)?,
+            ("restart", Some(m)) => sub_svc_restart(m)?,
+            ("signal", Some(m)) => sub_svc_signal(m)?,
             ("unload", Some(m)) => sub_svc_unload(m)?,
             ("start", Some(m)) => sub_svc_start(m)?,
             ("stop", Some(m)) => sub_svc_stop(m)?,
@@ -236,9 +242,13 @@ fn start(ui: &mut UI) -> Result<()> {
             _ => unreachable!(),
         },
         ("sup", Some(m)) => match m.subcommand() {
+            ("audit", Some(m)) => sub_sup_audit(m)?,
             ("depart", Some(m)) => sub_sup_depart(m)?,
             ("secret", Some(m)) => match m.subcommand() {
                 ("generate", _) => sub_sup_secret_generate()?,
+                ("add", Some(sc)) => sub_sup_secret_add(sc)?,
+                ("list", Some(sc)) => sub_sup_secret_list(sc)?,
+                ("remove", Some(sc)) => sub_sup_secret_remove(sc)?,
                 _ => unreachable!(),
             },
             // this is effectively an alias of `hab svc status`
@@ -731,7 +741,6 @@ fn sub_pkg_channels(ui: &mut UI, m: &ArgMatches) -> Result<()> {
 fn sub_svc_set(m: &ArgMatches) -> Result<()> {
     let cfg = config::load()?;
     let sup_addr = sup_addr_from_input(m)?;
-    let secret_key = ctl_secret_key(&cfg)?;
     let service_group = ServiceGroup::from_str(m.value_of("SERVICE_GROUP").unwrap())?;
     let mut ui = ui();
     let mut validate = protocol::ctl::SvcValidateCfg::default();
@@ -785,7 +794,7 @@ fn sub_svc_set(m: &ArgMatches) -> Result<()> {
             .unwrap_or("UNKNOWN".to_string()),
     ))?;
     ui.status(Status::Creating, format!("service configuration"))?;
-    SrvClient::connect(&sup_addr, &secret_key)
+    ctl_connect(m, &cfg, &sup_addr)?
         .and_then(|conn| {
             conn.call(validate)
                 .for_each(|reply| match reply.message_id() {
@@ -810,7 +819,7 @@ fn sub_svc_set(m: &ArgMatches) -> Result<()> {
     // JW: We should not need to make two connections here. I need a way to return the
     // SrvClient from a for_each iterator so we can chain upon a successful stream but I don't
     // know if it's possible with this version of futures.
-    SrvClient::connect(&sup_addr, secret_key)
+    ctl_connect(m, &cfg, &sup_addr)?
         .and_then(|conn| {
             conn.call(set).for_each(|reply| match reply.message_id() {
                 "NetOk" => Ok(()),
@@ -832,10 +841,9 @@ fn sub_svc_config(m: &ArgMatches) -> Result<()> {
     let ident = PackageIdent::from_str(m.value_of("PKG_IDENT").unwrap())?;
     let cfg = config::load()?;
     let sup_addr = sup_addr_from_input(m)?;
-    let secret_key = ctl_secret_key(&cfg)?;
     let mut msg = protocol::ctl::SvcGetDefaultCfg::default();
     msg.ident = Some(ident.into());
-    SrvClient::connect(&sup_addr, secret_key)
+    ctl_connect(m, &cfg, &sup_addr)?
         .and_then(|conn| {
             conn.call(msg).for_each(|reply| match reply.message_id() {
                 "ServiceCfg" => {
@@ -856,15 +864,28 @@ fn sub_svc_config(m: &ArgMatches) -> Result<()> {
     Ok(())
 }
 
+fn sub_svc_apply(m: &ArgMatches) -> Result<()> {
+    let manifest = ServiceManifest::from_file(m.value_of("FILE").unwrap())?;
+    let cfg = config::load()?;
+    let sup_addr = sup_addr_from_input(m)?;
+    let mut msg = protocol::ctl::SvcApply::default();
+    msg.services = manifest.svc_loads()?;
+    msg.unload = Some(m.is_present("UNLOAD"));
+    msg.dry_run = Some(m.is_present("DRY_RUN"));
+    ctl_connect(m, &cfg, &sup_addr)?
+        .and_then(|conn| conn.call(msg).for_each(handle_ctl_reply))
+        .wait()?;
+    Ok(())
+}
+
 fn sub_svc_load(m: &ArgMatches) -> Result<()> {
     let cfg = config::load()?;
     let sup_addr = sup_addr_from_input(m)?;
-    let secret_key = ctl_secret_key(&cfg)?;
     let mut msg = protocol::ctl::SvcLoad::default();
     update_svc_load_from_input(m, &mut msg)?;
     let ident: PackageIdent = m.value_of("PKG_IDENT").unwrap().parse()?;
     msg.ident = Some(ident.into());
-    SrvClient::connect(&sup_addr, secret_key)
+    ctl_connect(m, &cfg, &sup_addr)?
         .and_then(|conn| conn.call(msg).for_each(handle_ctl_reply))
         .wait()?;
     Ok(())
@@ -874,10 +895,84 @@ fn sub_svc_unload(m: &ArgMatches) -> Result<()> {
     let ident = PackageIdent::from_str(m.value_of("PKG_IDENT").unwrap())?;
     let cfg = config::load()?;
     let sup_addr = sup_addr_from_input(m)?;
-    let secret_key = ctl_secret_key(&cfg)?;
     let mut msg = protocol::ctl::SvcUnload::default();
     msg.ident = Some(ident.into());
-    SrvClient::connect(&sup_addr, secret_key)
+    ctl_connect(m, &cfg, &sup_addr)?
+        .and_then(|conn| conn.call(msg).for_each(handle_ctl_reply))
+        .wait()?;
+    Ok(())
+}
+
+fn sub_svc_hooks(m: &ArgMatches) -> Result<()> {
+    let ident = PackageIdent::from_str(m.value_of("PKG_IDENT").unwrap())?;
+    let cfg = config::load()?;
+    let sup_addr = sup_addr_from_input(m)?;
+    let mut msg = protocol::ctl::SvcHookHistory::default();
+    msg.ident = Some(ident.into());
+    msg.hook = m.value_of("HOOK").map(|h| h.to_string());
+    ctl_connect(m, &cfg, &sup_addr)?
+        .and_then(|conn| {
+            conn.call(msg).for_each(|reply| match reply.message_id() {
+                "SvcHookRun" => {
+                    let m = reply.parse::<protocol::ctl::SvcHookRun>().unwrap();
+                    Ok(command::service::hooks::print_run(io::stdout(), &m)?)
+                }
+                _ => handle_ctl_reply(reply),
+            })
+        })
+        .wait()?;
+    Ok(())
+}
+
+fn sub_svc_logs(m: &ArgMatches) -> Result<()> {
+    let ident = PackageIdent::from_str(m.value_of("PKG_IDENT").unwrap())?;
+    let cfg = config::load()?;
+    let sup_addr = sup_addr_from_input(m)?;
+    let mut msg = protocol::ctl::SvcLogs::default();
+    msg.ident = Some(ident.into());
+    msg.tail = m.value_of("TAIL").map(|t| t.parse().unwrap());
+    msg.follow = Some(m.is_present("FOLLOW"));
+    // The Supervisor resolves the duration against its own clock, which may differ from ours.
+    msg.since = m.value_of("SINCE")
+        .map(|since| command::service::parse_duration(since).unwrap());
+    ctl_connect(m, &cfg, &sup_addr)?
+        .and_then(|conn| {
+            conn.call(msg).for_each(|reply| match reply.message_id() {
+                "SvcLogLine" => {
+                    let m = reply.parse::<protocol::ctl::SvcLogLine>().unwrap();
+                    match protocol::ctl::svc_log_line::Stream::from_i32(m.stream) {
+                        Some(protocol::ctl::svc_log_line::Stream::Stderr) => eprintln!("{}", m),
+                        _ => println!("{}", m),
+                    }
+                    Ok(())
+                }
+                _ => handle_ctl_reply(reply),
+            })
+        })
+        .wait()?;
+    Ok(())
+}
+
+fn sub_svc_restart(m: &ArgMatches) -> Result<()> {
+    let ident = PackageIdent::from_str(m.value_of("PKG_IDENT").unwrap())?;
+    let cfg = config::load()?;
+    let sup_addr = sup_addr_from_input(m)?;
+    let mut msg = protocol::ctl::SvcRestart::default();
+    msg.ident = Some(ident.into());
+    ctl_connect(m, &cfg, &sup_addr)?
+        .and_then(|conn| conn.call(msg).for_each(handle_ctl_reply))
+        .wait()?;
+    Ok(())
+}
+
+fn sub_svc_signal(m: &ArgMatches) -> Result<()> {
+    let ident = PackageIdent::from_str(m.value_of("PKG_IDENT").unwrap())?;
+    let cfg = config::load()?;
+    let sup_addr = sup_addr_from_input(m)?;
+    let mut msg = protocol::ctl::SvcSignal::default();
+    msg.ident = Some(ident.into());
+    msg.signal = Some(m.value_of("SIGNAL").unwrap().to_string());
+    ctl_connect(m, &cfg, &sup_addr)?
         .and_then(|conn| conn.call(msg).for_each(handle_ctl_reply))
         .wait()?;
     Ok(())
@@ -887,10 +982,9 @@ fn sub_svc_start(m: &ArgMatches) -> Result<()> {
     let ident = PackageIdent::from_str(m.value_of("PKG_IDENT").unwrap())?;
     let cfg = config::load()?;
     let sup_addr = sup_addr_from_input(m)?;
-    let secret_key = ctl_secret_key(&cfg)?;
     let mut msg = protocol::ctl::SvcStart::default();
     msg.ident = Some(ident.into());
-    SrvClient::connect(&sup_addr, secret_key)
+    ctl_connect(m, &cfg, &sup_addr)?
         .and_then(|conn| conn.call(msg).for_each(handle_ctl_reply))
         .wait()?;
     Ok(())
@@ -899,44 +993,31 @@ fn sub_svc_start(m: &ArgMatches) -> Result<()> {
 fn sub_svc_status(m: &ArgMatches) -> Result<()> {
     let cfg = config::load()?;
     let sup_addr = sup_addr_from_input(m)?;
-    let secret_key = ctl_secret_key(&cfg)?;
     let mut msg = protocol::ctl::SvcStatus::default();
     if let Some(pkg) = m.value_of("PKG_IDENT") {
         msg.ident = Some(PackageIdent::from_str(pkg)?.into());
     }
-    SrvClient::connect(&sup_addr, secret_key)
+    let format = StatusFormat::from_str(m.value_of("FORMAT").unwrap_or("table"))?;
+    let statuses = ctl_connect(m, &cfg, &sup_addr)?
         .and_then(|conn| {
-            let mut out = TabWriter::new(io::stdout());
-            conn.call(msg)
-                .into_future()
-                .map_err(|(err, _)| err)
-                .and_then(move |(reply, rest)| {
-                    match reply {
-                        None => {
-                            return Err(SrvClientError::from(io::Error::from(
-                                io::ErrorKind::UnexpectedEof,
-                            )))
-                        }
-                        Some(m) => print_svc_status(&mut out, m, true)?,
-                    }
-                    Ok((out, rest))
-                })
-                .and_then(|(mut out, rest)| {
-                    rest.for_each(move |reply| print_svc_status(&mut out, reply, false))
-                })
+            conn.call(msg).fold(vec![], |mut statuses, reply| {
+                if let Some(status) = svc_status_from_reply(reply)? {
+                    statuses.push(status);
+                }
+                Ok::<_, SrvClientError>(statuses)
+            })
         })
         .wait()?;
-    Ok(())
+    command::service::status::print(io::stdout(), format, statuses)
 }
 
 fn sub_svc_stop(m: &ArgMatches) -> Result<()> {
     let ident = PackageIdent::from_str(m.value_of("PKG_IDENT").unwrap())?;
     let cfg = config::load()?;
     let sup_addr = sup_addr_from_input(m)?;
-    let secret_key = ctl_secret_key(&cfg)?;
     let mut msg = protocol::ctl::SvcStop::default();
     msg.ident = Some(ident.into());
-    SrvClient::connect(&sup_addr, secret_key)
+    ctl_connect(m, &cfg, &sup_addr)?
         .and_then(|conn| conn.call(msg).for_each(handle_ctl_reply))
         .wait()?;
     Ok(())
@@ -946,7 +1027,6 @@ fn sub_file_put(m: &ArgMatches) -> Result<()> {
     let service_group = ServiceGroup::from_str(m.value_of("SERVICE_GROUP").unwrap())?;
     let cfg = config::load()?;
     let sup_addr = sup_addr_from_input(m)?;
-    let secret_key = ctl_secret_key(&cfg)?;
     let mut ui = ui();
     let mut msg = protocol::ctl::SvcFilePut::default();
     let file = Path::new(m.value_of("FILE").unwrap());
@@ -993,7 +1073,7 @@ fn sub_file_put(m: &ArgMatches) -> Result<()> {
         }
         _ => msg.content = Some(buf.to_vec()),
     }
-    SrvClient::connect(&sup_addr, secret_key)
+    ctl_connect(m, &cfg, &sup_addr)?
         .and_then(|conn| {
             ui.status(Status::Applying, format!("via peer {}", sup_addr))
                 .unwrap();
@@ -1019,14 +1099,33 @@ fn sub_file_put(m: &ArgMatches) -> Result<()> {
     Ok(())
 }
 
+fn sub_sup_audit(m: &ArgMatches) -> Result<()> {
+    let cfg = config::load()?;
+    let sup_addr = sup_addr_from_input(m)?;
+    let mut msg = protocol::ctl::SupAuditLog::default();
+    msg.tail = m.value_of("TAIL").map(|t| t.parse().unwrap());
+    ctl_connect(m, &cfg, &sup_addr)?
+        .and_then(|conn| {
+            conn.call(msg).for_each(|reply| match reply.message_id() {
+                "SupAuditEntry" => {
+                    let m = reply.parse::<protocol::ctl::SupAuditEntry>().unwrap();
+                    println!("{}", m.entry);
+                    Ok(())
+                }
+                _ => handle_ctl_reply(reply),
+            })
+        })
+        .wait()?;
+    Ok(())
+}
+
 fn sub_sup_depart(m: &ArgMatches) -> Result<()> {
     let cfg = config::load()?;
     let sup_addr = sup_addr_from_input(m)?;
-    let secret_key = ctl_secret_key(&cfg)?;
     let mut ui = ui();
     let mut msg = protocol::ctl::SupDepart::default();
     msg.member_id = Some(m.value_of("MEMBER_ID").unwrap().to_string());
-    SrvClient::connect(&sup_addr, secret_key)
+    ctl_connect(m, &cfg, &sup_addr)?
         .and_then(|conn| {
             ui.begin(format!(
                 "Permanently marking {} as departed",
@@ -1061,6 +1160,50 @@ fn sub_sup_secret_generate() -> Result<()> {
     Ok(())
 }
 
+fn sub_sup_secret_add(m: &ArgMatches) -> Result<()> {
+    let name = m.value_of("NAME").unwrap();
+    let role = match m.value_of("ROLE") {
+        Some(role) => CtlRole::from_str(role)?,
+        None => CtlRole::ReadOnly,
+    };
+    let sup_root = sup_root_from_input(m);
+    let mut tokens = token::read_ctl_tokens(&sup_root)?;
+    if tokens.iter().any(|t| t.name == name) {
+        return Err(Error::ArgumentError("A token with the given name already exists"));
+    }
+    let new_token = CtlToken::new(name, role)?;
+    let secret = new_token.secret.clone();
+    tokens.push(new_token);
+    token::write_ctl_tokens(&sup_root, &tokens)?;
+    let mut ui = ui();
+    ui.info(secret)?;
+    Ok(())
+}
+
+fn sub_sup_secret_list(m: &ArgMatches) -> Result<()> {
+    let sup_root = sup_root_from_input(m);
+    let mut out = TabWriter::new(io::stdout());
+    write!(out, "name\trole\n")?;
+    for t in token::read_ctl_tokens(&sup_root)? {
+        write!(out, "{}\t{}\n", t.name, t.role)?;
+    }
+    out.flush()?;
+    Ok(())
+}
+
+fn sub_sup_secret_remove(m: &ArgMatches) -> Result<()> {
+    let name = m.value_of("NAME").unwrap();
+    let sup_root = sup_root_from_input(m);
+    let mut tokens = token::read_ctl_tokens(&sup_root)?;
+    let count = tokens.len();
+    tokens.retain(|t| t.name != name);
+    if tokens.len() == count {
+        return Err(Error::ArgumentError("No token with the given name exists"));
+    }
+    token::write_ctl_tokens(&sup_root, &tokens)?;
+    Ok(())
+}
+
 fn sub_supportbundle(ui: &mut UI) -> Result<()> {
     init();
 
@@ -1208,6 +1351,43 @@ fn ctl_secret_key(config: &Config) -> Result<String> {
     }
 }
 
+/// Returns the TLS configuration for connecting to the Supervisor's Control Gateway if a CA
+/// certificate has been set in the environment or `cli.toml`. The host name given with
+/// `--remote-sup`, if any, is verified against the Supervisor's certificate.
+fn ctl_tls(m: &ArgMatches, config: &Config) -> Result<Option<TlsConfig>> {
+    let ca_cert_path = match henv::var(CTL_CA_CERT_ENVVAR) {
+        Ok(v) => PathBuf::from(v),
+        Err(_) => match config.ctl_ca_cert {
+            Some(ref v) => PathBuf::from(v),
+            None => return Ok(None),
+        },
+    };
+    if !ca_cert_path.is_file() {
+        return Err(Error::FileNotFound(ca_cert_path.to_string_lossy().into_owned()));
+    }
+    Ok(Some(TlsConfig {
+        ca_cert_path: ca_cert_path,
+        server_name: m.value_of("REMOTE_SUP").and_then(remote_sup_host),
+    }))
+}
+
+/// The host name of a `--remote-sup` address, without its port. Addresses given as IP addresses,
+/// including bracketed IPv6 addresses, have no host name to verify a certificate against.
+fn remote_sup_host(remote_sup: &str) -> Option<String> {
+    if SocketAddr::from_str(remote_sup).is_ok() {
+        return None;
+    }
+    let bare = remote_sup.trim_left_matches('[').trim_right_matches(']');
+    if IpAddr::from_str(bare).is_ok() {
+        return None;
+    }
+    let host = match remote_sup.rfind(':') {
+        Some(idx) => &remote_sup[..idx],
+        None => remote_sup,
+    };
+    Some(host.to_string())
+}
+
 /// Check to see if an auth token exists and convert it to a string slice if it does. Unlike
 /// auth_token_param_or_env, it's ok for no auth token to be present here. This is useful for
 /// commands that can optionally take an auth token for operating on private packages.
@@ -1333,54 +1513,22 @@ fn handle_ctl_reply(reply: SrvMessage) -> result::Result<(), SrvClientError> {
     Ok(())
 }
 
-fn print_svc_status<T>(
-    out: &mut T,
+fn svc_status_from_reply(
     reply: SrvMessage,
-    print_header: bool,
-) -> result::Result<(), SrvClientError>
-where
-    T: io::Write,
-{
-    let status = match reply.message_id() {
-        "ServiceStatus" => reply.parse::<protocol::types::ServiceStatus>()?,
-        "NetOk" => {
-            println!("No services loaded.");
-            return Ok(());
-        }
+) -> result::Result<Option<protocol::types::ServiceStatus>, SrvClientError> {
+    match reply.message_id() {
+        "ServiceStatus" => Ok(Some(reply.parse::<protocol::types::ServiceStatus>()?)),
+        // Sent in place of any status when no services are loaded
+        "NetOk" => Ok(None),
         "NetErr" => {
             let err = reply.parse::<protocol::net::NetErr>()?;
-            return Err(SrvClientError::from(err));
+            Err(SrvClientError::from(err))
         }
         _ => {
             warn!("Unexpected status message, {:?}", reply);
-            return Ok(());
+            Ok(None)
         }
-    };
-    let svc_type = status.composite.unwrap_or("standalone".to_string());
-    let (svc_state, svc_pid, svc_elapsed) = {
-        match status.process {
-            Some(process) => (
-                process.state.to_string(),
-                process.pid.unwrap_or_default().to_string(),
-                process.elapsed.unwrap_or_default().to_string(),
-            ),
-            None => (
-                ProcessState::default().to_string(),
-                "<none>".to_string(),
-                "<none>".to_string(),
-            ),
-        }
-    };
-    if print_header {
-        write!(out, "{}\n", STATUS_HEADER.join("\t")).unwrap();
     }
-    write!(
-        out,
-        "{}\t{}\t{}\t{}\t{}\t{}\n",
-        status.ident, svc_type, svc_state, svc_elapsed, svc_pid, status.service_group,
-    )?;
-    out.flush()?;
-    return Ok(());
 }
 
 /// A Builder URL, but *only* if the user specified it via CLI args or
@@ -1457,8 +1605,45 @@ fn get_strategy_from_input(m: &ArgMatches) -> Option<UpdateStrategy> {
         .and_then(|f| UpdateStrategy::from_str(f).ok())
 }
 
-fn sup_addr_from_input(m: &ArgMatches) -> Result<SocketAddr> {
+/// Address of a Supervisor's Control Gateway given with `--remote-sup`.
+enum CtlAddr {
+    Tcp(SocketAddr),
+    /// Path of the Control Gateway's Unix domain socket, given as `unix:<path>`.
+    Unix(PathBuf),
+}
+
+impl fmt::Display for CtlAddr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CtlAddr::Tcp(ref addr) => write!(f, "{}", addr),
+            CtlAddr::Unix(ref path) => write!(f, "unix:{}", path.display()),
+        }
+    }
+}
+
+/// The state directory of the local Supervisor named by `--override-name`, as `hab sup run`
+/// chooses it.
+fn sup_root_from_input(m: &ArgMatches) -> PathBuf {
+    protocol::sup_root(m.value_of("OVERRIDE_NAME"), None::<String>)
+}
+
+fn sup_addr_from_input(m: &ArgMatches) -> Result<CtlAddr> {
     match m.value_of("REMOTE_SUP") {
+        Some(rs) if rs.starts_with("unix:") => {
+            if cfg!(windows) {
+                return Err(Error::ArgumentError(
+                    "Connecting to a Unix domain socket is not supported on Windows",
+                ));
+            }
+            // A bare `unix:` names the socket of the local Supervisor.
+            match &rs["unix:".len()..] {
+                "" => Ok(CtlAddr::Unix(protocol::ctl_socket_path(protocol::sup_root(
+                    None::<String>,
+                    None::<String>,
+                )))),
+                path => Ok(CtlAddr::Unix(PathBuf::from(path))),
+            }
+        }
         Some(rs) => {
             let sup_addr = if rs.find(':').is_some() {
                 rs.to_string()
@@ -1471,9 +1656,29 @@ fn sup_addr_from_input(m: &ArgMatches) -> Result<SocketAddr> {
                     return Err(Error::RemoteSupResolutionError(sup_addr, e));
                 }
             };
-            Ok(addrs[0])
+            Ok(CtlAddr::Tcp(addrs[0]))
         }
-        None => Ok(protocol::ctl::default_addr()),
+        None => Ok(CtlAddr::Tcp(protocol::ctl::default_addr())),
+    }
+}
+
+/// Connect to the Supervisor's Control Gateway at the given address. A Supervisor may trust the
+/// user connecting over its Unix domain socket, so the secret key is only sent over it if one
+/// can be found.
+fn ctl_connect(
+    m: &ArgMatches,
+    config: &Config,
+    sup_addr: &CtlAddr,
+) -> Result<Box<Future<Item = SrvClient, Error = SrvClientError>>> {
+    match *sup_addr {
+        CtlAddr::Tcp(ref addr) => {
+            let secret_key = ctl_secret_key(config)?;
+            Ok(SrvClient::connect(addr, secret_key, ctl_tls(m, config)?.as_ref()))
+        }
+        #[cfg(unix)]
+        CtlAddr::Unix(ref path) => Ok(SrvClient::connect_unix(path, ctl_secret_key(config).ok())),
+        #[cfg(windows)]
+        CtlAddr::Unix(_) => unreachable!(),
     }
 }
 
@@ -1528,5 +1733,46 @@ fn update_svc_load_from_input(m: &ArgMatches, msg: &mut protocol::ctl::SvcLoad)
     msg.binding_mode = get_binding_mode_from_input(m).map(|v| v as i32);
     msg.topology = get_topology_from_input(m).map(|v| v as i32);
     msg.update_strategy = get_strategy_from_input(m).map(|v| v as i32);
+    msg.health_check_interval = m.value_of("HEALTH_CHECK_INTERVAL")
+        .and_then(command::service::parse_duration);
+    msg.health_check_timeout = m.value_of("HEALTH_CHECK_TIMEOUT")
+        .and_then(command::service::parse_duration);
+    msg.health_check_success_threshold = m.value_of("HEALTH_CHECK_SUCCESS_THRESHOLD")
+        .and_then(|t| t.parse().ok());
+    msg.health_check_failure_threshold = m.value_of("HEALTH_CHECK_FAILURE_THRESHOLD")
+        .and_then(|t| t.parse().ok());
+    msg.shutdown_signal = m.value_of("SHUTDOWN_SIGNAL").map(|s| s.to_uppercase());
+    msg.shutdown_timeout = m.value_of("SHUTDOWN_TIMEOUT")
+        .and_then(command::service::parse_duration)
+        .map(|t| t as u32);
+    msg.restart_policy = m.value_of("RESTART_POLICY")
+        .and_then(|p| RestartPolicy::from_str(p).ok())
+        .map(|p| p as i32);
+    msg.restart_backoff_base = m.value_of("RESTART_BACKOFF_BASE")
+        .and_then(command::service::parse_duration);
+    msg.restart_backoff_max = m.value_of("RESTART_BACKOFF_MAX")
+        .and_then(command::service::parse_duration);
+    msg.max_restarts = m.value_of("MAX_RESTARTS").and_then(|t| t.parse().ok());
+    msg.restart_window = m.value_of("RESTART_WINDOW")
+        .and_then(command::service::parse_duration);
     Ok(())
 }
+
+#[cfg(test)]
+mod test {
+    use super::remote_sup_host;
+
+    #[test]
+    fn remote_sup_host_of_names_and_addresses() {
+        assert_eq!(
+            remote_sup_host("sup.example.com:9632"),
+            Some("sup.example.com".to_string())
+        );
+        assert_eq!(remote_sup_host("sup.example.com"), Some("sup.example.com".to_string()));
+        assert_eq!(remote_sup_host("10.0.0.1:9632"), None);
+        assert_eq!(remote_sup_host("10.0.0.1"), None);
+        assert_eq!(remote_sup_host("[::1]:9632"), None);
+        assert_eq!(remote_sup_host("[::1]"), None);
+        assert_eq!(remote_sup_host("::1"), None);
+    }
+}
@@ -12,9 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::io;
 use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
 
 use core::os::process::Pid;
 use ipc_channel::ipc::{IpcOneShotServer, IpcReceiver, IpcSender};
@@ -23,12 +27,35 @@ use protocol;
 
 use error::{Error, Result};
 
+/// How long to wait for the Launcher to answer a query about a service, such as its exit code or
+/// resource usage. Launchers which predate these queries never answer them.
+const QUERY_TIMEOUT_MS: u64 = 2_000;
+
 type Env = HashMap<String, String>;
 type IpcServer = IpcOneShotServer<Vec<u8>>;
 
+/// Client of the Launcher's pipe, which may be shared between threads.
+///
+/// Each request carries its own transaction id, and the messages from the Launcher are read on a
+/// dedicated thread which hands each reply to the request it answers. A request waiting for a
+/// slow reply, such as the termination of a service, doesn't hold up the others.
 pub struct LauncherCli {
-    tx: IpcSender<Vec<u8>>,
-    rx: IpcReceiver<Vec<u8>>,
+    tx: Mutex<IpcSender<Vec<u8>>>,
+    inbox: Arc<(Mutex<Inbox>, Condvar)>,
+    next_txn_id: AtomicUsize,
+}
+
+/// Messages received from the Launcher which weren't taken yet.
+#[derive(Default)]
+struct Inbox {
+    /// Replies by the transaction id of the request they answer.
+    replies: HashMap<u64, Vec<u8>>,
+    /// Transaction ids of requests which timed out, whose replies are dropped.
+    abandoned: HashSet<u64>,
+    /// Whether the Launcher asked the Supervisor to shut down.
+    stopping: bool,
+    /// Set once the Launcher's pipe can no longer be read from.
+    closed: Option<io::ErrorKind>,
 }
 
 impl LauncherCli {
@@ -37,10 +64,20 @@ impl LauncherCli {
         let (ipc_srv, pipe) = IpcServer::new().map_err(Error::BadPipe)?;
         let mut cmd = protocol::Register::new();
         cmd.set_pipe(pipe);
-        Self::send(&tx, &cmd)?;
+        Self::send(&tx, &cmd, 0)?;
         let (rx, raw) = ipc_srv.accept().map_err(|_| Error::AcceptConn)?;
         Self::read::<protocol::NetOk>(&raw)?;
-        Ok(LauncherCli { tx: tx, rx: rx })
+        let inbox = Arc::new((Mutex::new(Inbox::default()), Condvar::new()));
+        let receiver_inbox = inbox.clone();
+        thread::Builder::new()
+            .name("launcher-client".to_string())
+            .spawn(move || Self::receive(rx, receiver_inbox))
+            .expect("Unable to start launcher-client thread");
+        Ok(LauncherCli {
+            tx: Mutex::new(tx),
+            inbox: inbox,
+            next_txn_id: AtomicUsize::new(1),
+        })
     }
 
     /// Read a launcher protocol message from a byte array
@@ -58,57 +95,122 @@ impl LauncherCli {
         Ok(msg)
     }
 
-    /// Receive and read protocol message from an IpcReceiver
-    fn recv<T>(rx: &IpcReceiver<Vec<u8>>) -> Result<T>
+    /// Receive messages from the Launcher until its pipe is closed, filing each one in the inbox.
+    fn receive(rx: IpcReceiver<Vec<u8>>, inbox: Arc<(Mutex<Inbox>, Condvar)>) {
+        let &(ref lock, ref cvar) = &*inbox;
+        loop {
+            let received = rx.recv();
+            let mut inbox = lock.lock().expect("Launcher inbox lock poisoned");
+            match received {
+                Ok(bytes) => match protocol::NetTxn::from_bytes(&bytes) {
+                    Ok(ref txn) if txn.message_id() == "Shutdown" => inbox.stopping = true,
+                    Ok(ref txn) if txn.txn_id() != 0 => {
+                        if !inbox.abandoned.remove(&txn.txn_id()) {
+                            inbox.replies.insert(txn.txn_id(), bytes);
+                        }
+                    }
+                    _ => (),
+                },
+                Err(err) => {
+                    inbox.closed = match Error::from(*err) {
+                        Error::IPCIO(kind) => Some(kind),
+                        _ => Some(io::ErrorKind::InvalidData),
+                    };
+                }
+            }
+            cvar.notify_all();
+            if inbox.closed.is_some() {
+                return;
+            }
+        }
+    }
+
+    /// Send a request to the Launcher and wait for its reply, or for at most `timeout` if given.
+    fn request<T, R>(&self, message: &T, timeout: Option<Duration>) -> Result<R>
     where
         T: protobuf::MessageStatic,
+        R: protobuf::MessageStatic,
     {
-        match rx.recv() {
-            Ok(bytes) => Self::read(&bytes),
-            Err(err) => Err(Error::from(*err)),
+        let txn_id = self.next_txn_id.fetch_add(1, Ordering::Relaxed) as u64;
+        Self::send(
+            &self.tx.lock().expect("Launcher pipe lock poisoned"),
+            message,
+            txn_id,
+        )?;
+        let sent_at = Instant::now();
+        let &(ref lock, ref cvar) = &*self.inbox;
+        let mut inbox = lock.lock().expect("Launcher inbox lock poisoned");
+        loop {
+            if let Some(bytes) = inbox.replies.remove(&txn_id) {
+                return Self::read(&bytes);
+            }
+            if let Some(kind) = inbox.closed {
+                return Err(Error::IPCIO(kind));
+            }
+            inbox = match timeout {
+                Some(timeout) => {
+                    let waited = sent_at.elapsed();
+                    if waited >= timeout {
+                        inbox.abandoned.insert(txn_id);
+                        return Err(Error::Timeout);
+                    }
+                    cvar.wait_timeout(inbox, timeout - waited)
+                        .expect("Launcher inbox lock poisoned")
+                        .0
+                }
+                None => cvar.wait(inbox).expect("Launcher inbox lock poisoned"),
+            };
         }
     }
 
     /// Send a command to a Launcher
-    fn send<T>(tx: &IpcSender<Vec<u8>>, message: &T) -> Result<()>
+    fn send<T>(tx: &IpcSender<Vec<u8>>, message: &T, txn_id: u64) -> Result<()>
     where
         T: protobuf::MessageStatic,
     {
-        let txn = protocol::NetTxn::build(message).map_err(Error::Serialize)?;
+        let mut txn = protocol::NetTxn::build(message).map_err(Error::Serialize)?;
+        txn.set_txn_id(txn_id);
         let bytes = txn.to_bytes().map_err(Error::Serialize)?;
         tx.send(bytes).map_err(Error::Send)?;
         Ok(())
     }
 
-    /// Receive and read protocol message from an IpcReceiver
-    fn try_recv<T>(rx: &IpcReceiver<Vec<u8>>) -> Result<Option<T>>
-    where
-        T: protobuf::MessageStatic,
-    {
-        match rx.try_recv().map_err(|err| Error::from(*err)) {
-            Ok(bytes) => {
-                let msg = Self::read::<T>(&bytes)?;
-                Ok(Some(msg))
-            }
-            Err(Error::IPCIO(io::ErrorKind::WouldBlock)) => Ok(None),
-            Err(err) => Err(err),
-        }
+    pub fn is_stopping(&self) -> bool {
+        let inbox = self.inbox.0.lock().expect("Launcher inbox lock poisoned");
+        inbox.stopping || inbox.closed.is_some()
     }
 
-    pub fn is_stopping(&self) -> bool {
-        match Self::try_recv::<protocol::Shutdown>(&self.rx) {
-            Ok(Some(_)) | Err(Error::IPCIO(_)) => true,
-            Ok(None) => false,
-            Err(err) => panic!("Unexpected error checking for shutdown request, {}", err),
+    /// Take the exit code of a service process which exited on its own. The exit code is `None`
+    /// if the process was terminated by a signal, and a `NoPID` error is returned if the Launcher
+    /// isn't holding the exit code of the process, for example because it was already taken. A
+    /// `Timeout` error is returned if the Launcher doesn't answer, as older Launchers don't.
+    pub fn exit_code(&self, pid: Pid) -> Result<Option<i32>> {
+        let mut msg = protocol::ExitCode::new();
+        msg.set_pid(pid.into());
+        let timeout = Duration::from_millis(QUERY_TIMEOUT_MS);
+        let reply = self.request::<_, protocol::ExitCodeOk>(&msg, Some(timeout))?;
+        if reply.has_exit_code() {
+            Ok(Some(reply.get_exit_code()))
+        } else {
+            Ok(None)
         }
     }
 
+    /// Read the resources used by the cgroup of a running process. The usage is left unset if
+    /// the process was spawned without resource limits, or where cgroups v2 are unavailable. A
+    /// `Timeout` error is returned if the Launcher doesn't answer, as older Launchers don't.
+    pub fn resource_usage(&self, pid: Pid) -> Result<protocol::ResourceUsageOk> {
+        let mut msg = protocol::ResourceUsage::new();
+        msg.set_pid(pid.into());
+        let timeout = Duration::from_millis(QUERY_TIMEOUT_MS);
+        self.request::<_, protocol::ResourceUsageOk>(&msg, Some(timeout))
+    }
+
     /// Restart a running process with the same arguments
     pub fn restart(&self, pid: Pid) -> Result<Pid> {
         let mut msg = protocol::Restart::new();
         msg.set_pid(pid.into());
-        Self::send(&self.tx, &msg)?;
-        let reply = Self::recv::<protocol::SpawnOk>(&self.rx)?;
+        let reply = self.request::<_, protocol::SpawnOk>(&msg, None)?;
         Ok(reply.get_pid() as Pid)
     }
 
@@ -117,7 +219,16 @@ impl LauncherCli {
     /// `user` and `group` are string names, while `user_id` and
     /// `group_id` are numeric IDs. Newer versions of the Launcher can
     /// accept either, but prefer numeric IDs.
-    pub fn spawn<I, B, U, G, P>(
+    ///
+    /// The service's output is additionally appended to the file at
+    /// `log_path` by Launchers which support it.
+    ///
+    /// On Linux, the process is spawned in a cgroup applying the given
+    /// `resource_limits`, if any are set. The `rlimits` are set on Unix
+    /// and, on Linux, the process enters the namespaces requested by
+    /// `isolation` and raises the `ambient_capabilities` before it is
+    /// executed.
+    pub fn spawn<I, B, U, G, P, L>(
         &self,
         id: I,
         bin: B,
@@ -127,6 +238,13 @@ impl LauncherCli {
         group_id: Option<u32>,
         password: Option<P>,
         env: Env,
+        log_path: L,
+        shutdown_signal: Option<&str>,
+        shutdown_timeout: Option<u32>,
+        resource_limits: Option<protocol::ResourceLimits>,
+        rlimits: Option<protocol::Rlimits>,
+        ambient_capabilities: Vec<String>,
+        isolation: Option<protocol::Isolation>,
     ) -> Result<Pid>
     where
         I: ToString,
@@ -134,6 +252,7 @@ impl LauncherCli {
         U: ToString,
         G: ToString,
         P: ToString,
+        L: AsRef<Path>,
     {
         let mut msg = protocol::Spawn::new();
         msg.set_binary(bin.as_ref().to_path_buf().to_string_lossy().into_owned());
@@ -163,16 +282,44 @@ impl LauncherCli {
         }
         msg.set_env(env);
         msg.set_id(id.to_string());
-        Self::send(&self.tx, &msg)?;
-        let reply = Self::recv::<protocol::SpawnOk>(&self.rx)?;
+        msg.set_log_path(log_path.as_ref().to_string_lossy().into_owned());
+        if let Some(signal) = shutdown_signal {
+            msg.set_shutdown_signal(signal.to_string());
+        }
+        if let Some(timeout) = shutdown_timeout {
+            msg.set_shutdown_timeout(timeout);
+        }
+        if let Some(limits) = resource_limits {
+            msg.set_resource_limits(limits);
+        }
+        if let Some(limits) = rlimits {
+            msg.set_rlimits(limits);
+        }
+        msg.set_ambient_capabilities(protobuf::RepeatedField::from_vec(ambient_capabilities));
+        if let Some(isolation) = isolation {
+            msg.set_isolation(isolation);
+        }
+        let reply = self.request::<_, protocol::SpawnOk>(&msg, None)?;
         Ok(reply.get_pid() as Pid)
     }
 
-    pub fn terminate(&self, pid: Pid) -> Result<i32> {
+    /// Stop the process with the given pid, overriding the shutdown signal and timeout it was
+    /// spawned with if they're given.
+    pub fn terminate(
+        &self,
+        pid: Pid,
+        shutdown_signal: Option<&str>,
+        shutdown_timeout: Option<u32>,
+    ) -> Result<i32> {
         let mut msg = protocol::Terminate::new();
         msg.set_pid(pid.into());
-        Self::send(&self.tx, &msg)?;
-        let reply = Self::recv::<protocol::TerminateOk>(&self.rx)?;
+        if let Some(signal) = shutdown_signal {
+            msg.set_shutdown_signal(signal.to_string());
+        }
+        if let Some(timeout) = shutdown_timeout {
+            msg.set_shutdown_timeout(timeout);
+        }
+        let reply = self.request::<_, protocol::TerminateOk>(&msg, None)?;
         Ok(reply.get_exit_code())
     }
 }
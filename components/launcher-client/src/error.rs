@@ -34,6 +34,7 @@ pub enum Error {
     Protocol(protocol::NetErr),
     Send(ipc_channel::Error),
     Serialize(protobuf::ProtobufError),
+    Timeout,
 }
 
 pub type Result<T> = result::Result<T, Error>;
@@ -54,6 +55,7 @@ impl fmt::Display for Error {
             Error::Protocol(ref e) => format!("{}", e),
             Error::Send(ref e) => format!("Unable to send to Launcher's pipe, {}", e),
             Error::Serialize(ref e) => format!("Unable to serialize message to Launcher, {}", e),
+            Error::Timeout => format!("Timed out waiting for a reply from Launcher"),
         };
         write!(f, "{}", msg)
     }
@@ -71,6 +73,7 @@ impl error::Error for Error {
             Error::Protocol(_) => "Received an error from Launcher",
             Error::Send(_) => "Unable to send to Launcher's pipe",
             Error::Serialize(_) => "Unable to serialize message to Launcher",
+            Error::Timeout => "Timed out waiting for a reply from Launcher",
         }
     }
 }
@@ -20,8 +20,11 @@ extern crate protobuf;
 mod client;
 pub mod error;
 
-pub use protocol::{ERR_NO_RETRY_EXCODE, LAUNCHER_LOCK_CLEAN_ENV, LAUNCHER_PID_ENV,
+pub use protocol::{Isolation, ResourceLimits, ResourceUsageOk, Rlimits, ERR_NO_RETRY_EXCODE,
+                   GATEWAY_AUTH_TOKEN_ENV, LAUNCHER_LOCK_CLEAN_ENV, LAUNCHER_PID_ENV,
                    OK_NO_RETRY_EXCODE};
+#[cfg(unix)]
+pub use protocol::signal_from_name;
 
 pub use client::LauncherCli;
 pub use error::Error;
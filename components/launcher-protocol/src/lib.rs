@@ -12,12 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+extern crate habitat_core as core;
 extern crate protobuf;
 
 mod message;
 
 use std::fmt;
 
+#[cfg(unix)]
+use core::os::process::Signal;
 use protobuf::Message;
 
 pub use message::error::*;
@@ -30,6 +33,9 @@ pub const LAUNCHER_PID_ENV: &'static str = "HAB_LAUNCHER_PID";
 // Set to instruct the Supervisor to clean the Launcher's process LOCK on startup. This is useful
 // when restarting a Supervisor which terminated normally.
 pub const LAUNCHER_LOCK_CLEAN_ENV: &'static str = "HAB_LAUNCHER_LOCK_CLEAN";
+/// Environment variable holding the token clients must present to the Supervisor's HTTP Gateway.
+/// It's only passed on to the Supervisor, never to the services the Launcher spawns.
+pub const GATEWAY_AUTH_TOKEN_ENV: &'static str = "HAB_SUP_GATEWAY_AUTH_TOKEN";
 /// Process exit code from Supervisor which indicates to Launcher that the Supervisor
 /// ran to completion with a successful result. The Launcher should not attempt to restart
 /// the Supervisor and should exit immediately with a successful exit code.
@@ -38,6 +44,7 @@ pub const OK_NO_RETRY_EXCODE: i32 = 84;
 /// exit code. The Launcher should exit immediately with a non-zero exit code.
 pub const ERR_NO_RETRY_EXCODE: i32 = 86;
 
+#[derive(Debug)]
 pub struct NetTxn(Envelope);
 
 impl NetTxn {
@@ -77,6 +84,16 @@ impl NetTxn {
         self.0.get_message_id()
     }
 
+    /// Identifies a request, so that its reply can be told apart from replies to other requests
+    /// which are in flight at the same time.
+    pub fn txn_id(&self) -> u64 {
+        self.0.get_txn_id()
+    }
+
+    pub fn set_txn_id(&mut self, txn_id: u64) {
+        self.0.set_txn_id(txn_id)
+    }
+
     pub fn to_bytes(&self) -> Result<Vec<u8>, protobuf::ProtobufError> {
         self.0.write_to_bytes()
     }
@@ -92,6 +109,24 @@ where
     message
 }
 
+/// Parse the name of a signal, with or without the `SIG` prefix, into a `Signal` which the
+/// Launcher may send to a service.
+#[cfg(unix)]
+pub fn signal_from_name(name: &str) -> Option<Signal> {
+    let name = name.to_uppercase();
+    match name.trim_left_matches("SIG") {
+        "ALRM" => Some(Signal::ALRM),
+        "HUP" => Some(Signal::HUP),
+        "INT" => Some(Signal::INT),
+        "KILL" => Some(Signal::KILL),
+        "QUIT" => Some(Signal::QUIT),
+        "TERM" => Some(Signal::TERM),
+        "USR1" => Some(Signal::USR1),
+        "USR2" => Some(Signal::USR2),
+        _ => None,
+    }
+}
+
 impl fmt::Display for NetErr {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{:?}: {}", self.get_code(), self.get_msg())
@@ -108,3 +143,34 @@ impl fmt::Display for ShutdownMethod {
         write!(f, "{}", printable)
     }
 }
+
+#[cfg(all(test, unix))]
+mod test {
+    use super::*;
+
+    fn is_term(signal: Option<Signal>) -> bool {
+        match signal {
+            Some(Signal::TERM) => true,
+            _ => false,
+        }
+    }
+
+    #[test]
+    fn signal_from_name_with_or_without_prefix() {
+        assert!(is_term(signal_from_name("TERM")));
+        assert!(is_term(signal_from_name("SIGTERM")));
+        assert!(is_term(signal_from_name("sigterm")));
+        match signal_from_name("Usr2") {
+            Some(Signal::USR2) => (),
+            _ => panic!("USR2 was not parsed"),
+        }
+    }
+
+    #[test]
+    fn signal_from_name_rejects_unknown_signals() {
+        assert!(signal_from_name("WINCH").is_none());
+        assert!(signal_from_name("SIG").is_none());
+        assert!(signal_from_name("").is_none());
+        assert!(signal_from_name("TERMINATE").is_none());
+    }
+}
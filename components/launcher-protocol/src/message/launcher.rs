@@ -207,6 +207,1828 @@ impl ::protobuf::reflect::ProtobufValue for Register {
     }
 }
 
+#[derive(PartialEq,Clone,Default)]
+pub struct ExitCode {
+    // message fields
+    pid: ::std::option::Option<i64>,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+// see codegen.rs for the explanation why impl Sync explicitly
+unsafe impl ::std::marker::Sync for ExitCode {}
+
+impl ExitCode {
+    pub fn new() -> ExitCode {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static ExitCode {
+        static mut instance: ::protobuf::lazy::Lazy<ExitCode> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ExitCode,
+        };
+        unsafe {
+            instance.get(ExitCode::new)
+        }
+    }
+
+    // optional int64 pid = 1;
+
+    pub fn clear_pid(&mut self) {
+        self.pid = ::std::option::Option::None;
+    }
+
+    pub fn has_pid(&self) -> bool {
+        self.pid.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_pid(&mut self, v: i64) {
+        self.pid = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_pid(&self) -> i64 {
+        self.pid.unwrap_or(0)
+    }
+
+    fn get_pid_for_reflect(&self) -> &::std::option::Option<i64> {
+        &self.pid
+    }
+
+    fn mut_pid_for_reflect(&mut self) -> &mut ::std::option::Option<i64> {
+        &mut self.pid
+    }
+}
+
+impl ::protobuf::Message for ExitCode {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_int64()?;
+                    self.pid = ::std::option::Option::Some(tmp);
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if let Some(v) = self.pid {
+            my_size += ::protobuf::rt::value_size(1, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if let Some(v) = self.pid {
+            os.write_int64(1, v)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
+
+impl ::protobuf::MessageStatic for ExitCode {
+    fn new() -> ExitCode {
+        ExitCode::new()
+    }
+
+    fn descriptor_static(_: ::std::option::Option<ExitCode>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeInt64>(
+                    "pid",
+                    ExitCode::get_pid_for_reflect,
+                    ExitCode::mut_pid_for_reflect,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<ExitCode>(
+                    "ExitCode",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+}
+
+impl ::protobuf::Clear for ExitCode {
+    fn clear(&mut self) {
+        self.clear_pid();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for ExitCode {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for ExitCode {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct ExitCodeOk {
+    // message fields
+    exit_code: ::std::option::Option<i32>,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+// see codegen.rs for the explanation why impl Sync explicitly
+unsafe impl ::std::marker::Sync for ExitCodeOk {}
+
+impl ExitCodeOk {
+    pub fn new() -> ExitCodeOk {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static ExitCodeOk {
+        static mut instance: ::protobuf::lazy::Lazy<ExitCodeOk> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ExitCodeOk,
+        };
+        unsafe {
+            instance.get(ExitCodeOk::new)
+        }
+    }
+
+    // optional int32 exit_code = 1;
+
+    pub fn clear_exit_code(&mut self) {
+        self.exit_code = ::std::option::Option::None;
+    }
+
+    pub fn has_exit_code(&self) -> bool {
+        self.exit_code.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_exit_code(&mut self, v: i32) {
+        self.exit_code = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_exit_code(&self) -> i32 {
+        self.exit_code.unwrap_or(0)
+    }
+
+    fn get_exit_code_for_reflect(&self) -> &::std::option::Option<i32> {
+        &self.exit_code
+    }
+
+    fn mut_exit_code_for_reflect(&mut self) -> &mut ::std::option::Option<i32> {
+        &mut self.exit_code
+    }
+}
+
+impl ::protobuf::Message for ExitCodeOk {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_int32()?;
+                    self.exit_code = ::std::option::Option::Some(tmp);
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if let Some(v) = self.exit_code {
+            my_size += ::protobuf::rt::value_size(1, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if let Some(v) = self.exit_code {
+            os.write_int32(1, v)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
+
+impl ::protobuf::MessageStatic for ExitCodeOk {
+    fn new() -> ExitCodeOk {
+        ExitCodeOk::new()
+    }
+
+    fn descriptor_static(_: ::std::option::Option<ExitCodeOk>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeInt32>(
+                    "exit_code",
+                    ExitCodeOk::get_exit_code_for_reflect,
+                    ExitCodeOk::mut_exit_code_for_reflect,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<ExitCodeOk>(
+                    "ExitCodeOk",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+}
+
+impl ::protobuf::Clear for ExitCodeOk {
+    fn clear(&mut self) {
+        self.clear_exit_code();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for ExitCodeOk {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for ExitCodeOk {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct ResourceLimits {
+    // message fields
+    memory_max: ::std::option::Option<u64>,
+    cpu_weight: ::std::option::Option<u32>,
+    cpu_quota: ::std::option::Option<u32>,
+    pids_max: ::std::option::Option<u64>,
+    io_weight: ::std::option::Option<u32>,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+// see codegen.rs for the explanation why impl Sync explicitly
+unsafe impl ::std::marker::Sync for ResourceLimits {}
+
+impl ResourceLimits {
+    pub fn new() -> ResourceLimits {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static ResourceLimits {
+        static mut instance: ::protobuf::lazy::Lazy<ResourceLimits> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ResourceLimits,
+        };
+        unsafe {
+            instance.get(ResourceLimits::new)
+        }
+    }
+
+    // optional uint64 memory_max = 1;
+
+    pub fn clear_memory_max(&mut self) {
+        self.memory_max = ::std::option::Option::None;
+    }
+
+    pub fn has_memory_max(&self) -> bool {
+        self.memory_max.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_memory_max(&mut self, v: u64) {
+        self.memory_max = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_memory_max(&self) -> u64 {
+        self.memory_max.unwrap_or(0)
+    }
+
+    fn get_memory_max_for_reflect(&self) -> &::std::option::Option<u64> {
+        &self.memory_max
+    }
+
+    fn mut_memory_max_for_reflect(&mut self) -> &mut ::std::option::Option<u64> {
+        &mut self.memory_max
+    }
+
+    // optional uint32 cpu_weight = 2;
+
+    pub fn clear_cpu_weight(&mut self) {
+        self.cpu_weight = ::std::option::Option::None;
+    }
+
+    pub fn has_cpu_weight(&self) -> bool {
+        self.cpu_weight.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_cpu_weight(&mut self, v: u32) {
+        self.cpu_weight = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_cpu_weight(&self) -> u32 {
+        self.cpu_weight.unwrap_or(0)
+    }
+
+    fn get_cpu_weight_for_reflect(&self) -> &::std::option::Option<u32> {
+        &self.cpu_weight
+    }
+
+    fn mut_cpu_weight_for_reflect(&mut self) -> &mut ::std::option::Option<u32> {
+        &mut self.cpu_weight
+    }
+
+    // optional uint32 cpu_quota = 3;
+
+    pub fn clear_cpu_quota(&mut self) {
+        self.cpu_quota = ::std::option::Option::None;
+    }
+
+    pub fn has_cpu_quota(&self) -> bool {
+        self.cpu_quota.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_cpu_quota(&mut self, v: u32) {
+        self.cpu_quota = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_cpu_quota(&self) -> u32 {
+        self.cpu_quota.unwrap_or(0)
+    }
+
+    fn get_cpu_quota_for_reflect(&self) -> &::std::option::Option<u32> {
+        &self.cpu_quota
+    }
+
+    fn mut_cpu_quota_for_reflect(&mut self) -> &mut ::std::option::Option<u32> {
+        &mut self.cpu_quota
+    }
+
+    // optional uint64 pids_max = 4;
+
+    pub fn clear_pids_max(&mut self) {
+        self.pids_max = ::std::option::Option::None;
+    }
+
+    pub fn has_pids_max(&self) -> bool {
+        self.pids_max.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_pids_max(&mut self, v: u64) {
+        self.pids_max = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_pids_max(&self) -> u64 {
+        self.pids_max.unwrap_or(0)
+    }
+
+    fn get_pids_max_for_reflect(&self) -> &::std::option::Option<u64> {
+        &self.pids_max
+    }
+
+    fn mut_pids_max_for_reflect(&mut self) -> &mut ::std::option::Option<u64> {
+        &mut self.pids_max
+    }
+
+    // optional uint32 io_weight = 5;
+
+    pub fn clear_io_weight(&mut self) {
+        self.io_weight = ::std::option::Option::None;
+    }
+
+    pub fn has_io_weight(&self) -> bool {
+        self.io_weight.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_io_weight(&mut self, v: u32) {
+        self.io_weight = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_io_weight(&self) -> u32 {
+        self.io_weight.unwrap_or(0)
+    }
+
+    fn get_io_weight_for_reflect(&self) -> &::std::option::Option<u32> {
+        &self.io_weight
+    }
+
+    fn mut_io_weight_for_reflect(&mut self) -> &mut ::std::option::Option<u32> {
+        &mut self.io_weight
+    }
+}
+
+impl ::protobuf::Message for ResourceLimits {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.memory_max = ::std::option::Option::Some(tmp);
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.cpu_weight = ::std::option::Option::Some(tmp);
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.cpu_quota = ::std::option::Option::Some(tmp);
+                },
+                4 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.pids_max = ::std::option::Option::Some(tmp);
+                },
+                5 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.io_weight = ::std::option::Option::Some(tmp);
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if let Some(v) = self.memory_max {
+            my_size += ::protobuf::rt::value_size(1, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(v) = self.cpu_weight {
+            my_size += ::protobuf::rt::value_size(2, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(v) = self.cpu_quota {
+            my_size += ::protobuf::rt::value_size(3, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(v) = self.pids_max {
+            my_size += ::protobuf::rt::value_size(4, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(v) = self.io_weight {
+            my_size += ::protobuf::rt::value_size(5, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if let Some(v) = self.memory_max {
+            os.write_uint64(1, v)?;
+        }
+        if let Some(v) = self.cpu_weight {
+            os.write_uint32(2, v)?;
+        }
+        if let Some(v) = self.cpu_quota {
+            os.write_uint32(3, v)?;
+        }
+        if let Some(v) = self.pids_max {
+            os.write_uint64(4, v)?;
+        }
+        if let Some(v) = self.io_weight {
+            os.write_uint32(5, v)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
+
+impl ::protobuf::MessageStatic for ResourceLimits {
+    fn new() -> ResourceLimits {
+        ResourceLimits::new()
+    }
+
+    fn descriptor_static(_: ::std::option::Option<ResourceLimits>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "memory_max",
+                    ResourceLimits::get_memory_max_for_reflect,
+                    ResourceLimits::mut_memory_max_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "cpu_weight",
+                    ResourceLimits::get_cpu_weight_for_reflect,
+                    ResourceLimits::mut_cpu_weight_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "cpu_quota",
+                    ResourceLimits::get_cpu_quota_for_reflect,
+                    ResourceLimits::mut_cpu_quota_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "pids_max",
+                    ResourceLimits::get_pids_max_for_reflect,
+                    ResourceLimits::mut_pids_max_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "io_weight",
+                    ResourceLimits::get_io_weight_for_reflect,
+                    ResourceLimits::mut_io_weight_for_reflect,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<ResourceLimits>(
+                    "ResourceLimits",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+}
+
+impl ::protobuf::Clear for ResourceLimits {
+    fn clear(&mut self) {
+        self.clear_memory_max();
+        self.clear_cpu_weight();
+        self.clear_cpu_quota();
+        self.clear_pids_max();
+        self.clear_io_weight();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for ResourceLimits {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for ResourceLimits {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct Rlimits {
+    // message fields
+    nofile: ::std::option::Option<u64>,
+    nproc: ::std::option::Option<u64>,
+    core: ::std::option::Option<u64>,
+    memlock: ::std::option::Option<u64>,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+// see codegen.rs for the explanation why impl Sync explicitly
+unsafe impl ::std::marker::Sync for Rlimits {}
+
+impl Rlimits {
+    pub fn new() -> Rlimits {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static Rlimits {
+        static mut instance: ::protobuf::lazy::Lazy<Rlimits> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const Rlimits,
+        };
+        unsafe {
+            instance.get(Rlimits::new)
+        }
+    }
+
+    // optional uint64 nofile = 1;
+
+    pub fn clear_nofile(&mut self) {
+        self.nofile = ::std::option::Option::None;
+    }
+
+    pub fn has_nofile(&self) -> bool {
+        self.nofile.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_nofile(&mut self, v: u64) {
+        self.nofile = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_nofile(&self) -> u64 {
+        self.nofile.unwrap_or(0)
+    }
+
+    fn get_nofile_for_reflect(&self) -> &::std::option::Option<u64> {
+        &self.nofile
+    }
+
+    fn mut_nofile_for_reflect(&mut self) -> &mut ::std::option::Option<u64> {
+        &mut self.nofile
+    }
+
+    // optional uint64 nproc = 2;
+
+    pub fn clear_nproc(&mut self) {
+        self.nproc = ::std::option::Option::None;
+    }
+
+    pub fn has_nproc(&self) -> bool {
+        self.nproc.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_nproc(&mut self, v: u64) {
+        self.nproc = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_nproc(&self) -> u64 {
+        self.nproc.unwrap_or(0)
+    }
+
+    fn get_nproc_for_reflect(&self) -> &::std::option::Option<u64> {
+        &self.nproc
+    }
+
+    fn mut_nproc_for_reflect(&mut self) -> &mut ::std::option::Option<u64> {
+        &mut self.nproc
+    }
+
+    // optional uint64 core = 3;
+
+    pub fn clear_core(&mut self) {
+        self.core = ::std::option::Option::None;
+    }
+
+    pub fn has_core(&self) -> bool {
+        self.core.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_core(&mut self, v: u64) {
+        self.core = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_core(&self) -> u64 {
+        self.core.unwrap_or(0)
+    }
+
+    fn get_core_for_reflect(&self) -> &::std::option::Option<u64> {
+        &self.core
+    }
+
+    fn mut_core_for_reflect(&mut self) -> &mut ::std::option::Option<u64> {
+        &mut self.core
+    }
+
+    // optional uint64 memlock = 4;
+
+    pub fn clear_memlock(&mut self) {
+        self.memlock = ::std::option::Option::None;
+    }
+
+    pub fn has_memlock(&self) -> bool {
+        self.memlock.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_memlock(&mut self, v: u64) {
+        self.memlock = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_memlock(&self) -> u64 {
+        self.memlock.unwrap_or(0)
+    }
+
+    fn get_memlock_for_reflect(&self) -> &::std::option::Option<u64> {
+        &self.memlock
+    }
+
+    fn mut_memlock_for_reflect(&mut self) -> &mut ::std::option::Option<u64> {
+        &mut self.memlock
+    }
+}
+
+impl ::protobuf::Message for Rlimits {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.nofile = ::std::option::Option::Some(tmp);
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.nproc = ::std::option::Option::Some(tmp);
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.core = ::std::option::Option::Some(tmp);
+                },
+                4 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.memlock = ::std::option::Option::Some(tmp);
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if let Some(v) = self.nofile {
+            my_size += ::protobuf::rt::value_size(1, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(v) = self.nproc {
+            my_size += ::protobuf::rt::value_size(2, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(v) = self.core {
+            my_size += ::protobuf::rt::value_size(3, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(v) = self.memlock {
+            my_size += ::protobuf::rt::value_size(4, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if let Some(v) = self.nofile {
+            os.write_uint64(1, v)?;
+        }
+        if let Some(v) = self.nproc {
+            os.write_uint64(2, v)?;
+        }
+        if let Some(v) = self.core {
+            os.write_uint64(3, v)?;
+        }
+        if let Some(v) = self.memlock {
+            os.write_uint64(4, v)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
+
+impl ::protobuf::MessageStatic for Rlimits {
+    fn new() -> Rlimits {
+        Rlimits::new()
+    }
+
+    fn descriptor_static(_: ::std::option::Option<Rlimits>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "nofile",
+                    Rlimits::get_nofile_for_reflect,
+                    Rlimits::mut_nofile_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "nproc",
+                    Rlimits::get_nproc_for_reflect,
+                    Rlimits::mut_nproc_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "core",
+                    Rlimits::get_core_for_reflect,
+                    Rlimits::mut_core_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "memlock",
+                    Rlimits::get_memlock_for_reflect,
+                    Rlimits::mut_memlock_for_reflect,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<Rlimits>(
+                    "Rlimits",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+}
+
+impl ::protobuf::Clear for Rlimits {
+    fn clear(&mut self) {
+        self.clear_nofile();
+        self.clear_nproc();
+        self.clear_core();
+        self.clear_memlock();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for Rlimits {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Rlimits {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct Isolation {
+    // message fields
+    mount: ::std::option::Option<bool>,
+    pid: ::std::option::Option<bool>,
+    network: ::std::option::Option<bool>,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+// see codegen.rs for the explanation why impl Sync explicitly
+unsafe impl ::std::marker::Sync for Isolation {}
+
+impl Isolation {
+    pub fn new() -> Isolation {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static Isolation {
+        static mut instance: ::protobuf::lazy::Lazy<Isolation> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const Isolation,
+        };
+        unsafe {
+            instance.get(Isolation::new)
+        }
+    }
+
+    // optional bool mount = 1;
+
+    pub fn clear_mount(&mut self) {
+        self.mount = ::std::option::Option::None;
+    }
+
+    pub fn has_mount(&self) -> bool {
+        self.mount.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_mount(&mut self, v: bool) {
+        self.mount = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_mount(&self) -> bool {
+        self.mount.unwrap_or(false)
+    }
+
+    fn get_mount_for_reflect(&self) -> &::std::option::Option<bool> {
+        &self.mount
+    }
+
+    fn mut_mount_for_reflect(&mut self) -> &mut ::std::option::Option<bool> {
+        &mut self.mount
+    }
+
+    // optional bool pid = 2;
+
+    pub fn clear_pid(&mut self) {
+        self.pid = ::std::option::Option::None;
+    }
+
+    pub fn has_pid(&self) -> bool {
+        self.pid.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_pid(&mut self, v: bool) {
+        self.pid = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_pid(&self) -> bool {
+        self.pid.unwrap_or(false)
+    }
+
+    fn get_pid_for_reflect(&self) -> &::std::option::Option<bool> {
+        &self.pid
+    }
+
+    fn mut_pid_for_reflect(&mut self) -> &mut ::std::option::Option<bool> {
+        &mut self.pid
+    }
+
+    // optional bool network = 3;
+
+    pub fn clear_network(&mut self) {
+        self.network = ::std::option::Option::None;
+    }
+
+    pub fn has_network(&self) -> bool {
+        self.network.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_network(&mut self, v: bool) {
+        self.network = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_network(&self) -> bool {
+        self.network.unwrap_or(false)
+    }
+
+    fn get_network_for_reflect(&self) -> &::std::option::Option<bool> {
+        &self.network
+    }
+
+    fn mut_network_for_reflect(&mut self) -> &mut ::std::option::Option<bool> {
+        &mut self.network
+    }
+}
+
+impl ::protobuf::Message for Isolation {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.mount = ::std::option::Option::Some(tmp);
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.pid = ::std::option::Option::Some(tmp);
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.network = ::std::option::Option::Some(tmp);
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if let Some(v) = self.mount {
+            my_size += 2;
+        }
+        if let Some(v) = self.pid {
+            my_size += 2;
+        }
+        if let Some(v) = self.network {
+            my_size += 2;
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if let Some(v) = self.mount {
+            os.write_bool(1, v)?;
+        }
+        if let Some(v) = self.pid {
+            os.write_bool(2, v)?;
+        }
+        if let Some(v) = self.network {
+            os.write_bool(3, v)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
+
+impl ::protobuf::MessageStatic for Isolation {
+    fn new() -> Isolation {
+        Isolation::new()
+    }
+
+    fn descriptor_static(_: ::std::option::Option<Isolation>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                    "mount",
+                    Isolation::get_mount_for_reflect,
+                    Isolation::mut_mount_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                    "pid",
+                    Isolation::get_pid_for_reflect,
+                    Isolation::mut_pid_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                    "network",
+                    Isolation::get_network_for_reflect,
+                    Isolation::mut_network_for_reflect,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<Isolation>(
+                    "Isolation",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+}
+
+impl ::protobuf::Clear for Isolation {
+    fn clear(&mut self) {
+        self.clear_mount();
+        self.clear_pid();
+        self.clear_network();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for Isolation {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Isolation {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct ResourceUsage {
+    // message fields
+    pid: ::std::option::Option<i64>,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+// see codegen.rs for the explanation why impl Sync explicitly
+unsafe impl ::std::marker::Sync for ResourceUsage {}
+
+impl ResourceUsage {
+    pub fn new() -> ResourceUsage {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static ResourceUsage {
+        static mut instance: ::protobuf::lazy::Lazy<ResourceUsage> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ResourceUsage,
+        };
+        unsafe {
+            instance.get(ResourceUsage::new)
+        }
+    }
+
+    // optional int64 pid = 1;
+
+    pub fn clear_pid(&mut self) {
+        self.pid = ::std::option::Option::None;
+    }
+
+    pub fn has_pid(&self) -> bool {
+        self.pid.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_pid(&mut self, v: i64) {
+        self.pid = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_pid(&self) -> i64 {
+        self.pid.unwrap_or(0)
+    }
+
+    fn get_pid_for_reflect(&self) -> &::std::option::Option<i64> {
+        &self.pid
+    }
+
+    fn mut_pid_for_reflect(&mut self) -> &mut ::std::option::Option<i64> {
+        &mut self.pid
+    }
+}
+
+impl ::protobuf::Message for ResourceUsage {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_int64()?;
+                    self.pid = ::std::option::Option::Some(tmp);
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if let Some(v) = self.pid {
+            my_size += ::protobuf::rt::value_size(1, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if let Some(v) = self.pid {
+            os.write_int64(1, v)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
+
+impl ::protobuf::MessageStatic for ResourceUsage {
+    fn new() -> ResourceUsage {
+        ResourceUsage::new()
+    }
+
+    fn descriptor_static(_: ::std::option::Option<ResourceUsage>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeInt64>(
+                    "pid",
+                    ResourceUsage::get_pid_for_reflect,
+                    ResourceUsage::mut_pid_for_reflect,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<ResourceUsage>(
+                    "ResourceUsage",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+}
+
+impl ::protobuf::Clear for ResourceUsage {
+    fn clear(&mut self) {
+        self.clear_pid();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for ResourceUsage {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for ResourceUsage {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct ResourceUsageOk {
+    // message fields
+    memory_current: ::std::option::Option<u64>,
+    cpu_usage_usec: ::std::option::Option<u64>,
+    pids_current: ::std::option::Option<u64>,
+    io_read_bytes: ::std::option::Option<u64>,
+    io_write_bytes: ::std::option::Option<u64>,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+// see codegen.rs for the explanation why impl Sync explicitly
+unsafe impl ::std::marker::Sync for ResourceUsageOk {}
+
+impl ResourceUsageOk {
+    pub fn new() -> ResourceUsageOk {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static ResourceUsageOk {
+        static mut instance: ::protobuf::lazy::Lazy<ResourceUsageOk> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ResourceUsageOk,
+        };
+        unsafe {
+            instance.get(ResourceUsageOk::new)
+        }
+    }
+
+    // optional uint64 memory_current = 1;
+
+    pub fn clear_memory_current(&mut self) {
+        self.memory_current = ::std::option::Option::None;
+    }
+
+    pub fn has_memory_current(&self) -> bool {
+        self.memory_current.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_memory_current(&mut self, v: u64) {
+        self.memory_current = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_memory_current(&self) -> u64 {
+        self.memory_current.unwrap_or(0)
+    }
+
+    fn get_memory_current_for_reflect(&self) -> &::std::option::Option<u64> {
+        &self.memory_current
+    }
+
+    fn mut_memory_current_for_reflect(&mut self) -> &mut ::std::option::Option<u64> {
+        &mut self.memory_current
+    }
+
+    // optional uint64 cpu_usage_usec = 2;
+
+    pub fn clear_cpu_usage_usec(&mut self) {
+        self.cpu_usage_usec = ::std::option::Option::None;
+    }
+
+    pub fn has_cpu_usage_usec(&self) -> bool {
+        self.cpu_usage_usec.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_cpu_usage_usec(&mut self, v: u64) {
+        self.cpu_usage_usec = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_cpu_usage_usec(&self) -> u64 {
+        self.cpu_usage_usec.unwrap_or(0)
+    }
+
+    fn get_cpu_usage_usec_for_reflect(&self) -> &::std::option::Option<u64> {
+        &self.cpu_usage_usec
+    }
+
+    fn mut_cpu_usage_usec_for_reflect(&mut self) -> &mut ::std::option::Option<u64> {
+        &mut self.cpu_usage_usec
+    }
+
+    // optional uint64 pids_current = 3;
+
+    pub fn clear_pids_current(&mut self) {
+        self.pids_current = ::std::option::Option::None;
+    }
+
+    pub fn has_pids_current(&self) -> bool {
+        self.pids_current.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_pids_current(&mut self, v: u64) {
+        self.pids_current = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_pids_current(&self) -> u64 {
+        self.pids_current.unwrap_or(0)
+    }
+
+    fn get_pids_current_for_reflect(&self) -> &::std::option::Option<u64> {
+        &self.pids_current
+    }
+
+    fn mut_pids_current_for_reflect(&mut self) -> &mut ::std::option::Option<u64> {
+        &mut self.pids_current
+    }
+
+    // optional uint64 io_read_bytes = 4;
+
+    pub fn clear_io_read_bytes(&mut self) {
+        self.io_read_bytes = ::std::option::Option::None;
+    }
+
+    pub fn has_io_read_bytes(&self) -> bool {
+        self.io_read_bytes.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_io_read_bytes(&mut self, v: u64) {
+        self.io_read_bytes = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_io_read_bytes(&self) -> u64 {
+        self.io_read_bytes.unwrap_or(0)
+    }
+
+    fn get_io_read_bytes_for_reflect(&self) -> &::std::option::Option<u64> {
+        &self.io_read_bytes
+    }
+
+    fn mut_io_read_bytes_for_reflect(&mut self) -> &mut ::std::option::Option<u64> {
+        &mut self.io_read_bytes
+    }
+
+    // optional uint64 io_write_bytes = 5;
+
+    pub fn clear_io_write_bytes(&mut self) {
+        self.io_write_bytes = ::std::option::Option::None;
+    }
+
+    pub fn has_io_write_bytes(&self) -> bool {
+        self.io_write_bytes.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_io_write_bytes(&mut self, v: u64) {
+        self.io_write_bytes = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_io_write_bytes(&self) -> u64 {
+        self.io_write_bytes.unwrap_or(0)
+    }
+
+    fn get_io_write_bytes_for_reflect(&self) -> &::std::option::Option<u64> {
+        &self.io_write_bytes
+    }
+
+    fn mut_io_write_bytes_for_reflect(&mut self) -> &mut ::std::option::Option<u64> {
+        &mut self.io_write_bytes
+    }
+}
+
+impl ::protobuf::Message for ResourceUsageOk {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.memory_current = ::std::option::Option::Some(tmp);
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.cpu_usage_usec = ::std::option::Option::Some(tmp);
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.pids_current = ::std::option::Option::Some(tmp);
+                },
+                4 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.io_read_bytes = ::std::option::Option::Some(tmp);
+                },
+                5 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.io_write_bytes = ::std::option::Option::Some(tmp);
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if let Some(v) = self.memory_current {
+            my_size += ::protobuf::rt::value_size(1, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(v) = self.cpu_usage_usec {
+            my_size += ::protobuf::rt::value_size(2, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(v) = self.pids_current {
+            my_size += ::protobuf::rt::value_size(3, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(v) = self.io_read_bytes {
+            my_size += ::protobuf::rt::value_size(4, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(v) = self.io_write_bytes {
+            my_size += ::protobuf::rt::value_size(5, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if let Some(v) = self.memory_current {
+            os.write_uint64(1, v)?;
+        }
+        if let Some(v) = self.cpu_usage_usec {
+            os.write_uint64(2, v)?;
+        }
+        if let Some(v) = self.pids_current {
+            os.write_uint64(3, v)?;
+        }
+        if let Some(v) = self.io_read_bytes {
+            os.write_uint64(4, v)?;
+        }
+        if let Some(v) = self.io_write_bytes {
+            os.write_uint64(5, v)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
+
+impl ::protobuf::MessageStatic for ResourceUsageOk {
+    fn new() -> ResourceUsageOk {
+        ResourceUsageOk::new()
+    }
+
+    fn descriptor_static(_: ::std::option::Option<ResourceUsageOk>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "memory_current",
+                    ResourceUsageOk::get_memory_current_for_reflect,
+                    ResourceUsageOk::mut_memory_current_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "cpu_usage_usec",
+                    ResourceUsageOk::get_cpu_usage_usec_for_reflect,
+                    ResourceUsageOk::mut_cpu_usage_usec_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "pids_current",
+                    ResourceUsageOk::get_pids_current_for_reflect,
+                    ResourceUsageOk::mut_pids_current_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "io_read_bytes",
+                    ResourceUsageOk::get_io_read_bytes_for_reflect,
+                    ResourceUsageOk::mut_io_read_bytes_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "io_write_bytes",
+                    ResourceUsageOk::get_io_write_bytes_for_reflect,
+                    ResourceUsageOk::mut_io_write_bytes_for_reflect,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<ResourceUsageOk>(
+                    "ResourceUsageOk",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+}
+
+impl ::protobuf::Clear for ResourceUsageOk {
+    fn clear(&mut self) {
+        self.clear_memory_current();
+        self.clear_cpu_usage_usec();
+        self.clear_pids_current();
+        self.clear_io_read_bytes();
+        self.clear_io_write_bytes();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for ResourceUsageOk {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for ResourceUsageOk {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
 #[derive(PartialEq,Clone,Default)]
 pub struct Restart {
     // message fields
@@ -391,6 +2213,13 @@ pub struct Spawn {
     pub env: ::std::collections::HashMap<::std::string::String, ::std::string::String>,
     svc_user_id: ::std::option::Option<u32>,
     svc_group_id: ::std::option::Option<u32>,
+    log_path: ::protobuf::SingularField<::std::string::String>,
+    shutdown_signal: ::protobuf::SingularField<::std::string::String>,
+    shutdown_timeout: ::std::option::Option<u32>,
+    resource_limits: ::protobuf::SingularPtrField<ResourceLimits>,
+    rlimits: ::protobuf::SingularPtrField<Rlimits>,
+    ambient_capabilities: ::protobuf::RepeatedField<::std::string::String>,
+    isolation: ::protobuf::SingularPtrField<Isolation>,
     // special fields
     unknown_fields: ::protobuf::UnknownFields,
     cached_size: ::protobuf::CachedSize,
@@ -720,10 +2549,296 @@ impl Spawn {
     fn mut_svc_group_id_for_reflect(&mut self) -> &mut ::std::option::Option<u32> {
         &mut self.svc_group_id
     }
+
+    // optional string log_path = 9;
+
+    pub fn clear_log_path(&mut self) {
+        self.log_path.clear();
+    }
+
+    pub fn has_log_path(&self) -> bool {
+        self.log_path.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_log_path(&mut self, v: ::std::string::String) {
+        self.log_path = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_log_path(&mut self) -> &mut ::std::string::String {
+        if self.log_path.is_none() {
+            self.log_path.set_default();
+        }
+        self.log_path.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_log_path(&mut self) -> ::std::string::String {
+        self.log_path.take().unwrap_or_else(|| ::std::string::String::new())
+    }
+
+    pub fn get_log_path(&self) -> &str {
+        match self.log_path.as_ref() {
+            Some(v) => &v,
+            None => "",
+        }
+    }
+
+    fn get_log_path_for_reflect(&self) -> &::protobuf::SingularField<::std::string::String> {
+        &self.log_path
+    }
+
+    fn mut_log_path_for_reflect(&mut self) -> &mut ::protobuf::SingularField<::std::string::String> {
+        &mut self.log_path
+    }
+
+    // optional string shutdown_signal = 10;
+
+    pub fn clear_shutdown_signal(&mut self) {
+        self.shutdown_signal.clear();
+    }
+
+    pub fn has_shutdown_signal(&self) -> bool {
+        self.shutdown_signal.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_shutdown_signal(&mut self, v: ::std::string::String) {
+        self.shutdown_signal = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_shutdown_signal(&mut self) -> &mut ::std::string::String {
+        if self.shutdown_signal.is_none() {
+            self.shutdown_signal.set_default();
+        }
+        self.shutdown_signal.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_shutdown_signal(&mut self) -> ::std::string::String {
+        self.shutdown_signal.take().unwrap_or_else(|| ::std::string::String::new())
+    }
+
+    pub fn get_shutdown_signal(&self) -> &str {
+        match self.shutdown_signal.as_ref() {
+            Some(v) => &v,
+            None => "",
+        }
+    }
+
+    fn get_shutdown_signal_for_reflect(&self) -> &::protobuf::SingularField<::std::string::String> {
+        &self.shutdown_signal
+    }
+
+    fn mut_shutdown_signal_for_reflect(&mut self) -> &mut ::protobuf::SingularField<::std::string::String> {
+        &mut self.shutdown_signal
+    }
+
+    // optional uint32 shutdown_timeout = 11;
+
+    pub fn clear_shutdown_timeout(&mut self) {
+        self.shutdown_timeout = ::std::option::Option::None;
+    }
+
+    pub fn has_shutdown_timeout(&self) -> bool {
+        self.shutdown_timeout.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_shutdown_timeout(&mut self, v: u32) {
+        self.shutdown_timeout = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_shutdown_timeout(&self) -> u32 {
+        self.shutdown_timeout.unwrap_or(0)
+    }
+
+    fn get_shutdown_timeout_for_reflect(&self) -> &::std::option::Option<u32> {
+        &self.shutdown_timeout
+    }
+
+    fn mut_shutdown_timeout_for_reflect(&mut self) -> &mut ::std::option::Option<u32> {
+        &mut self.shutdown_timeout
+    }
+
+    // optional .launcher.ResourceLimits resource_limits = 12;
+
+    pub fn clear_resource_limits(&mut self) {
+        self.resource_limits.clear();
+    }
+
+    pub fn has_resource_limits(&self) -> bool {
+        self.resource_limits.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_resource_limits(&mut self, v: ResourceLimits) {
+        self.resource_limits = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_resource_limits(&mut self) -> &mut ResourceLimits {
+        if self.resource_limits.is_none() {
+            self.resource_limits.set_default();
+        }
+        self.resource_limits.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_resource_limits(&mut self) -> ResourceLimits {
+        self.resource_limits.take().unwrap_or_else(|| ResourceLimits::new())
+    }
+
+    pub fn get_resource_limits(&self) -> &ResourceLimits {
+        self.resource_limits.as_ref().unwrap_or_else(|| ResourceLimits::default_instance())
+    }
+
+    fn get_resource_limits_for_reflect(&self) -> &::protobuf::SingularPtrField<ResourceLimits> {
+        &self.resource_limits
+    }
+
+    fn mut_resource_limits_for_reflect(&mut self) -> &mut ::protobuf::SingularPtrField<ResourceLimits> {
+        &mut self.resource_limits
+    }
+
+    // optional .launcher.Rlimits rlimits = 13;
+
+    pub fn clear_rlimits(&mut self) {
+        self.rlimits.clear();
+    }
+
+    pub fn has_rlimits(&self) -> bool {
+        self.rlimits.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_rlimits(&mut self, v: Rlimits) {
+        self.rlimits = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_rlimits(&mut self) -> &mut Rlimits {
+        if self.rlimits.is_none() {
+            self.rlimits.set_default();
+        }
+        self.rlimits.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_rlimits(&mut self) -> Rlimits {
+        self.rlimits.take().unwrap_or_else(|| Rlimits::new())
+    }
+
+    pub fn get_rlimits(&self) -> &Rlimits {
+        self.rlimits.as_ref().unwrap_or_else(|| Rlimits::default_instance())
+    }
+
+    fn get_rlimits_for_reflect(&self) -> &::protobuf::SingularPtrField<Rlimits> {
+        &self.rlimits
+    }
+
+    fn mut_rlimits_for_reflect(&mut self) -> &mut ::protobuf::SingularPtrField<Rlimits> {
+        &mut self.rlimits
+    }
+
+    // repeated string ambient_capabilities = 14;
+
+    pub fn clear_ambient_capabilities(&mut self) {
+        self.ambient_capabilities.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_ambient_capabilities(&mut self, v: ::protobuf::RepeatedField<::std::string::String>) {
+        self.ambient_capabilities = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_ambient_capabilities(&mut self) -> &mut ::protobuf::RepeatedField<::std::string::String> {
+        &mut self.ambient_capabilities
+    }
+
+    // Take field
+    pub fn take_ambient_capabilities(&mut self) -> ::protobuf::RepeatedField<::std::string::String> {
+        ::std::mem::replace(&mut self.ambient_capabilities, ::protobuf::RepeatedField::new())
+    }
+
+    pub fn get_ambient_capabilities(&self) -> &[::std::string::String] {
+        &self.ambient_capabilities
+    }
+
+    fn get_ambient_capabilities_for_reflect(&self) -> &::protobuf::RepeatedField<::std::string::String> {
+        &self.ambient_capabilities
+    }
+
+    fn mut_ambient_capabilities_for_reflect(&mut self) -> &mut ::protobuf::RepeatedField<::std::string::String> {
+        &mut self.ambient_capabilities
+    }
+
+    // optional .launcher.Isolation isolation = 15;
+
+    pub fn clear_isolation(&mut self) {
+        self.isolation.clear();
+    }
+
+    pub fn has_isolation(&self) -> bool {
+        self.isolation.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_isolation(&mut self, v: Isolation) {
+        self.isolation = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_isolation(&mut self) -> &mut Isolation {
+        if self.isolation.is_none() {
+            self.isolation.set_default();
+        }
+        self.isolation.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_isolation(&mut self) -> Isolation {
+        self.isolation.take().unwrap_or_else(|| Isolation::new())
+    }
+
+    pub fn get_isolation(&self) -> &Isolation {
+        self.isolation.as_ref().unwrap_or_else(|| Isolation::default_instance())
+    }
+
+    fn get_isolation_for_reflect(&self) -> &::protobuf::SingularPtrField<Isolation> {
+        &self.isolation
+    }
+
+    fn mut_isolation_for_reflect(&mut self) -> &mut ::protobuf::SingularPtrField<Isolation> {
+        &mut self.isolation
+    }
 }
 
 impl ::protobuf::Message for Spawn {
     fn is_initialized(&self) -> bool {
+        for v in &self.resource_limits {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        for v in &self.rlimits {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        for v in &self.isolation {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
         true
     }
 
@@ -763,6 +2878,31 @@ impl ::protobuf::Message for Spawn {
                     let tmp = is.read_uint32()?;
                     self.svc_group_id = ::std::option::Option::Some(tmp);
                 },
+                9 => {
+                    ::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.log_path)?;
+                },
+                10 => {
+                    ::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.shutdown_signal)?;
+                },
+                11 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.shutdown_timeout = ::std::option::Option::Some(tmp);
+                },
+                12 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.resource_limits)?;
+                },
+                13 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.rlimits)?;
+                },
+                14 => {
+                    ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.ambient_capabilities)?;
+                },
+                15 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.isolation)?;
+                },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
@@ -797,6 +2937,30 @@ impl ::protobuf::Message for Spawn {
         if let Some(v) = self.svc_group_id {
             my_size += ::protobuf::rt::value_size(8, v, ::protobuf::wire_format::WireTypeVarint);
         }
+        if let Some(ref v) = self.log_path.as_ref() {
+            my_size += ::protobuf::rt::string_size(9, &v);
+        }
+        if let Some(ref v) = self.shutdown_signal.as_ref() {
+            my_size += ::protobuf::rt::string_size(10, &v);
+        }
+        if let Some(v) = self.shutdown_timeout {
+            my_size += ::protobuf::rt::value_size(11, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(ref v) = self.resource_limits.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
+        if let Some(ref v) = self.rlimits.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
+        for value in &self.ambient_capabilities {
+            my_size += ::protobuf::rt::string_size(14, &value);
+        };
+        if let Some(ref v) = self.isolation.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -825,6 +2989,33 @@ impl ::protobuf::Message for Spawn {
         if let Some(v) = self.svc_group_id {
             os.write_uint32(8, v)?;
         }
+        if let Some(ref v) = self.log_path.as_ref() {
+            os.write_string(9, &v)?;
+        }
+        if let Some(ref v) = self.shutdown_signal.as_ref() {
+            os.write_string(10, &v)?;
+        }
+        if let Some(v) = self.shutdown_timeout {
+            os.write_uint32(11, v)?;
+        }
+        if let Some(ref v) = self.resource_limits.as_ref() {
+            os.write_tag(12, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        if let Some(ref v) = self.rlimits.as_ref() {
+            os.write_tag(13, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        for v in &self.ambient_capabilities {
+            os.write_string(14, &v)?;
+        };
+        if let Some(ref v) = self.isolation.as_ref() {
+            os.write_tag(15, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -909,6 +3100,41 @@ impl ::protobuf::MessageStatic for Spawn {
                     Spawn::get_svc_group_id_for_reflect,
                     Spawn::mut_svc_group_id_for_reflect,
                 ));
+                fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "log_path",
+                    Spawn::get_log_path_for_reflect,
+                    Spawn::mut_log_path_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "shutdown_signal",
+                    Spawn::get_shutdown_signal_for_reflect,
+                    Spawn::mut_shutdown_signal_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "shutdown_timeout",
+                    Spawn::get_shutdown_timeout_for_reflect,
+                    Spawn::mut_shutdown_timeout_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<ResourceLimits>>(
+                    "resource_limits",
+                    Spawn::get_resource_limits_for_reflect,
+                    Spawn::mut_resource_limits_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Rlimits>>(
+                    "rlimits",
+                    Spawn::get_rlimits_for_reflect,
+                    Spawn::mut_rlimits_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "ambient_capabilities",
+                    Spawn::get_ambient_capabilities_for_reflect,
+                    Spawn::mut_ambient_capabilities_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Isolation>>(
+                    "isolation",
+                    Spawn::get_isolation_for_reflect,
+                    Spawn::mut_isolation_for_reflect,
+                ));
                 ::protobuf::reflect::MessageDescriptor::new::<Spawn>(
                     "Spawn",
                     fields,
@@ -929,6 +3155,13 @@ impl ::protobuf::Clear for Spawn {
         self.clear_env();
         self.clear_svc_user_id();
         self.clear_svc_group_id();
+        self.clear_log_path();
+        self.clear_shutdown_signal();
+        self.clear_shutdown_timeout();
+        self.clear_resource_limits();
+        self.clear_rlimits();
+        self.clear_ambient_capabilities();
+        self.clear_isolation();
         self.unknown_fields.clear();
     }
 }
@@ -1122,6 +3355,8 @@ impl ::protobuf::reflect::ProtobufValue for SpawnOk {
 pub struct Terminate {
     // message fields
     pid: ::std::option::Option<i64>,
+    shutdown_signal: ::protobuf::SingularField<::std::string::String>,
+    shutdown_timeout: ::std::option::Option<u32>,
     // special fields
     unknown_fields: ::protobuf::UnknownFields,
     cached_size: ::protobuf::CachedSize,
@@ -1171,6 +3406,77 @@ impl Terminate {
     fn mut_pid_for_reflect(&mut self) -> &mut ::std::option::Option<i64> {
         &mut self.pid
     }
+
+    // optional string shutdown_signal = 2;
+
+    pub fn clear_shutdown_signal(&mut self) {
+        self.shutdown_signal.clear();
+    }
+
+    pub fn has_shutdown_signal(&self) -> bool {
+        self.shutdown_signal.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_shutdown_signal(&mut self, v: ::std::string::String) {
+        self.shutdown_signal = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_shutdown_signal(&mut self) -> &mut ::std::string::String {
+        if self.shutdown_signal.is_none() {
+            self.shutdown_signal.set_default();
+        }
+        self.shutdown_signal.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_shutdown_signal(&mut self) -> ::std::string::String {
+        self.shutdown_signal.take().unwrap_or_else(|| ::std::string::String::new())
+    }
+
+    pub fn get_shutdown_signal(&self) -> &str {
+        match self.shutdown_signal.as_ref() {
+            Some(v) => &v,
+            None => "",
+        }
+    }
+
+    fn get_shutdown_signal_for_reflect(&self) -> &::protobuf::SingularField<::std::string::String> {
+        &self.shutdown_signal
+    }
+
+    fn mut_shutdown_signal_for_reflect(&mut self) -> &mut ::protobuf::SingularField<::std::string::String> {
+        &mut self.shutdown_signal
+    }
+
+    // optional uint32 shutdown_timeout = 3;
+
+    pub fn clear_shutdown_timeout(&mut self) {
+        self.shutdown_timeout = ::std::option::Option::None;
+    }
+
+    pub fn has_shutdown_timeout(&self) -> bool {
+        self.shutdown_timeout.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_shutdown_timeout(&mut self, v: u32) {
+        self.shutdown_timeout = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_shutdown_timeout(&self) -> u32 {
+        self.shutdown_timeout.unwrap_or(0)
+    }
+
+    fn get_shutdown_timeout_for_reflect(&self) -> &::std::option::Option<u32> {
+        &self.shutdown_timeout
+    }
+
+    fn mut_shutdown_timeout_for_reflect(&mut self) -> &mut ::std::option::Option<u32> {
+        &mut self.shutdown_timeout
+    }
 }
 
 impl ::protobuf::Message for Terminate {
@@ -1189,6 +3495,16 @@ impl ::protobuf::Message for Terminate {
                     let tmp = is.read_int64()?;
                     self.pid = ::std::option::Option::Some(tmp);
                 },
+                2 => {
+                    ::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.shutdown_signal)?;
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.shutdown_timeout = ::std::option::Option::Some(tmp);
+                },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
@@ -1204,6 +3520,12 @@ impl ::protobuf::Message for Terminate {
         if let Some(v) = self.pid {
             my_size += ::protobuf::rt::value_size(1, v, ::protobuf::wire_format::WireTypeVarint);
         }
+        if let Some(ref v) = self.shutdown_signal.as_ref() {
+            my_size += ::protobuf::rt::string_size(2, &v);
+        }
+        if let Some(v) = self.shutdown_timeout {
+            my_size += ::protobuf::rt::value_size(3, v, ::protobuf::wire_format::WireTypeVarint);
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -1213,6 +3535,12 @@ impl ::protobuf::Message for Terminate {
         if let Some(v) = self.pid {
             os.write_int64(1, v)?;
         }
+        if let Some(ref v) = self.shutdown_signal.as_ref() {
+            os.write_string(2, &v)?;
+        }
+        if let Some(v) = self.shutdown_timeout {
+            os.write_uint32(3, v)?;
+        }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -1262,6 +3590,16 @@ impl ::protobuf::MessageStatic for Terminate {
                     Terminate::get_pid_for_reflect,
                     Terminate::mut_pid_for_reflect,
                 ));
+                fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "shutdown_signal",
+                    Terminate::get_shutdown_signal_for_reflect,
+                    Terminate::mut_shutdown_signal_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "shutdown_timeout",
+                    Terminate::get_shutdown_timeout_for_reflect,
+                    Terminate::mut_shutdown_timeout_for_reflect,
+                ));
                 ::protobuf::reflect::MessageDescriptor::new::<Terminate>(
                     "Terminate",
                     fields,
@@ -1275,6 +3613,8 @@ impl ::protobuf::MessageStatic for Terminate {
 impl ::protobuf::Clear for Terminate {
     fn clear(&mut self) {
         self.clear_pid();
+        self.clear_shutdown_signal();
+        self.clear_shutdown_timeout();
         self.unknown_fields.clear();
     }
 }
@@ -1561,85 +3901,252 @@ impl ::protobuf::reflect::ProtobufValue for ShutdownMethod {
 
 static file_descriptor_proto_data: &'static [u8] = b"\
     \n\x18protocols/launcher.proto\x12\x08launcher\"\x1e\n\x08Register\x12\
-    \x12\n\x04pipe\x18\x01\x20\x01(\tR\x04pipe\"\x1b\n\x07Restart\x12\x10\n\
-    \x03pid\x18\x01\x20\x01(\x03R\x03pid\"\xb0\x02\n\x05Spawn\x12\x0e\n\x02i\
-    d\x18\x01\x20\x01(\tR\x02id\x12\x16\n\x06binary\x18\x02\x20\x01(\tR\x06b\
-    inary\x12\x19\n\x08svc_user\x18\x03\x20\x01(\tR\x07svcUser\x12\x1b\n\tsv\
-    c_group\x18\x04\x20\x01(\tR\x08svcGroup\x12!\n\x0csvc_password\x18\x05\
-    \x20\x01(\tR\x0bsvcPassword\x12*\n\x03env\x18\x06\x20\x03(\x0b2\x18.laun\
-    cher.Spawn.EnvEntryR\x03env\x12\x1e\n\x0bsvc_user_id\x18\x07\x20\x01(\rR\
-    \tsvcUserId\x12\x20\n\x0csvc_group_id\x18\x08\x20\x01(\rR\nsvcGroupId\
-    \x1a6\n\x08EnvEntry\x12\x10\n\x03key\x18\x01\x20\x01(\tR\x03key\x12\x14\
-    \n\x05value\x18\x02\x20\x01(\tR\x05value:\x028\x01\"\x1b\n\x07SpawnOk\
-    \x12\x10\n\x03pid\x18\x01\x20\x01(\x03R\x03pid\"\x1d\n\tTerminate\x12\
-    \x10\n\x03pid\x18\x01\x20\x01(\x03R\x03pid\"m\n\x0bTerminateOk\x12\x1b\n\
-    \texit_code\x18\x01\x20\x01(\x05R\x08exitCode\x12A\n\x0fshutdown_method\
-    \x18\x02\x20\x01(\x0e2\x18.launcher.ShutdownMethodR\x0eshutdownMethod*H\
-    \n\x0eShutdownMethod\x12\x11\n\rAlreadyExited\x10\0\x12\x17\n\x13Gracefu\
-    lTermination\x10\x01\x12\n\n\x06Killed\x10\x02J\x86\n\n\x06\x12\x04\0\0(\
-    \x01\n\x08\n\x01\x0c\x12\x03\0\0\x12\n\x08\n\x01\x02\x12\x03\x02\x08\x10\
-    \n\n\n\x02\x04\0\x12\x04\x04\0\x06\x01\n\n\n\x03\x04\0\x01\x12\x03\x04\
-    \x08\x10\n\x0b\n\x04\x04\0\x02\0\x12\x03\x05\x02\x1b\n\x0c\n\x05\x04\0\
-    \x02\0\x04\x12\x03\x05\x02\n\n\x0c\n\x05\x04\0\x02\0\x05\x12\x03\x05\x0b\
-    \x11\n\x0c\n\x05\x04\0\x02\0\x01\x12\x03\x05\x12\x16\n\x0c\n\x05\x04\0\
-    \x02\0\x03\x12\x03\x05\x19\x1a\n\n\n\x02\x04\x01\x12\x04\x08\0\n\x01\n\n\
-    \n\x03\x04\x01\x01\x12\x03\x08\x08\x0f\n\x0b\n\x04\x04\x01\x02\0\x12\x03\
-    \t\x02\x19\n\x0c\n\x05\x04\x01\x02\0\x04\x12\x03\t\x02\n\n\x0c\n\x05\x04\
-    \x01\x02\0\x05\x12\x03\t\x0b\x10\n\x0c\n\x05\x04\x01\x02\0\x01\x12\x03\t\
-    \x11\x14\n\x0c\n\x05\x04\x01\x02\0\x03\x12\x03\t\x17\x18\n\n\n\x02\x04\
-    \x02\x12\x04\x0c\0\x15\x01\n\n\n\x03\x04\x02\x01\x12\x03\x0c\x08\r\n\x0b\
-    \n\x04\x04\x02\x02\0\x12\x03\r\x02\x19\n\x0c\n\x05\x04\x02\x02\0\x04\x12\
-    \x03\r\x02\n\n\x0c\n\x05\x04\x02\x02\0\x05\x12\x03\r\x0b\x11\n\x0c\n\x05\
-    \x04\x02\x02\0\x01\x12\x03\r\x12\x14\n\x0c\n\x05\x04\x02\x02\0\x03\x12\
-    \x03\r\x17\x18\n\x0b\n\x04\x04\x02\x02\x01\x12\x03\x0e\x02\x1d\n\x0c\n\
-    \x05\x04\x02\x02\x01\x04\x12\x03\x0e\x02\n\n\x0c\n\x05\x04\x02\x02\x01\
-    \x05\x12\x03\x0e\x0b\x11\n\x0c\n\x05\x04\x02\x02\x01\x01\x12\x03\x0e\x12\
-    \x18\n\x0c\n\x05\x04\x02\x02\x01\x03\x12\x03\x0e\x1b\x1c\n\x0b\n\x04\x04\
-    \x02\x02\x02\x12\x03\x0f\x02\x1f\n\x0c\n\x05\x04\x02\x02\x02\x04\x12\x03\
-    \x0f\x02\n\n\x0c\n\x05\x04\x02\x02\x02\x05\x12\x03\x0f\x0b\x11\n\x0c\n\
-    \x05\x04\x02\x02\x02\x01\x12\x03\x0f\x12\x1a\n\x0c\n\x05\x04\x02\x02\x02\
-    \x03\x12\x03\x0f\x1d\x1e\n\x0b\n\x04\x04\x02\x02\x03\x12\x03\x10\x02\x20\
-    \n\x0c\n\x05\x04\x02\x02\x03\x04\x12\x03\x10\x02\n\n\x0c\n\x05\x04\x02\
-    \x02\x03\x05\x12\x03\x10\x0b\x11\n\x0c\n\x05\x04\x02\x02\x03\x01\x12\x03\
-    \x10\x12\x1b\n\x0c\n\x05\x04\x02\x02\x03\x03\x12\x03\x10\x1e\x1f\n\x0b\n\
-    \x04\x04\x02\x02\x04\x12\x03\x11\x02#\n\x0c\n\x05\x04\x02\x02\x04\x04\
-    \x12\x03\x11\x02\n\n\x0c\n\x05\x04\x02\x02\x04\x05\x12\x03\x11\x0b\x11\n\
-    \x0c\n\x05\x04\x02\x02\x04\x01\x12\x03\x11\x12\x1e\n\x0c\n\x05\x04\x02\
-    \x02\x04\x03\x12\x03\x11!\"\n\x0b\n\x04\x04\x02\x02\x05\x12\x03\x12\x02\
-    \x1e\n\r\n\x05\x04\x02\x02\x05\x04\x12\x04\x12\x02\x11#\n\x0c\n\x05\x04\
-    \x02\x02\x05\x06\x12\x03\x12\x02\x15\n\x0c\n\x05\x04\x02\x02\x05\x01\x12\
-    \x03\x12\x16\x19\n\x0c\n\x05\x04\x02\x02\x05\x03\x12\x03\x12\x1c\x1d\n\
-    \x0b\n\x04\x04\x02\x02\x06\x12\x03\x13\x02\"\n\x0c\n\x05\x04\x02\x02\x06\
-    \x04\x12\x03\x13\x02\n\n\x0c\n\x05\x04\x02\x02\x06\x05\x12\x03\x13\x0b\
-    \x11\n\x0c\n\x05\x04\x02\x02\x06\x01\x12\x03\x13\x12\x1d\n\x0c\n\x05\x04\
-    \x02\x02\x06\x03\x12\x03\x13\x20!\n\x0b\n\x04\x04\x02\x02\x07\x12\x03\
-    \x14\x02#\n\x0c\n\x05\x04\x02\x02\x07\x04\x12\x03\x14\x02\n\n\x0c\n\x05\
-    \x04\x02\x02\x07\x05\x12\x03\x14\x0b\x11\n\x0c\n\x05\x04\x02\x02\x07\x01\
-    \x12\x03\x14\x12\x1e\n\x0c\n\x05\x04\x02\x02\x07\x03\x12\x03\x14!\"\n\n\
-    \n\x02\x04\x03\x12\x04\x17\0\x19\x01\n\n\n\x03\x04\x03\x01\x12\x03\x17\
-    \x08\x0f\n\x0b\n\x04\x04\x03\x02\0\x12\x03\x18\x02\x19\n\x0c\n\x05\x04\
-    \x03\x02\0\x04\x12\x03\x18\x02\n\n\x0c\n\x05\x04\x03\x02\0\x05\x12\x03\
-    \x18\x0b\x10\n\x0c\n\x05\x04\x03\x02\0\x01\x12\x03\x18\x11\x14\n\x0c\n\
-    \x05\x04\x03\x02\0\x03\x12\x03\x18\x17\x18\n\n\n\x02\x04\x04\x12\x04\x1b\
-    \0\x1d\x01\n\n\n\x03\x04\x04\x01\x12\x03\x1b\x08\x11\n\x0b\n\x04\x04\x04\
-    \x02\0\x12\x03\x1c\x02\x19\n\x0c\n\x05\x04\x04\x02\0\x04\x12\x03\x1c\x02\
-    \n\n\x0c\n\x05\x04\x04\x02\0\x05\x12\x03\x1c\x0b\x10\n\x0c\n\x05\x04\x04\
-    \x02\0\x01\x12\x03\x1c\x11\x14\n\x0c\n\x05\x04\x04\x02\0\x03\x12\x03\x1c\
-    \x17\x18\n\n\n\x02\x04\x05\x12\x04\x1f\0\"\x01\n\n\n\x03\x04\x05\x01\x12\
-    \x03\x1f\x08\x13\n\x0b\n\x04\x04\x05\x02\0\x12\x03\x20\x02\x1f\n\x0c\n\
-    \x05\x04\x05\x02\0\x04\x12\x03\x20\x02\n\n\x0c\n\x05\x04\x05\x02\0\x05\
-    \x12\x03\x20\x0b\x10\n\x0c\n\x05\x04\x05\x02\0\x01\x12\x03\x20\x11\x1a\n\
-    \x0c\n\x05\x04\x05\x02\0\x03\x12\x03\x20\x1d\x1e\n\x0b\n\x04\x04\x05\x02\
-    \x01\x12\x03!\x02.\n\x0c\n\x05\x04\x05\x02\x01\x04\x12\x03!\x02\n\n\x0c\
-    \n\x05\x04\x05\x02\x01\x06\x12\x03!\x0b\x19\n\x0c\n\x05\x04\x05\x02\x01\
-    \x01\x12\x03!\x1a)\n\x0c\n\x05\x04\x05\x02\x01\x03\x12\x03!,-\n\n\n\x02\
-    \x05\0\x12\x04$\0(\x01\n\n\n\x03\x05\0\x01\x12\x03$\x05\x13\n\x0b\n\x04\
-    \x05\0\x02\0\x12\x03%\x02\x14\n\x0c\n\x05\x05\0\x02\0\x01\x12\x03%\x02\
-    \x0f\n\x0c\n\x05\x05\0\x02\0\x02\x12\x03%\x12\x13\n\x0b\n\x04\x05\0\x02\
-    \x01\x12\x03&\x02\x1a\n\x0c\n\x05\x05\0\x02\x01\x01\x12\x03&\x02\x15\n\
-    \x0c\n\x05\x05\0\x02\x01\x02\x12\x03&\x18\x19\n\x0b\n\x04\x05\0\x02\x02\
-    \x12\x03'\x02\r\n\x0c\n\x05\x05\0\x02\x02\x01\x12\x03'\x02\x08\n\x0c\n\
-    \x05\x05\0\x02\x02\x02\x12\x03'\x0b\x0c\
+    \x12\n\x04pipe\x18\x01\x20\x01(\tR\x04pipe\"\x1c\n\x08ExitCode\x12\x10\n\
+    \x03pid\x18\x01\x20\x01(\x03R\x03pid\")\n\nExitCodeOk\x12\x1b\n\texit_co\
+    de\x18\x01\x20\x01(\x05R\x08exitCode\"\xa3\x01\n\x0eResourceLimits\x12\
+    \x1d\n\nmemory_max\x18\x01\x20\x01(\x04R\tmemoryMax\x12\x1d\n\ncpu_weigh\
+    t\x18\x02\x20\x01(\rR\tcpuWeight\x12\x1b\n\tcpu_quota\x18\x03\x20\x01(\r\
+    R\x08cpuQuota\x12\x19\n\x08pids_max\x18\x04\x20\x01(\x04R\x07pidsMax\x12\
+    \x1b\n\tio_weight\x18\x05\x20\x01(\rR\x08ioWeight\"e\n\x07Rlimits\x12\
+    \x16\n\x06nofile\x18\x01\x20\x01(\x04R\x06nofile\x12\x14\n\x05nproc\x18\
+    \x02\x20\x01(\x04R\x05nproc\x12\x12\n\x04core\x18\x03\x20\x01(\x04R\x04c\
+    ore\x12\x18\n\x07memlock\x18\x04\x20\x01(\x04R\x07memlock\"M\n\tIsolatio\
+    n\x12\x14\n\x05mount\x18\x01\x20\x01(\x08R\x05mount\x12\x10\n\x03pid\x18\
+    \x02\x20\x01(\x08R\x03pid\x12\x18\n\x07network\x18\x03\x20\x01(\x08R\x07\
+    network\"!\n\rResourceUsage\x12\x10\n\x03pid\x18\x01\x20\x01(\x03R\x03pi\
+    d\"\xcb\x01\n\x0fResourceUsageOk\x12%\n\x0ememory_current\x18\x01\x20\
+    \x01(\x04R\rmemoryCurrent\x12$\n\x0ecpu_usage_usec\x18\x02\x20\x01(\x04R\
+    \x0ccpuUsageUsec\x12!\n\x0cpids_current\x18\x03\x20\x01(\x04R\x0bpidsCur\
+    rent\x12\"\n\rio_read_bytes\x18\x04\x20\x01(\x04R\x0bioReadBytes\x12$\n\
+    \x0eio_write_bytes\x18\x05\x20\x01(\x04R\x0cioWriteBytes\"\x1b\n\x07Rest\
+    art\x12\x10\n\x03pid\x18\x01\x20\x01(\x03R\x03pid\"\xf5\x04\n\x05Spawn\
+    \x12\x0e\n\x02id\x18\x01\x20\x01(\tR\x02id\x12\x16\n\x06binary\x18\x02\
+    \x20\x01(\tR\x06binary\x12\x19\n\x08svc_user\x18\x03\x20\x01(\tR\x07svcU\
+    ser\x12\x1b\n\tsvc_group\x18\x04\x20\x01(\tR\x08svcGroup\x12!\n\x0csvc_p\
+    assword\x18\x05\x20\x01(\tR\x0bsvcPassword\x12*\n\x03env\x18\x06\x20\x03\
+    (\x0b2\x18.launcher.Spawn.EnvEntryR\x03env\x12\x1e\n\x0bsvc_user_id\x18\
+    \x07\x20\x01(\rR\tsvcUserId\x12\x20\n\x0csvc_group_id\x18\x08\x20\x01(\r\
+    R\nsvcGroupId\x12\x19\n\x08log_path\x18\t\x20\x01(\tR\x07logPath\x12'\n\
+    \x0fshutdown_signal\x18\n\x20\x01(\tR\x0eshutdownSignal\x12)\n\x10shutdo\
+    wn_timeout\x18\x0b\x20\x01(\rR\x0fshutdownTimeout\x12A\n\x0fresource_lim\
+    its\x18\x0c\x20\x01(\x0b2\x18.launcher.ResourceLimitsR\x0eresourceLimits\
+    \x12+\n\x07rlimits\x18\r\x20\x01(\x0b2\x11.launcher.RlimitsR\x07rlimits\
+    \x121\n\x14ambient_capabilities\x18\x0e\x20\x03(\tR\x13ambientCapabiliti\
+    es\x121\n\tisolation\x18\x0f\x20\x01(\x0b2\x13.launcher.IsolationR\tisol\
+    ation\x1a6\n\x08EnvEntry\x12\x10\n\x03key\x18\x01\x20\x01(\tR\x03key\x12\
+    \x14\n\x05value\x18\x02\x20\x01(\tR\x05value:\x028\x01\"\x1b\n\x07SpawnO\
+    k\x12\x10\n\x03pid\x18\x01\x20\x01(\x03R\x03pid\"q\n\tTerminate\x12\x10\
+    \n\x03pid\x18\x01\x20\x01(\x03R\x03pid\x12'\n\x0fshutdown_signal\x18\x02\
+    \x20\x01(\tR\x0eshutdownSignal\x12)\n\x10shutdown_timeout\x18\x03\x20\
+    \x01(\rR\x0fshutdownTimeout\"m\n\x0bTerminateOk\x12\x1b\n\texit_code\x18\
+    \x01\x20\x01(\x05R\x08exitCode\x12A\n\x0fshutdown_method\x18\x02\x20\x01\
+    (\x0e2\x18.launcher.ShutdownMethodR\x0eshutdownMethod*H\n\x0eShutdownMet\
+    hod\x12\x11\n\rAlreadyExited\x10\0\x12\x17\n\x13GracefulTermination\x10\
+    \x01\x12\n\n\x06Killed\x10\x02J\xa2*\n\x06\x12\x04\0\0v\x01\n\x08\n\x01\
+    \x0c\x12\x03\0\0\x12\n\x08\n\x01\x02\x12\x03\x02\x08\x10\n\n\n\x02\x04\0\
+    \x12\x04\x04\0\x06\x01\n\n\n\x03\x04\0\x01\x12\x03\x04\x08\x10\n\x0b\n\
+    \x04\x04\0\x02\0\x12\x03\x05\x02\x1b\n\x0c\n\x05\x04\0\x02\0\x04\x12\x03\
+    \x05\x02\n\n\x0c\n\x05\x04\0\x02\0\x05\x12\x03\x05\x0b\x11\n\x0c\n\x05\
+    \x04\0\x02\0\x01\x12\x03\x05\x12\x16\n\x0c\n\x05\x04\0\x02\0\x03\x12\x03\
+    \x05\x19\x1a\n\xbb\x01\n\x02\x04\x01\x12\x04\n\0\x0c\x01\x1a\xae\x01\x20\
+    Ask\x20for\x20the\x20exit\x20code\x20of\x20a\x20service\x20process\x20wh\
+    ich\x20has\x20exited.\x20The\x20Launcher\x20replies\x20with\n\x20`ExitCo\
+    deOk`\x20once,\x20or\x20with\x20`NoPID`\x20if\x20it\x20isn't\x20holding\
+    \x20the\x20exit\x20code\x20of\x20the\x20process.\n\n\n\n\x03\x04\x01\x01\
+    \x12\x03\n\x08\x10\n\x0b\n\x04\x04\x01\x02\0\x12\x03\x0b\x02\x19\n\x0c\n\
+    \x05\x04\x01\x02\0\x04\x12\x03\x0b\x02\n\n\x0c\n\x05\x04\x01\x02\0\x05\
+    \x12\x03\x0b\x0b\x10\n\x0c\n\x05\x04\x01\x02\0\x01\x12\x03\x0b\x11\x14\n\
+    \x0c\n\x05\x04\x01\x02\0\x03\x12\x03\x0b\x17\x18\n\n\n\x02\x04\x02\x12\
+    \x04\x0e\0\x11\x01\n\n\n\x03\x04\x02\x01\x12\x03\x0e\x08\x12\n?\n\x04\
+    \x04\x02\x02\0\x12\x03\x10\x02\x1f\x1a2\x20Unset\x20if\x20the\x20process\
+    \x20was\x20terminated\x20by\x20a\x20signal.\n\n\x0c\n\x05\x04\x02\x02\0\
+    \x04\x12\x03\x10\x02\n\n\x0c\n\x05\x04\x02\x02\0\x05\x12\x03\x10\x0b\x10\
+    \n\x0c\n\x05\x04\x02\x02\0\x01\x12\x03\x10\x11\x1a\n\x0c\n\x05\x04\x02\
+    \x02\0\x03\x12\x03\x10\x1d\x1e\n\xbc\x01\n\x02\x04\x03\x12\x04\x15\0\x20\
+    \x01\x1a\xaf\x01\x20Resource\x20limits\x20applied\x20to\x20a\x20service\
+    \x20through\x20a\x20cgroup\x20v2\x20child\x20of\x20the\x20Launcher's\x20\
+    cgroup\x20root.\n\x20Unset\x20limits\x20are\x20left\x20to\x20the\x20kern\
+    el.\x20Ignored\x20where\x20cgroups\x20v2\x20are\x20unavailable.\n\n\n\n\
+    \x03\x04\x03\x01\x12\x03\x15\x08\x16\nZ\n\x04\x04\x03\x02\0\x12\x03\x17\
+    \x02!\x1aM\x20Bytes\x20of\x20memory\x20the\x20service\x20may\x20use\x20b\
+    efore\x20the\x20kernel\x20reclaims\x20or\x20kills\x20it.\n\n\x0c\n\x05\
+    \x04\x03\x02\0\x04\x12\x03\x17\x02\n\n\x0c\n\x05\x04\x03\x02\0\x05\x12\
+    \x03\x17\x0b\x11\n\x0c\n\x05\x04\x03\x02\0\x01\x12\x03\x17\x12\x1c\n\x0c\
+    \n\x05\x04\x03\x02\0\x03\x12\x03\x17\x1f\x20\nM\n\x04\x04\x03\x02\x01\
+    \x12\x03\x19\x02!\x1a@\x20Share\x20of\x20CPU\x20time\x20relative\x20to\
+    \x20other\x20services,\x20from\x201\x20to\x2010000.\n\n\x0c\n\x05\x04\
+    \x03\x02\x01\x04\x12\x03\x19\x02\n\n\x0c\n\x05\x04\x03\x02\x01\x05\x12\
+    \x03\x19\x0b\x11\n\x0c\n\x05\x04\x03\x02\x01\x01\x12\x03\x19\x12\x1c\n\
+    \x0c\n\x05\x04\x03\x02\x01\x03\x12\x03\x19\x1f\x20\n[\n\x04\x04\x03\x02\
+    \x02\x12\x03\x1b\x02\x20\x1aN\x20Percent\x20of\x20one\x20CPU\x20the\x20s\
+    ervice\x20may\x20use,\x20such\x20as\x20150\x20for\x20one\x20and\x20a\x20\
+    half\x20CPUs.\n\n\x0c\n\x05\x04\x03\x02\x02\x04\x12\x03\x1b\x02\n\n\x0c\
+    \n\x05\x04\x03\x02\x02\x05\x12\x03\x1b\x0b\x11\n\x0c\n\x05\x04\x03\x02\
+    \x02\x01\x12\x03\x1b\x12\x1b\n\x0c\n\x05\x04\x03\x02\x02\x03\x12\x03\x1b\
+    \x1e\x1f\nC\n\x04\x04\x03\x02\x03\x12\x03\x1d\x02\x1f\x1a6\x20Number\x20\
+    of\x20processes\x20and\x20threads\x20the\x20service\x20may\x20run.\n\n\
+    \x0c\n\x05\x04\x03\x02\x03\x04\x12\x03\x1d\x02\n\n\x0c\n\x05\x04\x03\x02\
+    \x03\x05\x12\x03\x1d\x0b\x11\n\x0c\n\x05\x04\x03\x02\x03\x01\x12\x03\x1d\
+    \x12\x1a\n\x0c\n\x05\x04\x03\x02\x03\x03\x12\x03\x1d\x1d\x1e\nG\n\x04\
+    \x04\x03\x02\x04\x12\x03\x1f\x02\x20\x1a:\x20Share\x20of\x20IO\x20relati\
+    ve\x20to\x20other\x20services,\x20from\x201\x20to\x2010000.\n\n\x0c\n\
+    \x05\x04\x03\x02\x04\x04\x12\x03\x1f\x02\n\n\x0c\n\x05\x04\x03\x02\x04\
+    \x05\x12\x03\x1f\x0b\x11\n\x0c\n\x05\x04\x03\x02\x04\x01\x12\x03\x1f\x12\
+    \x1b\n\x0c\n\x05\x04\x03\x02\x04\x03\x12\x03\x1f\x1e\x1f\n\xbc\x01\n\x02\
+    \x04\x04\x12\x04$\0-\x01\x1a\xaf\x01\x20Limits\x20applied\x20to\x20a\x20\
+    service's\x20process\x20with\x20`setrlimit`\x20before\x20it's\x20execute\
+    d.\x20Each\x20limit\x20sets\n\x20both\x20the\x20soft\x20and\x20the\x20ha\
+    rd\x20limit.\x20Unset\x20limits\x20are\x20inherited\x20from\x20the\x20La\
+    uncher.\n\n\n\n\x03\x04\x04\x01\x12\x03$\x08\x0f\n/\n\x04\x04\x04\x02\0\
+    \x12\x03&\x02\x1d\x1a\"\x20Number\x20of\x20open\x20file\x20descriptors.\
+    \n\n\x0c\n\x05\x04\x04\x02\0\x04\x12\x03&\x02\n\n\x0c\n\x05\x04\x04\x02\
+    \0\x05\x12\x03&\x0b\x11\n\x0c\n\x05\x04\x04\x02\0\x01\x12\x03&\x12\x18\n\
+    \x0c\n\x05\x04\x04\x02\0\x03\x12\x03&\x1b\x1c\n9\n\x04\x04\x04\x02\x01\
+    \x12\x03(\x02\x1c\x1a,\x20Number\x20of\x20processes\x20of\x20the\x20serv\
+    ice's\x20user.\n\n\x0c\n\x05\x04\x04\x02\x01\x04\x12\x03(\x02\n\n\x0c\n\
+    \x05\x04\x04\x02\x01\x05\x12\x03(\x0b\x11\n\x0c\n\x05\x04\x04\x02\x01\
+    \x01\x12\x03(\x12\x17\n\x0c\n\x05\x04\x04\x02\x01\x03\x12\x03(\x1a\x1b\n\
+    #\n\x04\x04\x04\x02\x02\x12\x03*\x02\x1b\x1a\x16\x20Bytes\x20of\x20core\
+    \x20dumps.\n\n\x0c\n\x05\x04\x04\x02\x02\x04\x12\x03*\x02\n\n\x0c\n\x05\
+    \x04\x04\x02\x02\x05\x12\x03*\x0b\x11\n\x0c\n\x05\x04\x04\x02\x02\x01\
+    \x12\x03*\x12\x16\n\x0c\n\x05\x04\x04\x02\x02\x03\x12\x03*\x19\x1a\n3\n\
+    \x04\x04\x04\x02\x03\x12\x03,\x02\x1e\x1a&\x20Bytes\x20of\x20memory\x20w\
+    hich\x20may\x20be\x20locked.\n\n\x0c\n\x05\x04\x04\x02\x03\x04\x12\x03,\
+    \x02\n\n\x0c\n\x05\x04\x04\x02\x03\x05\x12\x03,\x0b\x11\n\x0c\n\x05\x04\
+    \x04\x02\x03\x01\x12\x03,\x12\x19\n\x0c\n\x05\x04\x04\x02\x03\x03\x12\
+    \x03,\x1c\x1d\n]\n\x02\x04\x05\x12\x040\07\x01\x1aQ\x20Linux\x20namespac\
+    es\x20a\x20service's\x20process\x20is\x20isolated\x20in.\x20Unused\x20on\
+    \x20other\x20platforms.\n\n\n\n\x03\x04\x05\x01\x12\x030\x08\x11\na\n\
+    \x04\x04\x05\x02\0\x12\x032\x02\x1a\x1aT\x20Run\x20in\x20a\x20private\
+    \x20mount\x20namespace,\x20with\x20`/hab/pkgs`\x20read-only\x20and\x20a\
+    \x20private\x20`/tmp`.\n\n\x0c\n\x05\x04\x05\x02\0\x04\x12\x032\x02\n\n\
+    \x0c\n\x05\x04\x05\x02\0\x05\x12\x032\x0b\x0f\n\x0c\n\x05\x04\x05\x02\0\
+    \x01\x12\x032\x10\x15\n\x0c\n\x05\x04\x05\x02\0\x03\x12\x032\x18\x19\nE\
+    \n\x04\x04\x05\x02\x01\x12\x034\x02\x18\x1a8\x20Run\x20in\x20a\x20PID\
+    \x20namespace,\x20seeing\x20only\x20its\x20own\x20processes.\n\n\x0c\n\
+    \x05\x04\x05\x02\x01\x04\x12\x034\x02\n\n\x0c\n\x05\x04\x05\x02\x01\x05\
+    \x12\x034\x0b\x0f\n\x0c\n\x05\x04\x05\x02\x01\x01\x12\x034\x10\x13\n\x0c\
+    \n\x05\x04\x05\x02\x01\x03\x12\x034\x16\x17\nI\n\x04\x04\x05\x02\x02\x12\
+    \x036\x02\x1c\x1a<\x20Run\x20in\x20a\x20network\x20namespace\x20with\x20\
+    only\x20a\x20loopback\x20interface.\n\n\x0c\n\x05\x04\x05\x02\x02\x04\
+    \x12\x036\x02\n\n\x0c\n\x05\x04\x05\x02\x02\x05\x12\x036\x0b\x0f\n\x0c\n\
+    \x05\x04\x05\x02\x02\x01\x12\x036\x10\x17\n\x0c\n\x05\x04\x05\x02\x02\
+    \x03\x12\x036\x1a\x1b\n\xb2\x01\n\x02\x04\x06\x12\x04;\0=\x01\x1a\xa5\
+    \x01\x20Ask\x20for\x20the\x20resources\x20used\x20by\x20a\x20service's\
+    \x20cgroup.\x20The\x20Launcher\x20replies\x20with\x20`ResourceUsageOk`,\
+    \n\x20leaving\x20its\x20fields\x20unset\x20if\x20the\x20service\x20wasn'\
+    t\x20spawned\x20in\x20a\x20cgroup.\n\n\n\n\x03\x04\x06\x01\x12\x03;\x08\
+    \x15\n\x0b\n\x04\x04\x06\x02\0\x12\x03<\x02\x19\n\x0c\n\x05\x04\x06\x02\
+    \0\x04\x12\x03<\x02\n\n\x0c\n\x05\x04\x06\x02\0\x05\x12\x03<\x0b\x10\n\
+    \x0c\n\x05\x04\x06\x02\0\x01\x12\x03<\x11\x14\n\x0c\n\x05\x04\x06\x02\0\
+    \x03\x12\x03<\x17\x18\n\n\n\x02\x04\x07\x12\x04?\0F\x01\n\n\n\x03\x04\
+    \x07\x01\x12\x03?\x08\x17\n\x0b\n\x04\x04\x07\x02\0\x12\x03@\x02%\n\x0c\
+    \n\x05\x04\x07\x02\0\x04\x12\x03@\x02\n\n\x0c\n\x05\x04\x07\x02\0\x05\
+    \x12\x03@\x0b\x11\n\x0c\n\x05\x04\x07\x02\0\x01\x12\x03@\x12\x20\n\x0c\n\
+    \x05\x04\x07\x02\0\x03\x12\x03@#$\n-\n\x04\x04\x07\x02\x01\x12\x03B\x02%\
+    \x1a\x20\x20Microseconds\x20of\x20CPU\x20time\x20used.\n\n\x0c\n\x05\x04\
+    \x07\x02\x01\x04\x12\x03B\x02\n\n\x0c\n\x05\x04\x07\x02\x01\x05\x12\x03B\
+    \x0b\x11\n\x0c\n\x05\x04\x07\x02\x01\x01\x12\x03B\x12\x20\n\x0c\n\x05\
+    \x04\x07\x02\x01\x03\x12\x03B#$\n\x0b\n\x04\x04\x07\x02\x02\x12\x03C\x02\
+    #\n\x0c\n\x05\x04\x07\x02\x02\x04\x12\x03C\x02\n\n\x0c\n\x05\x04\x07\x02\
+    \x02\x05\x12\x03C\x0b\x11\n\x0c\n\x05\x04\x07\x02\x02\x01\x12\x03C\x12\
+    \x1e\n\x0c\n\x05\x04\x07\x02\x02\x03\x12\x03C!\"\n\x0b\n\x04\x04\x07\x02\
+    \x03\x12\x03D\x02$\n\x0c\n\x05\x04\x07\x02\x03\x04\x12\x03D\x02\n\n\x0c\
+    \n\x05\x04\x07\x02\x03\x05\x12\x03D\x0b\x11\n\x0c\n\x05\x04\x07\x02\x03\
+    \x01\x12\x03D\x12\x1f\n\x0c\n\x05\x04\x07\x02\x03\x03\x12\x03D\"#\n\x0b\
+    \n\x04\x04\x07\x02\x04\x12\x03E\x02%\n\x0c\n\x05\x04\x07\x02\x04\x04\x12\
+    \x03E\x02\n\n\x0c\n\x05\x04\x07\x02\x04\x05\x12\x03E\x0b\x11\n\x0c\n\x05\
+    \x04\x07\x02\x04\x01\x12\x03E\x12\x20\n\x0c\n\x05\x04\x07\x02\x04\x03\
+    \x12\x03E#$\n\n\n\x02\x04\x08\x12\x04H\0J\x01\n\n\n\x03\x04\x08\x01\x12\
+    \x03H\x08\x0f\n\x0b\n\x04\x04\x08\x02\0\x12\x03I\x02\x19\n\x0c\n\x05\x04\
+    \x08\x02\0\x04\x12\x03I\x02\n\n\x0c\n\x05\x04\x08\x02\0\x05\x12\x03I\x0b\
+    \x10\n\x0c\n\x05\x04\x08\x02\0\x01\x12\x03I\x11\x14\n\x0c\n\x05\x04\x08\
+    \x02\0\x03\x12\x03I\x17\x18\n\n\n\x02\x04\t\x12\x04L\0`\x01\n\n\n\x03\
+    \x04\t\x01\x12\x03L\x08\r\n\x0b\n\x04\x04\t\x02\0\x12\x03M\x02\x19\n\x0c\
+    \n\x05\x04\t\x02\0\x04\x12\x03M\x02\n\n\x0c\n\x05\x04\t\x02\0\x05\x12\
+    \x03M\x0b\x11\n\x0c\n\x05\x04\t\x02\0\x01\x12\x03M\x12\x14\n\x0c\n\x05\
+    \x04\t\x02\0\x03\x12\x03M\x17\x18\n\x0b\n\x04\x04\t\x02\x01\x12\x03N\x02\
+    \x1d\n\x0c\n\x05\x04\t\x02\x01\x04\x12\x03N\x02\n\n\x0c\n\x05\x04\t\x02\
+    \x01\x05\x12\x03N\x0b\x11\n\x0c\n\x05\x04\t\x02\x01\x01\x12\x03N\x12\x18\
+    \n\x0c\n\x05\x04\t\x02\x01\x03\x12\x03N\x1b\x1c\n\x0b\n\x04\x04\t\x02\
+    \x02\x12\x03O\x02\x1f\n\x0c\n\x05\x04\t\x02\x02\x04\x12\x03O\x02\n\n\x0c\
+    \n\x05\x04\t\x02\x02\x05\x12\x03O\x0b\x11\n\x0c\n\x05\x04\t\x02\x02\x01\
+    \x12\x03O\x12\x1a\n\x0c\n\x05\x04\t\x02\x02\x03\x12\x03O\x1d\x1e\n\x0b\n\
+    \x04\x04\t\x02\x03\x12\x03P\x02\x20\n\x0c\n\x05\x04\t\x02\x03\x04\x12\
+    \x03P\x02\n\n\x0c\n\x05\x04\t\x02\x03\x05\x12\x03P\x0b\x11\n\x0c\n\x05\
+    \x04\t\x02\x03\x01\x12\x03P\x12\x1b\n\x0c\n\x05\x04\t\x02\x03\x03\x12\
+    \x03P\x1e\x1f\n\x0b\n\x04\x04\t\x02\x04\x12\x03Q\x02#\n\x0c\n\x05\x04\t\
+    \x02\x04\x04\x12\x03Q\x02\n\n\x0c\n\x05\x04\t\x02\x04\x05\x12\x03Q\x0b\
+    \x11\n\x0c\n\x05\x04\t\x02\x04\x01\x12\x03Q\x12\x1e\n\x0c\n\x05\x04\t\
+    \x02\x04\x03\x12\x03Q!\"\n\x0b\n\x04\x04\t\x02\x05\x12\x03R\x02\x1e\n\
+    \x0c\n\x05\x04\t\x02\x05\x06\x12\x03R\x02\x15\n\x0c\n\x05\x04\t\x02\x05\
+    \x01\x12\x03R\x16\x19\n\x0c\n\x05\x04\t\x02\x05\x03\x12\x03R\x1c\x1d\n\
+    \x0b\n\x04\x04\t\x02\x06\x12\x03S\x02\"\n\x0c\n\x05\x04\t\x02\x06\x04\
+    \x12\x03S\x02\n\n\x0c\n\x05\x04\t\x02\x06\x05\x12\x03S\x0b\x11\n\x0c\n\
+    \x05\x04\t\x02\x06\x01\x12\x03S\x12\x1d\n\x0c\n\x05\x04\t\x02\x06\x03\
+    \x12\x03S\x20!\n\x0b\n\x04\x04\t\x02\x07\x12\x03T\x02#\n\x0c\n\x05\x04\t\
+    \x02\x07\x04\x12\x03T\x02\n\n\x0c\n\x05\x04\t\x02\x07\x05\x12\x03T\x0b\
+    \x11\n\x0c\n\x05\x04\t\x02\x07\x01\x12\x03T\x12\x1e\n\x0c\n\x05\x04\t\
+    \x02\x07\x03\x12\x03T!\"\n\x0b\n\x04\x04\t\x02\x08\x12\x03U\x02\x1f\n\
+    \x0c\n\x05\x04\t\x02\x08\x04\x12\x03U\x02\n\n\x0c\n\x05\x04\t\x02\x08\
+    \x05\x12\x03U\x0b\x11\n\x0c\n\x05\x04\t\x02\x08\x01\x12\x03U\x12\x1a\n\
+    \x0c\n\x05\x04\t\x02\x08\x03\x12\x03U\x1d\x1e\ni\n\x04\x04\t\x02\t\x12\
+    \x03W\x02'\x1a\\\x20Name\x20of\x20the\x20signal\x20asking\x20the\x20serv\
+    ice\x20to\x20stop,\x20such\x20as\x20\"TERM\"\x20or\x20\"INT\".\x20Unused\
+    \x20on\x20Windows.\n\n\x0c\n\x05\x04\t\x02\t\x04\x12\x03W\x02\n\n\x0c\n\
+    \x05\x04\t\x02\t\x05\x12\x03W\x0b\x11\n\x0c\n\x05\x04\t\x02\t\x01\x12\
+    \x03W\x12!\n\x0c\n\x05\x04\t\x02\t\x03\x12\x03W$&\nI\n\x04\x04\t\x02\n\
+    \x12\x03Y\x02(\x1a<\x20Seconds\x20to\x20wait\x20for\x20the\x20service\
+    \x20to\x20stop\x20before\x20killing\x20it.\n\n\x0c\n\x05\x04\t\x02\n\x04\
+    \x12\x03Y\x02\n\n\x0c\n\x05\x04\t\x02\n\x05\x12\x03Y\x0b\x11\n\x0c\n\x05\
+    \x04\t\x02\n\x01\x12\x03Y\x12\"\n\x0c\n\x05\x04\t\x02\n\x03\x12\x03Y%'\n\
+    \x0b\n\x04\x04\t\x02\x0b\x12\x03Z\x02/\n\x0c\n\x05\x04\t\x02\x0b\x04\x12\
+    \x03Z\x02\n\n\x0c\n\x05\x04\t\x02\x0b\x06\x12\x03Z\x0b\x19\n\x0c\n\x05\
+    \x04\t\x02\x0b\x01\x12\x03Z\x1a)\n\x0c\n\x05\x04\t\x02\x0b\x03\x12\x03Z,\
+    .\n\x0b\n\x04\x04\t\x02\x0c\x12\x03[\x02\x20\n\x0c\n\x05\x04\t\x02\x0c\
+    \x04\x12\x03[\x02\n\n\x0c\n\x05\x04\t\x02\x0c\x06\x12\x03[\x0b\x12\n\x0c\
+    \n\x05\x04\t\x02\x0c\x01\x12\x03[\x13\x1a\n\x0c\n\x05\x04\t\x02\x0c\x03\
+    \x12\x03[\x1d\x1f\n\xbb\x01\n\x04\x04\t\x02\r\x12\x03^\x02,\x1a\xad\x01\
+    \x20Linux\x20capabilities\x20raised\x20in\x20the\x20ambient\x20set\x20of\
+    \x20the\x20service's\x20process,\x20such\x20as\n\x20\"CAP_NET_BIND_SERVI\
+    CE\",\x20letting\x20it\x20use\x20them\x20after\x20it\x20drops\x20root.\
+    \x20Unused\x20on\x20other\x20platforms.\n\n\x0c\n\x05\x04\t\x02\r\x04\
+    \x12\x03^\x02\n\n\x0c\n\x05\x04\t\x02\r\x05\x12\x03^\x0b\x11\n\x0c\n\x05\
+    \x04\t\x02\r\x01\x12\x03^\x12&\n\x0c\n\x05\x04\t\x02\r\x03\x12\x03^)+\n\
+    \x0b\n\x04\x04\t\x02\x0e\x12\x03_\x02$\n\x0c\n\x05\x04\t\x02\x0e\x04\x12\
+    \x03_\x02\n\n\x0c\n\x05\x04\t\x02\x0e\x06\x12\x03_\x0b\x14\n\x0c\n\x05\
+    \x04\t\x02\x0e\x01\x12\x03_\x15\x1e\n\x0c\n\x05\x04\t\x02\x0e\x03\x12\
+    \x03_!#\n\n\n\x02\x04\n\x12\x04b\0d\x01\n\n\n\x03\x04\n\x01\x12\x03b\x08\
+    \x0f\n\x0b\n\x04\x04\n\x02\0\x12\x03c\x02\x19\n\x0c\n\x05\x04\n\x02\0\
+    \x04\x12\x03c\x02\n\n\x0c\n\x05\x04\n\x02\0\x05\x12\x03c\x0b\x10\n\x0c\n\
+    \x05\x04\n\x02\0\x01\x12\x03c\x11\x14\n\x0c\n\x05\x04\n\x02\0\x03\x12\
+    \x03c\x17\x18\n\n\n\x02\x04\x0b\x12\x04f\0k\x01\n\n\n\x03\x04\x0b\x01\
+    \x12\x03f\x08\x11\n\x0b\n\x04\x04\x0b\x02\0\x12\x03g\x02\x19\n\x0c\n\x05\
+    \x04\x0b\x02\0\x04\x12\x03g\x02\n\n\x0c\n\x05\x04\x0b\x02\0\x05\x12\x03g\
+    \x0b\x10\n\x0c\n\x05\x04\x0b\x02\0\x01\x12\x03g\x11\x14\n\x0c\n\x05\x04\
+    \x0b\x02\0\x03\x12\x03g\x17\x18\nQ\n\x04\x04\x0b\x02\x01\x12\x03i\x02&\
+    \x1aD\x20Override\x20the\x20shutdown\x20settings\x20given\x20when\x20the\
+    \x20service\x20was\x20spawned.\n\n\x0c\n\x05\x04\x0b\x02\x01\x04\x12\x03\
+    i\x02\n\n\x0c\n\x05\x04\x0b\x02\x01\x05\x12\x03i\x0b\x11\n\x0c\n\x05\x04\
+    \x0b\x02\x01\x01\x12\x03i\x12!\n\x0c\n\x05\x04\x0b\x02\x01\x03\x12\x03i$\
+    %\n\x0b\n\x04\x04\x0b\x02\x02\x12\x03j\x02'\n\x0c\n\x05\x04\x0b\x02\x02\
+    \x04\x12\x03j\x02\n\n\x0c\n\x05\x04\x0b\x02\x02\x05\x12\x03j\x0b\x11\n\
+    \x0c\n\x05\x04\x0b\x02\x02\x01\x12\x03j\x12\"\n\x0c\n\x05\x04\x0b\x02\
+    \x02\x03\x12\x03j%&\n\n\n\x02\x04\x0c\x12\x04m\0p\x01\n\n\n\x03\x04\x0c\
+    \x01\x12\x03m\x08\x13\n\x0b\n\x04\x04\x0c\x02\0\x12\x03n\x02\x1f\n\x0c\n\
+    \x05\x04\x0c\x02\0\x04\x12\x03n\x02\n\n\x0c\n\x05\x04\x0c\x02\0\x05\x12\
+    \x03n\x0b\x10\n\x0c\n\x05\x04\x0c\x02\0\x01\x12\x03n\x11\x1a\n\x0c\n\x05\
+    \x04\x0c\x02\0\x03\x12\x03n\x1d\x1e\n\x0b\n\x04\x04\x0c\x02\x01\x12\x03o\
+    \x02.\n\x0c\n\x05\x04\x0c\x02\x01\x04\x12\x03o\x02\n\n\x0c\n\x05\x04\x0c\
+    \x02\x01\x06\x12\x03o\x0b\x19\n\x0c\n\x05\x04\x0c\x02\x01\x01\x12\x03o\
+    \x1a)\n\x0c\n\x05\x04\x0c\x02\x01\x03\x12\x03o,-\n\n\n\x02\x05\0\x12\x04\
+    r\0v\x01\n\n\n\x03\x05\0\x01\x12\x03r\x05\x13\n\x0b\n\x04\x05\0\x02\0\
+    \x12\x03s\x02\x14\n\x0c\n\x05\x05\0\x02\0\x01\x12\x03s\x02\x0f\n\x0c\n\
+    \x05\x05\0\x02\0\x02\x12\x03s\x12\x13\n\x0b\n\x04\x05\0\x02\x01\x12\x03t\
+    \x02\x1a\n\x0c\n\x05\x05\0\x02\x01\x01\x12\x03t\x02\x15\n\x0c\n\x05\x05\
+    \0\x02\x01\x02\x12\x03t\x18\x19\n\x0b\n\x04\x05\0\x02\x02\x12\x03u\x02\r\
+    \n\x0c\n\x05\x05\0\x02\x02\x01\x12\x03u\x02\x08\n\x0c\n\x05\x05\0\x02\
+    \x02\x02\x12\x03u\x0b\x0c\
 ";
 
 static mut file_descriptor_proto_lazy: ::protobuf::lazy::Lazy<::protobuf::descriptor::FileDescriptorProto> = ::protobuf::lazy::Lazy {
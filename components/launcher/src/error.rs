@@ -38,6 +38,7 @@ pub enum Error {
     SupPackageNotFound,
     SupShutdown,
     SupSpawn(io::Error),
+    UnknownCapability(String),
     UserNotFound(String),
 }
 
@@ -67,6 +68,7 @@ impl fmt::Display for Error {
             }
             Error::SupShutdown => format!("Error waiting for Supervisor to shutdown"),
             Error::SupSpawn(ref e) => format!("Unable to spawn Supervisor, {}", e),
+            Error::UnknownCapability(ref e) => format!("Unknown capability '{}'", e),
             Error::UserNotFound(ref e) => format!("No UID for user '{}' could be found", e),
         };
         write!(f, "{}", msg)
@@ -89,6 +91,7 @@ impl error::Error for Error {
             Error::SupPackageNotFound => "Unable to locate Supervisor package on disk",
             Error::SupShutdown => "Error waiting for Supervisor to shutdown",
             Error::SupSpawn(_) => "Unable to spawn Supervisor",
+            Error::UnknownCapability(_) => "Unknown capability",
             Error::UserNotFound(_) => "No matching UID for user found",
         }
     }
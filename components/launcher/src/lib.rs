@@ -23,6 +23,8 @@ extern crate libc;
 #[macro_use]
 extern crate log;
 extern crate protobuf;
+#[cfg(test)]
+extern crate tempdir;
 extern crate time;
 #[cfg(windows)]
 extern crate winapi;
@@ -0,0 +1,42 @@
+// Copyright (c) 2018 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::os::process::Pid;
+use protocol;
+
+use super::{HandleResult, Handler};
+use server::ServiceTable;
+
+pub struct ExitCodeHandler;
+impl Handler for ExitCodeHandler {
+    type Message = protocol::ExitCode;
+    type Reply = protocol::ExitCodeOk;
+
+    fn handle(msg: Self::Message, services: &mut ServiceTable) -> HandleResult<Self::Reply> {
+        match services.take_exit(msg.get_pid() as Pid) {
+            Some(exit_code) => {
+                let mut reply = protocol::ExitCodeOk::new();
+                if let Some(code) = exit_code {
+                    reply.set_exit_code(code);
+                }
+                Ok(reply)
+            }
+            None => {
+                let mut reply = protocol::NetErr::new();
+                reply.set_code(protocol::ErrCode::NoPID);
+                Err(reply)
+            }
+        }
+    }
+}
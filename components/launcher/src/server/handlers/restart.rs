@@ -15,36 +15,33 @@
 use core::os::process::Pid;
 use protocol;
 
-use super::{HandleResult, Handler};
-use server::ServiceTable;
-use service;
+use server::{self, Sender, ServiceTable};
 
 pub struct RestartHandler;
-impl Handler for RestartHandler {
-    type Message = protocol::Restart;
-    type Reply = protocol::SpawnOk;
-
-    fn handle(msg: Self::Message, services: &mut ServiceTable) -> HandleResult<Self::Reply> {
-        let mut service = match services.remove(msg.get_pid() as Pid) {
-            Some(service) => service,
+impl RestartHandler {
+    /// Ask a service to stop and leave it to the `ServiceTable` to spawn it again and reply once
+    /// it has stopped, so that the Launcher keeps serving other requests meanwhile.
+    pub fn run(tx: &Sender, txn: protocol::NetTxn, services: &mut ServiceTable) {
+        let msg = match txn.decode::<protocol::Restart>() {
+            Ok(msg) => msg,
+            Err(err) => {
+                error!("{}: decoding, {}", txn.message_id(), err);
+                return;
+            }
+        };
+        trace!("{}, {:?}, {:?}", txn.message_id(), msg, services);
+        match services.remove(msg.get_pid() as Pid) {
+            Some(service) => {
+                debug!("Restarting: {}", service.id());
+                services.restart(service, txn);
+            }
             None => {
                 let mut reply = protocol::NetErr::new();
                 reply.set_code(protocol::ErrCode::NoPID);
-                return Err(reply);
-            }
-        };
-        service.kill();
-        match service.wait() {
-            Ok(_status) => match service::run(service.take_args()) {
-                Ok(new_service) => {
-                    let mut reply = protocol::SpawnOk::new();
-                    reply.set_pid(new_service.id().into());
-                    services.insert(new_service);
-                    Ok(reply)
+                if let Err(err) = server::reply(tx, &txn, &reply) {
+                    error!("{}: replying, {}", txn.message_id(), err);
                 }
-                Err(err) => Err(protocol::error(err)),
-            },
-            Err(err) => Err(protocol::error(err)),
+            }
         }
     }
 }
@@ -15,33 +15,39 @@
 use core::os::process::Pid;
 use protocol;
 
-use super::{HandleResult, Handler};
-use server::ServiceTable;
+use server::{self, Sender, ServiceTable};
+use service::ShutdownConfig;
 
 pub struct TerminateHandler;
-impl Handler for TerminateHandler {
-    type Message = protocol::Terminate;
-    type Reply = protocol::TerminateOk;
-
-    fn handle(msg: Self::Message, services: &mut ServiceTable) -> HandleResult<Self::Reply> {
-        match services.get_mut(msg.get_pid() as Pid) {
+impl TerminateHandler {
+    /// Ask a service to stop and leave it to the `ServiceTable` to reply once it has, so that the
+    /// Launcher keeps serving other requests while the service shuts down.
+    pub fn run(tx: &Sender, txn: protocol::NetTxn, services: &mut ServiceTable) {
+        let msg = match txn.decode::<protocol::Terminate>() {
+            Ok(msg) => msg,
+            Err(err) => {
+                error!("{}: decoding, {}", txn.message_id(), err);
+                return;
+            }
+        };
+        trace!("{}, {:?}, {:?}", txn.message_id(), msg, services);
+        match services.remove(msg.get_pid() as Pid) {
             Some(service) => {
-                debug!("Terminating: {}", service.id());
-                let shutdown_method = service.kill();
-                match service.wait() {
-                    Ok(status) => {
-                        let mut reply = protocol::TerminateOk::new();
-                        reply.set_exit_code(status.code().unwrap_or(0));
-                        reply.set_shutdown_method(shutdown_method);
-                        Ok(reply)
-                    }
-                    Err(err) => Err(protocol::error(err)),
-                }
+                let shutdown = ShutdownConfig::from_terminate(&msg, service.args());
+                debug!(
+                    "Terminating: {}, signal={}, timeout={}s",
+                    service.id(),
+                    shutdown.signal,
+                    shutdown.timeout
+                );
+                services.terminate(service, &shutdown, txn);
             }
             None => {
                 let mut reply = protocol::NetErr::new();
                 reply.set_code(protocol::ErrCode::NoPID);
-                Err(reply)
+                if let Err(err) = server::reply(tx, &txn, &reply) {
+                    error!("{}: replying, {}", txn.message_id(), err);
+                }
             }
         }
     }
@@ -15,6 +15,7 @@
 mod handlers;
 
 use std::collections::HashMap;
+use std::env;
 use std::path::PathBuf;
 use std::process::{Child, Command, Stdio};
 use std::str::FromStr;
@@ -37,10 +38,12 @@ use protocol::{self, ERR_NO_RETRY_EXCODE, OK_NO_RETRY_EXCODE};
 
 use self::handlers::Handler;
 use error::{Error, Result};
-use service::Service;
+use service::{self, Service, ShutdownConfig, Termination};
 use {SUP_CMD, SUP_PACKAGE_IDENT};
 
 const SUP_CMD_ENVVAR: &'static str = "HAB_SUP_BINARY";
+/// Exit codes held for the Supervisor before the Launcher starts forgetting them.
+const MAX_HELD_EXITS: usize = 256;
 static LOGKEY: &'static str = "SV";
 
 type Receiver = IpcReceiver<Vec<u8>>;
@@ -57,17 +60,23 @@ pub struct Server {
     rx: Receiver,
     supervisor: Child,
     args: Vec<String>,
+    /// Token for the Supervisor's HTTP Gateway, taken out of the Launcher's environment so that
+    /// the services it spawns don't inherit it.
+    gateway_auth_token: Option<String>,
 }
 
 impl Server {
     pub fn new(args: Vec<String>) -> Result<Self> {
-        let ((rx, tx), supervisor) = Self::init(&args, false)?;
+        let gateway_auth_token = env::var(protocol::GATEWAY_AUTH_TOKEN_ENV).ok();
+        env::remove_var(protocol::GATEWAY_AUTH_TOKEN_ENV);
+        let ((rx, tx), supervisor) = Self::init(&args, gateway_auth_token.as_ref(), false)?;
         Ok(Server {
             services: ServiceTable::default(),
             tx: tx,
             rx: rx,
             supervisor: supervisor,
             args: args,
+            gateway_auth_token: gateway_auth_token,
         })
     }
 
@@ -76,9 +85,13 @@ impl Server {
     /// Passing a value of true to the `clean` argument will force the Supervisor to clean the
     /// Launcher's process LOCK before starting. This is useful when restarting a Supervisor
     /// that terminated gracefully.
-    fn init(args: &[String], clean: bool) -> Result<((Receiver, Sender), Child)> {
+    fn init(
+        args: &[String],
+        gateway_auth_token: Option<&String>,
+        clean: bool,
+    ) -> Result<((Receiver, Sender), Child)> {
         let (server, pipe) = IpcOneShotServer::new().map_err(Error::OpenPipe)?;
-        let supervisor = spawn_supervisor(&pipe, args, clean)?;
+        let supervisor = spawn_supervisor(&pipe, args, gateway_auth_token, clean)?;
         let channel = setup_connection(server)?;
         Ok((channel, supervisor))
     }
@@ -87,7 +100,9 @@ impl Server {
     fn reload(&mut self) -> Result<()> {
         self.supervisor.kill();
         self.supervisor.wait();
-        let ((rx, tx), supervisor) = Self::init(&self.args, true)?;
+        let ((rx, tx), supervisor) =
+            Self::init(&self.args, self.gateway_auth_token.as_ref(), true)?;
+        self.services.forget_terminate_requests();
         self.tx = tx;
         self.rx = rx;
         self.supervisor = supervisor;
@@ -149,6 +164,7 @@ impl Server {
     }
 
     fn reap_services(&mut self) {
+        self.services.poll_terminations(&self.tx);
         self.services.reap_services()
     }
 
@@ -306,55 +322,181 @@ impl Server {
 }
 
 #[derive(Debug, Default)]
-pub struct ServiceTable(HashMap<Pid, Service>);
+pub struct ServiceTable {
+    services: HashMap<Pid, Service>,
+    /// Exit codes of services which exited on their own, until the Supervisor asks for them.
+    exits: HashMap<Pid, Option<i32>>,
+    /// Services which are being terminated, polled on each tick until they stopped.
+    terminating: Vec<Terminating>,
+}
+
+/// A service being terminated at the request of the Supervisor, which is replied to once the
+/// service stopped. The request is `None` if the Supervisor which sent it was restarted since.
+#[derive(Debug)]
+struct Terminating {
+    service: Service,
+    termination: Termination,
+    txn: Option<protocol::NetTxn>,
+    /// Whether the service is spawned again once it stopped, as asked by a `Restart`.
+    restart: bool,
+}
 
 impl ServiceTable {
     pub fn get(&self, pid: Pid) -> Option<&Service> {
-        self.0.get(&pid)
+        self.services.get(&pid)
     }
 
     pub fn get_mut(&mut self, pid: Pid) -> Option<&mut Service> {
-        self.0.get_mut(&pid)
+        self.services.get_mut(&pid)
     }
 
     pub fn insert(&mut self, service: Service) {
-        self.0.insert(service.id(), service);
+        self.services.insert(service.id(), service);
     }
 
     pub fn remove(&mut self, pid: Pid) -> Option<Service> {
-        self.0.remove(&pid)
+        self.services.remove(&pid)
+    }
+
+    /// Take the exit code of a service which exited on its own. The outer `Option` is `None` if
+    /// no such service was reaped, and the inner one if the service was terminated by a signal.
+    pub fn take_exit(&mut self, pid: Pid) -> Option<Option<i32>> {
+        self.exits.remove(&pid)
+    }
+
+    /// Start terminating a service, replying to the Supervisor's request once it has stopped.
+    pub fn terminate(
+        &mut self,
+        mut service: Service,
+        shutdown: &ShutdownConfig,
+        txn: protocol::NetTxn,
+    ) {
+        let termination = service.terminate(shutdown);
+        self.terminating.push(Terminating {
+            service: service,
+            termination: termination,
+            txn: Some(txn),
+            restart: false,
+        });
+    }
+
+    /// Start terminating a service as requested when it was spawned, spawning it again and
+    /// replying to the Supervisor's request once it has stopped.
+    pub fn restart(&mut self, mut service: Service, txn: protocol::NetTxn) {
+        let shutdown = ShutdownConfig::from_spawn(service.args());
+        let termination = service.terminate(&shutdown);
+        self.terminating.push(Terminating {
+            service: service,
+            termination: termination,
+            txn: Some(txn),
+            restart: true,
+        });
+    }
+
+    fn forget_terminate_requests(&mut self) {
+        for terminating in self.terminating.iter_mut() {
+            terminating.txn = None;
+        }
     }
 
     fn kill_all(&mut self) {
-        for service in self.0.values_mut() {
+        for service in self.services.values_mut() {
             outputln!(preamble service.name(), "Stopping...");
             let shutdown_method = service.kill();
             outputln!(preamble service.name(), "Shutdown OK: {}", shutdown_method);
         }
+        for terminating in self.terminating.iter_mut() {
+            let shutdown_method = terminating
+                .service
+                .wait_for_termination(&mut terminating.termination);
+            outputln!(preamble terminating.service.name(), "Shutdown OK: {}", shutdown_method);
+        }
+    }
+
+    /// Reply to the requests of services which stopped since the last tick, spawning again those
+    /// which are restarted. A service isn't spawned again if the Supervisor which asked for it
+    /// was restarted since, as that Supervisor starts its services anew.
+    fn poll_terminations(&mut self, tx: &Sender) {
+        let mut i = 0;
+        while i < self.terminating.len() {
+            let shutdown_method = {
+                let Terminating {
+                    ref mut service,
+                    ref mut termination,
+                    ..
+                } = self.terminating[i];
+                service.poll_termination(termination)
+            };
+            let shutdown_method = match shutdown_method {
+                Some(shutdown_method) => shutdown_method,
+                None => {
+                    i += 1;
+                    continue;
+                }
+            };
+            let Terminating {
+                mut service,
+                txn,
+                restart,
+                ..
+            } = self.terminating.swap_remove(i);
+            let txn = match txn {
+                Some(txn) => txn,
+                None => continue,
+            };
+            let result = match service.wait() {
+                Ok(_) if restart => match service::run(service.take_args()) {
+                    Ok(new_service) => {
+                        let mut msg = protocol::SpawnOk::new();
+                        msg.set_pid(new_service.id().into());
+                        self.insert(new_service);
+                        reply(tx, &txn, &msg)
+                    }
+                    Err(err) => reply(tx, &txn, &protocol::error(err)),
+                },
+                Ok(status) => {
+                    let mut msg = protocol::TerminateOk::new();
+                    msg.set_exit_code(status.code().unwrap_or(0));
+                    msg.set_shutdown_method(shutdown_method);
+                    reply(tx, &txn, &msg)
+                }
+                Err(err) => reply(tx, &txn, &protocol::error(err)),
+            };
+            if let Err(err) = result {
+                error!("{}: replying, {}", txn.message_id(), err);
+            }
+        }
     }
 
     fn reap_services(&mut self) {
-        let mut dead: Vec<Pid> = vec![];
-        for service in self.0.values_mut() {
+        let mut dead: Vec<(Pid, Option<i32>)> = vec![];
+        for service in self.services.values_mut() {
             match service.try_wait() {
                 Ok(None) => (),
-                Ok(Some(code)) => {
+                Ok(Some(status)) => {
                     outputln!(
                         "Child for service '{}' with PID {} exited with code {}",
                         service.name(),
                         service.id(),
-                        code
+                        status
                     );
-                    dead.push(service.id());
+                    dead.push((service.id(), status.code()));
                 }
                 Err(err) => {
                     warn!("Error waiting for child, {}, {}", service.id(), err);
-                    dead.push(service.id());
+                    dead.push((service.id(), None));
                 }
             }
         }
-        for pid in dead {
-            self.0.remove(&pid);
+        for (pid, code) in dead {
+            self.services.remove(&pid);
+            // Only Supervisors which restart a service on failure ask for its exit code, so make
+            // room by forgetting an arbitrary exit rather than holding them all.
+            if self.exits.len() >= MAX_HELD_EXITS {
+                let stale = *self.exits.keys().next().unwrap();
+                self.exits.remove(&stale);
+            }
+            self.exits.insert(pid, code);
         }
     }
 }
@@ -416,6 +558,8 @@ fn dispatch(tx: &Sender, bytes: &[u8], services: &mut ServiceTable) {
         }
     };
     let func = match msg.message_id() {
+        "ExitCode" => handlers::ExitCodeHandler::run,
+        "ResourceUsage" => handlers::ResourceUsageHandler::run,
         "Restart" => handlers::RestartHandler::run,
         "Spawn" => handlers::SpawnHandler::run,
         "Terminate" => handlers::TerminateHandler::run,
@@ -442,12 +586,20 @@ fn setup_connection(server: IpcOneShotServer<Vec<u8>>) -> Result<(Receiver, Send
 /// Passing a value of true to the `clean` argument will force the Supervisor to clean the
 /// Launcher's process LOCK before starting. This is useful when restarting a Supervisor
 /// that terminated gracefully.
-fn spawn_supervisor(pipe: &str, args: &[String], clean: bool) -> Result<Child> {
+fn spawn_supervisor(
+    pipe: &str,
+    args: &[String],
+    gateway_auth_token: Option<&String>,
+    clean: bool,
+) -> Result<Child> {
     let binary = supervisor_cmd()?;
     let mut command = Command::new(&binary);
     if clean {
         command.env(protocol::LAUNCHER_LOCK_CLEAN_ENV, clean.to_string());
     }
+    if let Some(token) = gateway_auth_token {
+        command.env(protocol::GATEWAY_AUTH_TOKEN_ENV, token);
+    }
     debug!("Starting Supervisor...");
     let child = command
         .stdout(Stdio::inherit())
@@ -13,19 +13,89 @@
 // limitations under the License.
 
 use std::fmt;
+use std::fs::{self, File, OpenOptions};
 use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::PathBuf;
 #[cfg(unix)]
 use std::process::{ChildStderr, ChildStdout, ExitStatus};
+use std::sync::{Arc, Mutex};
 use std::thread;
+use std::time::Duration as StdDuration;
 
 #[cfg(windows)]
 use core::os::process::windows_child::{ChildStderr, ChildStdout, ExitStatus};
 use core::os::process::Pid;
-use protocol;
+use protocol::{self, ShutdownMethod};
+use time::{self, Duration, SteadyTime};
 
 use error::Result;
 pub use sys::service::*;
 
+/// Size in bytes a service's log file may grow to before it is rotated.
+const MAX_LOG_SIZE: u64 = 10 * 1024 * 1024;
+
+/// Name of the signal asking a service to stop, unless it was spawned with another.
+pub const DEFAULT_SHUTDOWN_SIGNAL: &'static str = "TERM";
+/// Seconds to wait for a service to stop before killing it, unless it was spawned with another
+/// timeout.
+pub const DEFAULT_SHUTDOWN_TIMEOUT: u32 = 8;
+
+/// How to stop a service: the signal asking it to stop, which is unused on Windows, and the
+/// seconds to wait for it to do so before it's killed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ShutdownConfig {
+    pub signal: String,
+    pub timeout: u32,
+}
+
+impl ShutdownConfig {
+    /// The shutdown requested when a service was spawned.
+    pub fn from_spawn(spawn: &protocol::Spawn) -> Self {
+        ShutdownConfig {
+            signal: if spawn.has_shutdown_signal() {
+                spawn.get_shutdown_signal().to_string()
+            } else {
+                DEFAULT_SHUTDOWN_SIGNAL.to_string()
+            },
+            timeout: if spawn.has_shutdown_timeout() {
+                spawn.get_shutdown_timeout()
+            } else {
+                DEFAULT_SHUTDOWN_TIMEOUT
+            },
+        }
+    }
+
+    /// The shutdown requested when terminating a service, falling back to the one requested when
+    /// it was spawned.
+    pub fn from_terminate(msg: &protocol::Terminate, spawn: &protocol::Spawn) -> Self {
+        let mut shutdown = Self::from_spawn(spawn);
+        if msg.has_shutdown_signal() {
+            shutdown.signal = msg.get_shutdown_signal().to_string();
+        }
+        if msg.has_shutdown_timeout() {
+            shutdown.timeout = msg.get_shutdown_timeout();
+        }
+        shutdown
+    }
+}
+
+/// A stop of a service which is under way. The service was asked to stop, and is killed if it's
+/// still running once the deadline has passed.
+#[derive(Debug)]
+pub struct Termination {
+    pub deadline: SteadyTime,
+    pub method: ShutdownMethod,
+}
+
+impl Termination {
+    pub fn new(timeout: Duration, method: ShutdownMethod) -> Self {
+        Termination {
+            deadline: SteadyTime::now() + timeout,
+            method: method,
+        }
+    }
+}
+
 pub struct Service {
     args: protocol::Spawn,
     process: Process,
@@ -39,18 +109,35 @@ impl Service {
         stdout: Option<ChildStdout>,
         stderr: Option<ChildStderr>,
     ) -> Self {
+        let log = if spawn.has_log_path() {
+            match ServiceLog::open(spawn.get_log_path()) {
+                Ok(log) => Some(Arc::new(Mutex::new(log))),
+                Err(err) => {
+                    warn!(
+                        "Unable to open log file {} for {}, {}",
+                        spawn.get_log_path(),
+                        spawn.get_id(),
+                        err
+                    );
+                    None
+                }
+            }
+        } else {
+            None
+        };
         if let Some(stdout) = stdout {
             let id = spawn.get_id().to_string();
+            let log = log.clone();
             thread::Builder::new()
                 .name(format!("{}-out", spawn.get_id()))
-                .spawn(move || pipe_stdout(stdout, id))
+                .spawn(move || pipe_stdout(stdout, id, log))
                 .ok();
         }
         if let Some(stderr) = stderr {
             let id = spawn.get_id().to_string();
             thread::Builder::new()
                 .name(format!("{}-err", spawn.get_id()))
-                .spawn(move || pipe_stderr(stderr, id))
+                .spawn(move || pipe_stderr(stderr, id, log))
                 .ok();
         }
         Service {
@@ -68,16 +155,45 @@ impl Service {
         self.process.id()
     }
 
-    /// Attempt to gracefully terminate a proccess and then forcefully kill it after
-    /// 8 seconds if it has not terminated.
-    pub fn kill(&mut self) -> protocol::ShutdownMethod {
-        self.process.kill()
+    /// Attempt to gracefully terminate a proccess and then forcefully kill it if it has not
+    /// terminated, as requested when the service was spawned.
+    pub fn kill(&mut self) -> ShutdownMethod {
+        let shutdown = ShutdownConfig::from_spawn(&self.args);
+        let mut termination = self.terminate(&shutdown);
+        self.wait_for_termination(&mut termination)
+    }
+
+    /// Ask the service to stop as requested by the given shutdown, without waiting for it to do
+    /// so. The returned termination is polled with `poll_termination` until the service stopped.
+    pub fn terminate(&mut self, shutdown: &ShutdownConfig) -> Termination {
+        self.process.terminate(shutdown)
+    }
+
+    /// Check whether a service being terminated has stopped, killing it once the termination's
+    /// deadline has passed. Returns how the service was stopped once it has.
+    pub fn poll_termination(&mut self, termination: &mut Termination) -> Option<ShutdownMethod> {
+        self.process.poll_termination(termination)
+    }
+
+    /// Block until a service being terminated has stopped.
+    pub fn wait_for_termination(&mut self, termination: &mut Termination) -> ShutdownMethod {
+        loop {
+            if let Some(method) = self.poll_termination(termination) {
+                return method;
+            }
+            thread::sleep(StdDuration::from_millis(10));
+        }
     }
 
     pub fn name(&self) -> &str {
         self.args.get_id()
     }
 
+    /// Resources used by the service, as far as its resource limits let them be measured.
+    pub fn resource_usage(&self) -> protocol::ResourceUsageOk {
+        self.process.resource_usage()
+    }
+
     pub fn take_args(self) -> protocol::Spawn {
         self.args
     }
@@ -102,8 +218,75 @@ impl fmt::Debug for Service {
     }
 }
 
+/// Append-only file which receives a copy of each line of a service's output.
+///
+/// Each line is written as `<timestamp> <stream> <line>` where `timestamp` is an RFC 3339 UTC
+/// timestamp and `stream` is `O` for standard out or `E` for standard error. The Supervisor reads
+/// this file to serve a service's logs to remote clients. The file is moved aside to `<path>.1`
+/// once it grows past `MAX_LOG_SIZE`.
+struct ServiceLog {
+    path: PathBuf,
+    file: File,
+    size: u64,
+}
+
+impl ServiceLog {
+    fn open<T>(path: T) -> io::Result<Self>
+    where
+        T: Into<PathBuf>,
+    {
+        let path = path.into();
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let size = file.metadata()?.len();
+        Ok(ServiceLog {
+            path: path,
+            file: file,
+            size: size,
+        })
+    }
+
+    fn write_line(&mut self, stream: &str, line: &str) -> io::Result<()> {
+        if self.size >= MAX_LOG_SIZE {
+            self.rotate()?;
+        }
+        let timestamp = time::now_utc();
+        let entry = format!(
+            "{} {} {}\n",
+            timestamp.rfc3339(),
+            stream,
+            line.trim_right_matches('\n')
+        );
+        self.file.write_all(entry.as_bytes())?;
+        self.size += entry.len() as u64;
+        Ok(())
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        let mut rotated = self.path.clone().into_os_string();
+        rotated.push(".1");
+        fs::rename(&self.path, rotated)?;
+        self.file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+fn log_line(log: &Option<Arc<Mutex<ServiceLog>>>, stream: &str, line: &str) {
+    if let Some(ref log) = *log {
+        if let Err(err) = log.lock()
+            .expect("Service log lock poisoned")
+            .write_line(stream, line)
+        {
+            debug!("Unable to write to service log, {}", err);
+        }
+    }
+}
+
 /// Consume output from a child process until EOF, then finish
-fn pipe_stdout<T>(out: T, id: String)
+fn pipe_stdout<T>(out: T, id: String, log: Option<Arc<Mutex<ServiceLog>>>)
 where
     T: Read,
 {
@@ -112,12 +295,13 @@ where
     while reader.read_line(&mut buffer).unwrap() > 0 {
         let line = output_format!(preamble &id, logkey "O", buffer);
         writeln!(&mut io::stdout(), "{}", line).expect("unable to write to stdout");
+        log_line(&log, "O", &buffer);
         buffer.clear();
     }
 }
 
 /// Consume standard error from a child process until EOF, then finish
-fn pipe_stderr<T>(err: T, id: String)
+fn pipe_stderr<T>(err: T, id: String, log: Option<Arc<Mutex<ServiceLog>>>)
 where
     T: Read,
 {
@@ -126,6 +310,95 @@ where
     while reader.read_line(&mut buffer).unwrap() > 0 {
         let line = output_format!(preamble &id, logkey "E", buffer);
         writeln!(&mut io::stderr(), "{}", line).expect("unable to write to stderr");
+        log_line(&log, "E", &buffer);
         buffer.clear();
     }
 }
+
+#[cfg(test)]
+mod test {
+    use protocol;
+
+    use super::*;
+
+    fn spawn(signal: Option<&str>, timeout: Option<u32>) -> protocol::Spawn {
+        let mut spawn = protocol::Spawn::new();
+        spawn.set_id("redis".to_string());
+        if let Some(signal) = signal {
+            spawn.set_shutdown_signal(signal.to_string());
+        }
+        if let Some(timeout) = timeout {
+            spawn.set_shutdown_timeout(timeout);
+        }
+        spawn
+    }
+
+    #[test]
+    fn shutdown_from_spawn_falls_back_to_defaults() {
+        assert_eq!(
+            ShutdownConfig::from_spawn(&spawn(None, None)),
+            ShutdownConfig {
+                signal: DEFAULT_SHUTDOWN_SIGNAL.to_string(),
+                timeout: DEFAULT_SHUTDOWN_TIMEOUT,
+            }
+        );
+        assert_eq!(
+            ShutdownConfig::from_spawn(&spawn(Some("QUIT"), None)),
+            ShutdownConfig {
+                signal: "QUIT".to_string(),
+                timeout: DEFAULT_SHUTDOWN_TIMEOUT,
+            }
+        );
+        assert_eq!(
+            ShutdownConfig::from_spawn(&spawn(None, Some(30))),
+            ShutdownConfig {
+                signal: DEFAULT_SHUTDOWN_SIGNAL.to_string(),
+                timeout: 30,
+            }
+        );
+    }
+
+    #[test]
+    fn shutdown_from_terminate_overrides_spawn() {
+        let spawn = spawn(Some("HUP"), Some(30));
+        let mut msg = protocol::Terminate::new();
+        assert_eq!(
+            ShutdownConfig::from_terminate(&msg, &spawn),
+            ShutdownConfig {
+                signal: "HUP".to_string(),
+                timeout: 30,
+            }
+        );
+
+        msg.set_shutdown_timeout(5);
+        assert_eq!(
+            ShutdownConfig::from_terminate(&msg, &spawn),
+            ShutdownConfig {
+                signal: "HUP".to_string(),
+                timeout: 5,
+            }
+        );
+
+        msg.set_shutdown_signal("INT".to_string());
+        assert_eq!(
+            ShutdownConfig::from_terminate(&msg, &spawn),
+            ShutdownConfig {
+                signal: "INT".to_string(),
+                timeout: 5,
+            }
+        );
+    }
+
+    #[test]
+    fn shutdown_from_terminate_falls_back_to_defaults() {
+        let mut msg = protocol::Terminate::new();
+        msg.set_shutdown_signal("USR1".to_string());
+        assert_eq!(
+            ShutdownConfig::from_terminate(&msg, &spawn(None, None)),
+            ShutdownConfig {
+                signal: "USR1".to_string(),
+                timeout: DEFAULT_SHUTDOWN_TIMEOUT,
+            }
+        );
+    }
+}
@@ -0,0 +1,479 @@
+// Copyright (c) 2018 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Resource limits for services through cgroups v2.
+//!
+//! Each service spawned with resource limits gets its own cgroup, named after the service, under
+//! a `habitat` cgroup next to the Launcher's own cgroup, so that services stay within the limits
+//! and the delegation of whatever runs the Launcher, such as a systemd unit. Processes may only
+//! live in the leaves of the hierarchy, so the Launcher first moves the processes of its cgroup
+//! into a `hab-launcher` leaf below it. It enables the controllers the limits need on the way
+//! down, writes the limits, and the service's process joins the cgroup before it's executed.
+//! Where cgroups v2 aren't mounted, or can't be written, services run without limits.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+
+use libc;
+use protocol;
+
+/// Mount point of the cgroup v2 unified hierarchy.
+const CGROUP_ROOT: &'static str = "/sys/fs/cgroup";
+/// Cgroups the current process belongs to.
+const PROC_SELF_CGROUP: &'static str = "/proc/self/cgroup";
+/// Name of the cgroup holding the cgroups of all services.
+const CGROUP_PARENT: &'static str = "habitat";
+/// Name of the leaf cgroup the processes of the Launcher's own cgroup are moved into.
+const CGROUP_LAUNCHER: &'static str = "hab-launcher";
+/// Period in microseconds over which the CPU quota of a service is enforced.
+const CPU_PERIOD_USEC: u64 = 100_000;
+
+#[derive(Debug)]
+pub struct Cgroup {
+    path: PathBuf,
+}
+
+impl Cgroup {
+    /// Create the cgroup of the service with the given name and apply the given limits to it.
+    /// Returns `None` if no limits are given, or if they can't be applied.
+    pub fn create(name: &str, limits: &protocol::ResourceLimits) -> Option<Self> {
+        let controllers = controllers(limits);
+        if controllers.is_empty() {
+            return None;
+        }
+        let root = Path::new(CGROUP_ROOT);
+        if !root.join("cgroup.controllers").is_file() {
+            warn!(
+                "Resource limits for {} are ignored, cgroups v2 are unavailable at {}",
+                name, CGROUP_ROOT
+            );
+            return None;
+        }
+        let base = match delegated_cgroup(root, Path::new(PROC_SELF_CGROUP)) {
+            Ok(base) => base,
+            Err(err) => {
+                warn!(
+                    "Resource limits for {} are ignored, unable to read the Launcher's cgroup, {}",
+                    name, err
+                );
+                return None;
+            }
+        };
+        let parent = base.join(CGROUP_PARENT);
+        let path = parent.join(name.replace('/', "_"));
+        let created = vacate(root, &base)
+            .and_then(|_| enable_controllers(&base, &controllers))
+            .and_then(|_| create_dir(&parent))
+            .and_then(|_| enable_controllers(&parent, &controllers))
+            .and_then(|_| create_dir(&path));
+        if let Err(err) = created {
+            warn!(
+                "Resource limits for {} are ignored, unable to create cgroup {}, {}",
+                name,
+                path.display(),
+                err
+            );
+            return None;
+        }
+        let cgroup = Cgroup { path: path };
+        cgroup.apply(limits);
+        Some(cgroup)
+    }
+
+    /// Open the file processes join the cgroup through. It's opened by the Launcher, so that a
+    /// service's process can join the cgroup with `join` once it no longer has the privileges to
+    /// open the file itself.
+    pub fn procs(&self) -> io::Result<File> {
+        OpenOptions::new()
+            .write(true)
+            .open(self.path.join("cgroup.procs"))
+    }
+
+    /// Read the resources used by the processes of the cgroup. Resources which can't be read are
+    /// left unset.
+    pub fn usage(&self) -> protocol::ResourceUsageOk {
+        let mut usage = protocol::ResourceUsageOk::new();
+        if let Some(memory) = self.read_value("memory.current") {
+            usage.set_memory_current(memory);
+        }
+        if let Some(pids) = self.read_value("pids.current") {
+            usage.set_pids_current(pids);
+        }
+        if let Some(cpu) = self.read_keyed("cpu.stat", "usage_usec") {
+            usage.set_cpu_usage_usec(cpu);
+        }
+        if let Some((read, written)) = self.read_io() {
+            usage.set_io_read_bytes(read);
+            usage.set_io_write_bytes(written);
+        }
+        usage
+    }
+
+    fn apply(&self, limits: &protocol::ResourceLimits) {
+        for (file, value) in settings(limits) {
+            if let Err(err) = write_file(&self.path.join(file), &value) {
+                warn!(
+                    "Unable to set {} to {} in cgroup {}, {}",
+                    file,
+                    value,
+                    self.path.display(),
+                    err
+                );
+            }
+        }
+    }
+
+    fn read_value(&self, file: &str) -> Option<u64> {
+        let mut content = String::new();
+        File::open(self.path.join(file))
+            .and_then(|mut f| f.read_to_string(&mut content))
+            .ok()
+            .and_then(|_| content.trim().parse().ok())
+    }
+
+    /// Read the value of the given key from a flat keyed file such as `cpu.stat`.
+    fn read_keyed(&self, file: &str, key: &str) -> Option<u64> {
+        let file = match File::open(self.path.join(file)) {
+            Ok(file) => file,
+            Err(_) => return None,
+        };
+        BufReader::new(file)
+            .lines()
+            .filter_map(|l| l.ok())
+            .filter_map(|line| {
+                let mut parts = line.split_whitespace();
+                match (parts.next(), parts.next()) {
+                    (Some(k), Some(value)) if k == key => value.parse().ok(),
+                    _ => None,
+                }
+            })
+            .next()
+    }
+
+    /// Read the bytes read and written by the cgroup, summed over all devices in `io.stat`.
+    fn read_io(&self) -> Option<(u64, u64)> {
+        let file = match File::open(self.path.join("io.stat")) {
+            Ok(file) => file,
+            Err(_) => return None,
+        };
+        let (mut read, mut written) = (0, 0);
+        for line in BufReader::new(file).lines().filter_map(|l| l.ok()) {
+            for field in line.split_whitespace().skip(1) {
+                let mut parts = field.splitn(2, '=');
+                let value = parts.nth(1).and_then(|v| v.parse::<u64>().ok()).unwrap_or(0);
+                if field.starts_with("rbytes=") {
+                    read += value;
+                } else if field.starts_with("wbytes=") {
+                    written += value;
+                }
+            }
+        }
+        Some((read, written))
+    }
+}
+
+impl Drop for Cgroup {
+    fn drop(&mut self) {
+        // The cgroup can only be removed once its processes are gone. A cgroup left behind is
+        // reused when the service is spawned again.
+        if let Err(err) = fs::remove_dir(&self.path) {
+            debug!("Unable to remove cgroup {}, {}", self.path.display(), err);
+        }
+    }
+}
+
+/// Move the calling process into the cgroup whose `cgroup.procs` file is open as `procs`. This
+/// runs in the forked child, so it only makes system calls.
+pub fn join(mut procs: &File) -> io::Result<()> {
+    // Writing 0 moves the writing process.
+    procs.write_all(b"0")
+}
+
+/// The cgroup the services' cgroups are created under: the Launcher's own cgroup, as listed in
+/// `proc_cgroup` and found below the hierarchy mounted at `root`, or the one above it once the
+/// Launcher was moved into its leaf.
+fn delegated_cgroup(root: &Path, proc_cgroup: &Path) -> io::Result<PathBuf> {
+    let file = File::open(proc_cgroup)?;
+    // The unified hierarchy is listed as `0::<path>`.
+    let own = BufReader::new(file)
+        .lines()
+        .filter_map(|l| l.ok())
+        .filter_map(|line| {
+            if line.starts_with("0::") {
+                Some(line[3..].trim_left_matches('/').to_string())
+            } else {
+                None
+            }
+        })
+        .next()
+        .ok_or(io::Error::new(
+            io::ErrorKind::NotFound,
+            "not in a cgroup v2 hierarchy",
+        ))?;
+    let own = root.join(own);
+    if own.file_name().map_or(false, |name| name == CGROUP_LAUNCHER) {
+        Ok(own.parent().unwrap_or(root).to_path_buf())
+    } else {
+        Ok(own)
+    }
+}
+
+/// Move the processes of the cgroup at `path` into its `hab-launcher` leaf, so that controllers
+/// can be enabled for its children. The `root` cgroup is exempt and left as it is.
+fn vacate(root: &Path, path: &Path) -> io::Result<()> {
+    if path == root {
+        return Ok(());
+    }
+    let mut pids = String::new();
+    File::open(path.join("cgroup.procs"))?.read_to_string(&mut pids)?;
+    if pids.trim().is_empty() {
+        return Ok(());
+    }
+    let leaf = path.join(CGROUP_LAUNCHER);
+    create_dir(&leaf)?;
+    for pid in pids.lines() {
+        match write_file(&leaf.join("cgroup.procs"), pid) {
+            // Processes may exit while they're moved.
+            Err(ref err) if err.raw_os_error() == Some(libc::ESRCH) => (),
+            result => result?,
+        }
+    }
+    Ok(())
+}
+
+/// The controllers needed to apply the given limits.
+fn controllers(limits: &protocol::ResourceLimits) -> Vec<&'static str> {
+    let mut controllers = vec![];
+    if limits.has_memory_max() {
+        controllers.push("memory");
+    }
+    if limits.has_cpu_weight() || limits.has_cpu_quota() {
+        controllers.push("cpu");
+    }
+    if limits.has_pids_max() {
+        controllers.push("pids");
+    }
+    if limits.has_io_weight() {
+        controllers.push("io");
+    }
+    controllers
+}
+
+/// The files of a cgroup setting the given limits, along with the values they're set to.
+fn settings(limits: &protocol::ResourceLimits) -> Vec<(&'static str, String)> {
+    let mut settings = vec![];
+    if limits.has_memory_max() {
+        settings.push(("memory.max", limits.get_memory_max().to_string()));
+    }
+    if limits.has_cpu_weight() {
+        settings.push(("cpu.weight", limits.get_cpu_weight().to_string()));
+    }
+    if limits.has_cpu_quota() {
+        // The quota is a percentage of one CPU, and may exceed 100 to allow several CPUs.
+        let quota = u64::from(limits.get_cpu_quota()) * CPU_PERIOD_USEC / 100;
+        settings.push(("cpu.max", format!("{} {}", quota, CPU_PERIOD_USEC)));
+    }
+    if limits.has_pids_max() {
+        settings.push(("pids.max", limits.get_pids_max().to_string()));
+    }
+    if limits.has_io_weight() {
+        settings.push(("io.weight", format!("default {}", limits.get_io_weight())));
+    }
+    settings
+}
+
+fn create_dir(path: &Path) -> io::Result<()> {
+    match fs::create_dir(path) {
+        Err(ref err) if err.kind() == io::ErrorKind::AlreadyExists => Ok(()),
+        result => result,
+    }
+}
+
+/// Enable the given controllers for the children of the cgroup at `path`. Controllers the kernel
+/// doesn't offer are skipped, with a warning.
+fn enable_controllers(path: &Path, controllers: &[&str]) -> io::Result<()> {
+    let mut available = String::new();
+    File::open(path.join("cgroup.controllers"))?.read_to_string(&mut available)?;
+    for controller in controllers {
+        if !available.split_whitespace().any(|c| c == *controller) {
+            warn!(
+                "The {} cgroup controller is unavailable in {}",
+                controller,
+                path.display()
+            );
+            continue;
+        }
+        write_file(
+            &path.join("cgroup.subtree_control"),
+            &format!("+{}", controller),
+        )?;
+    }
+    Ok(())
+}
+
+fn write_file(path: &Path, value: &str) -> io::Result<()> {
+    let mut file = OpenOptions::new().write(true).open(path)?;
+    file.write_all(value.as_bytes())
+}
+
+#[cfg(test)]
+mod test {
+    use tempdir::TempDir;
+
+    use super::*;
+
+    fn fixture(dir: &Path, file: &str, content: &str) {
+        File::create(dir.join(file))
+            .and_then(|mut f| f.write_all(content.as_bytes()))
+            .expect("Could not write fixture");
+    }
+
+    #[test]
+    fn delegated_cgroup_from_proc_self_cgroup() {
+        let tmp = TempDir::new("cgroup").unwrap();
+        let (root, proc_cgroup) = (tmp.path().join("fs"), tmp.path().join("cgroup"));
+
+        fixture(
+            tmp.path(),
+            "cgroup",
+            "1:name=systemd:/system.slice/hab.service\n0::/system.slice/hab.service\n",
+        );
+        assert_eq!(
+            delegated_cgroup(&root, &proc_cgroup).unwrap(),
+            root.join("system.slice/hab.service")
+        );
+
+        // Once the Launcher was moved into its leaf, services go next to it.
+        fixture(tmp.path(), "cgroup", "0::/system.slice/hab.service/hab-launcher\n");
+        assert_eq!(
+            delegated_cgroup(&root, &proc_cgroup).unwrap(),
+            root.join("system.slice/hab.service")
+        );
+
+        fixture(tmp.path(), "cgroup", "0::/\n");
+        assert_eq!(delegated_cgroup(&root, &proc_cgroup).unwrap(), root);
+
+        fixture(tmp.path(), "cgroup", "4:memory:/user.slice\n1:cpu,cpuacct:/user.slice\n");
+        assert_eq!(
+            delegated_cgroup(&root, &proc_cgroup).unwrap_err().kind(),
+            io::ErrorKind::NotFound
+        );
+    }
+
+    #[test]
+    fn cgroup_usage_from_files() {
+        let tmp = TempDir::new("cgroup").unwrap();
+        fixture(tmp.path(), "memory.current", "4096\n");
+        fixture(tmp.path(), "pids.current", "3\n");
+        fixture(
+            tmp.path(),
+            "cpu.stat",
+            "usage_usec 1500\nuser_usec 1000\nsystem_usec 500\n",
+        );
+        fixture(
+            tmp.path(),
+            "io.stat",
+            "8:0 rbytes=100 wbytes=20 rios=1 wios=1 dbytes=0 dios=0\n\
+             8:16 rbytes=5 wbytes=2 rios=1 wios=1 dbytes=0 dios=0\n",
+        );
+        let cgroup = Cgroup {
+            path: tmp.path().to_path_buf(),
+        };
+        assert_eq!(cgroup.read_keyed("cpu.stat", "system_usec"), Some(500));
+        assert_eq!(cgroup.read_keyed("cpu.stat", "nr_periods"), None);
+        assert_eq!(cgroup.read_io(), Some((105, 22)));
+
+        let usage = cgroup.usage();
+        assert_eq!(usage.get_memory_current(), 4096);
+        assert_eq!(usage.get_pids_current(), 3);
+        assert_eq!(usage.get_cpu_usage_usec(), 1500);
+        assert_eq!(usage.get_io_read_bytes(), 105);
+        assert_eq!(usage.get_io_write_bytes(), 22);
+    }
+
+    #[test]
+    fn cgroup_usage_without_files() {
+        let tmp = TempDir::new("cgroup").unwrap();
+        fixture(tmp.path(), "memory.current", "max\n");
+        let cgroup = Cgroup {
+            path: tmp.path().to_path_buf(),
+        };
+        assert_eq!(cgroup.read_io(), None);
+        let usage = cgroup.usage();
+        assert!(!usage.has_memory_current());
+        assert!(!usage.has_pids_current());
+        assert!(!usage.has_cpu_usage_usec());
+        assert!(!usage.has_io_read_bytes());
+    }
+
+    #[test]
+    fn settings_from_limits() {
+        let mut limits = protocol::ResourceLimits::new();
+        assert!(settings(&limits).is_empty());
+        assert!(controllers(&limits).is_empty());
+
+        limits.set_memory_max(1 << 30);
+        limits.set_cpu_quota(50);
+        limits.set_pids_max(64);
+        limits.set_io_weight(200);
+        assert_eq!(
+            settings(&limits),
+            vec![
+                ("memory.max", "1073741824".to_string()),
+                ("cpu.max", "50000 100000".to_string()),
+                ("pids.max", "64".to_string()),
+                ("io.weight", "default 200".to_string()),
+            ]
+        );
+        assert_eq!(controllers(&limits), vec!["memory", "cpu", "pids", "io"]);
+
+        let mut limits = protocol::ResourceLimits::new();
+        limits.set_cpu_quota(250);
+        assert_eq!(settings(&limits), vec![("cpu.max", "250000 100000".to_string())]);
+        assert_eq!(controllers(&limits), vec!["cpu"]);
+
+        let mut limits = protocol::ResourceLimits::new();
+        limits.set_cpu_weight(100);
+        assert_eq!(settings(&limits), vec![("cpu.weight", "100".to_string())]);
+        assert_eq!(controllers(&limits), vec!["cpu"]);
+    }
+
+    #[test]
+    fn apply_writes_limits() {
+        let tmp = TempDir::new("cgroup").unwrap();
+        for file in &["memory.max", "cpu.max"] {
+            fixture(tmp.path(), file, "");
+        }
+        let mut limits = protocol::ResourceLimits::new();
+        limits.set_memory_max(4096);
+        limits.set_cpu_quota(25);
+        // The pids controller isn't enabled, so pids.max is missing and skipped.
+        limits.set_pids_max(8);
+        let cgroup = Cgroup {
+            path: tmp.path().to_path_buf(),
+        };
+        cgroup.apply(&limits);
+        let read = |file: &str| {
+            let mut content = String::new();
+            File::open(tmp.path().join(file))
+                .and_then(|mut f| f.read_to_string(&mut content))
+                .unwrap();
+            content
+        };
+        assert_eq!(read("memory.max"), "4096");
+        assert_eq!(read("cpu.max"), "25000 100000");
+        assert!(!tmp.path().join("pids.max").exists());
+    }
+}
@@ -0,0 +1,294 @@
+// Copyright (c) 2018 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Isolation of services in Linux namespaces.
+//!
+//! A service isolated in a PID namespace can't be the process the Launcher spawned, as only the
+//! children of a process enter the namespace it creates. The spawned process stays outside, where
+//! the Launcher tracks it, and forks the init of the namespace, which forks the process going on
+//! to execute the service. Both wait for their child and exit with its status. They forward the
+//! signals sent to them, such as those stopping the service or sent with `hab svc signal`, to the
+//! process group of their child. Each child starts its own process group, so that a signal sent
+//! to the group of the spawned process is delivered once, and is killed along with its parent.
+
+pub use self::imp::enter;
+
+use protocol;
+
+/// The namespaces requested by a spawn.
+#[derive(Clone, Copy, Debug)]
+pub struct Isolation {
+    mount: bool,
+    pid: bool,
+    network: bool,
+}
+
+impl Isolation {
+    /// The isolation requested by a spawn, or `None` if it doesn't request any.
+    pub fn from_spawn(msg: &protocol::Spawn) -> Option<Self> {
+        if !msg.has_isolation() {
+            return None;
+        }
+        let isolation = msg.get_isolation();
+        let isolation = Isolation {
+            mount: isolation.get_mount(),
+            pid: isolation.get_pid(),
+            network: isolation.get_network(),
+        };
+        if isolation.mount || isolation.pid || isolation.network {
+            Some(isolation)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod imp {
+    use std::cmp;
+    use std::io;
+    use std::ptr;
+    use std::sync::atomic::{AtomicIsize, Ordering, ATOMIC_ISIZE_INIT};
+
+    use libc::{self, c_char, c_int, c_short, c_ulong, pid_t};
+
+    use super::Isolation;
+
+    const SIOCGIFFLAGS: c_ulong = 0x8913;
+    const SIOCSIFFLAGS: c_ulong = 0x8914;
+    /// The most file descriptors closed by the processes which stay behind to wait.
+    const MAX_CLOSED_FDS: c_int = 65536;
+    /// Signals which the processes waiting for a service forward to it.
+    const FORWARDED_SIGNALS: &'static [c_int] = &[
+        libc::SIGALRM,
+        libc::SIGHUP,
+        libc::SIGINT,
+        libc::SIGQUIT,
+        libc::SIGTERM,
+        libc::SIGUSR1,
+        libc::SIGUSR2,
+    ];
+
+    /// The child of a process waiting for it, which the signals it receives are forwarded to.
+    static CHILD: AtomicIsize = ATOMIC_ISIZE_INIT;
+
+    /// `struct ifreq`, holding an interface name and its flags.
+    #[repr(C)]
+    struct IfReq {
+        name: [u8; 16],
+        flags: c_short,
+        _pad: [u8; 22],
+    }
+
+    /// Move the calling process into the requested namespaces. This runs in the forked child
+    /// which will execute the service, so it only makes system calls, and only returns in the
+    /// process which goes on to execute the service.
+    pub fn enter(isolation: &Isolation) -> io::Result<()> {
+        let mut flags = 0;
+        // The PID namespace needs its own mount namespace, to mount its `/proc`.
+        if isolation.mount || isolation.pid {
+            flags |= libc::CLONE_NEWNS;
+        }
+        if isolation.pid {
+            flags |= libc::CLONE_NEWPID;
+        }
+        if isolation.network {
+            flags |= libc::CLONE_NEWNET;
+        }
+        check(unsafe { libc::unshare(flags) })?;
+        if isolation.network {
+            loopback_up()?;
+        }
+        if flags & libc::CLONE_NEWNS != 0 {
+            // Keep the mounts below from propagating back to the host.
+            mount(None, b"/\0", None, libc::MS_REC | libc::MS_PRIVATE, None)?;
+        }
+        if isolation.pid {
+            // Returns in the init of the namespace, which reaps the orphans of the service.
+            fork_and_wait(false)?;
+            mount(
+                Some(b"proc\0"),
+                b"/proc\0",
+                Some(b"proc\0"),
+                libc::MS_NOSUID | libc::MS_NODEV | libc::MS_NOEXEC,
+                None,
+            )?;
+            fork_and_wait(true)?;
+        }
+        if isolation.mount {
+            mount(
+                Some(b"/hab/pkgs\0"),
+                b"/hab/pkgs\0",
+                None,
+                libc::MS_BIND | libc::MS_REC,
+                None,
+            )?;
+            mount(
+                None,
+                b"/hab/pkgs\0",
+                None,
+                libc::MS_BIND | libc::MS_REMOUNT | libc::MS_RDONLY,
+                None,
+            )?;
+            mount(
+                Some(b"tmpfs\0"),
+                b"/tmp\0",
+                Some(b"tmpfs\0"),
+                libc::MS_NOSUID | libc::MS_NODEV,
+                Some(b"mode=1777\0"),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Fork, returning in the child. The parent forwards signals to the child and waits for it,
+    /// reaping any other process if `reap_all` is set, and exits with the status of the child.
+    fn fork_and_wait(reap_all: bool) -> io::Result<()> {
+        set_forwarded_signals(libc::SIG_IGN);
+        let parent = unsafe { libc::getpid() };
+        let child = unsafe { libc::fork() };
+        if child == -1 {
+            return Err(io::Error::last_os_error());
+        }
+        if child == 0 {
+            set_forwarded_signals(libc::SIG_DFL);
+            unsafe {
+                libc::setpgid(0, 0);
+                libc::prctl(libc::PR_SET_PDEATHSIG, libc::SIGKILL);
+                // The parent may have been killed before the death signal was set. A parent
+                // outside the child's PID namespace has no pid there, so it can't be checked.
+                let ppid = libc::getppid();
+                if ppid != 0 && ppid != parent {
+                    libc::_exit(1);
+                }
+            }
+            return Ok(());
+        }
+        // Set in both processes, so that the group exists whichever runs first.
+        unsafe { libc::setpgid(child, child) };
+        CHILD.store(child as isize, Ordering::SeqCst);
+        set_forwarded_signals(forward_signal as libc::sighandler_t);
+        close_inherited_fds();
+        loop {
+            let mut status = 0 as c_int;
+            let pid = if reap_all { -1 } else { child };
+            let waited = unsafe { libc::waitpid(pid, &mut status, 0) };
+            if waited == child {
+                let code = if libc::WIFEXITED(status) {
+                    libc::WEXITSTATUS(status)
+                } else if libc::WIFSIGNALED(status) {
+                    128 + libc::WTERMSIG(status)
+                } else {
+                    1
+                };
+                unsafe { libc::_exit(code) };
+            }
+            if waited == -1 && io::Error::last_os_error().raw_os_error() != Some(libc::EINTR) {
+                unsafe { libc::_exit(1) };
+            }
+        }
+    }
+
+    fn set_forwarded_signals(handler: libc::sighandler_t) {
+        for signal in FORWARDED_SIGNALS {
+            unsafe { libc::signal(*signal, handler) };
+        }
+    }
+
+    extern "C" fn forward_signal(signal: c_int) {
+        let child = CHILD.load(Ordering::SeqCst) as pid_t;
+        if child > 0 {
+            unsafe { libc::kill(-child, signal) };
+        }
+    }
+
+    /// Close the descriptors inherited from the Launcher, other than standard in, out and error.
+    /// The process which spawned the service waits to learn whether it executed until they're
+    /// closed.
+    fn close_inherited_fds() {
+        let max = unsafe { libc::sysconf(libc::_SC_OPEN_MAX) };
+        let max = if max < 0 {
+            1024
+        } else {
+            cmp::min(max, MAX_CLOSED_FDS as libc::c_long) as c_int
+        };
+        for fd in 3..max {
+            unsafe { libc::close(fd) };
+        }
+    }
+
+    /// Bring up the loopback interface of a new network namespace.
+    fn loopback_up() -> io::Result<()> {
+        let mut req = IfReq {
+            name: [0; 16],
+            flags: 0,
+            _pad: [0; 22],
+        };
+        req.name[..2].copy_from_slice(b"lo");
+        let sock = unsafe { libc::socket(libc::AF_INET, libc::SOCK_DGRAM, 0) };
+        check(sock)?;
+        let result = check(unsafe { libc::ioctl(sock, SIOCGIFFLAGS as _, &mut req as *mut IfReq) })
+            .and_then(|_| {
+                req.flags |= libc::IFF_UP as c_short;
+                check(unsafe { libc::ioctl(sock, SIOCSIFFLAGS as _, &req as *const IfReq) })
+            });
+        unsafe { libc::close(sock) };
+        result
+    }
+
+    /// `mount(2)`, taking nul-terminated byte strings.
+    fn mount(
+        source: Option<&[u8]>,
+        target: &[u8],
+        fstype: Option<&[u8]>,
+        flags: c_ulong,
+        data: Option<&[u8]>,
+    ) -> io::Result<()> {
+        fn ptr_of(value: Option<&[u8]>) -> *const c_char {
+            value.map_or(ptr::null(), |v| v.as_ptr() as *const c_char)
+        }
+        check(unsafe {
+            libc::mount(
+                ptr_of(source),
+                target.as_ptr() as *const c_char,
+                ptr_of(fstype),
+                flags,
+                ptr_of(data) as *const libc::c_void,
+            )
+        })
+    }
+
+    fn check(ret: c_int) -> io::Result<()> {
+        if ret == -1 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Namespaces only exist on Linux; elsewhere isolation is rejected when the process is spawned.
+#[cfg(not(target_os = "linux"))]
+mod imp {
+    use std::io;
+
+    use super::Isolation;
+
+    pub fn enter(_isolation: &Isolation) -> io::Result<()> {
+        Err(io::Error::new(
+            io::ErrorKind::Other,
+            "namespace isolation is only supported on Linux",
+        ))
+    }
+}
@@ -0,0 +1,340 @@
+// Copyright (c) 2018 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Resource limits, namespaces and capabilities applied to a service's process between fork and
+//! exec.
+//!
+//! Raising hard limits, entering namespaces and keeping capabilities all need privileges the
+//! process loses once it switches to the service's user, so when any is requested the Launcher
+//! switches users itself, after applying the limits and isolating the process and before raising
+//! the ambient capabilities.
+
+use std::io;
+use std::ptr;
+
+use libc::{self, gid_t, uid_t};
+use protocol;
+
+use self::caps::{keep_capabilities, raise_ambient};
+use super::isolation::{self, Isolation};
+use error::{Error, Result};
+
+/// A limit set with `setrlimit`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Resource {
+    Nofile,
+    Nproc,
+    Core,
+    Memlock,
+}
+
+/// What to apply to a service's process before it's executed as the service's user.
+#[derive(Debug)]
+pub struct ExecLimits {
+    rlimits: Vec<(Resource, u64)>,
+    capabilities: Vec<u32>,
+    isolation: Option<Isolation>,
+}
+
+impl ExecLimits {
+    /// The limits, isolation and capabilities requested by a spawn, or `None` if there aren't
+    /// any.
+    pub fn from_spawn(msg: &protocol::Spawn) -> Result<Option<Self>> {
+        let mut rlimits = vec![];
+        if msg.has_rlimits() {
+            let limits = msg.get_rlimits();
+            if limits.has_nofile() {
+                rlimits.push((Resource::Nofile, limits.get_nofile()));
+            }
+            if limits.has_nproc() {
+                rlimits.push((Resource::Nproc, limits.get_nproc()));
+            }
+            if limits.has_core() {
+                rlimits.push((Resource::Core, limits.get_core()));
+            }
+            if limits.has_memlock() {
+                rlimits.push((Resource::Memlock, limits.get_memlock()));
+            }
+        }
+        let capabilities = capabilities(msg.get_ambient_capabilities())?;
+        let isolation = Isolation::from_spawn(msg);
+        if rlimits.is_empty() && capabilities.is_empty() && isolation.is_none() {
+            Ok(None)
+        } else {
+            Ok(Some(ExecLimits {
+                rlimits: rlimits,
+                capabilities: capabilities,
+                isolation: isolation,
+            }))
+        }
+    }
+
+    /// Apply the limits, enter the namespaces, switch to the given user and group, and raise the
+    /// capabilities. This runs in the forked child, so it only makes system calls.
+    pub fn apply(&self, uid: uid_t, gid: gid_t) -> io::Result<()> {
+        for &(resource, value) in &self.rlimits {
+            set_rlimit(resource, value)?;
+        }
+        if let Some(ref isolation) = self.isolation {
+            isolation::enter(isolation)?;
+        }
+        if !self.capabilities.is_empty() {
+            keep_capabilities()?;
+        }
+        switch_user(uid, gid)?;
+        if !self.capabilities.is_empty() {
+            raise_ambient(&self.capabilities)?;
+        }
+        Ok(())
+    }
+}
+
+/// Switch to the given user and group, like `Command::uid` and `Command::gid` do. This runs in
+/// the forked child, so it only makes system calls.
+pub fn switch_user(uid: uid_t, gid: gid_t) -> io::Result<()> {
+    unsafe {
+        // Like `Command::uid`, drop the supplementary groups of root.
+        if libc::getuid() == 0 && libc::setgroups(0, ptr::null()) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        if libc::setgid(gid) != 0 || libc::setuid(uid) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+fn set_rlimit(resource: Resource, value: u64) -> io::Result<()> {
+    let limit = libc::rlimit {
+        rlim_cur: value as libc::rlim_t,
+        rlim_max: value as libc::rlim_t,
+    };
+    let ret = unsafe {
+        match resource {
+            Resource::Nofile => libc::setrlimit(libc::RLIMIT_NOFILE, &limit),
+            Resource::Nproc => libc::setrlimit(libc::RLIMIT_NPROC, &limit),
+            Resource::Core => libc::setrlimit(libc::RLIMIT_CORE, &limit),
+            Resource::Memlock => libc::setrlimit(libc::RLIMIT_MEMLOCK, &limit),
+        }
+    };
+    if ret == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+/// Parse capability names, with or without the `CAP_` prefix, into their numbers.
+fn capabilities(names: &[String]) -> Result<Vec<u32>> {
+    names
+        .iter()
+        .map(|name| {
+            let upper = name.to_uppercase();
+            let bare = upper.trim_left_matches("CAP_");
+            CAPABILITIES
+                .iter()
+                .position(|cap| *cap == bare)
+                .map(|cap| cap as u32)
+                .ok_or(Error::UnknownCapability(name.to_string()))
+        })
+        .collect()
+}
+
+/// Names of the Linux capabilities, indexed by their numbers.
+const CAPABILITIES: &'static [&'static str] = &[
+    "CHOWN",
+    "DAC_OVERRIDE",
+    "DAC_READ_SEARCH",
+    "FOWNER",
+    "FSETID",
+    "KILL",
+    "SETGID",
+    "SETUID",
+    "SETPCAP",
+    "LINUX_IMMUTABLE",
+    "NET_BIND_SERVICE",
+    "NET_BROADCAST",
+    "NET_ADMIN",
+    "NET_RAW",
+    "IPC_LOCK",
+    "IPC_OWNER",
+    "SYS_MODULE",
+    "SYS_RAWIO",
+    "SYS_CHROOT",
+    "SYS_PTRACE",
+    "SYS_PACCT",
+    "SYS_ADMIN",
+    "SYS_BOOT",
+    "SYS_NICE",
+    "SYS_RESOURCE",
+    "SYS_TIME",
+    "SYS_TTY_CONFIG",
+    "MKNOD",
+    "LEASE",
+    "AUDIT_WRITE",
+    "AUDIT_CONTROL",
+    "SETFCAP",
+    "MAC_OVERRIDE",
+    "MAC_ADMIN",
+    "SYSLOG",
+    "WAKE_ALARM",
+    "BLOCK_SUSPEND",
+    "AUDIT_READ",
+];
+
+#[cfg(target_os = "linux")]
+mod caps {
+    use std::io;
+
+    use libc::{self, c_int, c_ulong};
+
+    const PR_CAP_AMBIENT: c_int = 47;
+    const PR_CAP_AMBIENT_RAISE: c_ulong = 2;
+    const LINUX_CAPABILITY_VERSION_3: u32 = 0x2008_0522;
+
+    #[repr(C)]
+    struct CapHeader {
+        version: u32,
+        pid: c_int,
+    }
+
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    struct CapData {
+        effective: u32,
+        permitted: u32,
+        inheritable: u32,
+    }
+
+    /// Keep the permitted capabilities of the process when it switches from root to another user.
+    pub fn keep_capabilities() -> io::Result<()> {
+        if unsafe { libc::prctl(libc::PR_SET_KEEPCAPS, 1, 0, 0, 0) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    /// Narrow the capabilities of the process to the given ones and raise them in its ambient
+    /// set, so they're kept by the service's executable.
+    pub fn raise_ambient(capabilities: &[u32]) -> io::Result<()> {
+        let header = CapHeader {
+            version: LINUX_CAPABILITY_VERSION_3,
+            pid: 0,
+        };
+        let mut data = [CapData {
+            effective: 0,
+            permitted: 0,
+            inheritable: 0,
+        }; 2];
+        for &cap in capabilities {
+            let word = &mut data[(cap / 32) as usize];
+            let bit = 1 << (cap % 32);
+            word.effective |= bit;
+            word.permitted |= bit;
+            word.inheritable |= bit;
+        }
+        if unsafe { libc::syscall(libc::SYS_capset, &header, data.as_ptr()) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        for &cap in capabilities {
+            let ret = unsafe {
+                libc::prctl(PR_CAP_AMBIENT, PR_CAP_AMBIENT_RAISE, cap as c_ulong, 0, 0)
+            };
+            if ret != 0 {
+                return Err(io::Error::last_os_error());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Ambient capabilities only exist on Linux; elsewhere they're rejected when the process is
+/// spawned.
+#[cfg(not(target_os = "linux"))]
+mod caps {
+    use std::io;
+
+    pub fn keep_capabilities() -> io::Result<()> {
+        Err(io::Error::new(
+            io::ErrorKind::Other,
+            "ambient capabilities are only supported on Linux",
+        ))
+    }
+
+    pub fn raise_ambient(_capabilities: &[u32]) -> io::Result<()> {
+        keep_capabilities()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn capabilities_with_or_without_prefix() {
+        assert_eq!(
+            capabilities(&names(&["NET_BIND_SERVICE", "CAP_SYS_ADMIN", "cap_chown"])).unwrap(),
+            vec![10, 21, 0]
+        );
+        assert_eq!(capabilities(&names(&["audit_read"])).unwrap(), vec![37]);
+        assert!(capabilities(&[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn capabilities_rejects_unknown_names() {
+        for name in &["NET_BIND", "CAP_", "", "CAP_NET_BIND_SERVICE_X"] {
+            match capabilities(&names(&["KILL", name])) {
+                Err(Error::UnknownCapability(ref n)) if n == name => (),
+                other => panic!("{:?} was not rejected, {:?}", name, other),
+            }
+        }
+    }
+
+    #[test]
+    fn exec_limits_from_spawn() {
+        let mut spawn = protocol::Spawn::new();
+        assert!(ExecLimits::from_spawn(&spawn).unwrap().is_none());
+
+        // Empty rlimits and isolation request nothing.
+        spawn.mut_rlimits();
+        spawn.mut_isolation();
+        assert!(ExecLimits::from_spawn(&spawn).unwrap().is_none());
+
+        spawn.mut_rlimits().set_nofile(1024);
+        spawn.mut_rlimits().set_memlock(65536);
+        let limits = ExecLimits::from_spawn(&spawn).unwrap().unwrap();
+        assert_eq!(
+            limits.rlimits,
+            vec![(Resource::Nofile, 1024), (Resource::Memlock, 65536)]
+        );
+        assert!(limits.capabilities.is_empty());
+        assert!(limits.isolation.is_none());
+
+        spawn.mut_ambient_capabilities().push("CAP_NET_BIND_SERVICE".to_string());
+        spawn.mut_isolation().set_network(true);
+        let limits = ExecLimits::from_spawn(&spawn).unwrap().unwrap();
+        assert_eq!(limits.capabilities, vec![10]);
+        assert!(limits.isolation.is_some());
+
+        spawn.mut_ambient_capabilities().push("CAP_TELEPORT".to_string());
+        match ExecLimits::from_spawn(&spawn) {
+            Err(Error::UnknownCapability(ref n)) if n == "CAP_TELEPORT" => (),
+            other => panic!("CAP_TELEPORT was not rejected, {:?}", other),
+        }
+    }
+}
@@ -24,19 +24,23 @@ use libc::{self, c_int, pid_t};
 use protocol::{self, ShutdownMethod};
 use time::{Duration, SteadyTime};
 
+use super::cgroup::{self, Cgroup};
+use super::limits::{self, ExecLimits};
 use error::{Error, Result};
-use service::Service;
+use service::{Service, ShutdownConfig, Termination, DEFAULT_SHUTDOWN_SIGNAL};
 
 pub struct Process {
     pid: pid_t,
     status: Option<ExitStatus>,
+    cgroup: Option<Cgroup>,
 }
 
 impl Process {
-    fn new(pid: u32) -> Self {
+    fn new(pid: u32, cgroup: Option<Cgroup>) -> Self {
         Process {
             pid: pid as pid_t,
             status: None,
+            cgroup: cgroup,
         }
     }
 
@@ -44,14 +48,59 @@ impl Process {
         self.pid
     }
 
-    /// Attempt to gracefully terminate a proccess and then forcefully kill it after
-    /// 8 seconds if it has not terminated.
-    pub fn kill(&mut self) -> ShutdownMethod {
-        let mut pid_to_kill = self.pid;
-        // check the group of the process being killed
-        // if it is the root process of the process group
-        // we send our signals to the entire process group
-        // to prevent orphaned processes.
+    /// Resources used by the cgroup of the process, if it was spawned with resource limits.
+    pub fn resource_usage(&self) -> protocol::ResourceUsageOk {
+        match self.cgroup {
+            Some(ref cgroup) => cgroup.usage(),
+            None => protocol::ResourceUsageOk::new(),
+        }
+    }
+
+    /// Ask a proccess to stop with the signal of the given shutdown. The returned termination is
+    /// polled until the process has stopped, killing it if it has not terminated within the
+    /// shutdown's timeout.
+    pub fn terminate(&mut self, shutdown: &ShutdownConfig) -> Termination {
+        // JW TODO: Determine if the error represents a case where the process was already
+        // exited before we return out and assume so.
+        let stop_signal = protocol::signal_from_name(&shutdown.signal).unwrap_or_else(|| {
+            warn!(
+                "Unsupported shutdown signal {} for pid {}, sending {} instead",
+                shutdown.signal, self.pid, DEFAULT_SHUTDOWN_SIGNAL
+            );
+            Signal::TERM
+        });
+        if signal(self.signal_target(), stop_signal).is_err() {
+            return Termination::new(Duration::zero(), ShutdownMethod::AlreadyExited);
+        }
+        Termination::new(
+            Duration::seconds(shutdown.timeout as i64),
+            ShutdownMethod::GracefulTermination,
+        )
+    }
+
+    /// Check whether a process being terminated has stopped, returning how it was stopped once
+    /// it has. The process is killed once the termination's deadline has passed.
+    pub fn poll_termination(&mut self, termination: &mut Termination) -> Option<ShutdownMethod> {
+        match self.try_wait() {
+            Ok(None) => (),
+            Ok(Some(_)) | Err(_) => return Some(termination.method),
+        }
+        if termination.method == ShutdownMethod::Killed || SteadyTime::now() < termination.deadline
+        {
+            return None;
+        }
+        // JW TODO: Determine if the error represents a case where the process was already
+        // exited before we return out and assume so.
+        if signal(self.signal_target(), Signal::KILL).is_err() {
+            return Some(ShutdownMethod::GracefulTermination);
+        }
+        termination.method = ShutdownMethod::Killed;
+        None
+    }
+
+    /// The pid to send signals stopping the process to. If the process is the root of its
+    /// process group, the signals are sent to the entire group to prevent orphaned processes.
+    fn signal_target(&self) -> pid_t {
         let pgid = unsafe { libc::getpgid(self.pid) };
         if self.pid == pgid {
             debug!(
@@ -60,28 +109,9 @@ impl Process {
             );
             // sending a signal to the negative pid sends it to the
             // entire process group instead just the single pid
-            pid_to_kill = self.pid.neg();
-        }
-
-        // JW TODO: Determine if the error represents a case where the process was already
-        // exited before we return out and assume so.
-        if signal(pid_to_kill, Signal::TERM).is_err() {
-            return ShutdownMethod::AlreadyExited;
-        }
-        let stop_time = SteadyTime::now() + Duration::seconds(8);
-        loop {
-            if let Ok(Some(_status)) = self.try_wait() {
-                return ShutdownMethod::GracefulTermination;
-            }
-            if SteadyTime::now() < stop_time {
-                continue;
-            }
-            // JW TODO: Determine if the error represents a case where the process was already
-            // exited before we return out and assume so.
-            if signal(pid_to_kill, Signal::KILL).is_err() {
-                return ShutdownMethod::GracefulTermination;
-            }
-            return ShutdownMethod::Killed;
+            self.pid.neg()
+        } else {
+            self.pid
         }
     }
 
@@ -136,14 +166,48 @@ pub fn run(msg: protocol::Spawn) -> Result<Service> {
     cmd.before_exec(owned_pgid);
     cmd.stdin(Stdio::null())
         .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .uid(uid)
-        .gid(gid);
+        .stderr(Stdio::piped());
+    let mut cgroup = if msg.has_resource_limits() {
+        Cgroup::create(msg.get_id(), msg.get_resource_limits())
+    } else {
+        None
+    };
+    let procs = cgroup.as_ref().map(|cgroup| cgroup.procs());
+    let procs = match procs {
+        Some(Ok(procs)) => Some(procs),
+        Some(Err(err)) => {
+            warn!(
+                "Resource limits for {} are ignored, unable to open its cgroup, {}",
+                msg.get_id(),
+                err
+            );
+            cgroup = None;
+            None
+        }
+        None => None,
+    };
+    let limits = ExecLimits::from_spawn(&msg)?;
+    if procs.is_none() && limits.is_none() {
+        cmd.uid(uid).gid(gid);
+    } else {
+        // The process joins its cgroup before it can fork, and before it enters its namespaces
+        // and switches to the service's user. The limits are applied while the process still
+        // runs as the Launcher's user, which then switches to the service's user itself.
+        cmd.before_exec(move || {
+            if let Some(ref procs) = procs {
+                cgroup::join(procs)?;
+            }
+            match limits {
+                Some(ref limits) => limits.apply(uid, gid),
+                None => limits::switch_user(uid, gid),
+            }
+        });
+    }
     for (key, val) in msg.get_env().iter() {
         cmd.env(key, val);
     }
     let child = cmd.spawn().map_err(Error::Spawn)?;
-    let process = Process::new(child.id());
+    let process = Process::new(child.id(), cgroup);
     Ok(Service::new(msg, process, child.stdout, child.stderr))
 }
 
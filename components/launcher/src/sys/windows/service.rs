@@ -24,7 +24,7 @@ use time::{Duration, SteadyTime};
 use winapi;
 
 use error::{Error, Result};
-use service::Service;
+use service::{Service, ShutdownConfig, Termination};
 
 const PROCESS_ACTIVE: u32 = 259;
 type ProcessTable = HashMap<winapi::DWORD, Vec<winapi::DWORD>>;
@@ -46,9 +46,18 @@ impl Process {
         unsafe { kernel32::GetProcessId(self.handle.raw()) as u32 }
     }
 
-    pub fn kill(&mut self) -> ShutdownMethod {
+    /// Resources used by the process. Resource limits are unsupported on Windows, so nothing is
+    /// reported.
+    pub fn resource_usage(&self) -> protocol::ResourceUsageOk {
+        protocol::ResourceUsageOk::new()
+    }
+
+    /// Ask a proccess to stop with a ctrl-break. The returned termination is polled until the
+    /// process has stopped, killing it along with its descendants if it has not terminated within
+    /// the shutdown's timeout. Windows has no signals, so the shutdown's signal is unused.
+    pub fn terminate(&mut self, shutdown: &ShutdownConfig) -> Termination {
         if self.status().is_some() {
-            return ShutdownMethod::AlreadyExited;
+            return Termination::new(Duration::zero(), ShutdownMethod::AlreadyExited);
         }
         let ret = unsafe { kernel32::GenerateConsoleCtrlEvent(1, self.id()) };
         if ret == 0 {
@@ -57,20 +66,27 @@ impl Process {
                 self.id(),
                 io::Error::last_os_error()
             );
+            return Termination::new(Duration::zero(), ShutdownMethod::GracefulTermination);
         }
+        Termination::new(
+            Duration::seconds(shutdown.timeout as i64),
+            ShutdownMethod::GracefulTermination,
+        )
+    }
 
-        let stop_time = SteadyTime::now() + Duration::seconds(8);
-        loop {
-            if ret == 0 || SteadyTime::now() > stop_time {
-                let proc_table = build_proc_table();
-                terminate_process_descendants(&proc_table, self.id());
-                return ShutdownMethod::Killed;
-            }
-
-            if self.status().is_some() {
-                return ShutdownMethod::GracefulTermination;
-            }
+    /// Check whether a process being terminated has stopped, returning how it was stopped once
+    /// it has. The process and its descendants are killed once the termination's deadline has
+    /// passed.
+    pub fn poll_termination(&mut self, termination: &mut Termination) -> Option<ShutdownMethod> {
+        if self.status().is_some() {
+            return Some(termination.method);
+        }
+        if SteadyTime::now() < termination.deadline {
+            return None;
         }
+        let proc_table = build_proc_table();
+        terminate_process_descendants(&proc_table, self.id());
+        Some(ShutdownMethod::Killed)
     }
 
     pub fn wait(&mut self) -> Result<ExitStatus> {
@@ -116,6 +132,17 @@ impl Process {
 }
 
 pub fn run(msg: protocol::Spawn) -> Result<Service> {
+    if msg.has_resource_limits()
+        || msg.has_rlimits()
+        || !msg.get_ambient_capabilities().is_empty()
+        || msg.has_isolation()
+    {
+        warn!(
+            "Resource limits, capabilities and isolation for {} are ignored, they're unsupported \
+             on Windows",
+            msg.get_id()
+        );
+    }
     // Supervisors prior to version 0.53.0 pulled in beta versions of
     // powershell. The official 6.0.0 version of powershell changed
     // the name of the powershell binary to pwsh.exe. Here we will
@@ -211,6 +211,80 @@
 #   [storage]="port host"
 # )
 #
+# ### pkg_health_check
+# An associative array tuning how the Supervisor runs the service's `health_check` hook. The
+# `interval` between checks and the `timeout` after which a running check is killed are given in
+# seconds. `success_threshold` and `failure_threshold` are the number of consecutive passing or
+# failing checks needed before the reported health of the service changes. Any of these may be
+# overridden when the service is loaded.
+# ```
+# pkg_health_check=(
+#   [interval]=10
+#   [timeout]=5
+#   [failure_threshold]=3
+# )
+# ```
+#
+# ### pkg_health_probe
+# An associative array declaring a health probe which the Supervisor runs itself, in place of a
+# `health_check` hook. The `type` is `http`, `tcp` or `exec`. An `http` probe GETs its `url` and
+# expects the given `status` (any successful status by default) and a body containing `body`, if
+# set. A `tcp` probe connects to its `address`. An `exec` probe runs its `command`, relative to the
+# package, and reads its exit code like that of a `health_check` hook.
+# ```
+# pkg_health_probe=(
+#   [type]=http
+#   [url]="http://localhost:8080/healthz"
+#   [status]=200
+# )
+# ```
+#
+# ### pkg_resources
+# An associative array of limits on the resources the service may use, enforced by the Launcher
+# through a cgroup v2 on Linux. `memory_max` is given in bytes and `pids_max` counts processes and
+# threads. `cpu_weight` and `io_weight` are shares relative to other services, from 1 to 10000.
+# `cpu_quota` is a percent of one CPU, such as 150 for one and a half CPUs. Any of these may be
+# overridden in the service's spec. Limits are ignored where cgroups v2 are unavailable.
+# ```
+# pkg_resources=(
+#   [memory_max]=536870912
+#   [cpu_quota]=150
+#   [pids_max]=256
+# )
+# ```
+#
+# ### pkg_rlimits
+# An associative array of limits set on the service's process before it runs, as both its soft
+# and hard limits. `nofile` is the number of open files, `nproc` the number of processes of the
+# service's user, and `core` and `memlock` are given in bytes. Any of these may be overridden in
+# the service's spec.
+# ```
+# pkg_rlimits=(
+#   [nofile]=65536
+#   [core]=0
+# )
+# ```
+#
+# ### pkg_ambient_capabilities
+# An array of Linux capabilities the service keeps once it runs as `pkg_svc_user`, such as
+# `CAP_NET_BIND_SERVICE` to listen on ports below 1024. They may be replaced in the service's spec.
+# ```
+# pkg_ambient_capabilities=(CAP_NET_BIND_SERVICE)
+# ```
+#
+# ### pkg_shutdown_signal
+# The signal the Supervisor sends to ask the service to stop. Defaults to `TERM` when unset.
+# ```
+# pkg_shutdown_signal=QUIT
+# ```
+#
+# ### pkg_shutdown_timeout_sec
+# The number of seconds the Supervisor waits for the service to stop after signalling it, before
+# killing it. Defaults to 8 seconds when unset.
+# ```
+# pkg_shutdown_timeout_sec=30
+# ```
+#
 # ### pkg_origin
 # A string to use for the origin. The origin is used to denote a particular upstream of a
 # package; when we resolve dependencies, we consider a version of a package to be equal
@@ -412,6 +486,15 @@ pkg_exposes=()
 declare -A pkg_exports
 declare -A pkg_binds
 declare -A pkg_binds_optional
+declare -A pkg_health_check
+declare -A pkg_health_probe
+declare -A pkg_resources
+declare -A pkg_rlimits
+pkg_ambient_capabilities=()
+# The signal asking the service to stop
+pkg_shutdown_signal=''
+# The seconds to wait for the service to stop before killing it
+pkg_shutdown_timeout_sec=''
 # The user to run the service as
 pkg_svc_user=hab
 # The group to run the service as
@@ -1872,6 +1955,13 @@ _build_metadata() {
   if [[ -f "$PLAN_CONTEXT/hooks/run" || -n "${pkg_svc_run:-}" ]]; then
     _render_metadata_SVC_USER
     _render_metadata_SVC_GROUP
+    _render_metadata_HEALTH_CHECK
+    _render_metadata_HEALTH_PROBE
+    _render_metadata_RESOURCES
+    _render_metadata_RLIMITS
+    _render_metadata_AMBIENT_CAPABILITIES
+    _render_metadata_SHUTDOWN_SIGNAL
+    _render_metadata_SHUTDOWN_TIMEOUT
   fi
 
   return 0
@@ -7,6 +7,15 @@
 ################################################################################
 # Common Metadata Rendering functions
 
+_render_metadata_AMBIENT_CAPABILITIES() {
+  if [[ ${#pkg_ambient_capabilities[@]} -gt 0 ]]; then
+    debug "Rendering AMBIENT_CAPABILITIES metadata file"
+    echo "${pkg_ambient_capabilities[*]}" > $pkg_prefix/AMBIENT_CAPABILITIES
+  else
+    debug "Would have rendered AMBIENT_CAPABILITIES, but there was no data for it"
+  fi
+}
+
 _render_metadata_BINDS() {
     _render_associative_array_file ${pkg_prefix} BINDS pkg_binds
 }
@@ -95,6 +104,14 @@ _render_metadata_FILES() {
   popd > /dev/null
 }
 
+_render_metadata_HEALTH_CHECK() {
+    _render_associative_array_file ${pkg_prefix} HEALTH_CHECK pkg_health_check
+}
+
+_render_metadata_HEALTH_PROBE() {
+    _render_associative_array_file ${pkg_prefix} HEALTH_PROBE pkg_health_probe
+}
+
 _render_metadata_IDENT() {
   debug "Rendering IDENT metadata file"
   echo "${pkg_origin}/${pkg_name}/${pkg_version}/${pkg_release}" >> $pkg_prefix/IDENT
@@ -192,6 +209,14 @@ _render_metadata_PKG_CONFIG_PATH() {
   fi
 }
 
+_render_metadata_RESOURCES() {
+    _render_associative_array_file ${pkg_prefix} RESOURCES pkg_resources
+}
+
+_render_metadata_RLIMITS() {
+    _render_associative_array_file ${pkg_prefix} RLIMITS pkg_rlimits
+}
+
 _render_metadata_RUNTIME_ENVIRONMENT(){
     debug "Rendering RUNTIME_ENVIRONMENT metadata file"
     _render_associative_array_file ${pkg_prefix} RUNTIME_ENVIRONMENT __runtime_environment
@@ -221,6 +246,24 @@ _render_metadata_RUNTIME_PATH(){
   fi
 }
 
+_render_metadata_SHUTDOWN_SIGNAL() {
+  if [[ -n "${pkg_shutdown_signal:-}" ]]; then
+    debug "Rendering SHUTDOWN_SIGNAL metadata file"
+    echo "$pkg_shutdown_signal" > $pkg_prefix/SHUTDOWN_SIGNAL
+  else
+    debug "Would have rendered SHUTDOWN_SIGNAL, but there was no data for it"
+  fi
+}
+
+_render_metadata_SHUTDOWN_TIMEOUT() {
+  if [[ -n "${pkg_shutdown_timeout_sec:-}" ]]; then
+    debug "Rendering SHUTDOWN_TIMEOUT metadata file"
+    echo "$pkg_shutdown_timeout_sec" > $pkg_prefix/SHUTDOWN_TIMEOUT
+  else
+    debug "Would have rendered SHUTDOWN_TIMEOUT, but there was no data for it"
+  fi
+}
+
 _render_metadata_SVC_GROUP() {
   debug "Rendering SVC_GROUP metadata file"
   echo "$pkg_svc_group" > $pkg_prefix/SVC_GROUP
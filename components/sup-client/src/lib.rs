@@ -20,7 +20,7 @@
 //! # RPC Call Example
 //!
 //! ```ignore
-//! let conn = SrvClient::connect(&listen_addr, secret_key).wait()?;
+//! let conn = SrvClient::connect(&listen_addr, secret_key, None).wait()?;
 //! let msg = protocols::ctl::ServiceGetDefaultCfg::new();
 //! conn.call(msg).for_each(|reply| {
 //!     match reply.message_id() {
@@ -43,22 +43,39 @@ extern crate futures;
 extern crate habitat_sup_protocol as protocol;
 #[macro_use]
 extern crate log;
+extern crate openssl;
 extern crate prost;
+#[cfg(test)]
+extern crate tempdir;
 extern crate tokio;
 extern crate tokio_io;
+extern crate tokio_openssl;
+#[cfg(unix)]
+extern crate tokio_uds;
 
 use std::error;
 use std::fmt;
-use std::io;
+use std::fs::File;
+use std::io::{self, Read};
 use std::net::SocketAddr;
+#[cfg(unix)]
+use std::path::Path;
 use std::path::PathBuf;
 
+use futures::future;
 use futures::prelude::*;
 use futures::sink;
+use openssl::error::ErrorStack;
+use openssl::ssl::{SslConnector, SslMethod};
+use openssl::x509::store::X509StoreBuilder;
+use openssl::x509::X509;
 use protocol::codec::*;
 use protocol::net::NetErr;
 use tokio::net::TcpStream;
 use tokio_io::AsyncRead;
+use tokio_openssl::ConnectConfigurationExt;
+#[cfg(unix)]
+use tokio_uds::UnixStream;
 
 pub type SrvSend = sink::Send<SrvStream>;
 
@@ -75,6 +92,8 @@ pub enum SrvClientError {
     Io(io::Error),
     /// An RPC call to the remote was received but failed.
     NetErr(NetErr),
+    /// Unable to configure a TLS connection to the remote.
+    Tls(ErrorStack),
 }
 
 impl error::Error for SrvClientError {
@@ -85,6 +104,7 @@ impl error::Error for SrvClientError {
             SrvClientError::Decode(ref err) => err.description(),
             SrvClientError::Io(ref err) => err.description(),
             SrvClientError::NetErr(ref err) => err.description(),
+            SrvClientError::Tls(ref err) => err.description(),
         }
     }
 }
@@ -102,6 +122,7 @@ impl fmt::Display for SrvClientError {
             SrvClientError::Decode(ref err) => format!("{}", err),
             SrvClientError::Io(ref err) => format!("{}", err),
             SrvClientError::NetErr(ref err) => format!("{}", err),
+            SrvClientError::Tls(ref err) => format!("Unable to configure TLS, {}", err),
         };
         write!(f, "{}", content)
     }
@@ -125,6 +146,35 @@ impl From<prost::DecodeError> for SrvClientError {
     }
 }
 
+impl From<ErrorStack> for SrvClientError {
+    fn from(err: ErrorStack) -> Self {
+        SrvClientError::Tls(err)
+    }
+}
+
+/// Configuration for connecting to a server which requires TLS.
+#[derive(Clone, Debug)]
+pub struct TlsConfig {
+    /// Path to the PEM encoded certificate of the certificate authority which must have signed
+    /// the server's certificate. No other certificate authority will be trusted.
+    pub ca_cert_path: PathBuf,
+    /// Name which the server's certificate must be issued for. If not set, the server is
+    /// trusted based solely on the pinned certificate authority.
+    pub server_name: Option<String>,
+}
+
+impl TlsConfig {
+    fn connector(&self) -> Result<SslConnector, SrvClientError> {
+        let mut pem = vec![];
+        File::open(&self.ca_cert_path).and_then(|mut f| f.read_to_end(&mut pem))?;
+        let mut store = X509StoreBuilder::new()?;
+        store.add_cert(X509::from_pem(&pem)?)?;
+        let mut builder = SslConnector::builder(SslMethod::tls())?;
+        builder.set_cert_store(store.build());
+        Ok(builder.build())
+    }
+}
+
 /// Client for connecting and communicating with a server listener which speaks SrvProtocol.
 ///
 /// See module doc for usage.
@@ -140,36 +190,51 @@ pub struct SrvClient {
 }
 
 impl SrvClient {
-    /// Connect to the given remote server and authenticate with the given secret_key.
+    /// Connect to the given remote server and authenticate with the given secret_key. The
+    /// connection is encrypted if a `TlsConfig` is given.
     pub fn connect<T>(
         addr: &SocketAddr,
         secret_key: T,
+        tls: Option<&TlsConfig>,
     ) -> Box<Future<Item = SrvClient, Error = SrvClientError> + 'static>
     where
         T: ToString,
     {
         let secret_key = secret_key.to_string();
+        let tls = match tls {
+            Some(tls) => match tls.connector() {
+                Ok(connector) => Some((connector, tls.server_name.clone())),
+                Err(err) => return Box::new(future::err(err)),
+            },
+            None => None,
+        };
         let conn = TcpStream::connect(addr)
             .map_err(SrvClientError::from)
-            .and_then(move |socket| {
-                let client = Self::new(socket, None);
-                let mut request = protocol::ctl::Handshake::default();
-                request.secret_key = Some(secret_key);
-                client
-                    .call(request)
-                    .into_future()
-                    .map_err(|(err, _)| err)
-                    .and_then(move |(m, io)| {
-                        m.map_or_else(
-                            || Err(SrvClientError::ConnectionClosed),
-                            move |m| {
-                                m.try_ok()
-                                    .map_err(SrvClientError::from)
-                                    .and_then(|()| Ok(io.into_inner()))
-                            },
-                        )
-                    })
-            });
+            .and_then(move |socket| -> Box<Future<Item = Box<SrvIo>, Error = SrvClientError>> {
+                match tls {
+                    Some((connector, name)) => Self::tls_connect(connector, name, socket),
+                    None => Box::new(future::ok(Box::new(socket) as Box<SrvIo>)),
+                }
+            })
+            .and_then(move |socket| Self::handshake(socket, Some(secret_key)));
+        Box::new(conn)
+    }
+
+    /// Connect to a server listening on the Unix domain socket at the given path.
+    ///
+    /// A server may trust clients based on the credentials of the connecting process alone, in
+    /// which case no secret key needs to be given.
+    #[cfg(unix)]
+    pub fn connect_unix<P>(
+        path: P,
+        secret_key: Option<String>,
+    ) -> Box<Future<Item = SrvClient, Error = SrvClientError> + 'static>
+    where
+        P: AsRef<Path>,
+    {
+        let conn = UnixStream::connect(path)
+            .map_err(SrvClientError::from)
+            .and_then(move |socket| Self::handshake(Box::new(socket), secret_key));
         Box::new(conn)
     }
 
@@ -180,7 +245,64 @@ impl SrvClient {
         Ok(buf)
     }
 
-    fn new(socket: TcpStream, current_txn: Option<SrvTxn>) -> Self {
+    /// Authenticate with the server on the other end of the given socket.
+    fn handshake(
+        socket: Box<SrvIo>,
+        secret_key: Option<String>,
+    ) -> Box<Future<Item = SrvClient, Error = SrvClientError>> {
+        let client = Self::new(socket, None);
+        let mut request = protocol::ctl::Handshake::default();
+        request.secret_key = secret_key;
+        let handshake = client
+            .call(request)
+            .into_future()
+            .map_err(|(err, _)| err)
+            .and_then(move |(m, io)| {
+                m.map_or_else(
+                    || Err(SrvClientError::ConnectionClosed),
+                    move |m| {
+                        m.try_ok()
+                            .map_err(SrvClientError::from)
+                            .and_then(|()| Ok(io.into_inner()))
+                    },
+                )
+            });
+        Box::new(handshake)
+    }
+
+    /// Negotiate a TLS session over the given socket.
+    fn tls_connect(
+        connector: SslConnector,
+        server_name: Option<String>,
+        socket: TcpStream,
+    ) -> Box<Future<Item = Box<SrvIo>, Error = SrvClientError>> {
+        let config = match connector.configure() {
+            Ok(config) => config,
+            Err(err) => return Box::new(future::err(SrvClientError::from(err))),
+        };
+        let (config, domain) = match server_name {
+            Some(name) => (config, name),
+            None => (
+                config
+                    .use_server_name_indication(false)
+                    .verify_hostname(false),
+                String::new(),
+            ),
+        };
+        Box::new(
+            config
+                .connect_async(&domain, socket)
+                .map(|stream| Box::new(stream) as Box<SrvIo>)
+                .map_err(|err| {
+                    SrvClientError::from(io::Error::new(
+                        io::ErrorKind::ConnectionAborted,
+                        format!("TLS handshake failed, {}", err),
+                    ))
+                }),
+        )
+    }
+
+    fn new(socket: Box<SrvIo>, current_txn: Option<SrvTxn>) -> Self {
         SrvClient {
             socket: socket.framed(SrvCodec::new()),
             current_txn: current_txn.unwrap_or_default(),
@@ -272,3 +394,144 @@ enum SrvReplyState {
     /// is true.
     Receiving(SrvStream, bool),
 }
+
+#[cfg(test)]
+mod test {
+    use std::fs::File;
+    use std::io::Write;
+    use std::net::TcpListener;
+    use std::path::{Path, PathBuf};
+    use std::thread;
+
+    use openssl::asn1::Asn1Time;
+    use openssl::bn::BigNum;
+    use openssl::hash::MessageDigest;
+    use openssl::pkey::{PKey, Private};
+    use openssl::rsa::Rsa;
+    use openssl::ssl::SslAcceptor;
+    use openssl::x509::extension::{BasicConstraints, SubjectAlternativeName};
+    use openssl::x509::{X509NameBuilder, X509Ref};
+    use tempdir::TempDir;
+
+    use super::*;
+
+    fn key() -> PKey<Private> {
+        PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap()
+    }
+
+    /// A certificate for `name` signed by `issuer`, or a self-signed certificate authority if no
+    /// issuer is given.
+    fn certificate(
+        name: &str,
+        key: &PKey<Private>,
+        issuer: Option<(&X509Ref, &PKey<Private>)>,
+    ) -> X509 {
+        let mut subject = X509NameBuilder::new().unwrap();
+        subject.append_entry_by_text("CN", name).unwrap();
+        let subject = subject.build();
+        let mut builder = X509::builder().unwrap();
+        builder.set_version(2).unwrap();
+        let serial = BigNum::from_u32(if issuer.is_some() { 2 } else { 1 }).unwrap();
+        builder
+            .set_serial_number(&serial.to_asn1_integer().unwrap())
+            .unwrap();
+        builder.set_subject_name(&subject).unwrap();
+        match issuer {
+            Some((ca, _)) => builder.set_issuer_name(ca.subject_name()).unwrap(),
+            None => builder.set_issuer_name(&subject).unwrap(),
+        }
+        builder.set_pubkey(key).unwrap();
+        builder
+            .set_not_before(&Asn1Time::days_from_now(0).unwrap())
+            .unwrap();
+        builder
+            .set_not_after(&Asn1Time::days_from_now(1).unwrap())
+            .unwrap();
+        match issuer {
+            Some((ca, ca_key)) => {
+                let san = SubjectAlternativeName::new()
+                    .dns(name)
+                    .build(&builder.x509v3_context(Some(ca), None))
+                    .unwrap();
+                builder.append_extension(san).unwrap();
+                builder.sign(ca_key, MessageDigest::sha256()).unwrap();
+            }
+            None => {
+                let constraints = BasicConstraints::new().critical().ca().build().unwrap();
+                builder.append_extension(constraints).unwrap();
+                builder.sign(key, MessageDigest::sha256()).unwrap();
+            }
+        }
+        builder.build()
+    }
+
+    fn write_pem(dir: &Path, name: &str, cert: &X509) -> PathBuf {
+        let path = dir.join(format!("{}.pem", name));
+        File::create(&path)
+            .and_then(|mut f| f.write_all(&cert.to_pem().unwrap()))
+            .unwrap();
+        path
+    }
+
+    /// Negotiate a TLS session with a server presenting a certificate for `sup.example` signed by
+    /// `ca`, trusting `trusted_ca` and verifying `server_name`.
+    fn tls_connect_to(
+        ca: &X509Ref,
+        ca_key: &PKey<Private>,
+        trusted_ca: PathBuf,
+        server_name: Option<&str>,
+    ) -> bool {
+        let key = key();
+        let cert = certificate("sup.example", &key, Some((ca, ca_key)));
+        let mut acceptor = SslAcceptor::mozilla_intermediate(SslMethod::tls()).unwrap();
+        acceptor.set_private_key(&key).unwrap();
+        acceptor.set_certificate(&cert).unwrap();
+        let acceptor = acceptor.build();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = thread::spawn(move || {
+            let (socket, _) = listener.accept().unwrap();
+            let _ = acceptor.accept(socket);
+        });
+
+        let tls = TlsConfig {
+            ca_cert_path: trusted_ca,
+            server_name: server_name.map(String::from),
+        };
+        let connector = tls.connector().unwrap();
+        let name = tls.server_name.clone();
+        let connected = TcpStream::connect(&addr)
+            .map_err(SrvClientError::from)
+            .and_then(move |socket| SrvClient::tls_connect(connector, name, socket))
+            .wait()
+            .is_ok();
+        server.join().unwrap();
+        connected
+    }
+
+    #[test]
+    fn tls_connect_verifies_ca_and_server_name() {
+        let dir = TempDir::new("sup-client-tls").unwrap();
+        let ca_key = key();
+        let ca = certificate("Habitat Test CA", &ca_key, None);
+        let ca_path = write_pem(dir.path(), "ca", &ca);
+        assert!(tls_connect_to(&ca, &ca_key, ca_path.clone(), Some("sup.example")));
+        assert!(tls_connect_to(&ca, &ca_key, ca_path.clone(), None));
+        assert!(!tls_connect_to(&ca, &ca_key, ca_path, Some("other.example")));
+
+        let other_key = key();
+        let other_ca = certificate("Other Test CA", &other_key, None);
+        let other_path = write_pem(dir.path(), "other-ca", &other_ca);
+        assert!(!tls_connect_to(&ca, &ca_key, other_path, None));
+    }
+
+    #[test]
+    fn connector_requires_readable_ca_cert() {
+        let dir = TempDir::new("sup-client-tls").unwrap();
+        let tls = TlsConfig {
+            ca_cert_path: dir.path().join("missing.pem"),
+            server_name: None,
+        };
+        assert!(tls.connector().is_err());
+    }
+}
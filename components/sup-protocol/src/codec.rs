@@ -61,8 +61,8 @@ use std::str;
 use bytes::{BigEndian, Buf, BufMut, Bytes, BytesMut};
 use futures;
 use prost::{self, Message};
-use tokio::net::TcpStream;
 use tokio_io::codec::{Decoder, Encoder, Framed};
+use tokio_io::{AsyncRead, AsyncWrite};
 
 use message::MessageStatic;
 use net::{NetErr, NetResult};
@@ -80,9 +80,19 @@ const RESPONSE_MASK: u32 = 0x1;
 const COMPLETE_OFFSET: u32 = 30;
 const COMPLETE_MASK: u32 = 0x1;
 
-/// A `TcpStream` framed with `SrvCodec`. This is the base socket connection that the CtlGateway
+/// A connection, such as a `TcpStream` or a TLS session layered over one, which the CtlGateway
+/// client and server may speak `SrvCodec` over.
+pub trait SrvIo: AsyncRead + AsyncWrite {}
+
+impl<T> SrvIo for T
+where
+    T: AsyncRead + AsyncWrite,
+{
+}
+
+/// A connection framed with `SrvCodec`. This is the base socket connection that the CtlGateway
 /// client and server speak.
-pub type SrvStream = Framed<TcpStream, SrvCodec>;
+pub type SrvStream = Framed<Box<SrvIo>, SrvCodec>;
 
 /// Sending half of `SrvStream`.
 pub type SrvSink = futures::stream::SplitSink<SrvStream>;
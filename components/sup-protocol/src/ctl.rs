@@ -36,3 +36,9 @@ impl fmt::Display for ConsoleLine {
         write!(f, "{}", self.line)
     }
 }
+
+impl fmt::Display for SvcLogLine {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.line)
+    }
+}
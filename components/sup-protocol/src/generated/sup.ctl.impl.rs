@@ -12,6 +12,12 @@ impl message::MessageStatic for ServiceBindList {
 impl message::MessageStatic for SupDepart {
     const MESSAGE_ID: &'static str = "SupDepart";
 }
+impl message::MessageStatic for SupAuditLog {
+    const MESSAGE_ID: &'static str = "SupAuditLog";
+}
+impl message::MessageStatic for SupAuditEntry {
+    const MESSAGE_ID: &'static str = "SupAuditEntry";
+}
 impl message::MessageStatic for SvcFilePut {
     const MESSAGE_ID: &'static str = "SvcFilePut";
 }
@@ -27,6 +33,9 @@ impl message::MessageStatic for SvcSetCfg {
 impl message::MessageStatic for SvcLoad {
     const MESSAGE_ID: &'static str = "SvcLoad";
 }
+impl message::MessageStatic for SvcApply {
+    const MESSAGE_ID: &'static str = "SvcApply";
+}
 impl message::MessageStatic for SvcUnload {
     const MESSAGE_ID: &'static str = "SvcUnload";
 }
@@ -36,9 +45,27 @@ impl message::MessageStatic for SvcStart {
 impl message::MessageStatic for SvcStop {
     const MESSAGE_ID: &'static str = "SvcStop";
 }
+impl message::MessageStatic for SvcRestart {
+    const MESSAGE_ID: &'static str = "SvcRestart";
+}
+impl message::MessageStatic for SvcSignal {
+    const MESSAGE_ID: &'static str = "SvcSignal";
+}
 impl message::MessageStatic for SvcStatus {
     const MESSAGE_ID: &'static str = "SvcStatus";
 }
+impl message::MessageStatic for SvcLogs {
+    const MESSAGE_ID: &'static str = "SvcLogs";
+}
+impl message::MessageStatic for SvcLogLine {
+    const MESSAGE_ID: &'static str = "SvcLogLine";
+}
+impl message::MessageStatic for SvcHookHistory {
+    const MESSAGE_ID: &'static str = "SvcHookHistory";
+}
+impl message::MessageStatic for SvcHookRun {
+    const MESSAGE_ID: &'static str = "SvcHookRun";
+}
 impl message::MessageStatic for ConsoleLine {
     const MESSAGE_ID: &'static str = "ConsoleLine";
 }
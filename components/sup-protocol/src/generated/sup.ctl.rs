@@ -34,6 +34,21 @@ pub struct SupDepart {
     #[prost(string, optional, tag = "1")]
     pub member_id: ::std::option::Option<String>,
 }
+/// Request the entries of the Supervisor's audit log of CtlGateway requests.
+#[derive(Clone, PartialEq, Message, Serialize, Deserialize, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub struct SupAuditLog {
+    /// Number of most recent entries to send. All entries are sent if left blank.
+    #[prost(uint64, optional, tag = "1")]
+    pub tail: ::std::option::Option<u64>,
+}
+/// A reply to `SupAuditLog` which contains a single audit log entry encoded as a JSON object.
+#[derive(Clone, PartialEq, Message, Serialize, Deserialize, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub struct SupAuditEntry {
+    #[prost(string, required, tag = "1")]
+    pub entry: String,
+}
 #[derive(Clone, PartialEq, Message, Serialize, Deserialize, Hash)]
 #[serde(rename_all = "kebab-case")]
 pub struct SvcFilePut {
@@ -135,6 +150,54 @@ pub struct SvcLoad {
     /// Update strategy for the service.
     #[prost(enumeration = "super::types::UpdateStrategy", optional, tag = "13")]
     pub update_strategy: ::std::option::Option<i32>,
+    /// Seconds between health checks of the service.
+    #[prost(uint64, optional, tag = "15")]
+    pub health_check_interval: ::std::option::Option<u64>,
+    /// Seconds after which a running health check hook is killed and the check fails.
+    #[prost(uint64, optional, tag = "16")]
+    pub health_check_timeout: ::std::option::Option<u64>,
+    /// Consecutive passing health checks needed for a failing service to be reported as healthy.
+    #[prost(uint32, optional, tag = "17")]
+    pub health_check_success_threshold: ::std::option::Option<u32>,
+    /// Consecutive failing health checks needed for a healthy service to be reported as failing.
+    #[prost(uint32, optional, tag = "18")]
+    pub health_check_failure_threshold: ::std::option::Option<u32>,
+    /// Name of the signal asking the service to stop, such as "TERM" or "INT".
+    #[prost(string, optional, tag = "19")]
+    pub shutdown_signal: ::std::option::Option<String>,
+    /// Seconds to wait for the service to stop before it's killed.
+    #[prost(uint32, optional, tag = "20")]
+    pub shutdown_timeout: ::std::option::Option<u32>,
+    /// Whether the service's process is restarted when it exits.
+    #[prost(enumeration = "super::types::RestartPolicy", optional, tag = "21")]
+    pub restart_policy: ::std::option::Option<i32>,
+    /// Seconds to wait before the first restart of a process which exited.
+    #[prost(uint64, optional, tag = "22")]
+    pub restart_backoff_base: ::std::option::Option<u64>,
+    /// Maximum seconds to wait before restarting a process which keeps exiting.
+    #[prost(uint64, optional, tag = "23")]
+    pub restart_backoff_max: ::std::option::Option<u64>,
+    /// Restarts allowed within the restart window before the service is considered crash-looping.
+    #[prost(uint32, optional, tag = "24")]
+    pub max_restarts: ::std::option::Option<u32>,
+    /// Seconds over which restarts are counted.
+    #[prost(uint64, optional, tag = "25")]
+    pub restart_window: ::std::option::Option<u64>,
+}
+/// Request to reconcile the Supervisor's loaded services with a list of services. Listed services
+/// which aren't loaded are loaded and listed services whose spec differs are reloaded.
+#[derive(Clone, PartialEq, Message, Serialize, Deserialize, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub struct SvcApply {
+    /// Services which should be loaded. The ident of each is required.
+    #[prost(message, repeated, tag = "1")]
+    pub services: ::std::vec::Vec<SvcLoad>,
+    /// If set to true, loaded services which aren't listed will be unloaded.
+    #[prost(bool, optional, tag = "2", default = "false")]
+    pub unload: ::std::option::Option<bool>,
+    /// If set to true, the changes which would be made are reported but not applied.
+    #[prost(bool, optional, tag = "3", default = "false")]
+    pub dry_run: ::std::option::Option<bool>,
 }
 /// Request to unload a loaded service.
 #[derive(Clone, PartialEq, Message, Serialize, Deserialize, Hash)]
@@ -157,6 +220,23 @@ pub struct SvcStop {
     #[prost(message, optional, tag = "1")]
     pub ident: ::std::option::Option<super::types::PackageIdent>,
 }
+/// Request to restart a running service's process without changing its desired state.
+#[derive(Clone, PartialEq, Message, Serialize, Deserialize, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub struct SvcRestart {
+    #[prost(message, optional, tag = "1")]
+    pub ident: ::std::option::Option<super::types::PackageIdent>,
+}
+/// Request to send a signal to a running service's process.
+#[derive(Clone, PartialEq, Message, Serialize, Deserialize, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub struct SvcSignal {
+    #[prost(message, optional, tag = "1")]
+    pub ident: ::std::option::Option<super::types::PackageIdent>,
+    /// Name of the signal to send without the `SIG` prefix (ex: HUP, USR1).
+    #[prost(string, optional, tag = "2")]
+    pub signal: ::std::option::Option<String>,
+}
 /// Request to retrieve the service status of one or all services.
 #[derive(Clone, PartialEq, Message, Serialize, Deserialize, Hash)]
 #[serde(rename_all = "kebab-case")]
@@ -166,6 +246,81 @@ pub struct SvcStatus {
     #[prost(message, optional, tag = "1")]
     pub ident: ::std::option::Option<super::types::PackageIdent>,
 }
+/// Request to stream the output of a loaded service.
+#[derive(Clone, PartialEq, Message, Serialize, Deserialize, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub struct SvcLogs {
+    #[prost(message, optional, tag = "1")]
+    pub ident: ::std::option::Option<super::types::PackageIdent>,
+    /// Number of previously written lines to send before any new output. All previously written
+    /// output is sent if left blank.
+    #[prost(uint64, optional, tag = "2")]
+    pub tail: ::std::option::Option<u64>,
+    /// If set to true, new output will be streamed to the client as it is written until the client
+    /// disconnects.
+    #[prost(bool, optional, tag = "3", default = "false")]
+    pub follow: ::std::option::Option<bool>,
+    /// If specified, only output written at most this many seconds before the Supervisor received
+    /// the request is sent.
+    #[prost(uint64, optional, tag = "4")]
+    pub since: ::std::option::Option<u64>,
+}
+/// A reply to `SvcLogs` which contains a single line of a service's output.
+#[derive(Clone, PartialEq, Message, Serialize, Deserialize, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub struct SvcLogLine {
+    /// The output stream the line was written to.
+    #[prost(enumeration = "svc_log_line::Stream", required, tag = "1")]
+    pub stream: i32,
+    /// Unix timestamp (in seconds) of when the line was written.
+    #[prost(int64, required, tag = "2")]
+    pub timestamp: i64,
+    #[prost(string, required, tag = "3")]
+    pub line: String,
+}
+pub mod svc_log_line {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Enumeration, Serialize, Deserialize, Hash)]
+    #[serde(rename_all = "kebab-case")]
+    pub enum Stream {
+        Stdout = 0,
+        Stderr = 1,
+    }
+}
+/// Request the recent runs of a loaded service's hooks.
+#[derive(Clone, PartialEq, Message, Serialize, Deserialize, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub struct SvcHookHistory {
+    #[prost(message, optional, tag = "1")]
+    pub ident: ::std::option::Option<super::types::PackageIdent>,
+    /// If specified, only the runs of the hook with this name, such as `reconfigure`, are sent.
+    #[prost(string, optional, tag = "2")]
+    pub hook: ::std::option::Option<String>,
+}
+/// A reply to `SvcHookHistory` which describes a single run of a hook. Runs are sent oldest first.
+#[derive(Clone, PartialEq, Message, Serialize, Deserialize, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub struct SvcHookRun {
+    #[prost(string, required, tag = "1")]
+    pub hook: String,
+    /// Unix timestamp (in seconds) of when the hook started.
+    #[prost(int64, required, tag = "2")]
+    pub started_at: i64,
+    /// Number of milliseconds the hook ran for.
+    #[prost(uint64, required, tag = "3")]
+    pub duration_ms: u64,
+    /// Exit code of the hook. Blank if the hook failed to start, was killed or was terminated by a
+    /// signal.
+    #[prost(int32, optional, tag = "4")]
+    pub exit_code: ::std::option::Option<i32>,
+    /// If set to true, the hook was killed for exceeding its timeout.
+    #[prost(bool, optional, tag = "5", default = "false")]
+    pub timed_out: ::std::option::Option<bool>,
+    /// The end of the output written by the hook, truncated by the Supervisor.
+    #[prost(string, optional, tag = "6")]
+    pub stdout: ::std::option::Option<String>,
+    #[prost(string, optional, tag = "7")]
+    pub stderr: ::std::option::Option<String>,
+}
 /// A reply to various requests which contains a pre-formatted console line.
 #[derive(Clone, PartialEq, Message, Serialize, Deserialize, Hash)]
 #[serde(rename_all = "kebab-case")]
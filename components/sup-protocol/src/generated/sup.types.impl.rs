@@ -9,6 +9,9 @@ impl message::MessageStatic for PackageIdent {
 impl message::MessageStatic for ProcessStatus {
     const MESSAGE_ID: &'static str = "ProcessStatus";
 }
+impl message::MessageStatic for ProcessStats {
+    const MESSAGE_ID: &'static str = "ProcessStats";
+}
 impl message::MessageStatic for ServiceBind {
     const MESSAGE_ID: &'static str = "ServiceBind";
 }
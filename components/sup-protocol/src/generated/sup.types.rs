@@ -27,6 +27,39 @@ pub struct ProcessStatus {
     pub pid: ::std::option::Option<u32>,
     #[prost(enumeration = "ProcessState", required, tag = "3")]
     pub state: i32,
+    /// Number of times the process has been restarted since the service was loaded.
+    #[prost(uint32, optional, tag = "4")]
+    pub restart_count: ::std::option::Option<u32>,
+    /// Exit code of the last process stopped by the Supervisor.
+    #[prost(int32, optional, tag = "5")]
+    pub last_exit_code: ::std::option::Option<i32>,
+    /// Set to true if the process kept exiting and is no longer restarted until an operator
+    /// restarts the service.
+    #[prost(bool, optional, tag = "6")]
+    pub crash_looping: ::std::option::Option<bool>,
+    /// Resources used by the process and its descendants, when it's running and they could be read.
+    #[prost(message, optional, tag = "7")]
+    pub stats: ::std::option::Option<ProcessStats>,
+}
+/// Resources used by the tree of processes of a service, sampled by the Supervisor.
+#[derive(Clone, PartialEq, Message, Serialize, Deserialize, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub struct ProcessStats {
+    /// Microseconds of CPU time used.
+    #[prost(uint64, optional, tag = "1")]
+    pub cpu_usec: ::std::option::Option<u64>,
+    /// Bytes of resident memory.
+    #[prost(uint64, optional, tag = "2")]
+    pub rss_bytes: ::std::option::Option<u64>,
+    /// Number of open file descriptors.
+    #[prost(uint64, optional, tag = "3")]
+    pub fds: ::std::option::Option<u64>,
+    /// Number of threads.
+    #[prost(uint64, optional, tag = "4")]
+    pub threads: ::std::option::Option<u64>,
+    /// Number of processes.
+    #[prost(uint64, optional, tag = "5")]
+    pub processes: ::std::option::Option<u64>,
 }
 #[derive(Clone, PartialEq, Message, Serialize, Deserialize, Hash)]
 #[serde(rename_all = "kebab-case")]
@@ -79,6 +112,63 @@ pub struct ServiceStatus {
     pub service_group: ServiceGroup,
     #[prost(string, optional, tag = "4")]
     pub composite: ::std::option::Option<String>,
+    #[prost(enumeration = "service_status::HealthCheck", optional, tag = "5")]
+    pub health_check: ::std::option::Option<i32>,
+    #[prost(enumeration = "service_status::DesiredState", optional, tag = "6")]
+    pub desired_state: ::std::option::Option<i32>,
+    #[prost(enumeration = "service_status::UpdateState", optional, tag = "7")]
+    pub update_state: ::std::option::Option<i32>,
+    #[prost(enumeration = "service_status::ElectionRole", optional, tag = "8")]
+    pub election_role: ::std::option::Option<i32>,
+    /// Binds which aren't satisfied by their service group, as `<NAME>:<SERVICE_GROUP>`.
+    #[prost(string, repeated, tag = "9")]
+    pub unsatisfied_binds: ::std::vec::Vec<String>,
+}
+pub mod service_status {
+    /// Result of the most recent health check of a service.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Enumeration, Serialize, Deserialize, Hash)]
+    #[serde(rename_all = "kebab-case")]
+    pub enum HealthCheck {
+        Ok = 0,
+        Warning = 1,
+        Critical = 2,
+        Unknown = 3,
+    }
+    /// Whether a service has been asked to run or to be stopped.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Enumeration, Serialize, Deserialize, Hash)]
+    #[serde(rename_all = "kebab-case")]
+    pub enum DesiredState {
+        Down = 0,
+        Up = 1,
+    }
+    /// Progress of a service through its update strategy.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Enumeration, Serialize, Deserialize, Hash)]
+    #[serde(rename_all = "kebab-case")]
+    pub enum UpdateState {
+        /// The service has no update strategy
+        None = 0,
+        /// Waiting for an update leader to be elected
+        Electing = 1,
+        /// Polling for a newer package
+        Polling = 2,
+        /// Waiting for the service group to agree on a package or for our turn to update
+        Waiting = 3,
+        /// Installing a newer package
+        Updating = 4,
+        /// The last update was aborted and the service kept its package, until an update succeeds
+        Aborted = 5,
+    }
+    /// Role of the Supervisor in the leader election of a service's group.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Enumeration, Serialize, Deserialize, Hash)]
+    #[serde(rename_all = "kebab-case")]
+    pub enum ElectionRole {
+        /// The service isn't running in a leader topology
+        Standalone = 0,
+        /// An election is pending or in progress
+        Candidate = 1,
+        Leader = 2,
+        Follower = 3,
+    }
 }
 /// Encapsulate all possible sources we can install packages from.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Enumeration, Serialize, Deserialize, Hash)]
@@ -109,6 +199,17 @@ pub enum UpdateStrategy {
     AtOnce = 1,
     Rolling = 2,
 }
+/// Whether a service's process is restarted when it exits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Enumeration, Serialize, Deserialize, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum RestartPolicy {
+    /// Restart the process whenever it exits
+    Always = 0,
+    /// Restart the process only when it exits with a non-zero code or is terminated by a signal
+    OnFailure = 1,
+    /// Leave the process down when it exits
+    Never = 2,
+}
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Enumeration, Serialize, Deserialize, Hash)]
 #[serde(rename_all = "kebab-case")]
 pub enum BindingMode {
@@ -63,6 +63,7 @@ pub mod codec;
 pub mod ctl;
 pub mod message;
 pub mod net;
+pub mod token;
 pub mod types;
 
 use std::fs::File;
@@ -75,6 +76,8 @@ use net::{ErrCode, NetResult};
 
 // Nane of file containing the CtlGateway secret key.
 const CTL_SECRET_FILENAME: &'static str = "CTL_SECRET";
+// Name of the Unix domain socket the CtlGateway optionally listens on.
+const CTL_SOCKET_FILENAME: &'static str = "CTL_SOCKET";
 /// Length of characters in CtlGateway secret key.
 const CTL_SECRET_LEN: usize = 64;
 
@@ -145,6 +148,14 @@ where
     sup_root.as_ref().join(CTL_SECRET_FILENAME)
 }
 
+/// Returns the location of the CtlGateway's Unix domain socket for the given Supervisor root.
+pub fn ctl_socket_path<T>(sup_root: T) -> PathBuf
+where
+    T: AsRef<Path>,
+{
+    sup_root.as_ref().join(CTL_SOCKET_FILENAME)
+}
+
 pub fn sup_root<T, U>(name: Option<T>, custom_state_path: Option<U>) -> PathBuf
 where
     T: AsRef<Path>,
@@ -0,0 +1,317 @@
+// Copyright (c) 2018 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Named tokens which may be used in place of a Supervisor's shared secret when connecting to its
+//! CtlGateway. Each token carries a role which limits the requests a client may make.
+//!
+//! Tokens are stored one per line in the `CTL_TOKENS` file of a Supervisor's state directory in
+//! the form `<name>:<role>:<secret>`. Blank lines and lines starting with `#` are ignored.
+
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::result;
+use std::str::FromStr;
+
+#[cfg(windows)]
+use core::util::perm;
+
+use net::{self, ErrCode, NetErr, NetResult};
+
+const CTL_TOKENS_FILENAME: &'static str = "CTL_TOKENS";
+const CTL_TOKENS_PERMISSIONS: u32 = 0o600;
+
+/// The level of access granted to a CtlGateway client. Each role grants everything granted by
+/// the roles declared before it.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum CtlRole {
+    /// May query the state and configuration of the Supervisor and its services.
+    ReadOnly,
+    /// May additionally start, stop, restart, and signal loaded services.
+    Operator,
+    /// May make any request, including loading and unloading services, applying configuration,
+    /// and departing Supervisors.
+    Admin,
+}
+
+impl CtlRole {
+    /// Returns true if this role grants at least the access of the given role.
+    pub fn permits(&self, other: CtlRole) -> bool {
+        *self >= other
+    }
+
+    pub fn as_str(&self) -> &str {
+        match *self {
+            CtlRole::ReadOnly => "read-only",
+            CtlRole::Operator => "operator",
+            CtlRole::Admin => "admin",
+        }
+    }
+}
+
+impl fmt::Display for CtlRole {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for CtlRole {
+    type Err = NetErr;
+
+    fn from_str(value: &str) -> result::Result<Self, Self::Err> {
+        match value.to_lowercase().as_ref() {
+            "read-only" | "readonly" => Ok(CtlRole::ReadOnly),
+            "operator" => Ok(CtlRole::Operator),
+            "admin" => Ok(CtlRole::Admin),
+            _ => Err(net::err(
+                ErrCode::InvalidPayload,
+                format!("Invalid role \"{}\"", value),
+            )),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CtlToken {
+    pub name: String,
+    pub role: CtlRole,
+    pub secret: String,
+}
+
+impl CtlToken {
+    /// Create a new token with a freshly generated secret.
+    pub fn new<T>(name: T, role: CtlRole) -> NetResult<Self>
+    where
+        T: ToString,
+    {
+        let name = name.to_string();
+        validate_name(&name)?;
+        let mut secret = String::new();
+        ::generate_secret_key(&mut secret);
+        Ok(CtlToken {
+            name: name,
+            role: role,
+            secret: secret,
+        })
+    }
+}
+
+impl fmt::Display for CtlToken {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.name, self.role, self.secret)
+    }
+}
+
+impl FromStr for CtlToken {
+    type Err = NetErr;
+
+    fn from_str(value: &str) -> result::Result<Self, Self::Err> {
+        let values: Vec<&str> = value.trim().splitn(3, ':').collect();
+        if values.len() != 3 || values[2].is_empty() {
+            return Err(net::err(
+                ErrCode::InvalidPayload,
+                "Invalid ctl token, must be of the form <NAME>:<ROLE>:<SECRET>",
+            ));
+        }
+        validate_name(values[0])?;
+        Ok(CtlToken {
+            name: values[0].to_string(),
+            role: CtlRole::from_str(values[1])?,
+            secret: values[2].to_string(),
+        })
+    }
+}
+
+pub fn ctl_tokens_path<T>(sup_root: T) -> PathBuf
+where
+    T: AsRef<Path>,
+{
+    sup_root.as_ref().join(CTL_TOKENS_FILENAME)
+}
+
+/// Read all tokens for the Supervisor with the given state directory. An empty list is returned
+/// if no tokens have been created. Malformed lines are logged and skipped, so that one bad line
+/// doesn't disable every other token.
+pub fn read_ctl_tokens<T>(sup_root: T) -> NetResult<Vec<CtlToken>>
+where
+    T: AsRef<Path>,
+{
+    let path = ctl_tokens_path(sup_root);
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    let mut content = String::new();
+    File::open(&path)
+        .and_then(|mut f| f.read_to_string(&mut content))
+        .map_err(|e| {
+            net::err(
+                ErrCode::Io,
+                format!("IoError while reading ctl tokens, {}, {}", path.display(), e),
+            )
+        })?;
+    Ok(parse_ctl_tokens(&content, &path))
+}
+
+fn parse_ctl_tokens(content: &str, path: &Path) -> Vec<CtlToken> {
+    content
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|&(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|(number, line)| match CtlToken::from_str(line) {
+            Ok(token) => Some(token),
+            Err(err) => {
+                warn!(
+                    "Skipping line {} of ctl tokens, {}, {}",
+                    number,
+                    path.display(),
+                    err
+                );
+                None
+            }
+        })
+        .collect()
+}
+
+/// Replace all tokens for the Supervisor with the given state directory.
+///
+/// The tokens are written to a private temporary file which then replaces the tokens file, so
+/// the secrets are never readable by others and readers never see a partially written file.
+pub fn write_ctl_tokens<T>(sup_root: T, tokens: &[CtlToken]) -> NetResult<()>
+where
+    T: AsRef<Path>,
+{
+    let path = ctl_tokens_path(sup_root);
+    let tmp_path = path.with_extension("tmp");
+    let io_err = |e: &fmt::Display| {
+        net::err(
+            ErrCode::Io,
+            format!("IoError while writing ctl tokens, {}, {}", path.display(), e),
+        )
+    };
+    let mut file = create_private(&tmp_path).map_err(|e| io_err(&e))?;
+    for token in tokens {
+        writeln!(file, "{}", token).map_err(|e| io_err(&e))?;
+    }
+    file.sync_all().map_err(|e| io_err(&e))?;
+    fs::rename(&tmp_path, &path).map_err(|e| io_err(&e))?;
+    Ok(())
+}
+
+/// Create a new file which only its owner may read, replacing any file left at `path`.
+#[cfg(unix)]
+fn create_private(path: &Path) -> io::Result<File> {
+    use std::os::unix::fs::OpenOptionsExt;
+
+    remove_stale(path)?;
+    fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(CTL_TOKENS_PERMISSIONS)
+        .open(path)
+}
+
+/// Create a new file which only its owner may read, replacing any file left at `path`.
+#[cfg(windows)]
+fn create_private(path: &Path) -> io::Result<File> {
+    remove_stale(path)?;
+    let file = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)?;
+    perm::set_permissions(path, CTL_TOKENS_PERMISSIONS)
+        .map_err(|e| io::Error::new(io::ErrorKind::PermissionDenied, e.to_string()))?;
+    Ok(file)
+}
+
+/// Remove a file left behind by an earlier write, which may have been opened by others.
+fn remove_stale(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(ref err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+        result => result,
+    }
+}
+
+fn validate_name(name: &str) -> NetResult<()> {
+    if name.is_empty() || name.contains(':') || name.contains(char::is_whitespace) {
+        return Err(net::err(
+            ErrCode::InvalidPayload,
+            format!(
+                "Invalid ctl token name \"{}\", must be non-empty and contain no whitespace or \
+                 colons",
+                name
+            ),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn role_ordering() {
+        assert!(CtlRole::Admin.permits(CtlRole::Operator));
+        assert!(CtlRole::Operator.permits(CtlRole::ReadOnly));
+        assert!(CtlRole::Operator.permits(CtlRole::Operator));
+        assert!(!CtlRole::ReadOnly.permits(CtlRole::Operator));
+        assert!(!CtlRole::Operator.permits(CtlRole::Admin));
+    }
+
+    #[test]
+    fn role_from_str() {
+        assert_eq!(CtlRole::from_str("read-only").unwrap(), CtlRole::ReadOnly);
+        assert_eq!(CtlRole::from_str("Operator").unwrap(), CtlRole::Operator);
+        assert_eq!(CtlRole::from_str("admin").unwrap(), CtlRole::Admin);
+        assert!(CtlRole::from_str("root").is_err());
+    }
+
+    #[test]
+    fn token_from_str() {
+        let token = CtlToken::from_str("ci:operator:c2VjcmV0==").unwrap();
+        assert_eq!(token.name, "ci");
+        assert_eq!(token.role, CtlRole::Operator);
+        assert_eq!(token.secret, "c2VjcmV0==");
+        assert_eq!(token.to_string(), "ci:operator:c2VjcmV0==");
+    }
+
+    #[test]
+    fn token_from_str_invalid() {
+        assert!(CtlToken::from_str("ci:operator").is_err());
+        assert!(CtlToken::from_str("ci:operator:").is_err());
+        assert!(CtlToken::from_str(":admin:c2VjcmV0").is_err());
+        assert!(CtlToken::from_str("ci:superuser:c2VjcmV0").is_err());
+    }
+
+    #[test]
+    fn parse_skips_malformed_lines() {
+        let content = "# comment\n\nci:operator:c2VjcmV0\nbroken\nops:root:c2VjcmV0\n\
+                       admin:admin:YWRtaW4=\n";
+        let tokens = parse_ctl_tokens(content, Path::new("CTL_TOKENS"));
+        let names: Vec<&str> = tokens.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["ci", "admin"]);
+    }
+
+    #[test]
+    fn new_token_generates_secret() {
+        let token = CtlToken::new("ci", CtlRole::ReadOnly).unwrap();
+        assert!(!token.secret.is_empty());
+        assert!(CtlToken::new("bad name", CtlRole::ReadOnly).is_err());
+    }
+}
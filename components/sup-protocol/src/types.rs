@@ -202,6 +202,54 @@ impl fmt::Display for service_cfg::Format {
     }
 }
 
+impl fmt::Display for service_status::HealthCheck {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let value = match *self {
+            service_status::HealthCheck::Ok => "ok",
+            service_status::HealthCheck::Warning => "warning",
+            service_status::HealthCheck::Critical => "critical",
+            service_status::HealthCheck::Unknown => "unknown",
+        };
+        write!(f, "{}", value)
+    }
+}
+
+impl fmt::Display for service_status::DesiredState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let value = match *self {
+            service_status::DesiredState::Down => "down",
+            service_status::DesiredState::Up => "up",
+        };
+        write!(f, "{}", value)
+    }
+}
+
+impl fmt::Display for service_status::UpdateState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let value = match *self {
+            service_status::UpdateState::None => "none",
+            service_status::UpdateState::Electing => "electing",
+            service_status::UpdateState::Polling => "polling",
+            service_status::UpdateState::Waiting => "waiting",
+            service_status::UpdateState::Updating => "updating",
+            service_status::UpdateState::Aborted => "aborted",
+        };
+        write!(f, "{}", value)
+    }
+}
+
+impl fmt::Display for service_status::ElectionRole {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let value = match *self {
+            service_status::ElectionRole::Standalone => "standalone",
+            service_status::ElectionRole::Candidate => "candidate",
+            service_status::ElectionRole::Leader => "leader",
+            service_status::ElectionRole::Follower => "follower",
+        };
+        write!(f, "{}", value)
+    }
+}
+
 impl From<core::service::BindingMode> for BindingMode {
     fn from(mode: core::service::BindingMode) -> Self {
         match mode {
@@ -333,6 +381,35 @@ impl fmt::Display for UpdateStrategy {
     }
 }
 
+impl RestartPolicy {
+    fn as_str(&self) -> &str {
+        match *self {
+            RestartPolicy::Always => "always",
+            RestartPolicy::OnFailure => "on-failure",
+            RestartPolicy::Never => "never",
+        }
+    }
+}
+
+impl FromStr for RestartPolicy {
+    type Err = NetErr;
+
+    fn from_str(policy: &str) -> Result<Self, Self::Err> {
+        match policy {
+            "always" => Ok(RestartPolicy::Always),
+            "on-failure" => Ok(RestartPolicy::OnFailure),
+            "never" => Ok(RestartPolicy::Never),
+            _ => Err(net::err(ErrCode::InvalidPayload, "Invalid restart policy.")),
+        }
+    }
+}
+
+impl fmt::Display for RestartPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 #[cfg(test)]
 mod test {
     extern crate toml;
@@ -454,4 +531,24 @@ mod test {
 
         assert!(toml.starts_with(r#"key = "at-once""#));
     }
+
+    #[test]
+    fn restart_policy_default() {
+        assert_eq!(RestartPolicy::default(), RestartPolicy::Always);
+    }
+
+    #[test]
+    fn restart_policy_from_str() {
+        assert_eq!(
+            RestartPolicy::from_str("on-failure").unwrap(),
+            RestartPolicy::OnFailure
+        );
+        assert!(RestartPolicy::from_str("sometimes").is_err());
+    }
+
+    #[test]
+    fn restart_policy_to_string() {
+        assert_eq!("on-failure", RestartPolicy::OnFailure.to_string());
+        assert_eq!("never", RestartPolicy::Never.to_string());
+    }
 }
@@ -120,6 +120,30 @@ impl CensusRing {
         self.census_groups.values().map(|cg| cg).collect()
     }
 
+    /// Summarize the parts of the census reported as `CensusEvent`s.
+    pub fn summary(&self) -> CensusSummary {
+        let mut summary = CensusSummary::default();
+        for group in self.census_groups.values() {
+            let mut group_summary = GroupSummary::default();
+            group_summary.leader_id = group.leader_id.clone();
+            group_summary.config_incarnation = group.service_config.as_ref().map(|c| c.incarnation);
+            for member in group.population.values() {
+                group_summary
+                    .members
+                    .insert(member.member_id.clone(), member.health());
+            }
+            for file in group.service_files.values() {
+                group_summary
+                    .files
+                    .insert(file.filename.clone(), file.incarnation);
+            }
+            summary
+                .groups
+                .insert(group.service_group.to_string(), group_summary);
+        }
+        summary
+    }
+
     /// Populates the census from `ServiceRumor`s and Butterfly-level
     /// membership lists.
     ///
@@ -624,6 +648,161 @@ impl CensusMember {
     pub fn departed(&self) -> bool {
         self.departed
     }
+
+    pub fn health(&self) -> Health {
+        if self.departed {
+            Health::Departed
+        } else if self.confirmed {
+            Health::Confirmed
+        } else if self.suspect {
+            Health::Suspect
+        } else {
+            Health::Alive
+        }
+    }
+}
+
+/// A change to the census between two updates from rumors.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum CensusEvent {
+    /// A member started running the service group.
+    ServiceAdded {
+        service_group: String,
+        member_id: MemberId,
+    },
+    /// A member no longer runs the service group.
+    ServiceRemoved {
+        service_group: String,
+        member_id: MemberId,
+    },
+    MemberHealth {
+        service_group: String,
+        member_id: MemberId,
+        health: String,
+    },
+    /// The elected leader of the service group changed, or the group lost its leader.
+    LeaderChanged {
+        service_group: String,
+        leader_id: Option<MemberId>,
+    },
+    ConfigChanged {
+        service_group: String,
+        incarnation: u64,
+    },
+    FileChanged {
+        service_group: String,
+        filename: String,
+        incarnation: u64,
+    },
+}
+
+impl CensusEvent {
+    /// Name of the event, as given in its `type` field.
+    pub fn name(&self) -> &'static str {
+        match *self {
+            CensusEvent::ServiceAdded { .. } => "service-added",
+            CensusEvent::ServiceRemoved { .. } => "service-removed",
+            CensusEvent::MemberHealth { .. } => "member-health",
+            CensusEvent::LeaderChanged { .. } => "leader-changed",
+            CensusEvent::ConfigChanged { .. } => "config-changed",
+            CensusEvent::FileChanged { .. } => "file-changed",
+        }
+    }
+
+    pub fn service_group(&self) -> &str {
+        match *self {
+            CensusEvent::ServiceAdded { ref service_group, .. }
+            | CensusEvent::ServiceRemoved { ref service_group, .. }
+            | CensusEvent::MemberHealth { ref service_group, .. }
+            | CensusEvent::LeaderChanged { ref service_group, .. }
+            | CensusEvent::ConfigChanged { ref service_group, .. }
+            | CensusEvent::FileChanged { ref service_group, .. } => service_group,
+        }
+    }
+}
+
+/// The parts of a `CensusRing` compared to find the `CensusEvent`s between two updates.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CensusSummary {
+    groups: BTreeMap<String, GroupSummary>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct GroupSummary {
+    members: BTreeMap<MemberId, Health>,
+    leader_id: Option<MemberId>,
+    config_incarnation: Option<u64>,
+    files: BTreeMap<String, u64>,
+}
+
+impl CensusSummary {
+    /// Returns the events which turn the `previous` census into this one.
+    pub fn events_since(&self, previous: &CensusSummary) -> Vec<CensusEvent> {
+        let empty = GroupSummary::default();
+        let mut events = Vec::new();
+        for (sg, group) in self.groups.iter() {
+            let before = previous.groups.get(sg).unwrap_or(&empty);
+            group.events_since(sg, before, &mut events);
+        }
+        for (sg, before) in previous.groups.iter() {
+            if !self.groups.contains_key(sg) {
+                empty.events_since(sg, before, &mut events);
+            }
+        }
+        events
+    }
+}
+
+impl GroupSummary {
+    fn events_since(&self, sg: &str, previous: &GroupSummary, events: &mut Vec<CensusEvent>) {
+        for (member_id, health) in self.members.iter() {
+            match previous.members.get(member_id) {
+                None => events.push(CensusEvent::ServiceAdded {
+                    service_group: sg.to_string(),
+                    member_id: member_id.clone(),
+                }),
+                Some(before) if before == health => continue,
+                Some(_) => (),
+            }
+            events.push(CensusEvent::MemberHealth {
+                service_group: sg.to_string(),
+                member_id: member_id.clone(),
+                health: health.to_string(),
+            });
+        }
+        for member_id in previous.members.keys() {
+            if !self.members.contains_key(member_id) {
+                events.push(CensusEvent::ServiceRemoved {
+                    service_group: sg.to_string(),
+                    member_id: member_id.clone(),
+                });
+            }
+        }
+        if self.leader_id != previous.leader_id {
+            events.push(CensusEvent::LeaderChanged {
+                service_group: sg.to_string(),
+                leader_id: self.leader_id.clone(),
+            });
+        }
+        if let Some(incarnation) = self.config_incarnation {
+            if previous.config_incarnation != Some(incarnation) {
+                events.push(CensusEvent::ConfigChanged {
+                    service_group: sg.to_string(),
+                    incarnation: incarnation,
+                });
+            }
+        }
+        for (filename, incarnation) in self.files.iter() {
+            if previous.files.get(filename) != Some(incarnation) {
+                events.push(CensusEvent::FileChanged {
+                    service_group: sg.to_string(),
+                    filename: filename.clone(),
+                    incarnation: *incarnation,
+                });
+            }
+        }
+    }
 }
 
 fn service_group_from_str(sg: &str) -> Result<ServiceGroup, hcore::Error> {
@@ -651,6 +830,42 @@ mod tests {
     use hcore::package::ident::PackageIdent;
     use hcore::service::ServiceGroup;
 
+    use http_gateway;
+
+    #[test]
+    fn census_matches_openapi_schema() {
+        let mut sys_info = SysInfo::new();
+        sys_info.set_ip("1.2.3.4".to_string());
+        sys_info.set_hostname("hostname".to_string());
+        let ident = PackageIdent::new("core", "redis", Some("4.0.8"), Some("20180301000000"));
+        let sg = ServiceGroup::new(None, "redis", "default", None).unwrap();
+
+        let service_store: RumorStore<ServiceRumor> = RumorStore::default();
+        service_store.insert(ServiceRumor::new("member-a", &ident, &sg, &sys_info, None));
+        let election_store: RumorStore<ElectionRumor> = RumorStore::default();
+        let mut election = ElectionRumor::new("member-a", sg.clone(), 10);
+        election.finish();
+        election_store.insert(election);
+        let service_config_store: RumorStore<ServiceConfigRumor> = RumorStore::default();
+        service_config_store.insert(ServiceConfigRumor::new(
+            "member-a",
+            sg.clone(),
+            b"port = 6379".to_vec(),
+        ));
+
+        let mut ring = CensusRing::new("member-a");
+        ring.update_from_rumors(
+            &service_store,
+            &election_store,
+            &RumorStore::default(),
+            &MemberList::new(),
+            &service_config_store,
+            &RumorStore::default(),
+        );
+        assert!(ring.census_group_for(&sg).unwrap().service_config.is_some());
+        http_gateway::test::assert_valid_schema("census", &ring);
+    }
+
     #[test]
     fn update_from_rumors() {
         let mut sys_info = SysInfo::new();
@@ -780,4 +995,61 @@ mod tests {
         assert_eq!(active_members[1].member_id, "suspect-one");
     }
 
+    #[test]
+    fn census_events_since() {
+        let mut before = GroupSummary::default();
+        before.members.insert("a".to_string(), Health::Alive);
+        before.members.insert("b".to_string(), Health::Alive);
+        before.leader_id = Some("a".to_string());
+        before.config_incarnation = Some(1);
+        before.files.insert("cert.pem".to_string(), 1);
+        let mut previous = CensusSummary::default();
+        previous.groups.insert("redis.default".to_string(), before.clone());
+        previous.groups.insert("web.default".to_string(), GroupSummary::default());
+        assert!(previous.events_since(&previous).is_empty());
+
+        let mut after = before;
+        after.members.remove("a");
+        after.members.insert("b".to_string(), Health::Suspect);
+        after.members.insert("c".to_string(), Health::Alive);
+        after.leader_id = Some("b".to_string());
+        after.config_incarnation = Some(2);
+        let mut current = CensusSummary::default();
+        current.groups.insert("redis.default".to_string(), after);
+
+        let events = current.events_since(&previous);
+        let sg = "redis.default".to_string();
+        assert_eq!(
+            events,
+            vec![
+                CensusEvent::MemberHealth {
+                    service_group: sg.clone(),
+                    member_id: "b".to_string(),
+                    health: "suspect".to_string(),
+                },
+                CensusEvent::ServiceAdded {
+                    service_group: sg.clone(),
+                    member_id: "c".to_string(),
+                },
+                CensusEvent::MemberHealth {
+                    service_group: sg.clone(),
+                    member_id: "c".to_string(),
+                    health: "alive".to_string(),
+                },
+                CensusEvent::ServiceRemoved {
+                    service_group: sg.clone(),
+                    member_id: "a".to_string(),
+                },
+                CensusEvent::LeaderChanged {
+                    service_group: sg.clone(),
+                    leader_id: Some("b".to_string()),
+                },
+                CensusEvent::ConfigChanged {
+                    service_group: sg,
+                    incarnation: 2,
+                },
+            ]
+        );
+        assert_eq!(events[0].name(), "member-health");
+    }
 }
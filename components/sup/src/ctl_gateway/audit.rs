@@ -0,0 +1,308 @@
+// Copyright (c) 2018 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Append-only record of the requests made to the CtlGateway.
+//!
+//! Every request dispatched to the Manager is written to the `ctl-audit.log` file of the
+//! Supervisor's state directory as a single JSON object per line. Each entry records who made the
+//! request, what they asked for, and how it ended. Arguments which may hold secrets or encrypted
+//! content are redacted before being written. Once the file grows too large it is moved aside to
+//! `<path>.1` and a new file is started.
+
+use std::collections::VecDeque;
+use std::ffi::OsString;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use hcore::util::perm;
+use prost::Message;
+use protocol;
+use protocol::codec::SrvMessage;
+use protocol::message::MessageStatic;
+use protocol::net::NetResult;
+use serde::Serialize;
+use serde_json::{self, Value};
+use time;
+
+const AUDIT_LOG_FILENAME: &'static str = "ctl-audit.log";
+/// The audit log may describe the Supervisor's configuration and should only be readable by the
+/// Supervisor process.
+const AUDIT_LOG_PERMISSIONS: u32 = 0o600;
+/// Size in bytes at which the audit log is rotated.
+const MAX_LOG_BYTES: u64 = 10 * 1024 * 1024;
+/// Value written in place of a redacted argument.
+const REDACTED: &'static str = "<redacted>";
+/// Arguments whose values are never written to the audit log.
+const REDACTED_ARGS: &'static [&'static str] =
+    &["cfg", "content", "secret-key", "svc-encrypted-password"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Outcome {
+    /// The request has not finished running.
+    Pending,
+    Ok,
+    Error,
+}
+
+/// A single request made to the CtlGateway.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AuditEntry {
+    /// RFC 3339 UTC timestamp of when the request was received.
+    pub timestamp: String,
+    /// Transaction id of the request, if it was transactional.
+    pub txn: Option<u32>,
+    /// Address of the client which made the request.
+    pub peer: String,
+    /// Name of the token, or the shared secret, the client authenticated with.
+    pub identity: String,
+    /// Message id of the request.
+    pub message: String,
+    /// Arguments of the request with any secrets redacted.
+    pub args: Value,
+    pub outcome: Outcome,
+    /// Reason the request failed, if it did.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub error: Option<String>,
+}
+
+impl AuditEntry {
+    pub fn new<P, I>(peer: P, identity: I, msg: &SrvMessage) -> Self
+    where
+        P: ToString,
+        I: ToString,
+    {
+        AuditEntry {
+            timestamp: time::now_utc().rfc3339().to_string(),
+            txn: msg.transaction().map(|txn| txn.id()),
+            peer: peer.to_string(),
+            identity: identity.to_string(),
+            message: msg.message_id().to_string(),
+            args: args(msg),
+            outcome: Outcome::Pending,
+            error: None,
+        }
+    }
+
+    /// Record the result of running the request.
+    pub fn complete(&mut self, result: &NetResult<()>) {
+        match *result {
+            Ok(()) => self.outcome = Outcome::Ok,
+            Err(ref err) => {
+                self.outcome = Outcome::Error;
+                self.error = Some(err.to_string());
+            }
+        }
+    }
+}
+
+pub struct AuditLog {
+    path: PathBuf,
+}
+
+impl AuditLog {
+    /// Open the audit log of the Supervisor with the given state directory.
+    pub fn new<T>(sup_root: T) -> Self
+    where
+        T: AsRef<Path>,
+    {
+        AuditLog {
+            path: sup_root.as_ref().join(AUDIT_LOG_FILENAME),
+        }
+    }
+
+    /// Append the given entry, rotating the log first if it has grown too large.
+    pub fn append(&self, entry: &AuditEntry) -> io::Result<()> {
+        match fs::metadata(&self.path) {
+            Ok(ref meta) if meta.len() >= MAX_LOG_BYTES => {
+                fs::rename(&self.path, self.rotated_path())?
+            }
+            Ok(_) => (),
+            Err(ref err) if err.kind() == io::ErrorKind::NotFound => (),
+            Err(err) => return Err(err),
+        }
+        let line = serde_json::to_string(entry)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let exists = self.path.exists();
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        if !exists {
+            perm::set_permissions(&self.path, AUDIT_LOG_PERMISSIONS)
+                .map_err(|e| io::Error::new(io::ErrorKind::PermissionDenied, e.to_string()))?;
+        }
+        writeln!(file, "{}", line)
+    }
+
+    /// Read the entries of the log, oldest first, keeping only the last `tail` entries if
+    /// specified. Each entry is returned as the JSON object it was written as.
+    pub fn read(&self, tail: Option<usize>) -> io::Result<Vec<String>> {
+        let mut entries = VecDeque::new();
+        for path in &[self.rotated_path(), self.path.clone()] {
+            let file = match File::open(path) {
+                Ok(file) => file,
+                Err(ref err) if err.kind() == io::ErrorKind::NotFound => continue,
+                Err(err) => return Err(err),
+            };
+            for line in BufReader::new(file).lines() {
+                let line = line?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                entries.push_back(line);
+                if tail.map_or(false, |tail| entries.len() > tail) {
+                    entries.pop_front();
+                }
+            }
+        }
+        Ok(entries.into_iter().collect())
+    }
+
+    fn rotated_path(&self) -> PathBuf {
+        let mut path = OsString::from(self.path.as_os_str());
+        path.push(".1");
+        PathBuf::from(path)
+    }
+}
+
+/// Decode the arguments of the given request for recording in the audit log. Requests which
+/// aren't understood by the CtlGateway are recorded without arguments.
+fn args(msg: &SrvMessage) -> Value {
+    match msg.message_id() {
+        "SupAuditLog" => decode::<protocol::ctl::SupAuditLog>(msg),
+        "SupDepart" => decode::<protocol::ctl::SupDepart>(msg),
+        "SvcApply" => decode::<protocol::ctl::SvcApply>(msg),
+        "SvcFilePut" => decode::<protocol::ctl::SvcFilePut>(msg),
+        "SvcGetDefaultCfg" => decode::<protocol::ctl::SvcGetDefaultCfg>(msg),
+        "SvcHookHistory" => decode::<protocol::ctl::SvcHookHistory>(msg),
+        "SvcLoad" => decode::<protocol::ctl::SvcLoad>(msg),
+        "SvcLogs" => decode::<protocol::ctl::SvcLogs>(msg),
+        "SvcRestart" => decode::<protocol::ctl::SvcRestart>(msg),
+        "SvcSetCfg" => decode::<protocol::ctl::SvcSetCfg>(msg),
+        "SvcSignal" => decode::<protocol::ctl::SvcSignal>(msg),
+        "SvcStart" => decode::<protocol::ctl::SvcStart>(msg),
+        "SvcStatus" => decode::<protocol::ctl::SvcStatus>(msg),
+        "SvcStop" => decode::<protocol::ctl::SvcStop>(msg),
+        "SvcUnload" => decode::<protocol::ctl::SvcUnload>(msg),
+        "SvcValidateCfg" => decode::<protocol::ctl::SvcValidateCfg>(msg),
+        _ => Value::Null,
+    }
+}
+
+fn decode<T>(msg: &SrvMessage) -> Value
+where
+    T: Message + MessageStatic + Default + Serialize,
+{
+    match msg.parse::<T>() {
+        Ok(m) => redact(&m),
+        Err(_) => Value::Null,
+    }
+}
+
+/// Serialize the given message, replacing the value of any argument which may hold a secret.
+pub fn redact<T>(msg: &T) -> Value
+where
+    T: Serialize,
+{
+    let mut value = serde_json::to_value(msg).unwrap_or(Value::Null);
+    redact_value(&mut value);
+    value
+}
+
+fn redact_value(value: &mut Value) {
+    match *value {
+        Value::Object(ref mut map) => for (key, value) in map.iter_mut() {
+            if REDACTED_ARGS.contains(&key.as_str()) {
+                if !value.is_null() {
+                    *value = Value::String(REDACTED.to_string());
+                }
+            } else {
+                redact_value(value);
+            }
+        },
+        Value::Array(ref mut values) => for value in values.iter_mut() {
+            redact_value(value);
+        },
+        _ => (),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use protocol::net::{self, ErrCode};
+    use tempdir::TempDir;
+
+    use super::*;
+
+    fn entry(message: &str) -> AuditEntry {
+        AuditEntry {
+            timestamp: "2018-03-01T12:00:00Z".to_string(),
+            txn: Some(1),
+            peer: "127.0.0.1:50000".to_string(),
+            identity: "ci".to_string(),
+            message: message.to_string(),
+            args: Value::Null,
+            outcome: Outcome::Pending,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn redact_secrets() {
+        let mut msg = protocol::ctl::SvcSetCfg::default();
+        msg.cfg = Some(b"password = \"hunter2\"".to_vec());
+        msg.version = Some(2);
+        let value = redact(&msg);
+        assert_eq!(value["cfg"], Value::String(REDACTED.to_string()));
+        assert_eq!(value["version"], Value::from(2));
+
+        let msg = protocol::ctl::SvcLoad::default();
+        assert!(redact(&msg)["svc-encrypted-password"].is_null());
+    }
+
+    #[test]
+    fn complete_records_outcome() {
+        let mut ok = entry("SvcStop");
+        ok.complete(&Ok(()));
+        assert_eq!(ok.outcome, Outcome::Ok);
+        assert!(ok.error.is_none());
+
+        let mut failed = entry("SvcStop");
+        failed.complete(&Err(net::err(ErrCode::NotFound, "Service not loaded")));
+        assert_eq!(failed.outcome, Outcome::Error);
+        assert!(failed.error.unwrap().contains("Service not loaded"));
+    }
+
+    #[test]
+    fn append_and_read() {
+        let tmpdir = TempDir::new("audit").unwrap();
+        let log = AuditLog::new(tmpdir.path());
+        assert!(log.read(None).unwrap().is_empty());
+        for message in &["SvcLoad", "SvcStart", "SvcStop"] {
+            log.append(&entry(message)).unwrap();
+        }
+
+        let entries = log.read(None).unwrap();
+        assert_eq!(entries.len(), 3);
+        let first: AuditEntry = serde_json::from_str(&entries[0]).unwrap();
+        assert_eq!(first, entry("SvcLoad"));
+
+        let entries = log.read(Some(1)).unwrap();
+        let last: AuditEntry = serde_json::from_str(&entries[0]).unwrap();
+        assert_eq!(last.message, "SvcStop");
+    }
+}
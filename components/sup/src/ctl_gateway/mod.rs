@@ -20,13 +20,16 @@
 //! The [`ctl_gateway.client`] and [`ctl_gateway.server`] speak a streaming, multiplexed, binary
 //! protocol defined in [`protocol.codec`].
 
+pub mod audit;
 pub mod server;
 
 use std::borrow::Cow;
 use std::fmt;
 use std::fs::{self, File};
 use std::io::{self, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
 use regex::Regex;
 
@@ -35,6 +38,7 @@ use depot_client::DisplayProgress;
 use futures::prelude::*;
 use hcore::output;
 use hcore::util::perm;
+use openssl::ssl::{SslAcceptor, SslFiletype, SslMethod};
 use protocol;
 
 use error::{Error, Result};
@@ -57,6 +61,33 @@ static LOGKEY: &'static str = "AG";
 /// Supervisor process
 pub const CTL_SECRET_PERMISSIONS: u32 = 0o600;
 
+/// Certificate and private key used to encrypt connections to the CtlGateway.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TlsConfig {
+    /// Path to a PEM encoded certificate chain, starting with the CtlGateway's certificate.
+    pub cert_path: PathBuf,
+    /// Path to the PEM encoded private key of the CtlGateway's certificate.
+    pub key_path: PathBuf,
+}
+
+impl TlsConfig {
+    /// Build an acceptor for negotiating TLS sessions with connecting clients.
+    pub fn acceptor(&self) -> Result<SslAcceptor> {
+        let mut builder = SslAcceptor::mozilla_intermediate(SslMethod::tls())
+            .map_err(|e| sup_error!(Error::CtlTls(e)))?;
+        builder
+            .set_private_key_file(&self.key_path, SslFiletype::PEM)
+            .map_err(|e| sup_error!(Error::CtlTls(e)))?;
+        builder
+            .set_certificate_chain_file(&self.cert_path)
+            .map_err(|e| sup_error!(Error::CtlTls(e)))?;
+        builder
+            .check_private_key()
+            .map_err(|e| sup_error!(Error::CtlTls(e)))?;
+        Ok(builder.build())
+    }
+}
+
 /// Used by modules outside of the CtlGateway for seamlessly replying to transactional messages.
 /// This type is used in functions which can be called by the CtlGateway such as
 /// [`Manager::service_load`] and [`Manager::service_unload`].
@@ -73,6 +104,8 @@ pub struct CtlRequest {
     tx: Option<server::CtlSender>,
     /// Transaction for the given request.
     transaction: Option<protocol::codec::SrvTxn>,
+    /// Set by the CtlGateway's server once the client's connection has closed.
+    closed: Arc<AtomicBool>,
 }
 
 impl CtlRequest {
@@ -85,16 +118,26 @@ impl CtlRequest {
         CtlRequest {
             tx: tx,
             transaction: transaction,
+            closed: Arc::new(AtomicBool::new(false)),
         }
     }
 
+    /// Returns true once the client which sent the request has disconnected. Handlers which keep
+    /// replying for an arbitrary amount of time should stop when this happens, even if they have
+    /// nothing to send.
+    pub fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::SeqCst)
+    }
+
     /// Reply to the transaction with the given message but indicate to the receiver that this is
     /// not the final message for the transaction.
-    pub fn reply_partial<T>(&mut self, msg: T)
+    ///
+    /// Returns false if the reply could not be delivered because the client has gone away.
+    pub fn reply_partial<T>(&mut self, msg: T) -> bool
     where
         T: Into<protocol::codec::SrvMessage> + fmt::Debug,
     {
-        self.send_msg(msg, false);
+        self.send_msg(msg, false)
     }
 
     /// Reply to the transaction with the given message and indicate to the receiver that this is
@@ -111,7 +154,7 @@ impl CtlRequest {
         self.transaction.is_some() && self.tx.is_some()
     }
 
-    fn send_msg<T>(&mut self, msg: T, complete: bool)
+    fn send_msg<T>(&mut self, msg: T, complete: bool) -> bool
     where
         T: Into<protocol::codec::SrvMessage> + fmt::Debug,
     {
@@ -120,11 +163,11 @@ impl CtlRequest {
                 "Attempted to reply to a non-transactional message with {:?}",
                 msg
             );
-            return;
+            return false;
         }
         let mut wire: protocol::codec::SrvMessage = msg.into();
         wire.reply_for(self.transaction.unwrap(), complete);
-        self.tx.as_ref().unwrap().start_send(wire).ok(); // ignore Err return
+        self.tx.as_ref().unwrap().start_send(wire).is_ok()
     }
 }
 
@@ -283,3 +326,79 @@ where
         Ok(out)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+
+    use openssl::asn1::Asn1Time;
+    use openssl::hash::MessageDigest;
+    use openssl::pkey::{PKey, Private};
+    use openssl::rsa::Rsa;
+    use openssl::x509::{X509, X509NameBuilder};
+    use tempdir::TempDir;
+
+    use super::*;
+
+    fn key() -> PKey<Private> {
+        PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap()
+    }
+
+    fn self_signed(key: &PKey<Private>) -> X509 {
+        let mut name = X509NameBuilder::new().unwrap();
+        name.append_entry_by_text("CN", "sup.example").unwrap();
+        let name = name.build();
+        let mut builder = X509::builder().unwrap();
+        builder.set_version(2).unwrap();
+        builder.set_subject_name(&name).unwrap();
+        builder.set_issuer_name(&name).unwrap();
+        builder.set_pubkey(key).unwrap();
+        builder
+            .set_not_before(&Asn1Time::days_from_now(0).unwrap())
+            .unwrap();
+        builder
+            .set_not_after(&Asn1Time::days_from_now(1).unwrap())
+            .unwrap();
+        builder.sign(key, MessageDigest::sha256()).unwrap();
+        builder.build()
+    }
+
+    fn write(path: &Path, pem: &[u8]) {
+        File::create(path)
+            .and_then(|mut f| f.write_all(pem))
+            .unwrap();
+    }
+
+    fn tls_config(dir: &Path, cert_key: &PKey<Private>, key: &PKey<Private>) -> TlsConfig {
+        let config = TlsConfig {
+            cert_path: dir.join("ctl.crt"),
+            key_path: dir.join("ctl.key"),
+        };
+        write(&config.cert_path, &self_signed(cert_key).to_pem().unwrap());
+        write(&config.key_path, &key.private_key_to_pem_pkcs8().unwrap());
+        config
+    }
+
+    #[test]
+    fn tls_acceptor_from_cert_and_key() {
+        let tmpdir = TempDir::new("ctl-tls").unwrap();
+        let key = key();
+        assert!(tls_config(tmpdir.path(), &key, &key).acceptor().is_ok());
+    }
+
+    #[test]
+    fn tls_acceptor_rejects_mismatched_key() {
+        let tmpdir = TempDir::new("ctl-tls").unwrap();
+        assert!(tls_config(tmpdir.path(), &key(), &key()).acceptor().is_err());
+    }
+
+    #[test]
+    fn tls_acceptor_requires_files() {
+        let tmpdir = TempDir::new("ctl-tls").unwrap();
+        let config = TlsConfig {
+            cert_path: tmpdir.path().join("missing.crt"),
+            key_path: tmpdir.path().join("missing.key"),
+        };
+        assert!(config.acceptor().is_err());
+    }
+}
@@ -24,10 +24,15 @@
 
 use std::cell::RefCell;
 use std::error;
+#[cfg(unix)]
+use std::fs;
 use std::fmt;
 use std::io;
 use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use std::thread;
 use std::time::Duration;
 
@@ -35,14 +40,24 @@ use crypto;
 use futures::future::{self, Either};
 use futures::prelude::*;
 use futures::sync::mpsc;
+#[cfg(unix)]
+use hcore::util::perm;
+#[cfg(unix)]
+use libc;
+use openssl::ssl::SslAcceptor;
 use prost;
 use protocol;
 use protocol::codec::*;
 use protocol::net::{self, ErrCode, NetErr, NetResult};
-use tokio::net::TcpListener;
+use protocol::token::{self, CtlRole, CtlToken};
+use tokio::net::{TcpListener, TcpStream};
 use tokio_core::reactor;
 use tokio_io::AsyncRead;
+use tokio_openssl::SslAcceptorExt;
+#[cfg(unix)]
+use tokio_uds::{UnixListener, UnixStream};
 
+use super::audit::{AuditEntry, AuditLog};
 use super::{CtlRequest, REQ_TIMEOUT};
 use manager::{Manager, ManagerState};
 
@@ -125,6 +140,8 @@ pub struct CtlCommand {
     //
     // https://github.com/rust-lang/rust/issues/28796
     fun: Box<Fn(&ManagerState, &mut CtlRequest) -> NetResult<()> + Send>,
+    /// Entry recorded in the Supervisor's audit log once the command has run.
+    audit: Option<AuditEntry>,
 }
 
 impl CtlCommand {
@@ -136,35 +153,108 @@ impl CtlCommand {
         CtlCommand {
             fun: Box::new(fun),
             req: CtlRequest::new(tx, txn),
+            audit: None,
         }
     }
 
+    /// Share the given flag, set once the client's connection has closed, with the request.
+    fn watch_connection(mut self, closed: Arc<AtomicBool>) -> Self {
+        self.req.closed = closed;
+        self
+    }
+
+    /// Record the outcome of this command in the Supervisor's audit log with the given entry
+    /// once it has run.
+    pub fn audit(mut self, entry: AuditEntry) -> Self {
+        self.audit = Some(entry);
+        self
+    }
+
     /// Run the contained closure with the given [`manager.ManagerState`].
     pub fn run(&mut self, state: &ManagerState) -> NetResult<()> {
-        (self.fun)(state, &mut self.req)
+        let result = (self.fun)(state, &mut self.req);
+        if let Some(mut entry) = self.audit.take() {
+            entry.complete(&result);
+            if let Err(err) = AuditLog::new(state.cfg.sup_root()).append(&entry) {
+                warn!("Unable to write ctl audit log entry, {}", err);
+            }
+        }
+        result
     }
 }
 
 /// Server's client representation. Each new connection will allocate a new Client.
 struct Client {
+    /// Description of the connected peer used when logging and auditing its requests.
+    peer: String,
+    /// Identity granted to the peer by the credentials of its process, if any. A trusted client
+    /// isn't required to present a secret in its handshake.
+    trusted: Option<ClientIdentity>,
     handle: reactor::Handle,
     state: Rc<RefCell<SrvState>>,
 }
 
 impl Client {
-    /// Serve the client from the given framed socket stream.
-    pub fn serve(self, socket: SrvStream) -> Box<Future<Item = (), Error = HandlerError>> {
+    /// Serve the client from the given socket, negotiating a TLS session first if the server
+    /// requires one.
+    pub fn serve(self, socket: TcpStream) -> Box<Future<Item = (), Error = HandlerError>> {
+        let io: Box<Future<Item = Box<SrvIo>, Error = HandlerError>> =
+            match self.state.borrow().tls {
+                Some(ref acceptor) => Box::new(
+                    acceptor
+                        .accept_async(socket)
+                        .map(|stream| Box::new(stream) as Box<SrvIo>)
+                        .map_err(|err| {
+                            HandlerError::from(io::Error::new(
+                                io::ErrorKind::ConnectionAborted,
+                                format!("TLS handshake failed, {}", err),
+                            ))
+                        }),
+                ),
+                None => Box::new(future::ok(Box::new(socket) as Box<SrvIo>)),
+            };
+        self.serve_io(io)
+    }
+
+    /// Serve the client from the given Unix domain socket. Connections over a Unix domain socket
+    /// are never encrypted.
+    #[cfg(unix)]
+    pub fn serve_unix(self, socket: UnixStream) -> Box<Future<Item = (), Error = HandlerError>> {
+        self.serve_io(Box::new(future::ok(Box::new(socket) as Box<SrvIo>)))
+    }
+
+    fn serve_io(
+        self,
+        io: Box<Future<Item = Box<SrvIo>, Error = HandlerError>>,
+    ) -> Box<Future<Item = (), Error = HandlerError>> {
         let mgr_tx = self.state.borrow().mgr_tx.clone();
+        let peer = self.peer.clone();
         Box::new(
-            self.handshake(socket)
-                .and_then(|socket| SrvHandler::new(socket, mgr_tx)),
+            io.and_then(move |io| self.handshake(io.framed(SrvCodec::new())))
+                .and_then(move |(socket, identity)| {
+                    SrvHandler::new(socket, mgr_tx, identity, peer)
+                }),
         )
     }
 
     /// Initiate a handshake with the connected client before allowing future requests. A failed
     /// handshake will close the connection.
-    fn handshake(&self, socket: SrvStream) -> Box<Future<Item = SrvStream, Error = HandlerError>> {
+    ///
+    /// The client may present either the Supervisor's secret key, which grants the admin role, or
+    /// the secret of one of the Supervisor's named tokens.
+    fn handshake(
+        &self,
+        socket: SrvStream,
+    ) -> Box<Future<Item = (SrvStream, ClientIdentity), Error = HandlerError>> {
         let secret_key = self.state.borrow().secret_key.to_string();
+        let trusted = self.trusted.clone();
+        let tokens = match token::read_ctl_tokens(&self.state.borrow().sup_root) {
+            Ok(tokens) => tokens,
+            Err(err) => {
+                warn!("Unable to read ctl tokens, only the secret key will be accepted, {}", err);
+                vec![]
+            }
+        };
         let handshake = socket
             .into_future()
             .map_err(|(err, _)| HandlerError::from(err))
@@ -191,14 +281,10 @@ impl Client {
                             Ok(decoded) => {
                                 trace!("Received handshake, {:?}", decoded);
                                 let decoded_key = decoded.secret_key.unwrap_or_default();
-                                Ok((
-                                    m,
-                                    crypto::util::fixed_time_eq(
-                                        decoded_key.as_bytes(),
-                                        secret_key.as_bytes(),
-                                    ),
-                                    io,
-                                ))
+                                let identity = trusted.or_else(|| {
+                                    authenticate(&decoded_key, &secret_key, &tokens)
+                                });
+                                Ok((m, identity, io))
                             }
                             Err(err) => {
                                 warn!("Handshake error, {:?}", err);
@@ -210,8 +296,8 @@ impl Client {
                     },
                 )
             })
-            .and_then(|(msg, success, socket)| {
-                let mut reply = if success {
+            .and_then(|(msg, identity, socket)| {
+                let mut reply = if identity.is_some() {
                     SrvMessage::from(net::ok())
                 } else {
                     SrvMessage::from(net::err(ErrCode::Unauthorized, "secret key mismatch"))
@@ -220,14 +306,14 @@ impl Client {
                 socket
                     .send(reply)
                     .map_err(HandlerError::from)
-                    .and_then(move |io| Ok((io, success)))
+                    .and_then(move |io| Ok((io, identity)))
             });
         Box::new(
             handshake
                 .select2(self.timeout(REQ_TIMEOUT))
                 .then(|res| match res {
-                    Ok(Either::A(((io, true), _to))) => future::ok(io),
-                    Ok(Either::A(((_, false), _to))) => future::err(HandlerError::from(
+                    Ok(Either::A(((io, Some(identity)), _to))) => future::ok((io, identity)),
+                    Ok(Either::A(((_, None), _to))) => future::err(HandlerError::from(
                         io::Error::new(io::ErrorKind::ConnectionAborted, "handshake failed"),
                     )),
                     Ok(Either::B((_to, _hs))) => future::err(HandlerError::from(io::Error::new(
@@ -255,10 +341,14 @@ struct SrvHandler {
     mgr_tx: MgrSender,
     rx: CtlReceiver,
     tx: CtlSender,
+    identity: ClientIdentity,
+    peer: String,
+    /// Set once the client's connection has closed, so long running requests stop.
+    closed: Arc<AtomicBool>,
 }
 
 impl SrvHandler {
-    fn new(io: SrvStream, mgr_tx: MgrSender) -> Self {
+    fn new(io: SrvStream, mgr_tx: MgrSender, identity: ClientIdentity, peer: String) -> Self {
         let (tx, rx) = mpsc::unbounded();
         SrvHandler {
             io: io,
@@ -266,10 +356,19 @@ impl SrvHandler {
             mgr_tx: mgr_tx,
             rx: rx,
             tx: tx,
+            identity: identity,
+            peer: peer,
+            closed: Arc::new(AtomicBool::new(false)),
         }
     }
 }
 
+impl Drop for SrvHandler {
+    fn drop(&mut self) {
+        self.closed.store(true, Ordering::SeqCst);
+    }
+}
+
 impl Future for SrvHandler {
     type Item = ();
     type Error = HandlerError;
@@ -280,7 +379,25 @@ impl Future for SrvHandler {
                 SrvHandlerState::Receiving => match try_ready!(self.io.poll()) {
                     Some(msg) => {
                         trace!("OnMessage, {}", msg.message_id());
+                        let required = required_role(msg.message_id());
                         let cmd = match msg.message_id() {
+                            _ if !self.identity.role.permits(required) => {
+                                warn!(
+                                    "Denied {} from {}, requires the {} role",
+                                    msg.message_id(),
+                                    self.identity.name,
+                                    required
+                                );
+                                let reason =
+                                    format!("{} requires the {} role", msg.message_id(), required);
+                                CtlCommand::new(
+                                    Some(self.tx.clone()),
+                                    msg.transaction(),
+                                    move |_, _| {
+                                        Err(net::err(ErrCode::Unauthorized, reason.clone()))
+                                    },
+                                )
+                            }
                             "SvcGetDefaultCfg" => {
                                 let m = msg.parse::<protocol::ctl::SvcGetDefaultCfg>()
                                     .map_err(HandlerError::from)?;
@@ -332,6 +449,17 @@ impl Future for SrvHandler {
                                     move |state, req| Manager::service_load(state, req, m.clone()),
                                 )
                             }
+                            "SvcApply" => {
+                                let m = msg.parse::<protocol::ctl::SvcApply>()
+                                    .map_err(HandlerError::from)?;
+                                CtlCommand::new(
+                                    Some(self.tx.clone()),
+                                    msg.transaction(),
+                                    move |state, req| {
+                                        Manager::service_apply(state, req, m.clone())
+                                    },
+                                )
+                            }
                             "SvcUnload" => {
                                 let m = msg.parse::<protocol::ctl::SvcUnload>()
                                     .map_err(HandlerError::from)?;
@@ -361,6 +489,28 @@ impl Future for SrvHandler {
                                     move |state, req| Manager::service_stop(state, req, m.clone()),
                                 )
                             }
+                            "SvcRestart" => {
+                                let m = msg.parse::<protocol::ctl::SvcRestart>()
+                                    .map_err(HandlerError::from)?;
+                                CtlCommand::new(
+                                    Some(self.tx.clone()),
+                                    msg.transaction(),
+                                    move |state, req| {
+                                        Manager::service_restart(state, req, m.clone())
+                                    },
+                                )
+                            }
+                            "SvcSignal" => {
+                                let m = msg.parse::<protocol::ctl::SvcSignal>()
+                                    .map_err(HandlerError::from)?;
+                                CtlCommand::new(
+                                    Some(self.tx.clone()),
+                                    msg.transaction(),
+                                    move |state, req| {
+                                        Manager::service_signal(state, req, m.clone())
+                                    },
+                                )
+                            }
                             "SvcStatus" => {
                                 let m = msg.parse::<protocol::ctl::SvcStatus>()
                                     .map_err(HandlerError::from)?;
@@ -372,6 +522,37 @@ impl Future for SrvHandler {
                                     },
                                 )
                             }
+                            "SvcLogs" => {
+                                let m = msg.parse::<protocol::ctl::SvcLogs>()
+                                    .map_err(HandlerError::from)?;
+                                CtlCommand::new(
+                                    Some(self.tx.clone()),
+                                    msg.transaction(),
+                                    move |state, req| Manager::service_logs(state, req, m.clone()),
+                                )
+                            }
+                            "SvcHookHistory" => {
+                                let m = msg.parse::<protocol::ctl::SvcHookHistory>()
+                                    .map_err(HandlerError::from)?;
+                                CtlCommand::new(
+                                    Some(self.tx.clone()),
+                                    msg.transaction(),
+                                    move |state, req| {
+                                        Manager::service_hook_history(state, req, m.clone())
+                                    },
+                                )
+                            }
+                            "SupAuditLog" => {
+                                let m = msg.parse::<protocol::ctl::SupAuditLog>()
+                                    .map_err(HandlerError::from)?;
+                                CtlCommand::new(
+                                    Some(self.tx.clone()),
+                                    msg.transaction(),
+                                    move |state, req| {
+                                        Manager::supervisor_audit_log(state, req, m.clone())
+                                    },
+                                )
+                            }
                             "SupDepart" => {
                                 let m = msg.parse::<protocol::ctl::SupDepart>()
                                     .map_err(HandlerError::from)?;
@@ -388,6 +569,8 @@ impl Future for SrvHandler {
                                 break;
                             }
                         };
+                        let cmd = cmd.watch_connection(self.closed.clone())
+                            .audit(AuditEntry::new(&self.peer, &self.identity.name, &msg));
                         match self.mgr_tx.start_send(cmd) {
                             Ok(AsyncSink::Ready) => {
                                 self.state = SrvHandlerState::Sending;
@@ -402,20 +585,34 @@ impl Future for SrvHandler {
                     }
                     None => break,
                 },
-                SrvHandlerState::Sending => match self.rx.poll() {
-                    Ok(Async::Ready(Some(msg))) => {
-                        trace!("MgrSender -> SrvHandler, {:?}", msg);
-                        if msg.is_complete() {
-                            self.state = SrvHandlerState::Sent;
+                SrvHandlerState::Sending => {
+                    // Clients don't send anything while replies are streamed to them, so the
+                    // connection is only readable once it has been closed.
+                    match self.io.poll() {
+                        Ok(Async::NotReady) => (),
+                        Ok(Async::Ready(Some(msg))) => {
+                            warn!("Ignoring {} sent during a transaction", msg.message_id());
+                        }
+                        Ok(Async::Ready(None)) | Err(_) => {
+                            trace!("Client disconnected while receiving replies");
+                            break;
                         }
-                        try_nb!(self.io.start_send(msg));
-                        try_ready!(self.io.poll_complete());
-                        continue;
                     }
-                    Ok(Async::Ready(None)) => self.state = SrvHandlerState::Sent,
-                    Ok(Async::NotReady) => return Ok(Async::NotReady),
-                    Err(()) => break,
-                },
+                    match self.rx.poll() {
+                        Ok(Async::Ready(Some(msg))) => {
+                            trace!("MgrSender -> SrvHandler, {:?}", msg);
+                            if msg.is_complete() {
+                                self.state = SrvHandlerState::Sent;
+                            }
+                            try_nb!(self.io.start_send(msg));
+                            try_ready!(self.io.poll_complete());
+                            continue;
+                        }
+                        Ok(Async::Ready(None)) => self.state = SrvHandlerState::Sent,
+                        Ok(Async::NotReady) => return Ok(Async::NotReady),
+                        Err(()) => break,
+                    }
+                }
                 SrvHandlerState::Sent => {
                     trace!("OnMessage complete");
                     break;
@@ -436,46 +633,228 @@ enum SrvHandlerState {
     Sent,
 }
 
+/// The authenticated identity of a connected client.
+#[derive(Clone, Debug)]
+pub struct ClientIdentity {
+    /// Name of the token presented by the client.
+    pub name: String,
+    pub role: CtlRole,
+}
+
 struct SrvState {
     secret_key: String,
+    sup_root: PathBuf,
+    tls: Option<SslAcceptor>,
     mgr_tx: MgrSender,
 }
 
 /// Start a new thread which will run the CtlGateway server.
 ///
-/// New connections will be authenticated using `secret_key`. Messages from the main thread
-/// will be sent over the channel `mgr_tx`.
-pub fn run(listen_addr: SocketAddr, secret_key: String, mgr_tx: MgrSender) {
+/// The server listens for TCP connections on `listen_addr` and for local connections on the Unix
+/// domain socket at `socket_path`, if given. New connections will be authenticated using
+/// `secret_key` or one of the tokens found in the Supervisor's state directory, `sup_root`, unless
+/// they arrive over the Unix domain socket from a trusted user. TCP connections are encrypted if a
+/// `tls` acceptor is given. Messages from the main thread will be sent over the channel `mgr_tx`.
+pub fn run(
+    listen_addr: Option<SocketAddr>,
+    socket_path: Option<PathBuf>,
+    secret_key: String,
+    sup_root: PathBuf,
+    tls: Option<SslAcceptor>,
+    mgr_tx: MgrSender,
+) {
     thread::Builder::new()
         .name("ctl-gateway".to_string())
         .spawn(move || {
             let mut core = reactor::Core::new().unwrap();
             let handle = core.handle();
-            let listener = TcpListener::bind(&listen_addr).unwrap();
             let state = SrvState {
                 secret_key: secret_key,
+                sup_root: sup_root,
+                tls: tls,
                 mgr_tx: mgr_tx,
             };
             let state = Rc::new(RefCell::new(state));
-            let clients = listener.incoming().map(|socket| {
-                let addr = socket.peer_addr().unwrap();
-                let io = socket.framed(SrvCodec::new());
-                (
-                    Client {
+            let mut servers: Vec<Box<Future<Item = (), Error = io::Error>>> = vec![];
+            if let Some(listen_addr) = listen_addr {
+                let listener = TcpListener::bind(&listen_addr).unwrap();
+                let handle = handle.clone();
+                let state = state.clone();
+                servers.push(Box::new(listener.incoming().for_each(move |socket| {
+                    let peer = socket.peer_addr().unwrap().to_string();
+                    let client = Client {
+                        peer: peer.clone(),
+                        trusted: None,
                         handle: handle.clone(),
                         state: state.clone(),
-                    }.serve(io),
-                    addr,
-                )
-            });
-            let server = clients.for_each(|(client, addr)| {
-                handle.spawn(client.then(move |res| {
-                    debug!("DISCONNECTED from {:?} with result {:?}", addr, res);
-                    future::ok(())
-                }));
-                Ok(())
-            });
-            core.run(server)
+                    }.serve(socket);
+                    spawn_client(&handle, client, peer);
+                    Ok(())
+                })));
+            }
+            if let Some(socket_path) = socket_path {
+                servers.push(unix_server(&socket_path, &handle, &state));
+            }
+            core.run(future::join_all(servers))
         })
         .expect("ctl-gateway thread start failure");
 }
+
+/// Listen for connections on the Unix domain socket at the given path. Only the Supervisor's user
+/// may connect to the socket, and the credentials of each connecting process are checked to
+/// determine if it may skip presenting a secret.
+#[cfg(unix)]
+fn unix_server(
+    path: &Path,
+    handle: &reactor::Handle,
+    state: &Rc<RefCell<SrvState>>,
+) -> Box<Future<Item = (), Error = io::Error>> {
+    // A socket left behind by a previous Supervisor would prevent binding a new one.
+    if path.exists() {
+        fs::remove_file(path).unwrap();
+    }
+    let listener = UnixListener::bind(path).unwrap();
+    perm::set_permissions(path, CTL_SOCKET_PERMISSIONS).unwrap();
+    let euid = unsafe { libc::geteuid() };
+    let handle = handle.clone();
+    let state = state.clone();
+    Box::new(listener.incoming().for_each(move |socket| {
+        let (peer, trusted) = match socket.peer_cred() {
+            Ok(cred) => (
+                format!("unix:uid={}", cred.uid),
+                trusted_identity(cred.uid, euid),
+            ),
+            Err(err) => {
+                warn!("Unable to read ctl client credentials, {}", err);
+                ("unix".to_string(), None)
+            }
+        };
+        let client = Client {
+            peer: peer.clone(),
+            trusted: trusted,
+            handle: handle.clone(),
+            state: state.clone(),
+        }.serve_unix(socket);
+        spawn_client(&handle, client, peer);
+        Ok(())
+    }))
+}
+
+#[cfg(windows)]
+fn unix_server(
+    _path: &Path,
+    _handle: &reactor::Handle,
+    _state: &Rc<RefCell<SrvState>>,
+) -> Box<Future<Item = (), Error = io::Error>> {
+    warn!("Listening for ctl connections on a Unix domain socket is not supported on Windows");
+    Box::new(future::ok(()))
+}
+
+fn spawn_client(
+    handle: &reactor::Handle,
+    client: Box<Future<Item = (), Error = HandlerError>>,
+    peer: String,
+) {
+    handle.spawn(client.then(move |res| {
+        debug!("DISCONNECTED from {} with result {:?}", peer, res);
+        future::ok(())
+    }));
+}
+
+/// The Unix domain socket should only be connectable by the Supervisor's user.
+#[cfg(unix)]
+const CTL_SOCKET_PERMISSIONS: u32 = 0o600;
+
+/// Determine the identity of a client connected over the Unix domain socket from the user id of
+/// its process. Processes running as root or as the Supervisor's own user are trusted as admins.
+#[cfg(unix)]
+fn trusted_identity(uid: u32, euid: u32) -> Option<ClientIdentity> {
+    if uid == 0 || uid == euid {
+        Some(ClientIdentity {
+            name: format!("uid:{}", uid),
+            role: CtlRole::Admin,
+        })
+    } else {
+        None
+    }
+}
+
+/// Name given to clients which authenticate with the Supervisor's secret key rather than a named
+/// token.
+const SECRET_KEY_IDENTITY: &'static str = "ctl-secret";
+
+/// Determine the identity of a client from the secret presented in its handshake, if any.
+fn authenticate(presented: &str, secret_key: &str, tokens: &[CtlToken]) -> Option<ClientIdentity> {
+    if crypto::util::fixed_time_eq(presented.as_bytes(), secret_key.as_bytes()) {
+        return Some(ClientIdentity {
+            name: SECRET_KEY_IDENTITY.to_string(),
+            role: CtlRole::Admin,
+        });
+    }
+    tokens
+        .iter()
+        .find(|t| crypto::util::fixed_time_eq(presented.as_bytes(), t.secret.as_bytes()))
+        .map(|t| ClientIdentity {
+            name: t.name.clone(),
+            role: t.role,
+        })
+}
+
+/// The minimum role a client must hold to make a request with the given message id.
+fn required_role(message_id: &str) -> CtlRole {
+    match message_id {
+        "SupAuditLog" | "SvcGetDefaultCfg" | "SvcHookHistory" | "SvcLogs" | "SvcStatus"
+        | "SvcValidateCfg" => CtlRole::ReadOnly,
+        "SvcRestart" | "SvcSignal" | "SvcStart" | "SvcStop" => CtlRole::Operator,
+        _ => CtlRole::Admin,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn new_token(name: &str, role: CtlRole, secret: &str) -> CtlToken {
+        CtlToken {
+            name: name.to_string(),
+            role: role,
+            secret: secret.to_string(),
+        }
+    }
+
+    #[test]
+    fn authenticate_secret_key_is_admin() {
+        let identity = authenticate("s3cr3t", "s3cr3t", &[]).unwrap();
+        assert_eq!(identity.name, SECRET_KEY_IDENTITY);
+        assert_eq!(identity.role, CtlRole::Admin);
+    }
+
+    #[test]
+    fn authenticate_token() {
+        let tokens = vec![
+            new_token("ci", CtlRole::Operator, "ci-secret"),
+            new_token("dash", CtlRole::ReadOnly, "dash-secret"),
+        ];
+        let identity = authenticate("dash-secret", "s3cr3t", &tokens).unwrap();
+        assert_eq!(identity.name, "dash");
+        assert_eq!(identity.role, CtlRole::ReadOnly);
+        assert!(authenticate("nope", "s3cr3t", &tokens).is_none());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn trusted_identity_by_uid() {
+        assert_eq!(trusted_identity(0, 1000).unwrap().role, CtlRole::Admin);
+        assert_eq!(trusted_identity(1000, 1000).unwrap().name, "uid:1000");
+        assert!(trusted_identity(1001, 1000).is_none());
+    }
+
+    #[test]
+    fn required_roles() {
+        assert_eq!(required_role("SvcStatus"), CtlRole::ReadOnly);
+        assert_eq!(required_role("SvcStop"), CtlRole::Operator);
+        assert_eq!(required_role("SvcUnload"), CtlRole::Admin);
+        assert_eq!(required_role("SvcSetCfg"), CtlRole::Admin);
+        assert_eq!(required_role("Unknown"), CtlRole::Admin);
+    }
+}
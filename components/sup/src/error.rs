@@ -59,6 +59,7 @@ use hcore::output::StructuredOutput;
 use hcore::package::{self, Identifiable, PackageInstall};
 use launcher_client;
 use notify;
+use openssl;
 use protocol;
 use serde_json;
 use toml;
@@ -116,6 +117,7 @@ pub enum Error {
     BadEnvConfig(String),
     ButterflyError(butterfly::error::Error),
     CtlSecretIo(PathBuf, io::Error),
+    CtlTls(openssl::error::ErrorStack),
     DepotClient(depot_client::Error),
     EnvJoinPathsError(env::JoinPathsError),
     ExecCommandNotFound(String),
@@ -124,12 +126,15 @@ pub enum Error {
     GroupNotFound(String),
     HabitatCommon(common::Error),
     HabitatCore(hcore::Error),
+    HttpGatewayTls(String),
     TemplateFileError(handlebars::TemplateFileError),
     TemplateRenderError(handlebars::RenderError),
     InvalidBinding(String),
     InvalidBinds(Vec<String>),
     InvalidKeyParameter(String),
     InvalidPidFile,
+    InvalidResourceLimit(String),
+    InvalidShutdownSignal(String),
     InvalidTopology(String),
     InvalidUpdateStrategy(String),
     Io(io::Error),
@@ -224,12 +229,18 @@ impl fmt::Display for SupError {
                 path.display(),
                 err
             ),
+            Error::CtlTls(ref err) => {
+                format!("Unable to configure TLS for the Control Gateway, {}", err)
+            }
             Error::ExecCommandNotFound(ref c) => {
                 format!("`{}' was not found on the filesystem or in PATH", c)
             }
             Error::Permissions(ref err) => format!("{}", err),
             Error::HabitatCommon(ref err) => format!("{}", err),
             Error::HabitatCore(ref err) => format!("{}", err),
+            Error::HttpGatewayTls(ref err) => {
+                format!("Unable to configure TLS for the HTTP Gateway, {}", err)
+            }
             Error::TemplateFileError(ref err) => format!("{:?}", err),
             Error::TemplateRenderError(ref err) => format!("{}", err),
             Error::DepotClient(ref err) => format!("{}", err),
@@ -249,6 +260,8 @@ impl fmt::Display for SupError {
                 format!("Invalid parameter for key generation: {:?}", e)
             }
             Error::InvalidPidFile => format!("Invalid child process PID file"),
+            Error::InvalidResourceLimit(ref l) => format!("Invalid resource limit: {}", l),
+            Error::InvalidShutdownSignal(ref s) => format!("Unsupported shutdown signal: {}", s),
             Error::InvalidTopology(ref t) => format!("Invalid topology: {}", t),
             Error::InvalidUpdateStrategy(ref s) => format!("Invalid update strategy: {}", s),
             Error::Io(ref err) => format!("{}", err),
@@ -362,12 +375,14 @@ impl error::Error for SupError {
             Error::BadEnvConfig(_) => "Unknown syntax in Env Configuration",
             Error::ButterflyError(ref err) => err.description(),
             Error::CtlSecretIo(_, _) => "IoError while reading ctl secret",
+            Error::CtlTls(_) => "Unable to configure TLS for the Control Gateway",
             Error::ExecCommandNotFound(_) => "Exec command was not found on filesystem or in PATH",
             Error::GroupNotFound(_) => "No matching GID for group found",
             Error::TemplateFileError(ref err) => err.description(),
             Error::TemplateRenderError(ref err) => err.description(),
             Error::HabitatCommon(ref err) => err.description(),
             Error::HabitatCore(ref err) => err.description(),
+            Error::HttpGatewayTls(_) => "Unable to configure TLS for the HTTP Gateway",
             Error::DepotClient(ref err) => err.description(),
             Error::EnvJoinPathsError(ref err) => err.description(),
             Error::FileNotFound(_) => "File not found",
@@ -378,6 +393,8 @@ impl error::Error for SupError {
             }
             Error::InvalidKeyParameter(_) => "Key parameter error",
             Error::InvalidPidFile => "Invalid child process PID file",
+            Error::InvalidResourceLimit(_) => "Invalid resource limit",
+            Error::InvalidShutdownSignal(_) => "Unsupported shutdown signal",
             Error::InvalidTopology(_) => "Invalid topology",
             Error::InvalidUpdateStrategy(_) => "Invalid update strategy",
             Error::Io(ref err) => err.description(),
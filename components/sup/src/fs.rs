@@ -66,6 +66,11 @@ pub fn svc_logs_path<T: AsRef<Path>>(service_name: T) -> PathBuf {
     svc_path(service_name).join("logs")
 }
 
+/// Returns the path to the file which receives a copy of a given service's output.
+pub fn svc_output_log<T: AsRef<Path>>(service_name: T) -> PathBuf {
+    svc_logs_path(service_name).join("service.log")
+}
+
 /// Returns the path to a given service's pid file.
 pub fn svc_pid_file<T: AsRef<Path>>(service_name: T) -> PathBuf {
     svc_path(service_name).join("PID")
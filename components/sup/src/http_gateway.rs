@@ -12,33 +12,73 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::cmp;
+use std::collections::{HashMap, VecDeque};
+use std::error;
 use std::fmt;
 use std::fs::File;
-use std::io::{self, Read};
+use std::io::{self, Read, Write};
 use std::net::{IpAddr, Ipv4Addr, SocketAddr, SocketAddrV4, ToSocketAddrs};
 use std::ops::{Deref, DerefMut};
 use std::option;
-use std::path::Path;
+use std::path::PathBuf;
 use std::result;
 use std::str::FromStr;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex, RwLock};
 use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
 
+use crypto;
+use hcore::crypto::hash;
 use hcore::service::{ApplicationEnvironment, ServiceGroup};
+use hyper_openssl::OpensslServer;
 use iron::modifiers::Header;
 use iron::prelude::*;
-use iron::{headers, status, typemap};
+use iron::response::WriteBody;
+use iron::{headers, status, typemap, BeforeMiddleware, Handler};
+use launcher_client;
 use persistent;
 use router::Router;
 use serde_json::{self, Value as Json};
+use url::form_urlencoded;
 
+use census::CensusEvent;
 use error::{Error, Result, SupError};
+use fs;
 use manager;
 use manager::service::hooks::{self, HealthCheckHook};
-use manager::service::HealthCheck;
+use manager::service::{HealthCheck, HookHistory};
+use metrics;
+use VERSION;
 
 static LOGKEY: &'static str = "HG";
+
+lazy_static! {
+    /// The OpenAPI description served on `/openapi.json`, labelled with the Supervisor's version.
+    static ref OPENAPI_DOC: String = {
+        let mut doc: Json = serde_json::from_str(OPENAPI).expect("Invalid OpenAPI document");
+        doc["info"]["x-supervisor-version"] = Json::String(VERSION.to_string());
+        doc.to_string()
+    };
+}
+/// Environment variable holding the token clients must present to the gateway, if any.
+pub const AUTH_TOKEN_ENVVAR: &'static str = launcher_client::GATEWAY_AUTH_TOKEN_ENV;
 const APIDOCS: &'static str = include_str!(concat!(env!("OUT_DIR"), "/api.html"));
+/// OpenAPI description of the gateway. The JSON Schemas of its responses are checked against the
+/// serialized types in this module's tests; its version must be bumped whenever they change.
+const OPENAPI: &'static str = include_str!("../doc/openapi.json");
+/// Number of census events kept for clients which reconnect with a `Last-Event-ID` header.
+const EVENT_HISTORY: usize = 1024;
+/// Number of event streams which may be open at once. Each open stream occupies one of the
+/// gateway's worker threads until the client disconnects.
+const MAX_EVENT_STREAMS: usize = 8;
+/// Seconds after which a comment is sent on an idle event stream, so that clients and proxies
+/// keep the connection open.
+const EVENT_KEEPALIVE_SECS: u64 = 15;
+/// Seconds after which the Supervisor is no longer considered alive if the Manager hasn't
+/// published its state.
+const LIVENESS_TIMEOUT_SECS: u64 = 60;
 
 #[derive(PartialEq, Eq, Debug, Clone)]
 pub struct ListenAddr(SocketAddr);
@@ -104,40 +144,425 @@ impl fmt::Display for ListenAddr {
     }
 }
 
+#[derive(Clone, Debug, PartialEq)]
+pub struct TlsConfig {
+    /// Path to a PEM encoded certificate chain, starting with the gateway's certificate.
+    pub cert_path: PathBuf,
+    /// Path to the PEM encoded private key of the gateway's certificate.
+    pub key_path: PathBuf,
+}
+
+impl TlsConfig {
+    fn server(&self) -> Result<OpensslServer> {
+        OpensslServer::from_files(&self.key_path, &self.cert_path)
+            .map_err(|e| sup_error!(Error::HttpGatewayTls(e.to_string())))
+    }
+}
+
+/// The services which must be ready for the Supervisor to report itself as ready on `/readyz`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ReadinessPolicy {
+    /// Every loaded service must be ready.
+    AllServices,
+    /// Only the given service groups must be ready. They must also be loaded.
+    Services(Vec<ServiceGroup>),
+}
+
+impl Default for ReadinessPolicy {
+    fn default() -> ReadinessPolicy {
+        ReadinessPolicy::AllServices
+    }
+}
+
+/// Readiness of the Supervisor, as served on `/readyz`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct Readiness {
+    pub ready: bool,
+    pub services: Vec<ServiceReadiness>,
+}
+
+impl Readiness {
+    /// Determine the readiness of the Supervisor from that of its loaded services.
+    pub fn new(policy: &ReadinessPolicy, services: Vec<ServiceReadiness>) -> Self {
+        let services = match *policy {
+            ReadinessPolicy::AllServices => services,
+            ReadinessPolicy::Services(ref groups) => groups
+                .iter()
+                .map(|sg| {
+                    let sg = sg.to_string();
+                    services
+                        .iter()
+                        .find(|s| s.service_group == sg)
+                        .cloned()
+                        .unwrap_or_else(|| ServiceReadiness::not_loaded(sg))
+                })
+                .collect(),
+        };
+        Readiness {
+            ready: services.iter().all(|s| s.ready),
+            services: services,
+        }
+    }
+}
+
+/// Readiness of a single service. A service is ready once it has been initialized and its last
+/// health check passed, with either an ok or a warning result.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ServiceReadiness {
+    pub service_group: String,
+    pub ready: bool,
+    pub loaded: bool,
+    pub initialized: bool,
+    pub health_check: Option<String>,
+}
+
+impl ServiceReadiness {
+    pub fn new(service_group: &ServiceGroup, initialized: bool, health_check: HealthCheck) -> Self {
+        let healthy = match health_check {
+            HealthCheck::Ok | HealthCheck::Warning => true,
+            HealthCheck::Critical | HealthCheck::Unknown => false,
+        };
+        ServiceReadiness {
+            service_group: service_group.to_string(),
+            ready: initialized && healthy,
+            loaded: true,
+            initialized: initialized,
+            health_check: Some(health_check.to_string()),
+        }
+    }
+
+    fn not_loaded(service_group: String) -> Self {
+        ServiceReadiness {
+            service_group: service_group,
+            ready: false,
+            loaded: false,
+            initialized: false,
+            health_check: None,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct LivenessBody {
+    alive: bool,
+    /// Seconds since the Manager last published its state.
+    last_tick_secs: Option<u64>,
+}
+
+/// A serialized copy of part of the Manager's state, as last published by the Manager.
+#[derive(Clone, Debug, Default)]
+pub struct Snapshot {
+    version: u64,
+    etag: String,
+    data: Arc<String>,
+}
+
+impl Snapshot {
+    /// Replace the content of the snapshot, moving it to a new version if the content changed.
+    ///
+    /// Returns `true` if the snapshot changed.
+    pub fn publish(&mut self, data: String) -> bool {
+        if self.is_published() && *self.data == data {
+            return false;
+        }
+        self.version += 1;
+        self.etag = hash::hash_string(&data);
+        self.data = Arc::new(data);
+        true
+    }
+
+    /// Returns `false` until the Manager has published the snapshot for the first time.
+    pub fn is_published(&self) -> bool {
+        self.version > 0
+    }
+
+    /// Number of times the content of the snapshot has changed.
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// Entity tag identifying the content of the snapshot.
+    pub fn etag(&self) -> &str {
+        &self.etag
+    }
+
+    pub fn data(&self) -> Arc<String> {
+        self.data.clone()
+    }
+}
+
+/// The parts of the Manager's state served by the gateway. The Manager publishes a new copy on
+/// every tick and requests are answered from the latest copy without touching the disk.
+#[derive(Debug, Default)]
+pub struct GatewayState {
+    pub butterfly: Snapshot,
+    pub census: Snapshot,
+    pub services: Snapshot,
+    /// Each entry of `services`, keyed by service group.
+    pub service: HashMap<String, Snapshot>,
+    pub readiness: Readiness,
+    /// When the Manager last published its state.
+    pub published_at: Option<Instant>,
+}
+
+impl GatewayState {
+    /// Publish the state of the loaded services, given as the service group and serialized state
+    /// of each service.
+    ///
+    /// Returns `true` if the state of any service changed.
+    pub fn publish_services(&mut self, services: Vec<(ServiceGroup, String)>) -> bool {
+        let list = format!(
+            "[{}]",
+            services
+                .iter()
+                .map(|&(_, ref data)| data.as_str())
+                .collect::<Vec<&str>>()
+                .join(",")
+        );
+        let mut service = HashMap::with_capacity(services.len());
+        for (service_group, data) in services {
+            let key = service_group.to_string();
+            let mut snapshot = self.service.remove(&key).unwrap_or_default();
+            snapshot.publish(data);
+            service.insert(key, snapshot);
+        }
+        self.service = service;
+        self.services.publish(list)
+    }
+}
+
+/// Census events published by the Manager for the clients of the `/events` stream.
+#[derive(Debug, Default)]
+pub struct EventLog {
+    history: Mutex<EventHistory>,
+    published: Condvar,
+    streams: AtomicUsize,
+}
+
+#[derive(Debug, Default)]
+struct EventHistory {
+    last_id: u64,
+    events: VecDeque<(u64, CensusEvent)>,
+}
+
+impl EventLog {
+    /// Record the given events and wake up every open event stream.
+    pub fn publish(&self, events: Vec<CensusEvent>) {
+        if events.is_empty() {
+            return;
+        }
+        let mut history = self.history.lock().expect("Event log lock is poisoned");
+        for event in events {
+            history.last_id += 1;
+            let id = history.last_id;
+            history.events.push_back((id, event));
+            if history.events.len() > EVENT_HISTORY {
+                history.events.pop_front();
+            }
+        }
+        self.published.notify_all();
+    }
+
+    /// Id of the most recently published event.
+    fn last_id(&self) -> u64 {
+        self.history
+            .lock()
+            .expect("Event log lock is poisoned")
+            .last_id
+    }
+
+    /// Returns the retained events published after the event with the given id, waiting up to
+    /// `timeout` for one to be published if there are none.
+    fn wait_since(&self, id: u64, timeout: Duration) -> Vec<(u64, CensusEvent)> {
+        let mut history = self.history.lock().expect("Event log lock is poisoned");
+        if history.last_id <= id {
+            history = self.published
+                .wait_timeout(history, timeout)
+                .expect("Event log lock is poisoned")
+                .0;
+        }
+        history
+            .events
+            .iter()
+            .filter(|&&(event_id, _)| event_id > id)
+            .cloned()
+            .collect()
+    }
+}
+
+/// Body of an `/events` response, writing census events as server-sent events until the client
+/// goes away.
+struct EventStream {
+    log: Arc<EventLog>,
+    service_group: Option<String>,
+    last_id: u64,
+}
+
+impl WriteBody for EventStream {
+    fn write_body(&mut self, res: &mut Write) -> io::Result<()> {
+        let keepalive = Duration::from_secs(EVENT_KEEPALIVE_SECS);
+        loop {
+            let mut written = false;
+            for (id, event) in self.log.wait_since(self.last_id, keepalive) {
+                self.last_id = id;
+                if let Some(ref sg) = self.service_group {
+                    if sg != event.service_group() {
+                        continue;
+                    }
+                }
+                let data = serde_json::to_string(&event)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                write!(res, "id: {}\nevent: {}\ndata: {}\n\n", id, event.name(), data)?;
+                written = true;
+            }
+            if !written {
+                res.write_all(b": keep-alive\n\n")?;
+            }
+            res.flush()?;
+        }
+    }
+}
+
+impl Drop for EventStream {
+    fn drop(&mut self) {
+        self.log.streams.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
 struct ManagerFs;
 
 impl typemap::Key for ManagerFs {
     type Value = manager::FsCfg;
 }
 
-pub struct Server(Iron<Chain>, ListenAddr);
+struct ManagerState;
+
+impl typemap::Key for ManagerState {
+    type Value = RwLock<GatewayState>;
+}
+
+struct ManagerEvents;
+
+impl typemap::Key for ManagerEvents {
+    type Value = EventLog;
+}
+
+/// Requires clients to present the gateway's auth token as a bearer token, if one is set.
+#[derive(Clone)]
+struct Authentication(Option<String>);
+
+impl Authentication {
+    /// Wrap the given handler so that it only runs for authenticated requests.
+    fn guard<H>(&self, handler: H) -> Chain
+    where
+        H: Handler,
+    {
+        let mut chain = Chain::new(handler);
+        chain.link_before(self.clone());
+        chain
+    }
+
+    fn authenticated(&self, req: &Request) -> bool {
+        let token = match self.0 {
+            Some(ref token) => token,
+            None => return true,
+        };
+        match req.headers.get::<headers::Authorization<headers::Bearer>>() {
+            Some(&headers::Authorization(ref bearer)) => {
+                crypto::util::fixed_time_eq(bearer.token.as_bytes(), token.as_bytes())
+            }
+            None => false,
+        }
+    }
+}
+
+impl BeforeMiddleware for Authentication {
+    fn before(&self, req: &mut Request) -> IronResult<()> {
+        if self.authenticated(req) {
+            return Ok(());
+        }
+        let mut response = Response::with(status::Unauthorized);
+        response
+            .headers
+            .set_raw("WWW-Authenticate", vec![b"Bearer".to_vec()]);
+        Err(IronError {
+            error: Box::new(Unauthorized),
+            response: response,
+        })
+    }
+}
+
+#[derive(Debug)]
+struct Unauthorized;
+
+impl fmt::Display for Unauthorized {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "missing or invalid bearer token")
+    }
+}
+
+impl error::Error for Unauthorized {
+    fn description(&self) -> &str {
+        "missing or invalid bearer token"
+    }
+}
+
+pub struct Server(Iron<Chain>, ListenAddr, Option<TlsConfig>);
 
 impl Server {
-    pub fn new(manager_state: Arc<manager::FsCfg>, listen_addr: ListenAddr) -> Self {
+    /// Create a new gateway. Health checks and the liveness and readiness probes are always
+    /// served anonymously, while every other endpoint requires the given `auth_token`, if any, to
+    /// be presented as a bearer token.
+    pub fn new(
+        manager_fs: Arc<manager::FsCfg>,
+        manager_state: Arc<RwLock<GatewayState>>,
+        manager_events: Arc<EventLog>,
+        listen_addr: ListenAddr,
+        auth_token: Option<String>,
+        tls: Option<TlsConfig>,
+    ) -> Self {
+        let auth = Authentication(auth_token);
         let router = router!(
-            doc: get "/" => doc,
-            butterfly: get "/butterfly" => butterfly,
-            census: get "/census" => census,
-            services: get "/services" => services,
-            service: get "/services/:svc/:group" => service,
-            service_org: get "/services/:svc/:group/:org" => service,
-            service_config: get "/services/:svc/:group/config" => config,
+            doc: get "/" => auth.guard(doc),
+            openapi: get "/openapi.json" => auth.guard(openapi),
+            butterfly: get "/butterfly" => auth.guard(butterfly),
+            census: get "/census" => auth.guard(census),
+            events: get "/events" => auth.guard(events),
+            metrics: get "/metrics" => auth.guard(metrics),
+            services: get "/services" => auth.guard(services),
+            service: get "/services/:svc/:group" => auth.guard(service),
+            service_org: get "/services/:svc/:group/:org" => auth.guard(service),
+            service_config: get "/services/:svc/:group/config" => auth.guard(config),
             service_health: get "/services/:svc/:group/health" => health,
-            service_config_org: get "/services/:svc/:group/:org/config" => config,
+            service_hooks: get "/services/:svc/:group/hooks" => auth.guard(hooks),
+            service_config_org: get "/services/:svc/:group/:org/config" => auth.guard(config),
             service_health_org: get "/services/:svc/:group/:org/health" => health,
+            service_hooks_org: get "/services/:svc/:group/:org/hooks" => auth.guard(hooks),
+            livez: get "/livez" => livez,
+            readyz: get "/readyz" => readyz,
         );
         let mut chain = Chain::new(router);
-        chain.link(persistent::Read::<ManagerFs>::both(manager_state));
-        Server(Iron::new(chain), listen_addr)
+        chain.link(persistent::Read::<ManagerFs>::both(manager_fs));
+        chain.link(persistent::Read::<ManagerState>::both(manager_state));
+        chain.link(persistent::Read::<ManagerEvents>::both(manager_events));
+        let mut iron = Iron::new(chain);
+        iron.threads += MAX_EVENT_STREAMS;
+        Server(iron, listen_addr, tls)
     }
 
     pub fn start(self) -> Result<JoinHandle<()>> {
+        let Server(iron, listen_addr, tls) = self;
+        let ssl = match tls {
+            Some(ref tls) => Some(tls.server()?),
+            None => None,
+        };
         let handle = thread::Builder::new()
             .name("http-gateway".to_string())
             .spawn(move || {
-                self.0
-                    .http(*self.1)
-                    .expect("unable to start http-gateway thread");
+                match ssl {
+                    Some(ssl) => iron.https(*listen_addr, ssl),
+                    None => iron.http(*listen_addr),
+                }.expect("unable to start http-gateway thread");
             })?;
         Ok(handle)
     }
@@ -151,44 +576,92 @@ struct HealthCheckBody {
 }
 
 fn butterfly(req: &mut Request) -> IronResult<Response> {
-    let state = req.get::<persistent::Read<ManagerFs>>().unwrap();
-    match File::open(&state.butterfly_data_path) {
-        Ok(file) => Ok(Response::with((
-            status::Ok,
-            Header(headers::ContentType::json()),
-            file,
-        ))),
-        Err(_) => Ok(Response::with(status::ServiceUnavailable)),
-    }
+    let state = req.get::<persistent::Read<ManagerState>>().unwrap();
+    let state = state.read().expect("Gateway state lock is poisoned");
+    Ok(snapshot_response(req, &state.butterfly))
 }
 
 fn census(req: &mut Request) -> IronResult<Response> {
-    let state = req.get::<persistent::Read<ManagerFs>>().unwrap();
-    match File::open(&state.census_data_path) {
-        Ok(file) => Ok(Response::with((
-            status::Ok,
-            Header(headers::ContentType::json()),
-            file,
-        ))),
-        Err(_) => Ok(Response::with(status::ServiceUnavailable)),
-    }
+    let state = req.get::<persistent::Read<ManagerState>>().unwrap();
+    let state = state.read().expect("Gateway state lock is poisoned");
+    Ok(snapshot_response(req, &state.census))
 }
 
 fn config(req: &mut Request) -> IronResult<Response> {
-    let state = req.get::<persistent::Read<ManagerFs>>().unwrap();
+    let state = req.get::<persistent::Read<ManagerState>>().unwrap();
     let service_group = match build_service_group(req) {
         Ok(sg) => sg,
         Err(_) => return Ok(Response::with(status::BadRequest)),
     };
-    match service_from_file(&service_group, &state.services_data_path) {
-        Ok(Some(service)) => Ok(Response::with((
-            status::Ok,
-            Header(headers::ContentType::json()),
-            service["cfg"].to_string(),
-        ))),
-        Ok(None) => Ok(Response::with(status::NotFound)),
-        Err(_) => Ok(Response::with(status::ServiceUnavailable)),
+    let state = state.read().expect("Gateway state lock is poisoned");
+    if !state.services.is_published() {
+        return Ok(Response::with(status::ServiceUnavailable));
     }
+    match state.service.get(&service_group.to_string()) {
+        Some(snapshot) => {
+            let service: Json = match serde_json::from_str(&snapshot.data()) {
+                Ok(service) => service,
+                Err(_) => return Ok(Response::with(status::InternalServerError)),
+            };
+            let cfg = service["cfg"].to_string();
+            // The config is only part of the service's snapshot, so it's tagged by its own
+            // content rather than by the snapshot's.
+            let etag = hash::hash_string(&cfg);
+            if not_modified(req, &etag) {
+                return Ok(Response::with((status::NotModified, etag_header(&etag))));
+            }
+            Ok(Response::with((
+                status::Ok,
+                Header(headers::ContentType::json()),
+                etag_header(&etag),
+                cfg,
+            )))
+        }
+        None => Ok(Response::with(status::NotFound)),
+    }
+}
+
+fn events(req: &mut Request) -> IronResult<Response> {
+    let log = req.get::<persistent::Read<ManagerEvents>>().unwrap();
+    let service_group = match req.url.query() {
+        Some(query) => form_urlencoded::parse(query.as_bytes())
+            .find(|&(ref key, _)| key == "service_group")
+            .map(|(_, value)| value.into_owned()),
+        None => None,
+    };
+    let service_group = match service_group {
+        Some(sg) => match ServiceGroup::from_str(&sg) {
+            Ok(sg) => Some(sg.to_string()),
+            Err(_) => return Ok(Response::with(status::BadRequest)),
+        },
+        None => None,
+    };
+    let last_id = match req.headers.get_raw("Last-Event-ID") {
+        Some(values) if !values.is_empty() => String::from_utf8_lossy(&values[0])
+            .trim()
+            .parse::<u64>()
+            .ok(),
+        _ => None,
+    };
+    if log.streams.fetch_add(1, Ordering::SeqCst) >= MAX_EVENT_STREAMS {
+        log.streams.fetch_sub(1, Ordering::SeqCst);
+        return Ok(Response::with(status::ServiceUnavailable));
+    }
+    let current_id = log.last_id();
+    let stream = EventStream {
+        log: log,
+        service_group: service_group,
+        last_id: last_id.map_or(current_id, |id| cmp::min(id, current_id)),
+    };
+    let mut response = Response::with(status::Ok);
+    response
+        .headers
+        .set_raw("Content-Type", vec![b"text/event-stream".to_vec()]);
+    response
+        .headers
+        .set(headers::CacheControl(vec![headers::CacheDirective::NoCache]));
+    response.body = Some(Box::new(stream));
+    Ok(response)
 }
 
 fn health(req: &mut Request) -> IronResult<Response> {
@@ -228,35 +701,116 @@ fn health(req: &mut Request) -> IronResult<Response> {
     }
 }
 
-fn service(req: &mut Request) -> IronResult<Response> {
-    let state = req.get::<persistent::Read<ManagerFs>>().unwrap();
+fn hooks(req: &mut Request) -> IronResult<Response> {
     let service_group = match build_service_group(req) {
         Ok(sg) => sg,
         Err(_) => return Ok(Response::with(status::BadRequest)),
     };
-    match service_from_file(&service_group, &state.services_data_path) {
-        Ok(Some(service)) => Ok(Response::with((
+    let hook = match req.url.query() {
+        Some(query) => form_urlencoded::parse(query.as_bytes())
+            .find(|&(ref key, _)| key == "hook")
+            .map(|(_, value)| value.into_owned()),
+        None => None,
+    };
+    let state = req.get::<persistent::Read<ManagerState>>().unwrap();
+    {
+        let state = state.read().expect("Gateway state lock is poisoned");
+        if !state.services.is_published() {
+            return Ok(Response::with(status::ServiceUnavailable));
+        }
+        if !state.service.contains_key(&service_group.to_string()) {
+            return Ok(Response::with(status::NotFound));
+        }
+    }
+    let history = HookHistory::new(fs::svc_logs_path(service_group.service()));
+    match history.read(hook.as_ref().map(String::as_str)) {
+        Ok(runs) => Ok(Response::with((
             status::Ok,
             Header(headers::ContentType::json()),
-            service.to_string(),
+            serde_json::to_string(&runs).unwrap(),
         ))),
-        Ok(None) => Ok(Response::with(status::NotFound)),
-        Err(_) => Ok(Response::with(status::ServiceUnavailable)),
+        Err(err) => {
+            warn!("Unable to read hook history of {}, {}", service_group, err);
+            Ok(Response::with(status::InternalServerError))
+        }
     }
 }
 
-fn services(req: &mut Request) -> IronResult<Response> {
-    let state = req.get::<persistent::Read<ManagerFs>>().unwrap();
-    match File::open(&state.services_data_path) {
-        Ok(file) => Ok(Response::with((
-            status::Ok,
-            Header(headers::ContentType::json()),
-            file,
-        ))),
-        Err(_) => Ok(Response::with(status::ServiceUnavailable)),
+fn livez(req: &mut Request) -> IronResult<Response> {
+    let state = req.get::<persistent::Read<ManagerState>>().unwrap();
+    let state = state.read().expect("Gateway state lock is poisoned");
+    let age = state.published_at.map(|at| at.elapsed());
+    let body = LivenessBody {
+        alive: age.map_or(false, |age| {
+            age < Duration::from_secs(LIVENESS_TIMEOUT_SECS)
+        }),
+        last_tick_secs: age.map(|age| age.as_secs()),
+    };
+    let http_status = if body.alive {
+        status::Ok
+    } else {
+        status::ServiceUnavailable
+    };
+    Ok(Response::with((
+        http_status,
+        Header(headers::ContentType::json()),
+        serde_json::to_string(&body).unwrap(),
+    )))
+}
+
+fn readyz(req: &mut Request) -> IronResult<Response> {
+    let state = req.get::<persistent::Read<ManagerState>>().unwrap();
+    let state = state.read().expect("Gateway state lock is poisoned");
+    let http_status = if state.readiness.ready {
+        status::Ok
+    } else {
+        status::ServiceUnavailable
+    };
+    Ok(Response::with((
+        http_status,
+        Header(headers::ContentType::json()),
+        serde_json::to_string(&state.readiness).unwrap(),
+    )))
+}
+
+fn metrics(_req: &mut Request) -> IronResult<Response> {
+    match metrics::encode() {
+        Ok((content_type, body)) => {
+            let mut response = Response::with((status::Ok, body));
+            response
+                .headers
+                .set_raw("Content-Type", vec![content_type.into_bytes()]);
+            Ok(response)
+        }
+        Err(err) => {
+            warn!("Unable to encode metrics, {}", err);
+            Ok(Response::with(status::InternalServerError))
+        }
     }
 }
 
+fn service(req: &mut Request) -> IronResult<Response> {
+    let state = req.get::<persistent::Read<ManagerState>>().unwrap();
+    let service_group = match build_service_group(req) {
+        Ok(sg) => sg,
+        Err(_) => return Ok(Response::with(status::BadRequest)),
+    };
+    let state = state.read().expect("Gateway state lock is poisoned");
+    if !state.services.is_published() {
+        return Ok(Response::with(status::ServiceUnavailable));
+    }
+    match state.service.get(&service_group.to_string()) {
+        Some(snapshot) => Ok(snapshot_response(req, snapshot)),
+        None => Ok(Response::with(status::NotFound)),
+    }
+}
+
+fn services(req: &mut Request) -> IronResult<Response> {
+    let state = req.get::<persistent::Read<ManagerState>>().unwrap();
+    let state = state.read().expect("Gateway state lock is poisoned");
+    Ok(snapshot_response(req, &state.services))
+}
+
 fn doc(_req: &mut Request) -> IronResult<Response> {
     Ok(Response::with((
         status::Ok,
@@ -265,6 +819,14 @@ fn doc(_req: &mut Request) -> IronResult<Response> {
     )))
 }
 
+fn openapi(_req: &mut Request) -> IronResult<Response> {
+    Ok(Response::with((
+        status::Ok,
+        Header(headers::ContentType::json()),
+        OPENAPI_DOC.as_str(),
+    )))
+}
+
 impl Into<Response> for HealthCheck {
     fn into(self) -> Response {
         let status: status::Status = self.into();
@@ -311,20 +873,392 @@ fn build_service_group(req: &mut Request) -> Result<ServiceGroup> {
     Ok(sg)
 }
 
-fn service_from_file<T>(
-    service_group: &ServiceGroup,
-    services_data_path: T,
-) -> result::Result<Option<Json>, io::Error>
-where
-    T: AsRef<Path>,
-{
-    match File::open(services_data_path) {
-        Ok(file) => match serde_json::from_reader(file) {
-            Ok(Json::Array(services)) => Ok(services
-                .into_iter()
-                .find(|s| s["service_group"] == service_group.as_ref())),
-            _ => Ok(None),
-        },
-        Err(err) => Err(err),
+/// Respond with the JSON content of the given snapshot, or with `304 Not Modified` if the client
+/// already holds the current version of it.
+fn snapshot_response(req: &Request, snapshot: &Snapshot) -> Response {
+    if !snapshot.is_published() {
+        return Response::with(status::ServiceUnavailable);
+    }
+    if not_modified(req, snapshot.etag()) {
+        return Response::with((status::NotModified, etag_header(snapshot.etag())));
+    }
+    Response::with((
+        status::Ok,
+        Header(headers::ContentType::json()),
+        etag_header(snapshot.etag()),
+        snapshot.data().to_string(),
+    ))
+}
+
+/// Returns `true` if the `If-None-Match` header of the request matches the given entity tag.
+fn not_modified(req: &Request, etag: &str) -> bool {
+    let etag = headers::EntityTag::strong(etag.to_string());
+    match req.headers.get::<headers::IfNoneMatch>() {
+        Some(&headers::IfNoneMatch::Any) => true,
+        Some(&headers::IfNoneMatch::Items(ref tags)) => tags.iter().any(|tag| tag.weak_eq(&etag)),
+        None => false,
+    }
+}
+
+fn etag_header(etag: &str) -> Header<headers::ETag> {
+    Header(headers::ETag(headers::EntityTag::strong(etag.to_string())))
+}
+
+#[cfg(test)]
+pub mod test {
+    use std::net::{TcpListener, TcpStream};
+
+    use butterfly;
+    use butterfly::member::Member;
+    use butterfly::rumor::service::{Service as ServiceRumor, SysInfo};
+    use butterfly::server::Suitability;
+    use butterfly::trace::Trace;
+    use hcore::package::PackageIdent;
+    use hyper;
+    use serde::Serialize;
+    use tempdir::TempDir;
+    use valico::json_schema;
+
+    use census::CensusRing;
+    use manager::service::HookExecution;
+
+    use super::*;
+
+    /// Asserts that `value` is valid according to the named schema of the OpenAPI document. Any
+    /// property which the schema does not list fails the assertion, so that a field added to a
+    /// type but not to the document is caught.
+    pub fn assert_valid_schema<T>(name: &str, value: &T)
+    where
+        T: Serialize,
+    {
+        let openapi: Json = serde_json::from_str(OPENAPI).unwrap();
+        let mut schema = Json::Null;
+        schema["$ref"] = Json::String(format!("#/components/schemas/{}", name));
+        schema["components"] = openapi["components"].clone();
+        forbid_unlisted_properties(&mut schema);
+        let mut scope = json_schema::scope::Scope::new();
+        let schema = scope
+            .compile_and_return(schema, false)
+            .expect("Could not compile the schema");
+        let value = serde_json::to_value(value).unwrap();
+        let result = schema.validate(&value);
+        assert!(
+            result.is_valid(),
+            "{} does not validate against the {} schema: {:?}",
+            value,
+            name,
+            result.errors
+        );
+    }
+
+    /// Sets `additionalProperties` to false on every object schema which lists its properties and
+    /// does not say what other properties may hold.
+    fn forbid_unlisted_properties(schema: &mut Json) {
+        match *schema {
+            Json::Object(ref mut map) => {
+                if map.contains_key("properties") && !map.contains_key("additionalProperties") {
+                    map.insert("additionalProperties".to_string(), Json::Bool(false));
+                }
+                for value in map.values_mut() {
+                    forbid_unlisted_properties(value);
+                }
+            }
+            Json::Array(ref mut values) => {
+                for value in values.iter_mut() {
+                    forbid_unlisted_properties(value);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    #[test]
+    fn schemas_reject_unlisted_properties() {
+        let mut liveness = serde_json::to_value(&LivenessBody {
+            alive: true,
+            last_tick_secs: None,
+        }).unwrap();
+        liveness["uptime"] = Json::from(1);
+        let openapi: Json = serde_json::from_str(OPENAPI).unwrap();
+        let mut schema = openapi["components"]["schemas"]["liveness"].clone();
+        forbid_unlisted_properties(&mut schema);
+        let mut scope = json_schema::scope::Scope::new();
+        let schema = scope.compile_and_return(schema, false).unwrap();
+        assert!(!schema.validate(&liveness).is_valid());
+    }
+
+    /// Starts a gateway on a free local port, requiring the given auth token if any, and returns
+    /// its URL.
+    fn start_gateway(auth_token: Option<&str>) -> String {
+        let port = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let fs_root = TempDir::new("http-gateway").unwrap();
+        Server::new(
+            Arc::new(manager::FsCfg::new(fs_root.path())),
+            Arc::new(RwLock::new(GatewayState::default())),
+            Arc::new(EventLog::default()),
+            ListenAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), port),
+            auth_token.map(str::to_string),
+            None,
+        ).start()
+            .unwrap();
+        for _ in 0..100 {
+            if TcpStream::connect(("127.0.0.1", port)).is_ok() {
+                break;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        format!("http://127.0.0.1:{}", port)
+    }
+
+    /// Requests the given path from a gateway, presenting the given bearer token if any.
+    fn get(url: &str, path: &str, token: Option<&str>) -> hyper::client::Response {
+        let client = hyper::Client::new();
+        let mut request = client.get(&format!("{}{}", url, path));
+        if let Some(token) = token {
+            request = request.header(headers::Authorization(headers::Bearer {
+                token: token.to_string(),
+            }));
+        }
+        request.send().unwrap()
+    }
+
+    #[test]
+    fn gateway_rejects_missing_or_wrong_token() {
+        let url = start_gateway(Some("letmein"));
+
+        let response = get(&url, "/services", None);
+        assert_eq!(response.status, status::Unauthorized);
+        assert_eq!(
+            response.headers.get_raw("WWW-Authenticate"),
+            Some(&[b"Bearer".to_vec()][..])
+        );
+        assert_eq!(
+            get(&url, "/services", Some("letmeout")).status,
+            status::Unauthorized
+        );
+        assert_eq!(get(&url, "/census", Some("")).status, status::Unauthorized);
+        assert_ne!(
+            get(&url, "/services", Some("letmein")).status,
+            status::Unauthorized
+        );
+    }
+
+    #[test]
+    fn gateway_serves_probes_anonymously() {
+        let url = start_gateway(Some("letmein"));
+        for path in &["/livez", "/readyz", "/services/redis/default/health"] {
+            assert_ne!(get(&url, path, None).status, status::Unauthorized, "{}", path);
+        }
+    }
+
+    #[test]
+    fn gateway_without_token_is_open() {
+        let url = start_gateway(None);
+        assert_ne!(get(&url, "/services", None).status, status::Unauthorized);
+    }
+
+    #[test]
+    fn snapshot_publish_changes_version() {
+        let mut snapshot = Snapshot::default();
+        assert!(!snapshot.is_published());
+
+        assert!(snapshot.publish("{}".to_string()));
+        let etag = snapshot.etag().to_string();
+        assert_eq!(snapshot.version(), 1);
+
+        assert!(!snapshot.publish("{}".to_string()));
+        assert_eq!(snapshot.version(), 1);
+        assert_eq!(snapshot.etag(), etag);
+
+        assert!(snapshot.publish("{\"a\":1}".to_string()));
+        assert_eq!(snapshot.version(), 2);
+        assert_ne!(snapshot.etag(), etag);
+    }
+
+    #[test]
+    fn event_log_wait_since() {
+        let log = EventLog::default();
+        let timeout = Duration::from_millis(10);
+        assert!(log.wait_since(0, timeout).is_empty());
+
+        log.publish(vec![
+            CensusEvent::ConfigChanged {
+                service_group: "redis.default".to_string(),
+                incarnation: 1,
+            },
+            CensusEvent::ConfigChanged {
+                service_group: "redis.default".to_string(),
+                incarnation: 2,
+            },
+        ]);
+        assert_eq!(log.last_id(), 2);
+        assert_eq!(log.wait_since(0, timeout).len(), 2);
+        let events = log.wait_since(1, timeout);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].0, 2);
+        assert!(log.wait_since(2, timeout).is_empty());
+    }
+
+    #[test]
+    fn gateway_state_publish_services() {
+        let redis = ServiceGroup::new(None, "redis", "default", None).unwrap();
+        let web = ServiceGroup::new(None, "web", "default", None).unwrap();
+        let mut state = GatewayState::default();
+
+        assert!(state.publish_services(vec![
+            (redis.clone(), "{\"a\":1}".to_string()),
+            (web.clone(), "{\"b\":2}".to_string()),
+        ]));
+        assert_eq!(*state.services.data(), "[{\"a\":1},{\"b\":2}]");
+        assert_eq!(*state.service["redis.default"].data(), "{\"a\":1}");
+
+        assert!(state.publish_services(vec![(redis.clone(), "{\"a\":1}".to_string())]));
+        assert_eq!(state.service["redis.default"].version(), 1);
+        assert!(!state.service.contains_key("web.default"));
+        assert!(!state.publish_services(vec![(redis, "{\"a\":1}".to_string())]));
+    }
+
+    #[test]
+    fn readiness_of_policy() {
+        let redis = ServiceGroup::new(None, "redis", "default", None).unwrap();
+        let web = ServiceGroup::new(None, "web", "default", None).unwrap();
+        let cache = ServiceGroup::new(None, "cache", "default", None).unwrap();
+        let services = vec![
+            ServiceReadiness::new(&redis, true, HealthCheck::Warning),
+            ServiceReadiness::new(&web, false, HealthCheck::Ok),
+        ];
+
+        let readiness = Readiness::new(&ReadinessPolicy::AllServices, services.clone());
+        assert!(!readiness.ready);
+        assert_eq!(readiness.services.len(), 2);
+        assert!(readiness.services[0].ready);
+        assert!(!readiness.services[1].ready);
+
+        let policy = ReadinessPolicy::Services(vec![redis.clone()]);
+        let readiness = Readiness::new(&policy, services.clone());
+        assert!(readiness.ready);
+        assert_eq!(readiness.services.len(), 1);
+
+        let policy = ReadinessPolicy::Services(vec![redis, cache]);
+        let readiness = Readiness::new(&policy, services);
+        assert!(!readiness.ready);
+        assert_eq!(readiness.services[1].service_group, "cache.default");
+        assert!(!readiness.services[1].loaded);
+
+        assert!(Readiness::new(&ReadinessPolicy::AllServices, vec![]).ready);
+    }
+
+    #[test]
+    fn openapi_document() {
+        let doc: Json = serde_json::from_str(&OPENAPI_DOC).unwrap();
+        assert_eq!(doc["info"]["x-supervisor-version"], VERSION);
+        for path in &[
+            "/",
+            "/openapi.json",
+            "/butterfly",
+            "/census",
+            "/events",
+            "/livez",
+            "/readyz",
+            "/metrics",
+            "/services",
+            "/services/{svc}/{group}",
+            "/services/{svc}/{group}/{org}",
+            "/services/{svc}/{group}/config",
+            "/services/{svc}/{group}/{org}/config",
+            "/services/{svc}/{group}/health",
+            "/services/{svc}/{group}/{org}/health",
+            "/services/{svc}/{group}/hooks",
+            "/services/{svc}/{group}/{org}/hooks",
+        ] {
+            assert!(doc["paths"][path]["get"].is_object(), "{} is undocumented", path);
+        }
+    }
+
+    #[derive(Debug)]
+    struct ZeroSuitability;
+
+    impl Suitability for ZeroSuitability {
+        fn get(&self, _service_group: &ServiceGroup) -> u64 {
+            0
+        }
+    }
+
+    #[test]
+    fn openapi_schemas_match_responses() {
+        let sg = ServiceGroup::new(None, "redis", "default", None).unwrap();
+        let readiness = Readiness::new(
+            &ReadinessPolicy::Services(vec![sg.clone()]),
+            vec![ServiceReadiness::new(&sg, true, HealthCheck::Ok)],
+        );
+        assert_valid_schema("readiness", &readiness);
+        assert_valid_schema(
+            "readiness",
+            &Readiness::new(&ReadinessPolicy::Services(vec![sg.clone()]), vec![]),
+        );
+        assert_valid_schema(
+            "liveness",
+            &LivenessBody {
+                alive: true,
+                last_tick_secs: Some(1),
+            },
+        );
+        assert_valid_schema(
+            "healthCheckOutput",
+            &HealthCheckBody {
+                status: HealthCheck::Warning.to_string(),
+                stdout: "disk almost full".to_string(),
+                stderr: String::new(),
+            },
+        );
+        assert_valid_schema(
+            "hookRun",
+            &HookExecution {
+                hook: "reconfigure".to_string(),
+                started_at: 1519905600,
+                duration_ms: 250,
+                exit_code: None,
+                timed_out: true,
+                stdout: String::new(),
+                stderr: "waiting for lock".to_string(),
+            },
+        );
+        assert_valid_schema(
+            "censusEvent",
+            &CensusEvent::LeaderChanged {
+                service_group: sg.to_string(),
+                leader_id: None,
+            },
+        );
+        assert_valid_schema(
+            "censusEvent",
+            &CensusEvent::FileChanged {
+                service_group: sg.to_string(),
+                filename: "ca.pem".to_string(),
+                incarnation: 2,
+            },
+        );
+        assert_valid_schema("census", &CensusRing::new("member"));
+
+        let butterfly = butterfly::Server::new(
+            "127.0.0.1:0",
+            "127.0.0.1:0",
+            Member::default(),
+            Trace::default(),
+            None,
+            None,
+            None::<PathBuf>,
+            Box::new(ZeroSuitability),
+        ).unwrap();
+        let ident = PackageIdent::new("core", "redis", Some("4.0.8"), Some("20180301000000"));
+        let mut sys_info = SysInfo::new();
+        sys_info.set_ip("1.2.3.4".to_string());
+        sys_info.set_hostname("hostname".to_string());
+        butterfly.insert_service(ServiceRumor::new("member-a", &ident, &sg, &sys_info, None));
+        assert_valid_schema("butterfly", &butterfly);
     }
 }
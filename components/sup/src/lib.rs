@@ -62,6 +62,8 @@ extern crate habitat_eventsrv_client as eventsrv_client;
 extern crate habitat_launcher_client as launcher_client;
 extern crate habitat_sup_protocol as protocol;
 extern crate handlebars;
+extern crate hyper;
+extern crate hyper_openssl;
 extern crate iron;
 #[macro_use]
 extern crate lazy_static;
@@ -69,7 +71,10 @@ extern crate libc;
 #[macro_use]
 extern crate log;
 extern crate notify;
+extern crate openssl;
 extern crate persistent;
+#[macro_use]
+extern crate prometheus;
 extern crate prost;
 extern crate protobuf;
 extern crate rand;
@@ -90,6 +95,9 @@ extern crate tokio;
 #[macro_use]
 extern crate tokio_core;
 extern crate tokio_io;
+extern crate tokio_openssl;
+#[cfg(unix)]
+extern crate tokio_uds;
 extern crate toml;
 extern crate url;
 extern crate valico;
@@ -114,6 +122,7 @@ pub mod error;
 pub mod fs;
 pub mod http_gateway;
 pub mod manager;
+pub mod metrics;
 mod sys;
 pub mod templating;
 pub mod util;
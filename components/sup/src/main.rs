@@ -32,9 +32,10 @@ extern crate tokio_core;
 extern crate url;
 
 use std::env;
-use std::io::{self, Write};
+use std::fs::File;
+use std::io::{self, Read, Write};
 use std::net::{SocketAddr, ToSocketAddrs};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process;
 use std::result;
 use std::str::{self, FromStr};
@@ -56,6 +57,7 @@ use url::Url;
 
 use sup::command;
 use sup::config::{GossipListenAddr, GOSSIP_DEFAULT_PORT};
+use sup::ctl_gateway;
 use sup::error::{Error, Result, SupError};
 use sup::feat;
 use sup::http_gateway;
@@ -147,8 +149,37 @@ fn cli<'a, 'b>() -> App<'a, 'b> {
                 "The listen address for the gossip system [default: 0.0.0.0:9638]")
             (@arg LISTEN_HTTP: --("listen-http") +takes_value {valid_socket_addr}
                 "The listen address for the HTTP Gateway [default: 0.0.0.0:9631]")
+            (@arg HTTP_TLS_CERT: --("http-tls-cert") +takes_value requires[HTTP_TLS_KEY]
+                {file_exists}
+                "Path to a PEM encoded certificate chain used to serve the HTTP Gateway over \
+                HTTPS; [default: not set].")
+            (@arg HTTP_TLS_KEY: --("http-tls-key") +takes_value requires[HTTP_TLS_CERT]
+                {file_exists}
+                "Path to the PEM encoded private key of the HTTP Gateway's certificate; \
+                [default: not set].")
+            (@arg HTTP_AUTH_TOKEN_FILE: --("http-auth-token-file") +takes_value {file_exists}
+                "Path to a file holding the bearer token clients must present to the HTTP \
+                Gateway, instead of the HAB_SUP_GATEWAY_AUTH_TOKEN environment variable; \
+                [default: not set].")
+            (@arg READY_SERVICES: --("ready-services") +takes_value +multiple
+                {valid_service_group}
+                "Service groups which must be ready for the HTTP Gateway's /readyz endpoint to \
+                report the Supervisor as ready [default: all loaded services].")
             (@arg LISTEN_CTL: --("listen-ctl") +takes_value {valid_socket_addr}
                 "The listen address for the Control Gateway [default: 127.0.0.1:9632]")
+            (@arg LISTEN_CTL_UNIX: --("listen-ctl-unix")
+                "Also listen for Control Gateway connections on a Unix domain socket in the \
+                Supervisor's state directory; [default: false].")
+            (@arg NO_LISTEN_CTL_TCP: --("no-listen-ctl-tcp") requires[LISTEN_CTL_UNIX]
+                "Only accept Control Gateway connections on the Unix domain socket")
+            (@arg CTL_TLS_CERT: --("ctl-tls-cert") +takes_value requires[CTL_TLS_KEY]
+                {file_exists}
+                "Path to a PEM encoded certificate chain used to encrypt Control Gateway \
+                connections; [default: not set].")
+            (@arg CTL_TLS_KEY: --("ctl-tls-key") +takes_value requires[CTL_TLS_CERT]
+                {file_exists}
+                "Path to the PEM encoded private key of the Control Gateway's certificate; \
+                [default: not set].")
             (@arg NAME: --("override-name") +takes_value
                 "The name of the Supervisor if launching more than one [default: default]")
             (@arg ORGANIZATION: --org +takes_value
@@ -289,10 +320,48 @@ fn mgrcfg_from_matches(m: &ArgMatches) -> Result<ManagerConfig> {
     if let Some(addr_str) = m.value_of("LISTEN_HTTP") {
         cfg.http_listen = http_gateway::ListenAddr::from_str(addr_str)?;
     }
+    if let (Some(cert), Some(key)) = (m.value_of("HTTP_TLS_CERT"), m.value_of("HTTP_TLS_KEY")) {
+        cfg.http_tls = Some(http_gateway::TlsConfig {
+            cert_path: PathBuf::from(cert),
+            key_path: PathBuf::from(key),
+        });
+    }
+    if let Some(groups) = m.values_of("READY_SERVICES") {
+        let mut services = Vec::new();
+        for group in groups {
+            services.push(hcore::service::ServiceGroup::from_str(group)?);
+        }
+        cfg.readiness_policy = http_gateway::ReadinessPolicy::Services(services);
+    }
+    let http_auth_token = match m.value_of("HTTP_AUTH_TOKEN_FILE") {
+        Some(path) => {
+            let mut token = String::new();
+            File::open(path)?.read_to_string(&mut token)?;
+            Some(token.trim().to_string())
+        }
+        None => henv::var(http_gateway::AUTH_TOKEN_ENVVAR).ok(),
+    };
+    // The token is only needed by the gateway, so it's kept from the hooks the Supervisor runs.
+    env::remove_var(http_gateway::AUTH_TOKEN_ENVVAR);
+    cfg.http_auth_token = http_auth_token.and_then(|token| {
+        if token.is_empty() {
+            None
+        } else {
+            Some(token)
+        }
+    });
     if let Some(addr_str) = m.value_of("LISTEN_CTL") {
         cfg.ctl_listen =
             SocketAddr::from_str(addr_str).unwrap_or_else(|_err| protocol::ctl::default_addr());
     }
+    cfg.ctl_listen_unix = m.is_present("LISTEN_CTL_UNIX");
+    cfg.ctl_listen_tcp = !m.is_present("NO_LISTEN_CTL_TCP");
+    if let (Some(cert), Some(key)) = (m.value_of("CTL_TLS_CERT"), m.value_of("CTL_TLS_KEY")) {
+        cfg.ctl_tls = Some(ctl_gateway::TlsConfig {
+            cert_path: PathBuf::from(cert),
+            key_path: PathBuf::from(key),
+        });
+    }
     if let Some(name_str) = m.value_of("NAME") {
         cfg.name = Some(String::from(name_str));
         outputln!("");
@@ -465,6 +534,14 @@ fn get_password_from_input(_m: &ArgMatches) -> Result<Option<String>> {
 // CLAP Validation Functions
 ////////////////////////////////////////////////////////////////////////
 
+fn file_exists(val: String) -> result::Result<(), String> {
+    if Path::new(&val).is_file() {
+        Ok(())
+    } else {
+        Err(format!("File: '{}' cannot be found", &val))
+    }
+}
+
 fn dir_exists(val: String) -> result::Result<(), String> {
     if Path::new(&val).is_dir() {
         Ok(())
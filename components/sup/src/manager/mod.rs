@@ -29,7 +29,7 @@ use std;
 use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::fs::{self, File, OpenOptions};
-use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::io::{BufRead, BufReader, Read, Write};
 use std::mem;
 use std::net::SocketAddr;
 use std::ops::DerefMut;
@@ -38,7 +38,8 @@ use std::rc::Rc;
 use std::result;
 use std::str::FromStr;
 use std::sync::{Arc, RwLock};
-use std::time::Duration;
+use std::thread;
+use std::time::{Duration, Instant};
 
 use butterfly;
 use butterfly::member::Member;
@@ -56,6 +57,9 @@ use hcore::os::process::{self, Pid, Signal};
 use hcore::package::metadata::PackageType;
 use hcore::package::{Identifiable, PackageIdent, PackageInstall};
 use hcore::service::ServiceGroup;
+use hcore::util::deserialize_using_from_str;
+#[cfg(unix)]
+use launcher_client::signal_from_name;
 use launcher_client::{LauncherCli, LAUNCHER_LOCK_CLEAN_ENV, LAUNCHER_PID_ENV};
 use protocol;
 use protocol::net::{self, ErrCode, NetResult};
@@ -69,16 +73,18 @@ use self::peer_watcher::PeerWatcher;
 use self::self_updater::{SelfUpdater, SUP_PKG_IDENT};
 pub use self::service::{CompositeSpec, Service, ServiceBind, ServiceSpec, Spec, Topology,
                         UpdateStrategy};
-use self::service::{DesiredState, IntoServiceSpec, Pkg, ProcessState};
+use self::service::{DesiredState, ElectionRole, HealthCheck, IntoServiceSpec, Pkg, ProcessState,
+                    ProcessStats, UpdateState};
 use self::service_updater::ServiceUpdater;
 use self::spec_watcher::{SpecWatcher, SpecWatcherEvent};
 pub use self::sys::Sys;
 use self::user_config_watcher::UserConfigWatcher;
-use census::CensusRing;
+use census::{CensusRing, CensusSummary};
 use config::GossipListenAddr;
 use ctl_gateway::{self, CtlRequest};
 use error::{Error, Result, SupError};
-use http_gateway;
+use http_gateway::{self, EventLog, GatewayState, Readiness, ServiceReadiness};
+use metrics;
 use util;
 use VERSION;
 
@@ -106,7 +112,7 @@ pub struct FsCfg {
 }
 
 impl FsCfg {
-    fn new<T>(sup_root: T) -> Self
+    pub fn new<T>(sup_root: T) -> Self
     where
         T: Into<PathBuf>,
     {
@@ -140,7 +146,17 @@ pub struct ManagerConfig {
     pub update_channel: String,
     pub gossip_listen: GossipListenAddr,
     pub ctl_listen: SocketAddr,
+    /// Whether the CtlGateway accepts connections on `ctl_listen`.
+    pub ctl_listen_tcp: bool,
+    /// Whether the CtlGateway accepts connections on a Unix domain socket in the state directory.
+    pub ctl_listen_unix: bool,
+    pub ctl_tls: Option<ctl_gateway::TlsConfig>,
     pub http_listen: http_gateway::ListenAddr,
+    /// Token which clients must present to the HTTP gateway, except for health checks.
+    pub http_auth_token: Option<String>,
+    pub http_tls: Option<http_gateway::TlsConfig>,
+    /// Services which must be ready for the HTTP gateway to report the Supervisor as ready.
+    pub readiness_policy: http_gateway::ReadinessPolicy,
     pub gossip_peers: Vec<SocketAddr>,
     pub gossip_permanent: bool,
     pub ring_key: Option<SymKey>,
@@ -165,7 +181,13 @@ impl Default for ManagerConfig {
             update_channel: "".to_string(),
             gossip_listen: GossipListenAddr::default(),
             ctl_listen: protocol::ctl::default_addr(),
+            ctl_listen_tcp: true,
+            ctl_listen_unix: false,
+            ctl_tls: None,
             http_listen: http_gateway::ListenAddr::default(),
+            http_auth_token: None,
+            http_tls: None,
+            readiness_policy: http_gateway::ReadinessPolicy::default(),
             gossip_peers: vec![],
             gossip_permanent: false,
             ring_key: None,
@@ -189,7 +211,9 @@ pub struct Manager {
     census_ring: CensusRing,
     events_group: Option<ServiceGroup>,
     fs_cfg: Arc<FsCfg>,
-    launcher: LauncherCli,
+    launcher: Arc<LauncherCli>,
+    /// Threads stopping removed services, by the name of the service.
+    stopping_services: HashMap<String, thread::JoinHandle<()>>,
     updater: ServiceUpdater,
     peer_watcher: Option<PeerWatcher>,
     spec_watcher: SpecWatcher,
@@ -197,6 +221,12 @@ pub struct Manager {
     organization: Option<String>,
     self_updater: Option<SelfUpdater>,
     service_states: HashMap<PackageIdent, Timespec>,
+    /// Serialized state of the services which are watched for changes but aren't running.
+    inactive_services: Vec<(ServiceGroup, String)>,
+    gateway_state: Arc<RwLock<GatewayState>>,
+    gateway_events: Arc<EventLog>,
+    /// The census as of the last events published to the HTTP gateway.
+    census_summary: CensusSummary,
     sys: Arc<Sys>,
 }
 
@@ -269,27 +299,28 @@ impl Manager {
         req: &mut CtlRequest,
         opts: protocol::ctl::SvcStatus,
     ) -> NetResult<()> {
-        let statuses = Self::status(&mgr.cfg)?;
+        let statuses: Vec<(PackageIdent, protocol::types::ServiceStatus)> =
+            Self::status(&mgr.cfg)?
+                .into_iter()
+                .map(|status| (status.pkg.ident.clone(), status.into()))
+                .collect();
         if statuses.is_empty() {
             req.reply_complete(net::ok());
             return Ok(());
         }
         if let Some(ident) = opts.ident {
-            for status in statuses {
-                if status.pkg.ident.satisfies(&ident) {
-                    let mut msg: protocol::types::ServiceStatus = status.into();
-                    req.reply_complete(msg);
-                    break;
+            match statuses.into_iter().find(|&(ref i, _)| i.satisfies(&ident)) {
+                Some((_, msg)) => req.reply_complete(msg),
+                None => {
+                    return Err(net::err(
+                        ErrCode::NotFound,
+                        format!("Service not loaded, {}", ident),
+                    ))
                 }
             }
-            return Err(net::err(
-                ErrCode::NotFound,
-                format!("Service not loaded, {}", ident),
-            ));
         } else {
             let mut list = statuses.into_iter().peekable();
-            while let Some(status) = list.next() {
-                let mut msg: protocol::types::ServiceStatus = status.into();
+            while let Some((_, msg)) = list.next() {
                 if list.peek().is_some() {
                     req.reply_partial(msg);
                 } else {
@@ -410,13 +441,18 @@ impl Manager {
             census_ring: CensusRing::new(sys.member_id.clone()),
             butterfly: server,
             events_group: cfg.eventsrv_group,
-            launcher: launcher,
+            launcher: Arc::new(launcher),
+            stopping_services: HashMap::new(),
             peer_watcher: peer_watcher,
             spec_watcher: SpecWatcher::run(&fs_cfg.specs_path)?,
             user_config_watcher: UserConfigWatcher::new(),
             fs_cfg: Arc::new(fs_cfg),
             organization: cfg.organization,
             service_states: HashMap::new(),
+            inactive_services: Vec::new(),
+            gateway_state: Arc::new(RwLock::new(GatewayState::default())),
+            gateway_events: Arc::new(EventLog::default()),
+            census_summary: CensusSummary::default(),
             sys: Arc::new(sys),
         })
     }
@@ -597,6 +633,11 @@ impl Manager {
     }
 
     fn add_service(&mut self, spec: ServiceSpec) {
+        // A service which is started again while its previous process is still stopping waits for
+        // it, so that the two don't run at the same time.
+        if let Some(stopping) = self.stopping_services.remove(&spec.ident.name) {
+            stopping.join().ok();
+        }
         outputln!("Starting {}", &spec.ident);
         // JW TODO: This clone sucks, but our data structures are a bit messy here. What we really
         // want is the service to hold the spec and, on failure, return an error with the spec
@@ -674,15 +715,50 @@ impl Manager {
         );
         self.butterfly.start(Timing::default())?;
         debug!("gossip-listener started");
-        self.persist_state();
+        self.refresh_inactive_services();
+        self.publish_state();
         let http_listen_addr = self.sys.http_listen();
         let ctl_listen_addr = self.sys.ctl_listen();
         let ctl_secret_key = ctl_gateway::readgen_secret_key(&self.fs_cfg.sup_root)?;
-        outputln!("Starting ctl-gateway on {}", &ctl_listen_addr);
-        ctl_gateway::server::run(ctl_listen_addr, ctl_secret_key, ctl_tx);
+        let ctl_tls = match self.state.cfg.ctl_tls {
+            Some(ref tls) => Some(tls.acceptor()?),
+            None => None,
+        };
+        let ctl_listen_addr = if self.state.cfg.ctl_listen_tcp {
+            if ctl_tls.is_some() {
+                outputln!("Starting ctl-gateway on {} with TLS", &ctl_listen_addr);
+            } else {
+                outputln!("Starting ctl-gateway on {}", &ctl_listen_addr);
+            }
+            Some(ctl_listen_addr)
+        } else {
+            None
+        };
+        let ctl_socket_path = if self.state.cfg.ctl_listen_unix {
+            let path = protocol::ctl_socket_path(&self.fs_cfg.sup_root);
+            outputln!("Starting ctl-gateway on {}", path.display());
+            Some(path)
+        } else {
+            None
+        };
+        ctl_gateway::server::run(
+            ctl_listen_addr,
+            ctl_socket_path,
+            ctl_secret_key,
+            self.fs_cfg.sup_root.clone(),
+            ctl_tls,
+            ctl_tx,
+        );
         debug!("ctl-gateway started");
         outputln!("Starting http-gateway on {}", &http_listen_addr);
-        http_gateway::Server::new(self.fs_cfg.clone(), http_listen_addr).start()?;
+        http_gateway::Server::new(
+            self.fs_cfg.clone(),
+            self.gateway_state.clone(),
+            self.gateway_events.clone(),
+            http_listen_addr,
+            self.state.cfg.http_auth_token.clone(),
+            self.state.cfg.http_tls.clone(),
+        ).start()?;
         debug!("http-gateway started");
         let events = match self.events_group {
             Some(ref evg) => Some(events::EventsMgr::start(evg.clone())),
@@ -720,12 +796,13 @@ impl Manager {
                 &self.butterfly.service_file_store,
             );
 
-            if self.check_for_changed_services() {
-                self.persist_state();
-            }
+            self.refresh_inactive_services();
 
             if self.census_ring.changed() {
-                self.persist_state();
+                let summary = self.census_ring.summary();
+                self.gateway_events
+                    .publish(summary.events_since(&self.census_summary));
+                self.census_summary = summary;
                 events
                     .as_ref()
                     .map(|events| events.try_connect(&self.census_ring));
@@ -757,7 +834,10 @@ impl Manager {
                 if service.tick(&self.census_ring, &self.launcher) {
                     self.gossip_latest_service_rumor(&service);
                 }
+                service.observe_metrics();
             }
+            metrics::observe_butterfly(&self.butterfly);
+            self.publish_state();
             let time_to_wait = ((next_check - time::get_time()).num_milliseconds()).max(100);
             core.turn(Some(Duration::from_millis(time_to_wait as u64)));
         }
@@ -932,6 +1012,123 @@ impl Manager {
         mgr: &ManagerState,
         req: &mut CtlRequest,
         opts: protocol::ctl::SvcLoad,
+    ) -> NetResult<()> {
+        Self::load_service(mgr, req, opts)?;
+        req.reply_complete(net::ok());
+        Ok(())
+    }
+
+    /// Reconcile the loaded services with the services listed in the request, loading each
+    /// listed service which isn't loaded and rewriting the spec of each loaded service whose
+    /// spec differs from the one described by the request. Every listed service is validated
+    /// before any change is applied. Services which belong to a composite are left alone when
+    /// unloading unlisted services.
+    pub fn service_apply(
+        mgr: &ManagerState,
+        req: &mut CtlRequest,
+        opts: protocol::ctl::SvcApply,
+    ) -> NetResult<()> {
+        let dry_run = opts.dry_run.unwrap_or(false);
+        let mut listed = HashSet::new();
+        let mut loads = vec![];
+        let mut updates = vec![];
+        for mut svc in opts.services {
+            let ident: PackageIdent = svc.ident.clone().ok_or(err_update_client())?.into();
+            if !listed.insert(ident.name.clone()) {
+                return Err(net::err(
+                    ErrCode::InvalidPayload,
+                    format!("Service listed more than once, {}", ident.name),
+                ));
+            }
+            // The desired spec is described by the request alone, so anything left out of it
+            // is reset rather than carried over from the current spec.
+            let mut desired = ServiceSpec::default_for(ident.clone());
+            svc.into_spec(&mut desired);
+            match Self::existing_specs_for_ident(&mgr.cfg, &ident)? {
+                None => {
+                    req.info(format!("Load {}", ident))?;
+                    Self::validate_applied_spec(req, &desired, dry_run)?;
+                    loads.push(svc);
+                }
+                Some(Spec::Service(current)) => {
+                    desired.desired_state = current.desired_state.clone();
+                    let changes = current.diff(&desired);
+                    if changes.is_empty() {
+                        req.info(format!("The {} service is up to date", current.ident))?;
+                        continue;
+                    }
+                    req.info(format!("Update {}", current.ident))?;
+                    for change in changes {
+                        req.info(format!("    {}", change))?;
+                    }
+                    Self::validate_applied_spec(req, &desired, dry_run)?;
+                    updates.push(desired);
+                }
+                Some(Spec::Composite(composite_spec, _)) => {
+                    req.info(format!("Update composite {}", composite_spec.ident()))?;
+                    svc.force = Some(true);
+                    loads.push(svc);
+                }
+            }
+        }
+        let mut unloads = vec![];
+        if opts.unload.unwrap_or(false) {
+            let specs_path = Self::specs_path(mgr.cfg.sup_root());
+            for spec_file in SpecWatcher::spec_files(&specs_path)? {
+                let spec = ServiceSpec::from_file(&spec_file)?;
+                if spec.composite.is_none() && !listed.contains(&spec.ident.name) {
+                    req.info(format!("Unload {}", spec.ident))?;
+                    unloads.push(spec.ident);
+                }
+            }
+        }
+        if dry_run {
+            req.info("Dry run, no changes were applied")?;
+        } else {
+            for svc in loads {
+                Self::load_service(mgr, req, svc)?;
+            }
+            for spec in updates {
+                Self::save_spec_for(&mgr.cfg, &spec)?;
+                req.info(format!("The {} service was successfully updated", spec.ident))?;
+            }
+            for ident in unloads {
+                Self::unload_service(mgr, req, &ident)?;
+            }
+        }
+        req.reply_complete(net::ok());
+        Ok(())
+    }
+
+    /// Validates a spec given to `service_apply` against its package, installing the package
+    /// if it's missing. Packages aren't installed on a dry run, so a spec whose package is
+    /// missing isn't validated then.
+    fn validate_applied_spec(
+        req: &mut CtlRequest,
+        spec: &ServiceSpec,
+        dry_run: bool,
+    ) -> NetResult<()> {
+        let package = if dry_run {
+            match util::pkg::installed(&spec.ident) {
+                Some(package) => package,
+                None => return Ok(()),
+            }
+        } else {
+            let source = InstallSource::Ident(spec.ident.clone());
+            util::pkg::satisfy_or_install(req, &source, &spec.bldr_url, &spec.channel)?
+        };
+        // Composites generate the specs of their services, which are validated when loaded.
+        match package.pkg_type()? {
+            PackageType::Standalone => spec.validate(&package)?,
+            PackageType::Composite => (),
+        }
+        Ok(())
+    }
+
+    fn load_service(
+        mgr: &ManagerState,
+        req: &mut CtlRequest,
+        opts: protocol::ctl::SvcLoad,
     ) -> NetResult<()> {
         let ident: PackageIdent = opts.ident.clone().ok_or(err_update_client())?.into();
         let bldr_url = opts.bldr_url
@@ -1115,7 +1312,6 @@ impl Manager {
                 }
             }
         }
-        req.reply_complete(net::ok());
         Ok(())
     }
 
@@ -1125,9 +1321,19 @@ impl Manager {
         opts: protocol::ctl::SvcUnload,
     ) -> NetResult<()> {
         let ident: PackageIdent = opts.ident.ok_or(err_update_client())?.into();
+        Self::unload_service(mgr, req, &ident)?;
+        req.reply_complete(net::ok());
+        Ok(())
+    }
+
+    fn unload_service(
+        mgr: &ManagerState,
+        req: &mut CtlRequest,
+        ident: &PackageIdent,
+    ) -> NetResult<()> {
         // Gather up the paths to all the spec files we care about. This
         // includes all service specs as well as any composite spec.
-        let spec_paths = match Self::existing_specs_for_ident(&mgr.cfg, &ident)? {
+        let spec_paths = match Self::existing_specs_for_ident(&mgr.cfg, ident)? {
             Some(Spec::Service(spec)) => vec![Self::spec_path_for(&mgr.cfg, &spec)],
             Some(Spec::Composite(composite_spec, specs)) => {
                 let mut paths = Vec::with_capacity(specs.len() + 1);
@@ -1151,7 +1357,6 @@ impl Manager {
             // the work to complete.
             req.info(format!("Unloading {}", ident))?;
         }
-        req.reply_complete(net::ok());
         Ok(())
     }
 
@@ -1251,6 +1456,200 @@ impl Manager {
         Ok(())
     }
 
+    pub fn service_restart(
+        mgr: &ManagerState,
+        req: &mut CtlRequest,
+        opts: protocol::ctl::SvcRestart,
+    ) -> NetResult<()> {
+        let ident: PackageIdent = opts.ident.ok_or(err_update_client())?.into();
+        let mut services = mgr.services.write().expect("Services lock is poisoned!");
+        let service = services
+            .iter_mut()
+            .find(|s| s.pkg.ident.satisfies(&ident))
+            .ok_or(net::err(
+                ErrCode::NotFound,
+                format!("Service not loaded, {}", ident),
+            ))?;
+        service.request_restart();
+        req.info(format!(
+            "Supervisor restarting {}. See the Supervisor output for more details.",
+            service.service_group
+        ))?;
+        req.reply_complete(net::ok());
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    pub fn service_signal(
+        mgr: &ManagerState,
+        req: &mut CtlRequest,
+        opts: protocol::ctl::SvcSignal,
+    ) -> NetResult<()> {
+        let ident: PackageIdent = opts.ident.ok_or(err_update_client())?.into();
+        let name = opts.signal.ok_or(err_update_client())?;
+        let signal = signal_from_name(&name).ok_or(net::err(
+            ErrCode::InvalidPayload,
+            format!("Unsupported signal, {}", name),
+        ))?;
+        let services = mgr.services.read().expect("Services lock is poisoned!");
+        let service = services
+            .iter()
+            .find(|s| s.pkg.ident.satisfies(&ident))
+            .ok_or(net::err(
+                ErrCode::NotFound,
+                format!("Service not loaded, {}", ident),
+            ))?;
+        service.signal(signal)?;
+        req.info(format!("Sent {} to {}", name.to_uppercase(), service.service_group))?;
+        req.reply_complete(net::ok());
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    pub fn service_signal(
+        _mgr: &ManagerState,
+        _req: &mut CtlRequest,
+        _opts: protocol::ctl::SvcSignal,
+    ) -> NetResult<()> {
+        Err(net::err(
+            ErrCode::NotSupported,
+            "Signals are not supported on this platform",
+        ))
+    }
+
+    pub fn service_logs(
+        mgr: &ManagerState,
+        req: &mut CtlRequest,
+        opts: protocol::ctl::SvcLogs,
+    ) -> NetResult<()> {
+        let ident: PackageIdent = opts.ident.ok_or(err_update_client())?.into();
+        let (service_group, log) = mgr.services
+            .read()
+            .expect("Services lock is poisoned!")
+            .iter()
+            .find(|s| s.pkg.ident.satisfies(&ident))
+            .map(|s| (s.service_group.clone(), s.output_log()))
+            .ok_or(net::err(
+                ErrCode::NotFound,
+                format!("Service not loaded, {}", ident),
+            ))?;
+        if !log.exists() {
+            return Err(net::err(
+                ErrCode::NotFound,
+                format!("No output has been recorded for {}", service_group),
+            ));
+        }
+        let tail = opts.tail.map(|t| t as usize);
+        let follow = opts.follow.unwrap_or(false);
+        // The client asks for a duration so the clocks of the client and Supervisor needn't
+        // agree.
+        let since = opts.since.map(|since| time::get_time().sec - since as i64);
+        let services = mgr.services.clone();
+        let mut req = req.clone();
+        // Reading and following a log may take an arbitrary amount of time so we stream the
+        // replies from a separate thread rather than blocking the main loop.
+        thread::Builder::new()
+            .name(format!("svc-logs-{}", service_group))
+            .spawn(move || {
+                let offset = match log.read(tail, since) {
+                    Ok((lines, offset)) => {
+                        for line in lines {
+                            if !req.reply_partial(line) {
+                                return;
+                            }
+                        }
+                        offset
+                    }
+                    Err(err) => {
+                        req.reply_complete(net::err(ErrCode::Io, err.to_string()));
+                        return;
+                    }
+                };
+                if !follow {
+                    req.reply_complete(net::ok());
+                    return;
+                }
+                // Stop following once the client disconnects or the service is unloaded, even if
+                // the service has stopped writing output.
+                let watcher = req.clone();
+                let loaded = || {
+                    services
+                        .read()
+                        .expect("Services lock is poisoned!")
+                        .iter()
+                        .any(|s| s.service_group == service_group)
+                };
+                let result = log.follow(
+                    offset,
+                    || !watcher.is_closed() && loaded(),
+                    |line| req.reply_partial(line),
+                );
+                match result {
+                    Ok(()) if !req.is_closed() => req.reply_complete(net::ok()),
+                    Ok(()) => (),
+                    Err(err) => req.reply_complete(net::err(ErrCode::Io, err.to_string())),
+                }
+            })
+            .map_err(|e| net::err(ErrCode::Internal, e.to_string()))?;
+        Ok(())
+    }
+
+    pub fn service_hook_history(
+        mgr: &ManagerState,
+        req: &mut CtlRequest,
+        opts: protocol::ctl::SvcHookHistory,
+    ) -> NetResult<()> {
+        let ident: PackageIdent = opts.ident.ok_or(err_update_client())?.into();
+        let history = mgr.services
+            .read()
+            .expect("Services lock is poisoned!")
+            .iter()
+            .find(|s| s.pkg.ident.satisfies(&ident))
+            .map(|s| s.hook_history())
+            .ok_or(net::err(
+                ErrCode::NotFound,
+                format!("Service not loaded, {}", ident),
+            ))?;
+        let runs = history
+            .read(opts.hook.as_ref().map(String::as_str))
+            .map_err(|e| {
+                net::err(
+                    ErrCode::Io,
+                    format!("IoError while reading hook history, {}", e),
+                )
+            })?;
+        for run in runs {
+            if !req.reply_partial(protocol::ctl::SvcHookRun::from(run)) {
+                return Ok(());
+            }
+        }
+        req.reply_complete(net::ok());
+        Ok(())
+    }
+
+    pub fn supervisor_audit_log(
+        mgr: &ManagerState,
+        req: &mut CtlRequest,
+        opts: protocol::ctl::SupAuditLog,
+    ) -> NetResult<()> {
+        let log = ctl_gateway::audit::AuditLog::new(mgr.cfg.sup_root());
+        let entries = log.read(opts.tail.map(|t| t as usize)).map_err(|e| {
+            net::err(
+                ErrCode::Io,
+                format!("IoError while reading ctl audit log, {}", e),
+            )
+        })?;
+        for entry in entries {
+            let mut msg = protocol::ctl::SupAuditEntry::default();
+            msg.entry = entry;
+            if !req.reply_partial(msg) {
+                return Ok(());
+            }
+        }
+        req.reply_complete(net::ok());
+        Ok(())
+    }
+
     pub fn supervisor_depart(
         mgr: &ManagerState,
         req: &mut CtlRequest,
@@ -1302,6 +1701,7 @@ impl Manager {
             {
                 self.gossip_latest_service_rumor(&service);
             }
+            service.update_state = self.updater.update_state(&service.service_group);
         }
     }
 
@@ -1357,103 +1757,86 @@ impl Manager {
         }
     }
 
-    fn persist_state(&self) {
-        debug!("Writing census state to disk");
-        self.persist_census_state();
-        debug!("Writing butterfly state to disk");
-        self.persist_butterfly_state();
-        debug!("Writing services state to disk");
-        self.persist_services_state();
-    }
+    /// Publish the current census, butterfly and services state to the HTTP gateway. Any part of
+    /// the state which changed is also written to its file in the data directory.
+    fn publish_state(&self) {
+        let census = serde_json::to_string(&self.census_ring).unwrap();
+        let butterfly = serde_json::to_string(&self.butterfly).unwrap();
+        let services = self.service_states();
+        let readiness = Readiness::new(&self.state.cfg.readiness_policy, self.service_readiness());
 
-    fn persist_census_state(&self) {
-        let tmp_file = self.fs_cfg.census_data_path.with_extension("dat.tmp");
-        let file = match File::create(&tmp_file) {
-            Ok(file) => file,
-            Err(err) => {
-                warn!("Couldn't open temporary census state file, {}", err);
-                return;
-            }
-        };
-        let mut writer = BufWriter::new(file);
-        if let Some(err) = writer
-            .write(serde_json::to_string(&self.census_ring).unwrap().as_bytes())
-            .err()
+        let mut changed = Vec::new();
         {
-            warn!("Couldn't write to census state file, {}", err);
-        }
-        if let Some(err) = writer.flush().err() {
-            warn!("Couldn't flush census state buffer to disk, {}", err);
-        }
-        if let Some(err) = fs::rename(&tmp_file, &self.fs_cfg.census_data_path).err() {
-            warn!("Couldn't finalize census state on disk, {}", err);
-        }
-    }
-
-    fn persist_butterfly_state(&self) {
-        let tmp_file = self.fs_cfg.butterfly_data_path.with_extension("dat.tmp");
-        let file = match File::create(&tmp_file) {
-            Ok(file) => file,
-            Err(err) => {
-                warn!("Couldn't open temporary butterfly state file, {}", err);
-                return;
+            let mut state = self.gateway_state
+                .write()
+                .expect("Gateway state lock is poisoned");
+            state.published_at = Some(Instant::now());
+            state.readiness = readiness;
+            if state.census.publish(census) {
+                changed.push((&self.fs_cfg.census_data_path, state.census.data()));
+            }
+            if state.butterfly.publish(butterfly) {
+                changed.push((&self.fs_cfg.butterfly_data_path, state.butterfly.data()));
+            }
+            if state.publish_services(services) {
+                changed.push((&self.fs_cfg.services_data_path, state.services.data()));
             }
-        };
-        let mut writer = BufWriter::new(file);
-        if let Some(err) = writer
-            .write(serde_json::to_string(&self.butterfly).unwrap().as_bytes())
-            .err()
-        {
-            warn!("Couldn't write to butterfly state file, {}", err);
-        }
-        if let Some(err) = writer.flush().err() {
-            warn!("Couldn't flush butterfly state buffer to disk, {}", err);
         }
-        if let Some(err) = fs::rename(&tmp_file, &self.fs_cfg.butterfly_data_path).err() {
-            warn!("Couldn't finalize butterfly state on disk, {}", err);
+        for (path, data) in changed {
+            debug!("Writing state to {}", path.display());
+            persist_state_file(path, &data);
         }
     }
 
-    fn persist_services_state(&self) {
-        let tmp_file = self.fs_cfg.services_data_path.with_extension("dat.tmp");
-        let file = match File::create(&tmp_file) {
-            Ok(file) => file,
-            Err(err) => {
-                warn!("Couldn't open temporary services state file, {}", err);
-                return;
-            }
-        };
-        let mut writer = BufWriter::new(file);
-        if let Some(err) = writer.get_mut().write("[".as_bytes()).err() {
-            warn!("Couldn't write to service state file, {}", err);
-        }
-
-        let mut is_first = true;
-        let mut persisted_idents = Vec::new();
-
+    /// Serialize the state of every service, running or not.
+    fn service_states(&self) -> Vec<(ServiceGroup, String)> {
+        let mut states = Vec::new();
         for service in self.state
             .services
             .read()
             .expect("Services lock is poisoned!")
             .iter()
         {
-            persisted_idents.push(service.spec_ident.clone());
-            if let Some(err) = self.write_service(service, is_first, writer.get_mut())
-                .err()
-            {
-                warn!("Couldn't write to service state file, {}", err);
+            match serde_json::to_string(service) {
+                Ok(data) => states.push((service.service_group.clone(), data)),
+                Err(err) => warn!("Couldn't serialize state of {}, {}", service, err),
             }
-            is_first = false;
         }
+        states.extend(self.inactive_services.iter().cloned());
+        states
+    }
 
-        // add services that are not active but are being watched for changes
-        // These would include stopped persistent services or other
-        // persistent services that failed to load
+    /// Readiness of each running service.
+    fn service_readiness(&self) -> Vec<ServiceReadiness> {
+        self.state
+            .services
+            .read()
+            .expect("Services lock is poisoned!")
+            .iter()
+            .map(|s| ServiceReadiness::new(&s.service_group, s.initialized, s.health_check()))
+            .collect()
+    }
+
+    /// Reload the state of services that are not active but are being watched for changes, if the
+    /// set of services has changed. These would include stopped persistent services or other
+    /// persistent services that failed to load.
+    fn refresh_inactive_services(&mut self) {
+        if !self.check_for_changed_services() {
+            return;
+        }
+        let active: Vec<PackageIdent> = self.state
+            .services
+            .read()
+            .expect("Services lock is poisoned!")
+            .iter()
+            .map(|s| s.spec_ident.clone())
+            .collect();
+        let mut inactive = Vec::new();
         for down in self.spec_watcher
             .specs_from_watch_path()
             .unwrap()
             .values()
-            .filter(|s| !persisted_idents.contains(&s.ident))
+            .filter(|s| !active.contains(&s.ident))
         {
             match Service::load(
                 self.sys.clone(),
@@ -1461,40 +1844,23 @@ impl Manager {
                 self.fs_cfg.clone(),
                 self.organization.as_ref().map(|org| &**org),
             ) {
-                Ok(service) => {
-                    if let Some(err) = self.write_service(&service, is_first, writer.get_mut())
-                        .err()
-                    {
-                        warn!("Couldn't write to service state file, {}", err);
-                    }
-                    is_first = false;
-                }
+                Ok(service) => match serde_json::to_string(&service) {
+                    Ok(data) => inactive.push((service.service_group.clone(), data)),
+                    Err(err) => warn!("Couldn't serialize state of {}, {}", service, err),
+                },
                 Err(e) => debug!("Error loading inactive service struct: {}", e),
             }
         }
-
-        if let Some(err) = writer.get_mut().write("]".as_bytes()).err() {
-            warn!("Couldn't write to service state file, {}", err);
-        }
-        if let Some(err) = writer.flush().err() {
-            warn!("Couldn't flush services state buffer to disk, {}", err);
-        }
-        if let Some(err) = fs::rename(&tmp_file, &self.fs_cfg.services_data_path).err() {
-            warn!("Couldn't finalize services state on disk, {}", err);
-        }
+        self.inactive_services = inactive;
     }
 
     /// Remove the given service from the manager.
     ///
-    /// Passing `true` for the term argument will also request the Launcher to terminate the running
-    /// service. Passing a value of `false` will let the Launcher keep the service running. This
-    /// useful if you want the Supervisor to shutdown temporarily and then come back and re-attach
-    /// to all running processes.
-    fn remove_service(&mut self, service: &mut Service, term: bool) {
+    /// The service's process is left running. This useful if you want the Supervisor to shutdown
+    /// temporarily and then come back and re-attach to all running processes.
+    fn remove_service(&mut self, service: &mut Service) {
         // JW TODO: Update service rumor to remove service from cluster
-        if term {
-            service.stop(&self.launcher);
-        }
+        metrics::remove_service(&service.service_group);
         if let Err(err) = fs::remove_file(self.fs_cfg.health_check_cache(&service.service_group)) {
             outputln!(
                 "Unable to cleanup service health cache, {}, {}",
@@ -1510,20 +1876,16 @@ impl Manager {
         }
     }
 
-    fn write_service<W: ?Sized>(
-        &self,
-        service: &Service,
-        is_first: bool,
-        writer: &mut W,
-    ) -> Result<()>
-    where
-        W: io::Write,
-    {
-        if !is_first {
-            writer.write(",".as_bytes())?;
-        }
-        serde_json::to_writer(writer, service)
-            .map_err(|e| sup_error!(Error::ServiceSerializationError(e)))
+    /// Request the Launcher to terminate a removed service. The service is stopped on its own
+    /// thread, as it may take as long as its shutdown timeout to do so.
+    fn stop_service(&mut self, mut service: Service) {
+        let launcher = self.launcher.clone();
+        let name = service.spec_ident.name.clone();
+        let handle = thread::Builder::new()
+            .name(format!("stop-{}", service.service_group))
+            .spawn(move || service.stop(&launcher))
+            .expect("Unable to start service stop thread");
+        self.stopping_services.insert(name, handle);
     }
 
     /// Check if any elections need restarting.
@@ -1551,7 +1913,10 @@ impl Manager {
         }
 
         for mut service in svcs.drain(..) {
-            self.remove_service(&mut service, false);
+            self.remove_service(&mut service);
+        }
+        for (_, stopping) in self.stopping_services.drain() {
+            stopping.join().ok();
         }
         release_process_lock(&self.fs_cfg);
     }
@@ -1651,7 +2016,8 @@ impl Manager {
             service = services.remove(services_idx);
         }
 
-        self.remove_service(&mut service, true);
+        self.remove_service(&mut service);
+        self.stop_service(service);
         Ok(())
     }
 }
@@ -1662,6 +2028,14 @@ pub struct ProcessStatus {
     pub elapsed: TimeDuration,
     pub pid: Option<u32>,
     pub state: ProcessState,
+    #[serde(default)]
+    pub restart_count: u32,
+    #[serde(default)]
+    pub last_exit_code: Option<i32>,
+    #[serde(default)]
+    pub crash_looping: bool,
+    #[serde(default)]
+    pub stats: Option<ProcessStats>,
 }
 
 impl fmt::Display for ProcessStatus {
@@ -1683,6 +2057,17 @@ pub struct ServiceStatus {
     pub process: ProcessStatus,
     pub service_group: ServiceGroup,
     pub composite: Option<String>,
+    // The fields below are absent from the state written by older Supervisors.
+    #[serde(default, deserialize_with = "deserialize_using_from_str")]
+    pub desired_state: DesiredState,
+    #[serde(default)]
+    pub health_check: HealthCheck,
+    #[serde(default)]
+    pub update_state: UpdateState,
+    #[serde(default)]
+    pub election_role: ElectionRole,
+    #[serde(default)]
+    pub unsatisfied_binds: Vec<String>,
 }
 
 impl fmt::Display for ServiceStatus {
@@ -1790,6 +2175,23 @@ where
     }
 }
 
+/// Write the given state to `path`, replacing the previous content of the file atomically so that
+/// readers never see a partially written file.
+fn persist_state_file(path: &Path, data: &str) {
+    let tmp_file = path.with_extension("dat.tmp");
+    let result = File::create(&tmp_file).and_then(|mut file| {
+        file.write_all(data.as_bytes())?;
+        file.flush()
+    });
+    if let Err(err) = result {
+        warn!("Couldn't write state file {}, {}", tmp_file.display(), err);
+        return;
+    }
+    if let Err(err) = fs::rename(&tmp_file, path) {
+        warn!("Couldn't finalize state file {}, {}", path.display(), err);
+    }
+}
+
 fn release_process_lock(fs_cfg: &FsCfg) {
     if let Err(err) = fs::remove_file(&fs_cfg.proc_lock_file) {
         debug!("Couldn't cleanup Supervisor process lock, {}", err);
@@ -1899,6 +2301,22 @@ impl From<ProcessStatus> for protocol::types::ProcessStatus {
         if let Some(pid) = other.pid {
             proto.pid = Some(pid);
         }
+        proto.restart_count = Some(other.restart_count);
+        proto.last_exit_code = other.last_exit_code;
+        proto.crash_looping = Some(other.crash_looping);
+        proto.stats = other.stats.map(Into::into);
+        proto
+    }
+}
+
+impl From<ProcessStats> for protocol::types::ProcessStats {
+    fn from(other: ProcessStats) -> Self {
+        let mut proto = protocol::types::ProcessStats::default();
+        proto.cpu_usec = Some(other.cpu_usec);
+        proto.rss_bytes = Some(other.rss_bytes);
+        proto.fds = Some(other.fds);
+        proto.threads = Some(other.threads);
+        proto.processes = Some(other.processes);
         proto
     }
 }
@@ -1921,10 +2339,38 @@ impl From<ServiceStatus> for protocol::types::ServiceStatus {
         if let Some(composite) = other.composite {
             proto.composite = Some(composite);
         }
+        proto.health_check =
+            Some(protocol::types::service_status::HealthCheck::from(other.health_check) as i32);
+        proto.desired_state =
+            Some(protocol::types::service_status::DesiredState::from(other.desired_state) as i32);
+        proto.update_state = Some(other.update_state.into());
+        proto.election_role = Some(other.election_role.into());
+        proto.unsatisfied_binds = other.unsatisfied_binds;
+        proto.unsatisfied_binds.sort();
         proto
     }
 }
 
+impl From<HealthCheck> for protocol::types::service_status::HealthCheck {
+    fn from(other: HealthCheck) -> Self {
+        match other {
+            HealthCheck::Ok => protocol::types::service_status::HealthCheck::Ok,
+            HealthCheck::Warning => protocol::types::service_status::HealthCheck::Warning,
+            HealthCheck::Critical => protocol::types::service_status::HealthCheck::Critical,
+            HealthCheck::Unknown => protocol::types::service_status::HealthCheck::Unknown,
+        }
+    }
+}
+
+impl From<DesiredState> for protocol::types::service_status::DesiredState {
+    fn from(other: DesiredState) -> Self {
+        match other {
+            DesiredState::Down => protocol::types::service_status::DesiredState::Down,
+            DesiredState::Up => protocol::types::service_status::DesiredState::Up,
+        }
+    }
+}
+
 impl Into<service::ServiceBind> for protocol::types::ServiceBind {
     fn into(self) -> service::ServiceBind {
         service::ServiceBind {
@@ -1939,7 +2385,9 @@ impl Into<service::ServiceBind> for protocol::types::ServiceBind {
 mod test {
     use std::path::PathBuf;
 
+    use protocol;
     use protocol::STATE_PATH_PREFIX;
+    use serde_json;
 
     use super::ManagerConfig;
 
@@ -1984,4 +2432,27 @@ mod test {
 
         assert_eq!(PathBuf::from("/tmp/partay"), path);
     }
+
+    #[test]
+    fn process_status_from_older_state() {
+        let status: super::ProcessStatus =
+            serde_json::from_str(r#"{"pid": 42, "state": "up", "state_entered": 0}"#).unwrap();
+        assert_eq!(status.restart_count, 0);
+
+        let proto: protocol::types::ProcessStatus = status.into();
+        assert_eq!(proto.pid, Some(42));
+        assert_eq!(proto.restart_count, Some(0));
+        assert!(proto.last_exit_code.is_none());
+        assert_eq!(proto.crash_looping, Some(false));
+        assert!(proto.stats.is_none());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn signal_from_name_ignores_case_and_prefix() {
+        assert!(super::signal_from_name("HUP").is_some());
+        assert!(super::signal_from_name("usr1").is_some());
+        assert!(super::signal_from_name("SIGTERM").is_some());
+        assert!(super::signal_from_name("BOGUS").is_none());
+    }
 }
@@ -12,9 +12,22 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::cmp;
 use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+use std::time::Duration;
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize)]
+static LOGKEY: &'static str = "HC";
+
+/// Name of the package metadata file holding the `pkg_health_check` settings of a plan, written
+/// as `key=value` lines.
+const HEALTH_CHECK_METAFILE: &'static str = "HEALTH_CHECK";
+/// Seconds between health checks of a service which doesn't set its own interval.
+pub const DEFAULT_HEALTH_CHECK_INTERVAL: u64 = 30;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum HealthCheck {
     Ok,
     Warning,
@@ -28,6 +41,17 @@ impl Default for HealthCheck {
     }
 }
 
+impl HealthCheck {
+    /// Whether the result counts as a passing check. Ok and warning results pass, while critical
+    /// and unknown results fail.
+    pub fn is_passing(&self) -> bool {
+        match *self {
+            HealthCheck::Ok | HealthCheck::Warning => true,
+            HealthCheck::Critical | HealthCheck::Unknown => false,
+        }
+    }
+}
+
 impl From<i8> for HealthCheck {
     fn from(value: i8) -> HealthCheck {
         match value {
@@ -52,6 +76,135 @@ impl fmt::Display for HealthCheck {
     }
 }
 
+/// How often the health of a service is checked and how the results of its checks change its
+/// status. Each setting may be given by the `pkg_health_check` of the service's plan and
+/// overridden when the service is loaded; unset settings take their defaults.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(default)]
+pub struct HealthCheckSettings {
+    /// Seconds between the end of a check and the start of the next.
+    pub interval: Option<u64>,
+    /// Seconds after which a running `health_check` hook is killed and the check fails.
+    pub timeout: Option<u64>,
+    /// Consecutive passing checks needed for a failing service to be reported as healthy.
+    pub success_threshold: Option<u32>,
+    /// Consecutive failing checks needed for a healthy service to be reported as failing.
+    pub failure_threshold: Option<u32>,
+}
+
+impl HealthCheckSettings {
+    /// Read the settings from the metadata of the package installed at `pkg_path`. Settings
+    /// which can't be parsed are ignored.
+    pub fn from_package<P>(pkg_path: P) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        let path = pkg_path.as_ref().join(HEALTH_CHECK_METAFILE);
+        match File::open(&path) {
+            Ok(file) => Self::from_metadata(BufReader::new(file)),
+            Err(ref err) if err.kind() == io::ErrorKind::NotFound => Self::default(),
+            Err(err) => {
+                outputln!("Couldn't read {}, {}", path.display(), err);
+                Self::default()
+            }
+        }
+    }
+
+    fn from_metadata<R>(reader: R) -> Self
+    where
+        R: BufRead,
+    {
+        let mut settings = Self::default();
+        for line in reader.lines().filter_map(|l| l.ok()) {
+            let mut parts = line.splitn(2, '=');
+            let (key, value) = match (parts.next(), parts.next()) {
+                (Some(key), Some(value)) => (key.trim(), value.trim()),
+                _ => continue,
+            };
+            let valid = match key {
+                "interval" => value.parse().map(|v| settings.interval = Some(v)).is_ok(),
+                "timeout" => value.parse().map(|v| settings.timeout = Some(v)).is_ok(),
+                "success_threshold" => value
+                    .parse()
+                    .map(|v| settings.success_threshold = Some(v))
+                    .is_ok(),
+                "failure_threshold" => value
+                    .parse()
+                    .map(|v| settings.failure_threshold = Some(v))
+                    .is_ok(),
+                _ => false,
+            };
+            if !valid {
+                outputln!("Ignoring invalid health check setting, {}", line);
+            }
+        }
+        settings
+    }
+
+    /// Returns these settings with each setting given by `overrides` replaced.
+    pub fn merge(&self, overrides: &HealthCheckSettings) -> Self {
+        HealthCheckSettings {
+            interval: overrides.interval.or(self.interval),
+            timeout: overrides.timeout.or(self.timeout),
+            success_threshold: overrides.success_threshold.or(self.success_threshold),
+            failure_threshold: overrides.failure_threshold.or(self.failure_threshold),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn interval(&self) -> Duration {
+        Duration::from_secs(self.interval.unwrap_or(DEFAULT_HEALTH_CHECK_INTERVAL))
+    }
+
+    /// Determine the status of a service from its current status and the result of its latest
+    /// check. `streak` counts the consecutive results which disagreed with the current status,
+    /// and is updated with this result.
+    pub fn next_status(
+        &self,
+        current: HealthCheck,
+        result: HealthCheck,
+        streak: &mut u32,
+    ) -> HealthCheck {
+        if result.is_passing() == current.is_passing() {
+            *streak = 0;
+            return result;
+        }
+        let threshold = if result.is_passing() {
+            self.success_threshold
+        } else {
+            self.failure_threshold
+        };
+        *streak += 1;
+        if *streak >= cmp::max(threshold.unwrap_or(1), 1) {
+            *streak = 0;
+            result
+        } else {
+            current
+        }
+    }
+}
+
+impl fmt::Display for HealthCheckSettings {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let settings: Vec<String> = vec![
+            ("interval", self.interval),
+            ("timeout", self.timeout),
+            ("success_threshold", self.success_threshold.map(u64::from)),
+            ("failure_threshold", self.failure_threshold.map(u64::from)),
+        ].into_iter()
+            .filter_map(|(key, value)| value.map(|v| format!("{}={}", key, v)))
+            .collect();
+        if settings.is_empty() {
+            write!(f, "default")
+        } else {
+            write!(f, "{}", settings.join(" "))
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub enum SmokeCheck {
     Ok,
@@ -75,3 +228,68 @@ impl fmt::Display for SmokeCheck {
         write!(f, "{}", msg)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn health_check_settings_from_metadata() {
+        let metadata = "interval=5\ntimeout=3\nfailure_threshold=2\nbogus=1\nsuccess_threshold=x\n";
+        let settings = HealthCheckSettings::from_metadata(metadata.as_bytes());
+        assert_eq!(settings.interval(), Duration::from_secs(5));
+        assert_eq!(settings.timeout, Some(3));
+        assert_eq!(settings.failure_threshold, Some(2));
+        assert_eq!(settings.success_threshold, None);
+
+        let overrides = HealthCheckSettings {
+            interval: Some(120),
+            ..HealthCheckSettings::default()
+        };
+        let merged = settings.merge(&overrides);
+        assert_eq!(merged.interval, Some(120));
+        assert_eq!(merged.timeout, Some(3));
+        assert_eq!(merged.to_string(), "interval=120 timeout=3 failure_threshold=2");
+        assert_eq!(HealthCheckSettings::default().to_string(), "default");
+        assert_eq!(
+            HealthCheckSettings::default().interval(),
+            Duration::from_secs(DEFAULT_HEALTH_CHECK_INTERVAL)
+        );
+    }
+
+    #[test]
+    fn next_status_applies_thresholds() {
+        let settings = HealthCheckSettings {
+            success_threshold: Some(2),
+            failure_threshold: Some(3),
+            ..HealthCheckSettings::default()
+        };
+        let mut streak = 0;
+        let mut status = HealthCheck::Unknown;
+        status = settings.next_status(status, HealthCheck::Ok, &mut streak);
+        assert_eq!(status, HealthCheck::Unknown);
+        status = settings.next_status(status, HealthCheck::Ok, &mut streak);
+        assert_eq!(status, HealthCheck::Ok);
+        status = settings.next_status(status, HealthCheck::Warning, &mut streak);
+        assert_eq!(status, HealthCheck::Warning);
+
+        for _ in 0..2 {
+            status = settings.next_status(status, HealthCheck::Critical, &mut streak);
+            assert_eq!(status, HealthCheck::Warning);
+        }
+        status = settings.next_status(status, HealthCheck::Ok, &mut streak);
+        assert_eq!(streak, 0);
+        for _ in 0..3 {
+            status = settings.next_status(status, HealthCheck::Critical, &mut streak);
+        }
+        assert_eq!(status, HealthCheck::Critical);
+
+        let mut streak = 0;
+        let status = HealthCheckSettings::default().next_status(
+            HealthCheck::Ok,
+            HealthCheck::Critical,
+            &mut streak,
+        );
+        assert_eq!(status, HealthCheck::Critical);
+    }
+}
@@ -0,0 +1,241 @@
+// Copyright (c) 2018 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A bounded history of the runs of a service's hooks.
+//!
+//! The last runs of each hook are kept as a JSON list in `<hook>.history.json`, next to the
+//! `<hook>.stdout.log` and `<hook>.stderr.log` files which only hold the output of the latest
+//! run. Being on disk, the history outlives restarts of the Supervisor.
+
+use std::fs::{self, File};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use protocol;
+use serde_json;
+
+/// Number of runs of each hook which are kept.
+pub const HISTORY_LENGTH: usize = 10;
+/// Number of bytes kept from the end of each output stream of a run.
+pub const OUTPUT_LIMIT: u64 = 4096;
+const HISTORY_SUFFIX: &'static str = ".history.json";
+
+/// A single run of a hook.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct HookExecution {
+    pub hook: String,
+    /// When the hook started, in seconds since the Unix epoch.
+    pub started_at: i64,
+    pub duration_ms: u64,
+    /// Exit code of the hook. Hooks which failed to start, were killed or were terminated by a
+    /// signal have none.
+    pub exit_code: Option<i32>,
+    /// Whether the hook was killed for exceeding its timeout.
+    pub timed_out: bool,
+    /// The end of what the hook wrote to standard out, limited to `OUTPUT_LIMIT` bytes.
+    pub stdout: String,
+    /// The end of what the hook wrote to standard error, limited to `OUTPUT_LIMIT` bytes.
+    pub stderr: String,
+}
+
+impl HookExecution {
+    /// Describe a finished run of a hook whose output was written to the given log files.
+    pub fn new(
+        hook: &str,
+        started_at: i64,
+        duration: Duration,
+        exit_code: Option<i32>,
+        timed_out: bool,
+        stdout_log: &Path,
+        stderr_log: &Path,
+    ) -> Self {
+        HookExecution {
+            hook: hook.to_string(),
+            started_at: started_at,
+            duration_ms: duration.as_secs() * 1000 + (duration.subsec_nanos() / 1_000_000) as u64,
+            exit_code: exit_code,
+            timed_out: timed_out,
+            stdout: read_tail(stdout_log),
+            stderr: read_tail(stderr_log),
+        }
+    }
+}
+
+impl From<HookExecution> for protocol::ctl::SvcHookRun {
+    fn from(run: HookExecution) -> Self {
+        let mut msg = protocol::ctl::SvcHookRun::default();
+        msg.hook = run.hook;
+        msg.started_at = run.started_at;
+        msg.duration_ms = run.duration_ms;
+        msg.exit_code = run.exit_code;
+        msg.timed_out = Some(run.timed_out);
+        msg.stdout = Some(run.stdout);
+        msg.stderr = Some(run.stderr);
+        msg
+    }
+}
+
+/// The history of the hooks of a service, kept in the given directory.
+pub struct HookHistory {
+    dir: PathBuf,
+}
+
+impl HookHistory {
+    pub fn new<T>(dir: T) -> Self
+    where
+        T: Into<PathBuf>,
+    {
+        HookHistory { dir: dir.into() }
+    }
+
+    /// Add a run to the history of its hook, forgetting the oldest run if the history is full.
+    pub fn record(&self, run: &HookExecution) -> io::Result<()> {
+        let path = self.path(&run.hook);
+        let mut runs = read_runs(&path)?;
+        runs.push(run.clone());
+        if runs.len() > HISTORY_LENGTH {
+            let excess = runs.len() - HISTORY_LENGTH;
+            runs.drain(..excess);
+        }
+        let tmp_path = path.with_extension("json.tmp");
+        {
+            let mut file = File::create(&tmp_path)?;
+            serde_json::to_writer(&mut file, &runs)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            file.write_all(b"\n")?;
+        }
+        fs::rename(&tmp_path, &path)
+    }
+
+    /// The recorded runs of every hook, or only of the named hook, oldest first.
+    pub fn read(&self, hook: Option<&str>) -> io::Result<Vec<HookExecution>> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(ref err) if err.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(err) => return Err(err),
+        };
+        let mut runs = vec![];
+        for entry in entries {
+            let entry = entry?;
+            let file_name = entry.file_name();
+            let name = match file_name.to_str() {
+                Some(name) if name.ends_with(HISTORY_SUFFIX) => {
+                    &name[..name.len() - HISTORY_SUFFIX.len()]
+                }
+                _ => continue,
+            };
+            // Hook names are matched against the files on disk rather than joined onto the
+            // directory, so a requested name can never point outside of it.
+            if hook.map_or(true, |hook| hook == name) {
+                runs.extend(read_runs(&entry.path())?);
+            }
+        }
+        runs.sort_by_key(|run| run.started_at);
+        Ok(runs)
+    }
+
+    fn path(&self, hook: &str) -> PathBuf {
+        self.dir.join(format!("{}{}", hook, HISTORY_SUFFIX))
+    }
+}
+
+fn read_runs(path: &Path) -> io::Result<Vec<HookExecution>> {
+    let mut content = String::new();
+    match File::open(path) {
+        Ok(mut file) => file.read_to_string(&mut content)?,
+        Err(ref err) if err.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(err) => return Err(err),
+    };
+    serde_json::from_str(&content).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Read the last `OUTPUT_LIMIT` bytes of a log file. A missing log reads as empty.
+fn read_tail(path: &Path) -> String {
+    let mut buf = vec![];
+    if let Ok(mut file) = File::open(path) {
+        let len = file.metadata().map(|m| m.len()).unwrap_or(0);
+        if len > OUTPUT_LIMIT {
+            let _ = file.seek(SeekFrom::Start(len - OUTPUT_LIMIT));
+        }
+        let _ = file.read_to_end(&mut buf);
+    }
+    String::from_utf8_lossy(&buf).into_owned()
+}
+
+#[cfg(test)]
+mod test {
+    use std::iter;
+
+    use tempdir::TempDir;
+
+    use super::*;
+
+    fn execution(hook: &str, started_at: i64) -> HookExecution {
+        HookExecution {
+            hook: hook.to_string(),
+            started_at: started_at,
+            duration_ms: 20,
+            exit_code: Some(0),
+            timed_out: false,
+            stdout: String::new(),
+            stderr: String::new(),
+        }
+    }
+
+    #[test]
+    fn execution_keeps_end_of_output() {
+        let tmpdir = TempDir::new("hook-history").unwrap();
+        let stdout = tmpdir.path().join("init.stdout.log");
+        let output: String = iter::repeat("x").take(OUTPUT_LIMIT as usize).collect();
+        File::create(&stdout)
+            .unwrap()
+            .write_all(format!("start{}", output).as_bytes())
+            .unwrap();
+        let run = HookExecution::new(
+            "init",
+            1519905600,
+            Duration::from_millis(1500),
+            Some(1),
+            false,
+            &stdout,
+            &tmpdir.path().join("init.stderr.log"),
+        );
+        assert_eq!(run.duration_ms, 1500);
+        assert_eq!(run.stdout, output);
+        assert_eq!(run.stderr, "");
+    }
+
+    #[test]
+    fn history_is_bounded_per_hook() {
+        let tmpdir = TempDir::new("hook-history").unwrap();
+        let history = HookHistory::new(tmpdir.path());
+        assert!(history.read(None).unwrap().is_empty());
+
+        for i in 0..(HISTORY_LENGTH as i64 + 2) {
+            history.record(&execution("reconfigure", i * 2)).unwrap();
+        }
+        history.record(&execution("init", 1)).unwrap();
+
+        let runs = history.read(Some("reconfigure")).unwrap();
+        assert_eq!(runs.len(), HISTORY_LENGTH);
+        assert_eq!(runs[0].started_at, 4);
+
+        let runs = history.read(None).unwrap();
+        assert_eq!(runs.len(), HISTORY_LENGTH + 1);
+        assert_eq!(runs[0].hook, "init");
+
+        assert!(history.read(Some("../reconfigure")).unwrap().is_empty());
+    }
+}
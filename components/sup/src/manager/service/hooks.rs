@@ -23,19 +23,33 @@ use std::path::{Path, PathBuf};
 #[cfg(not(windows))]
 use std::process::{Child, ExitStatus};
 use std::result;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
 
 use hcore;
 use hcore::crypto;
 use hcore::service::ServiceGroup;
 use serde::{Serialize, Serializer};
+use time;
 
+use super::hook_history::{HookExecution, HookHistory};
 use super::{health, Pkg};
 use error::{Result, SupError};
 use fs;
+use metrics;
 use templating::{RenderContext, TemplateRenderer};
 use util::exec;
 
 pub const HOOK_PERMISSIONS: u32 = 0o755;
+/// Environment variable giving the `pre-update` and `post-update` hooks the identifier of the
+/// package which is being replaced.
+pub const UPDATE_FROM_ENVVAR: &'static str = "HAB_UPDATE_FROM";
+/// Environment variable giving the `pre-update` and `post-update` hooks the identifier of the
+/// package replacing it.
+pub const UPDATE_TO_ENVVAR: &'static str = "HAB_UPDATE_TO";
 static LOGKEY: &'static str = "HK";
 
 pub fn stdout_log_path<T>(service_group: &ServiceGroup) -> PathBuf
@@ -55,6 +69,105 @@ where
 #[derive(Debug, Copy, Clone)]
 pub struct ExitCode(i32);
 
+/// The outcome of running a hook.
+#[derive(Debug)]
+pub struct HookRun<T> {
+    /// The result of the hook, as interpreted by its type.
+    pub value: T,
+    /// The timeout which the hook exceeded, if it was killed for running for too long.
+    pub exceeded_timeout: Option<Duration>,
+}
+
+/// A hook whose last run was killed for exceeding its timeout.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct TimedOutHook {
+    /// Seconds the hook was allowed to run for.
+    pub timeout: u64,
+    /// When the hook was killed, in seconds since the Unix epoch.
+    pub killed_at: i64,
+}
+
+/// Seconds each hook of a service may run for before it's killed, along with any processes it
+/// started. Hooks without a timeout of their own use `default`, and a timeout of 0 lets a hook run
+/// for as long as it takes.
+///
+/// ```toml
+/// [hook_timeouts]
+/// default = 60
+/// init = 600
+/// post_stop = 0
+/// ```
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct HookTimeouts {
+    pub default: Option<u64>,
+    pub file_updated: Option<u64>,
+    pub health_check: Option<u64>,
+    pub init: Option<u64>,
+    pub reload: Option<u64>,
+    pub reconfigure: Option<u64>,
+    pub suitability: Option<u64>,
+    pub post_run: Option<u64>,
+    pub post_stop: Option<u64>,
+    pub pre_update: Option<u64>,
+    pub post_update: Option<u64>,
+}
+
+impl HookTimeouts {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// How long the given hook may run for, or `None` if it may run for as long as it takes.
+    pub fn timeout<H>(&self) -> Option<Duration>
+    where
+        H: Hook,
+    {
+        let timeout = match H::file_name() {
+            "file_updated" => self.file_updated,
+            "health_check" => self.health_check,
+            "init" => self.init,
+            "reload" => self.reload,
+            "reconfigure" => self.reconfigure,
+            "suitability" => self.suitability,
+            "post-run" => self.post_run,
+            "post-stop" => self.post_stop,
+            "pre-update" => self.pre_update,
+            "post-update" => self.post_update,
+            _ => None,
+        };
+        match timeout.or(self.default) {
+            Some(0) | None => None,
+            Some(secs) => Some(Duration::from_secs(secs)),
+        }
+    }
+}
+
+impl fmt::Display for HookTimeouts {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let timeouts: Vec<String> = vec![
+            ("default", self.default),
+            ("file_updated", self.file_updated),
+            ("health_check", self.health_check),
+            ("init", self.init),
+            ("reload", self.reload),
+            ("reconfigure", self.reconfigure),
+            ("suitability", self.suitability),
+            ("post_run", self.post_run),
+            ("post_stop", self.post_stop),
+            ("pre_update", self.pre_update),
+            ("post_update", self.post_update),
+        ].into_iter()
+            .filter_map(|(hook, value)| value.map(|v| format!("{}={}", hook, v)))
+            .collect();
+        if timeouts.is_empty() {
+            write!(f, "none")
+        } else {
+            write!(f, "{}", timeouts.join(" "))
+        }
+    }
+}
+
 impl Default for ExitCode {
     fn default() -> ExitCode {
         ExitCode(-1)
@@ -127,24 +240,97 @@ pub trait Hook: fmt::Debug + Sized {
     where
         T: ToString,
     {
+        self.execute(
+            service_group,
+            pkg,
+            svc_encrypted_password,
+            &HookTimeouts::default(),
+        ).value
+    }
+
+    /// Run a compiled hook, killing it along with any processes it started if it runs for longer
+    /// than its timeout.
+    fn execute<T>(
+        &self,
+        service_group: &ServiceGroup,
+        pkg: &Pkg,
+        svc_encrypted_password: Option<T>,
+        timeouts: &HookTimeouts,
+    ) -> HookRun<Self::ExitValue>
+    where
+        T: ToString,
+    {
+        let started = Instant::now();
+        let started_at = time::get_time().sec;
         let mut child = match exec::run(self.path(), &pkg, svc_encrypted_password) {
             Ok(child) => child,
             Err(err) => {
                 outputln!(preamble service_group,
                     "Hook failed to run, {}, {}", Self::file_name(), err);
-                return Self::ExitValue::default();
+                let mut execution = self.execution(started_at, started, None, false);
+                execution.stdout.clear();
+                execution.stderr = format!("Hook failed to run, {}", err);
+                record_execution(service_group, &execution);
+                return HookRun::completed(Self::ExitValue::default());
             }
         };
+        let timeout = timeouts.timeout::<Self>();
+        let watchdog = timeout.map(|timeout| Watchdog::start(child.id(), timeout));
         let mut hook_output = HookOutput::new(self.stdout_log_path(), self.stderr_log_path());
         hook_output.stream_output::<Self>(service_group, &mut child);
-        match child.wait() {
-            Ok(status) => self.handle_exit(service_group, &hook_output, &status),
+        let result = child.wait();
+        if watchdog.map_or(false, |watchdog| watchdog.stop()) {
+            let timeout = timeout.unwrap();
+            outputln!(preamble service_group,
+                "{} timed out after {}s and was killed", Self::file_name(), timeout.as_secs());
+            metrics::observe_hook(service_group, Self::file_name(), started.elapsed(), None);
+            record_execution(service_group, &self.execution(started_at, started, None, true));
+            return HookRun {
+                value: Self::timed_out(),
+                exceeded_timeout: Some(timeout),
+            };
+        }
+        let value = match result {
+            Ok(status) => {
+                metrics::observe_hook(
+                    service_group,
+                    Self::file_name(),
+                    started.elapsed(),
+                    status.code(),
+                );
+                record_execution(
+                    service_group,
+                    &self.execution(started_at, started, status.code(), false),
+                );
+                self.handle_exit(service_group, &hook_output, &status)
+            }
             Err(err) => {
                 outputln!(preamble service_group,
                     "Hook failed to run, {}, {}", Self::file_name(), err);
+                record_execution(service_group, &self.execution(started_at, started, None, false));
                 Self::ExitValue::default()
             }
-        }
+        };
+        HookRun::completed(value)
+    }
+
+    /// Describe a finished run of the hook, for its history.
+    fn execution(
+        &self,
+        started_at: i64,
+        started: Instant,
+        exit_code: Option<i32>,
+        timed_out: bool,
+    ) -> HookExecution {
+        HookExecution::new(
+            Self::file_name(),
+            started_at,
+            started.elapsed(),
+            exit_code,
+            timed_out,
+            self.stdout_log_path(),
+            self.stderr_log_path(),
+        )
     }
 
     fn handle_exit<'a>(
@@ -154,6 +340,12 @@ pub trait Hook: fmt::Debug + Sized {
         status: &ExitStatus,
     ) -> Self::ExitValue;
 
+    /// The result of a run which was killed for exceeding its timeout. This is the same as the
+    /// result of a hook which failed to run, unless the type of hook says otherwise.
+    fn timed_out() -> Self::ExitValue {
+        Self::ExitValue::default()
+    }
+
     fn path(&self) -> &Path;
 
     fn renderer(&self) -> &TemplateRenderer;
@@ -257,6 +449,12 @@ impl Hook for HealthCheckHook {
         }
     }
 
+    /// A health check which doesn't complete in time is taken as a sign that the service is
+    /// unresponsive.
+    fn timed_out() -> Self::ExitValue {
+        health::HealthCheck::Critical
+    }
+
     fn path(&self) -> &Path {
         &self.render_pair.path
     }
@@ -608,6 +806,10 @@ impl Hook for SmokeTestHook {
         }
     }
 
+    fn timed_out() -> Self::ExitValue {
+        health::SmokeCheck::Failed(-1)
+    }
+
     fn path(&self) -> &Path {
         &self.render_pair.path
     }
@@ -771,6 +973,129 @@ impl Hook for PostStopHook {
     }
 }
 
+/// Run before the package of a service is updated, by the package which is being replaced. A
+/// failing run aborts the update, keeping the running release.
+#[derive(Debug, Serialize)]
+pub struct PreUpdateHook {
+    render_pair: RenderPair,
+    stdout_log_path: PathBuf,
+    stderr_log_path: PathBuf,
+}
+
+impl Hook for PreUpdateHook {
+    type ExitValue = bool;
+
+    fn file_name() -> &'static str {
+        "pre-update"
+    }
+
+    fn new(service_group: &ServiceGroup, pair: RenderPair) -> Self {
+        PreUpdateHook {
+            render_pair: pair,
+            stdout_log_path: stdout_log_path::<Self>(service_group),
+            stderr_log_path: stderr_log_path::<Self>(service_group),
+        }
+    }
+
+    fn handle_exit<'a>(
+        &self,
+        service_group: &ServiceGroup,
+        _: &'a HookOutput,
+        status: &ExitStatus,
+    ) -> Self::ExitValue {
+        match status.code() {
+            Some(0) => true,
+            Some(code) => {
+                outputln!(preamble service_group, "Pre update failed! '{}' exited with \
+                    status code {}", Self::file_name(), code);
+                false
+            }
+            None => {
+                outputln!(preamble service_group, "Pre update failed! '{}' exited without a \
+                    status code", Self::file_name());
+                false
+            }
+        }
+    }
+
+    fn path(&self) -> &Path {
+        &self.render_pair.path
+    }
+
+    fn renderer(&self) -> &TemplateRenderer {
+        &self.render_pair.renderer
+    }
+
+    fn stdout_log_path(&self) -> &Path {
+        &self.stdout_log_path
+    }
+
+    fn stderr_log_path(&self) -> &Path {
+        &self.stderr_log_path
+    }
+}
+
+/// Run once the updated package of a service has been started, by the updated package.
+#[derive(Debug, Serialize)]
+pub struct PostUpdateHook {
+    render_pair: RenderPair,
+    stdout_log_path: PathBuf,
+    stderr_log_path: PathBuf,
+}
+
+impl Hook for PostUpdateHook {
+    type ExitValue = bool;
+
+    fn file_name() -> &'static str {
+        "post-update"
+    }
+
+    fn new(service_group: &ServiceGroup, pair: RenderPair) -> Self {
+        PostUpdateHook {
+            render_pair: pair,
+            stdout_log_path: stdout_log_path::<Self>(service_group),
+            stderr_log_path: stderr_log_path::<Self>(service_group),
+        }
+    }
+
+    fn handle_exit<'a>(
+        &self,
+        service_group: &ServiceGroup,
+        _: &'a HookOutput,
+        status: &ExitStatus,
+    ) -> Self::ExitValue {
+        match status.code() {
+            Some(0) => true,
+            Some(code) => {
+                outputln!(preamble service_group, "Post update failed! '{}' exited with \
+                    status code {}", Self::file_name(), code);
+                false
+            }
+            None => {
+                outputln!(preamble service_group, "Post update failed! '{}' exited without a \
+                    status code", Self::file_name());
+                false
+            }
+        }
+    }
+
+    fn path(&self) -> &Path {
+        &self.render_pair.path
+    }
+
+    fn renderer(&self) -> &TemplateRenderer {
+        &self.render_pair.renderer
+    }
+
+    fn stdout_log_path(&self) -> &Path {
+        &self.stdout_log_path
+    }
+
+    fn stderr_log_path(&self) -> &Path {
+        &self.stderr_log_path
+    }
+}
+
 /// Cryptographically hash the contents of the compiled hook
 /// file.
 ///
@@ -814,6 +1139,8 @@ pub struct HookTable {
     pub post_run: Option<PostRunHook>,
     pub smoke_test: Option<SmokeTestHook>,
     pub post_stop: Option<PostStopHook>,
+    pub pre_update: Option<PreUpdateHook>,
+    pub post_update: Option<PostUpdateHook>,
 }
 
 impl HookTable {
@@ -836,6 +1163,8 @@ impl HookTable {
                 table.post_run = PostRunHook::load(service_group, &hooks_path, &templates);
                 table.smoke_test = SmokeTestHook::load(service_group, &hooks_path, &templates);
                 table.post_stop = PostStopHook::load(service_group, &hooks_path, &templates);
+                table.pre_update = PreUpdateHook::load(service_group, &hooks_path, &templates);
+                table.post_update = PostUpdateHook::load(service_group, &hooks_path, &templates);
             }
         }
         debug!(
@@ -884,6 +1213,12 @@ impl HookTable {
         if let Some(ref hook) = self.post_stop {
             changed = self.compile_one(hook, service_group, ctx) || changed;
         }
+        if let Some(ref hook) = self.pre_update {
+            changed = self.compile_one(hook, service_group, ctx) || changed;
+        }
+        if let Some(ref hook) = self.post_update {
+            changed = self.compile_one(hook, service_group, ctx) || changed;
+        }
         outputln!(preamble service_group, "Hooks compiled");
         changed
     }
@@ -944,6 +1279,71 @@ impl Serialize for RenderPair {
     }
 }
 
+impl<T> HookRun<T> {
+    fn completed(value: T) -> Self {
+        HookRun {
+            value: value,
+            exceeded_timeout: None,
+        }
+    }
+
+    /// The entry recording this run as timed out, if it was.
+    pub fn timed_out_hook(&self) -> Option<TimedOutHook> {
+        self.exceeded_timeout.map(|timeout| TimedOutHook {
+            timeout: timeout.as_secs(),
+            killed_at: time::get_time().sec,
+        })
+    }
+}
+
+/// Add a run of one of the service's hooks to its history.
+fn record_execution(service_group: &ServiceGroup, execution: &HookExecution) {
+    let history = HookHistory::new(fs::svc_logs_path(service_group.service()));
+    if let Err(err) = history.record(execution) {
+        warn!(
+            "Couldn't record the run of {} in its history, {}, {}",
+            execution.hook, service_group, err
+        );
+    }
+}
+
+/// Kills a hook's process, along with any processes it started, once it has run for too long.
+struct Watchdog {
+    done: mpsc::Sender<()>,
+    fired: Arc<AtomicBool>,
+    handle: JoinHandle<()>,
+}
+
+impl Watchdog {
+    /// Kill the process with the given pid, and the processes it started, unless the watchdog is
+    /// stopped within `timeout`.
+    fn start(pid: u32, timeout: Duration) -> Self {
+        let (done, rx) = mpsc::channel();
+        let fired = Arc::new(AtomicBool::new(false));
+        let watchdog_fired = fired.clone();
+        let handle = thread::spawn(move || {
+            if let Err(RecvTimeoutError::Timeout) = rx.recv_timeout(timeout) {
+                watchdog_fired.store(true, Ordering::SeqCst);
+                if let Err(err) = exec::kill(pid) {
+                    debug!("Couldn't kill hook process {}, {}", pid, err);
+                }
+            }
+        });
+        Watchdog {
+            done: done,
+            fired: fired,
+            handle: handle,
+        }
+    }
+
+    /// Stop the watchdog, returning whether it killed the process.
+    fn stop(self) -> bool {
+        let _ = self.done.send(());
+        let _ = self.handle.join();
+        self.fired.load(Ordering::SeqCst)
+    }
+}
+
 pub struct HookOutput<'a> {
     stdout_log_file: &'a Path,
     stderr_log_file: &'a Path,
@@ -1528,4 +1928,63 @@ echo "The message is Hello"
 
         fs::remove_dir_all(tmp_dir).expect("remove temp dir");
     }
+
+    #[test]
+    #[cfg(unix)]
+    fn watchdog_kills_process_group_after_timeout() {
+        use libc;
+        use std::io::Read;
+        use std::os::unix::process::CommandExt;
+
+        // The backgrounded sleep holds stdout open, so reading it to the end only finishes once
+        // the whole process group has been killed.
+        let mut child = Command::new("sh")
+            .args(&["-c", "sleep 30 & wait"])
+            .stdout(Stdio::piped())
+            .before_exec(|| {
+                unsafe {
+                    libc::setpgid(0, 0);
+                }
+                Ok(())
+            })
+            .spawn()
+            .expect("couldn't run sh");
+        let watchdog = Watchdog::start(child.id(), Duration::from_millis(100));
+        let mut output = String::new();
+        child
+            .stdout
+            .take()
+            .unwrap()
+            .read_to_string(&mut output)
+            .unwrap();
+        let status = child.wait().unwrap();
+        assert!(watchdog.stop());
+        assert!(!status.success());
+
+        let mut child = Command::new("true").spawn().expect("couldn't run true");
+        let watchdog = Watchdog::start(child.id(), Duration::from_secs(30));
+        assert!(child.wait().unwrap().success());
+        assert!(!watchdog.stop());
+    }
+
+    #[test]
+    fn hook_timeouts_fall_back_to_default() {
+        let mut timeouts = HookTimeouts::default();
+        assert_eq!(timeouts.timeout::<InitHook>(), None);
+        assert_eq!(timeouts.to_string(), "none");
+
+        timeouts.default = Some(60);
+        timeouts.init = Some(600);
+        timeouts.post_stop = Some(0);
+        timeouts.pre_update = Some(900);
+        assert_eq!(timeouts.timeout::<InitHook>(), Some(Duration::from_secs(600)));
+        assert_eq!(timeouts.timeout::<ReloadHook>(), Some(Duration::from_secs(60)));
+        assert_eq!(timeouts.timeout::<PostStopHook>(), None);
+        assert_eq!(timeouts.timeout::<PreUpdateHook>(), Some(Duration::from_secs(900)));
+        assert_eq!(timeouts.timeout::<PostUpdateHook>(), Some(Duration::from_secs(60)));
+        assert_eq!(
+            timeouts.to_string(),
+            "default=60 init=600 post_stop=0 pre_update=900"
+        );
+    }
 }
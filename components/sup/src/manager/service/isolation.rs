@@ -0,0 +1,85 @@
+// Copyright (c) 2018 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use launcher_client;
+
+/// Linux namespaces the Launcher isolates a service's process in. Isolation is opt-in and
+/// configured in the service's spec; by default the service shares the host's namespaces.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(default)]
+pub struct Isolation {
+    /// Private mount namespace, where `/hab/pkgs` is read-only and `/tmp` is private.
+    pub mount: bool,
+    /// PID namespace, where the service only sees its own processes.
+    pub pid: bool,
+    /// Network namespace, where the service only has a loopback interface.
+    pub network: bool,
+}
+
+impl Isolation {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The namespaces to send to the Launcher when spawning the process, unless none are set.
+    pub fn to_launcher(&self) -> Option<launcher_client::Isolation> {
+        if self.is_empty() {
+            return None;
+        }
+        let mut isolation = launcher_client::Isolation::new();
+        isolation.set_mount(self.mount);
+        isolation.set_pid(self.pid);
+        isolation.set_network(self.network);
+        Some(isolation)
+    }
+}
+
+impl fmt::Display for Isolation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let namespaces: Vec<&str> = vec![
+            ("mount", self.mount),
+            ("pid", self.pid),
+            ("network", self.network),
+        ].into_iter()
+            .filter_map(|(name, set)| if set { Some(name) } else { None })
+            .collect();
+        if namespaces.is_empty() {
+            write!(f, "none")
+        } else {
+            write!(f, "{}", namespaces.join(" "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn isolation_to_launcher() {
+        assert_eq!(Isolation::default().to_string(), "none");
+        assert!(Isolation::default().to_launcher().is_none());
+
+        let isolation = Isolation {
+            mount: true,
+            pid: true,
+            network: false,
+        };
+        assert_eq!(isolation.to_string(), "mount pid");
+        let msg = isolation.to_launcher().unwrap();
+        assert!(msg.get_mount() && msg.get_pid() && !msg.get_network());
+    }
+}
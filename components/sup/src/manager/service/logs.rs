@@ -0,0 +1,252 @@
+// Copyright (c) 2018 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Reader for the output of a service as recorded by the Launcher.
+//!
+//! The Launcher appends each line a service writes to standard out or standard error to the
+//! service's log file as `<timestamp> <stream> <line>`, where `timestamp` is an RFC 3339 UTC
+//! timestamp and `stream` is `O` or `E`. Once the file grows too large it is moved aside to
+//! `<path>.1` and a new file is started.
+
+use std::collections::VecDeque;
+use std::ffi::OsString;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Seek, SeekFrom};
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+
+use protocol::ctl::svc_log_line::Stream;
+use protocol::ctl::SvcLogLine;
+use time;
+
+/// Time to wait between checks for new output while following a log.
+const FOLLOW_INTERVAL_MS: u64 = 500;
+
+pub struct ServiceLog {
+    path: PathBuf,
+}
+
+impl ServiceLog {
+    pub fn new<T>(path: T) -> Self
+    where
+        T: Into<PathBuf>,
+    {
+        ServiceLog { path: path.into() }
+    }
+
+    pub fn exists(&self) -> bool {
+        self.path.exists()
+    }
+
+    /// Read the recorded output written at or after `since`, keeping only the last `tail` lines
+    /// if specified. The byte offset at which reading stopped is returned along with the lines so
+    /// the caller may continue with [`ServiceLog::follow`].
+    pub fn read(
+        &self,
+        tail: Option<usize>,
+        since: Option<i64>,
+    ) -> io::Result<(Vec<SvcLogLine>, u64)> {
+        let mut lines = VecDeque::new();
+        let rotated = self.rotated_path();
+        if rotated.exists() {
+            read_lines(File::open(&rotated)?, tail, since, &mut lines)?;
+        }
+        let offset = read_lines(File::open(&self.path)?, tail, since, &mut lines)?;
+        Ok((lines.into_iter().collect(), offset))
+    }
+
+    /// Send each line written to the log after `offset` to the given callback as it appears,
+    /// until the callback returns false. While no output is written, `keep_following` is checked
+    /// every time the log is polled, and following stops once it returns false.
+    pub fn follow<K, F>(&self, offset: u64, keep_following: K, mut f: F) -> io::Result<()>
+    where
+        K: Fn() -> bool,
+        F: FnMut(SvcLogLine) -> bool,
+    {
+        let mut file = File::open(&self.path)?;
+        file.seek(SeekFrom::Start(offset))?;
+        let mut reader = BufReader::new(file);
+        let mut offset = offset;
+        let mut buf = String::new();
+        loop {
+            let read = reader.read_line(&mut buf)?;
+            if read > 0 {
+                offset += read as u64;
+                // A partially written line is kept in the buffer until the rest of it arrives.
+                if !buf.ends_with('\n') {
+                    continue;
+                }
+                if let Some(line) = parse_line(&buf) {
+                    if !f(line) {
+                        return Ok(());
+                    }
+                }
+                buf.clear();
+                continue;
+            }
+            if !keep_following() {
+                return Ok(());
+            }
+            thread::sleep(Duration::from_millis(FOLLOW_INTERVAL_MS));
+            // The Launcher starts a new file when rotating, so the one we have open will never
+            // grow again once the file on disk is smaller than what we've already read.
+            match fs::metadata(&self.path) {
+                Ok(ref meta) if meta.len() < offset => {
+                    reader = BufReader::new(File::open(&self.path)?);
+                    offset = 0;
+                    buf.clear();
+                }
+                Ok(_) => (),
+                Err(ref err) if err.kind() == io::ErrorKind::NotFound => (),
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    fn rotated_path(&self) -> PathBuf {
+        let mut path = OsString::from(self.path.as_os_str());
+        path.push(".1");
+        PathBuf::from(path)
+    }
+}
+
+/// Parse a single line as written by the Launcher. Lines which don't conform are ignored.
+fn parse_line(raw: &str) -> Option<SvcLogLine> {
+    let mut parts = raw.trim_right_matches('\n').splitn(3, ' ');
+    let timestamp = match parts.next().map(|t| time::strptime(t, "%Y-%m-%dT%H:%M:%SZ")) {
+        Some(Ok(tm)) => tm.to_timespec().sec,
+        _ => return None,
+    };
+    let stream = match parts.next() {
+        Some("O") => Stream::Stdout,
+        Some("E") => Stream::Stderr,
+        _ => return None,
+    };
+    Some(SvcLogLine {
+        stream: stream as i32,
+        timestamp: timestamp,
+        line: parts.next().unwrap_or_default().to_string(),
+    })
+}
+
+fn read_lines(
+    file: File,
+    tail: Option<usize>,
+    since: Option<i64>,
+    lines: &mut VecDeque<SvcLogLine>,
+) -> io::Result<u64> {
+    let mut reader = BufReader::new(file);
+    let mut offset = 0;
+    let mut buf = String::new();
+    loop {
+        let read = reader.read_line(&mut buf)?;
+        // Stop short of a partially written line so a follower will pick it up once complete.
+        if read == 0 || !buf.ends_with('\n') {
+            break;
+        }
+        offset += read as u64;
+        if let Some(line) = parse_line(&buf) {
+            if since.map_or(true, |since| line.timestamp >= since) {
+                lines.push_back(line);
+                if tail.map_or(false, |tail| lines.len() > tail) {
+                    lines.pop_front();
+                }
+            }
+        }
+        buf.clear();
+    }
+    Ok(offset)
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs::File;
+    use std::io::Write;
+    use std::path::Path;
+
+    use tempdir::TempDir;
+
+    use super::*;
+
+    fn write_log(path: &Path, content: &str) {
+        let mut file = File::create(path).expect("failed to create log");
+        file.write_all(content.as_bytes())
+            .expect("failed to write log");
+    }
+
+    #[test]
+    fn parse_line_stdout() {
+        let line = parse_line("2018-03-01T12:00:00Z O hello world\n").unwrap();
+        assert_eq!(line.stream, Stream::Stdout as i32);
+        assert_eq!(line.timestamp, 1519905600);
+        assert_eq!(line.line, "hello world");
+    }
+
+    #[test]
+    fn parse_line_stderr() {
+        let line = parse_line("2018-03-01T12:00:00Z E uh oh\n").unwrap();
+        assert_eq!(line.stream, Stream::Stderr as i32);
+        assert_eq!(line.line, "uh oh");
+    }
+
+    #[test]
+    fn parse_line_invalid() {
+        assert!(parse_line("not a log line\n").is_none());
+        assert!(parse_line("2018-03-01T12:00:00Z X what\n").is_none());
+    }
+
+    #[test]
+    fn read_tail_and_since() {
+        let tmpdir = TempDir::new("logs").unwrap();
+        let path = tmpdir.path().join("service.log");
+        write_log(
+            &tmpdir.path().join("service.log.1"),
+            "2018-03-01T12:00:00Z O one\n",
+        );
+        write_log(
+            &path,
+            "2018-03-01T12:00:01Z O two\n2018-03-01T12:00:02Z E three\n2018-03-01T12:00:03Z O four",
+        );
+        let log = ServiceLog::new(&path);
+
+        let (lines, offset) = log.read(None, None).unwrap();
+        let text: Vec<&str> = lines.iter().map(|l| l.line.as_str()).collect();
+        assert_eq!(text, vec!["one", "two", "three"]);
+        assert_eq!(offset, 56);
+
+        let (lines, _) = log.read(Some(2), None).unwrap();
+        let text: Vec<&str> = lines.iter().map(|l| l.line.as_str()).collect();
+        assert_eq!(text, vec!["two", "three"]);
+
+        let (lines, _) = log.read(None, Some(1519905602)).unwrap();
+        let text: Vec<&str> = lines.iter().map(|l| l.line.as_str()).collect();
+        assert_eq!(text, vec!["three"]);
+    }
+
+    #[test]
+    fn follow_stops_when_idle_and_told_to() {
+        let tmpdir = TempDir::new("logs").unwrap();
+        let path = tmpdir.path().join("service.log");
+        write_log(&path, "2018-03-01T12:00:00Z O one\n2018-03-01T12:00:01Z O two\n");
+        let log = ServiceLog::new(&path);
+
+        let mut text = vec![];
+        log.follow(27, || false, |line| {
+            text.push(line.line);
+            true
+        }).unwrap();
+        assert_eq!(text, vec!["two"]);
+    }
+}
@@ -16,13 +16,20 @@ mod composite_spec;
 pub mod config;
 mod dir;
 mod health;
+mod hook_history;
 pub mod hooks;
+mod isolation;
+mod logs;
 mod package;
+mod probe;
+mod resources;
+mod restart;
 mod spec;
+mod stats;
 mod supervisor;
 
 use std;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::fmt;
 use std::fs::File;
 use std::io::prelude::*;
@@ -34,28 +41,45 @@ use std::time::{Duration, Instant};
 use butterfly::rumor::service::Service as ServiceRumor;
 use hcore::crypto::hash;
 use hcore::fs::FS_ROOT_PATH;
+#[cfg(unix)]
+use hcore::os::process::Signal;
 use hcore::package::metadata::Bind;
 use hcore::package::{PackageIdent, PackageInstall};
 use hcore::service::ServiceGroup;
 use hcore::util::perm::{set_owner, set_permissions};
+use hcore::util::serialize_using_to_string;
 use launcher_client::LauncherCli;
-pub use protocol::types::{BindingMode, ProcessState, Topology, UpdateStrategy};
-use time::Timespec;
+pub use protocol::types::service_status::{ElectionRole, UpdateState};
+pub use protocol::types::{BindingMode, ProcessState, RestartPolicy, Topology, UpdateStrategy};
+use time::{self, Timespec};
 
 pub use self::composite_spec::CompositeSpec;
 use self::config::CfgRenderer;
 pub use self::config::{Cfg, UserConfigPath};
 use self::dir::SvcDir;
-pub use self::health::{HealthCheck, SmokeCheck};
-use self::hooks::{Hook, HookTable, HOOK_PERMISSIONS};
+pub use self::health::{HealthCheck, HealthCheckSettings, SmokeCheck};
+pub use self::hook_history::{HookExecution, HookHistory};
+pub use self::isolation::Isolation;
+pub use self::logs::ServiceLog;
+use self::hooks::{FileUpdatedHook, HealthCheckHook, Hook, HookRun, HookTable, HookTimeouts,
+                  InitHook, PostRunHook, PostStopHook, PostUpdateHook, PreUpdateHook,
+                  ReconfigureHook, ReloadHook, TimedOutHook, HOOK_PERMISSIONS,
+                  UPDATE_FROM_ENVVAR, UPDATE_TO_ENVVAR};
 pub use self::package::{Env, Pkg};
+pub use self::probe::HealthProbe;
+pub use self::resources::{ResourceLimits, ResourceUsage, Rlimits};
+pub use self::restart::RestartSettings;
+use self::probe::DEFAULT_PROBE_TIMEOUT;
 pub use self::spec::{BindMap, DesiredState, IntoServiceSpec, ServiceBind, ServiceSpec, Spec};
-use self::supervisor::Supervisor;
+pub use self::stats::ProcessStats;
+pub use self::supervisor::ShutdownConfig;
+use self::supervisor::{ambient_capabilities_from_package, Supervisor};
 use super::Sys;
 use census::{CensusGroup, CensusRing, ElectionStatus, ServiceFile};
 use error::{Error, Result, SupError};
 use fs;
 use manager;
+use metrics;
 use sys::abilities;
 use templating::RenderContext;
 
@@ -63,10 +87,6 @@ static LOGKEY: &'static str = "SR";
 
 pub const GOSSIP_FILE_PERMISSIONS: u32 = 0o640;
 
-lazy_static! {
-    static ref HEALTH_CHECK_INTERVAL: Duration = { Duration::from_millis(30_000) };
-}
-
 /// When evaluating whether a particular service group can satisfy a
 /// bind of the Service, there are several states it can be
 /// in. Depending on which point in the lifecycle of the Service we
@@ -96,6 +116,10 @@ pub struct Service {
     pub channel: String,
     pub spec_file: PathBuf,
     pub spec_ident: PackageIdent,
+    /// Whether the service's spec asks for it to be running. Services which were stopped are
+    /// still loaded to report their state, with a desired state of down.
+    #[serde(serialize_with = "serialize_using_to_string")]
+    pub desired_state: DesiredState,
     pub topology: Topology,
     pub update_strategy: UpdateStrategy,
     pub cfg: Cfg,
@@ -107,9 +131,45 @@ pub struct Service {
     #[serde(skip_serializing)]
     config_renderer: CfgRenderer,
     health_check: HealthCheck,
+    /// Health check settings of the service's package, overridden by those of its spec.
+    health_check_settings: HealthCheckSettings,
+    /// Health check settings given by the service's spec.
+    #[serde(skip_serializing)]
+    health_check_overrides: HealthCheckSettings,
+    /// Consecutive health check results which disagreed with `health_check`.
+    #[serde(skip_serializing)]
+    health_check_streak: u32,
+    /// Health probe of the service's package, replaced by that of its spec.
+    health_probe: Option<HealthProbe>,
+    /// Health probe given by the service's spec.
+    #[serde(skip_serializing)]
+    health_probe_override: Option<HealthProbe>,
+    hook_timeouts: HookTimeouts,
+    /// Resource limits given by the service's spec.
+    #[serde(skip_serializing)]
+    resource_overrides: ResourceLimits,
+    /// Rlimits given by the service's spec.
+    #[serde(skip_serializing)]
+    rlimit_overrides: Rlimits,
+    /// Ambient capabilities given by the service's spec, replacing those of its package.
+    #[serde(skip_serializing)]
+    capabilities_override: Option<Vec<String>>,
+    /// Shutdown settings given by the service's spec.
+    #[serde(skip_serializing)]
+    shutdown_overrides: ShutdownConfig,
+    /// Hooks which were killed on their last run for exceeding their timeout.
+    timed_out_hooks: BTreeMap<&'static str, TimedOutHook>,
     last_election_status: ElectionStatus,
+    /// Role of this Supervisor in the leader election of the service group.
+    election_role: ElectionRole,
+    /// Progress of the service through its update strategy, maintained by the `ServiceUpdater`.
+    pub update_state: UpdateState,
+    /// The package the service was updated from, until its `post-update` hook has run.
+    #[serde(skip_serializing)]
+    updated_from: Option<PackageIdent>,
     needs_reload: bool,
     needs_reconfiguration: bool,
+    needs_restart: bool,
     smoke_check: SmokeCheck,
     /// The mapping of bind name to a service group, specified by the
     /// user when the service definition was loaded into the Supervisor.
@@ -131,10 +191,9 @@ pub struct Service {
     /// requisite exports, because no live members of the group exist,
     /// or because the group itself does not exist in the census.
     ///
-    /// We don't serialize because this is purely runtime information
-    /// that should be reconciled against the current state of the
-    /// census.
-    #[serde(skip_serializing)]
+    /// This is purely runtime information that should be reconciled
+    /// against the current state of the census; it is only serialized
+    /// to report the status of the service.
     unsatisfied_binds: HashSet<ServiceBind>,
     hooks: HookTable,
     config_from: Option<PathBuf>,
@@ -172,6 +231,21 @@ impl Service {
         )?;
         let config_root = Self::config_root(&pkg, spec.config_from.as_ref());
         let hooks_root = Self::hooks_root(&pkg, spec.config_from.as_ref());
+        let health_check_settings =
+            HealthCheckSettings::from_package(&pkg.path).merge(&spec.health_check);
+        let health_probe = spec.health_probe
+            .clone()
+            .or_else(|| HealthProbe::from_package(&pkg.path));
+        let shutdown_overrides = ShutdownConfig::from_spec(&spec);
+        let mut supervisor = Supervisor::new(&service_group);
+        supervisor.shutdown = ShutdownConfig::from_package(&pkg.path).merge(&shutdown_overrides);
+        supervisor.restart = spec.restart;
+        supervisor.isolation = spec.isolation;
+        supervisor.resources = ResourceLimits::from_package(&pkg.path).merge(&spec.resources);
+        supervisor.rlimits = Rlimits::from_package(&pkg.path).merge(&spec.rlimits);
+        supervisor.ambient_capabilities = spec.ambient_capabilities
+            .clone()
+            .unwrap_or_else(|| ambient_capabilities_from_package(&pkg.path));
         Ok(Service {
             sys: sys,
             cfg: Cfg::new(&pkg, spec.config_from.as_ref())?,
@@ -179,6 +253,17 @@ impl Service {
             bldr_url: spec.bldr_url,
             channel: spec.channel,
             health_check: HealthCheck::default(),
+            health_check_settings: health_check_settings,
+            health_check_overrides: spec.health_check,
+            health_check_streak: 0,
+            health_probe: health_probe,
+            health_probe_override: spec.health_probe,
+            hook_timeouts: spec.hook_timeouts,
+            resource_overrides: spec.resources,
+            rlimit_overrides: spec.rlimits,
+            capabilities_override: spec.ambient_capabilities,
+            shutdown_overrides: shutdown_overrides,
+            timed_out_hooks: BTreeMap::new(),
             hooks: HookTable::load(
                 &service_group,
                 &hooks_root,
@@ -186,11 +271,15 @@ impl Service {
             ),
             initialized: false,
             last_election_status: ElectionStatus::None,
+            election_role: ElectionRole::Standalone,
+            update_state: UpdateState::None,
+            updated_from: None,
             needs_reload: false,
             needs_reconfiguration: false,
+            needs_restart: false,
             user_config_updated: false,
             manager_fs_cfg: manager_fs_cfg,
-            supervisor: Supervisor::new(&service_group),
+            supervisor: supervisor,
             pkg: pkg,
             service_group: service_group,
             smoke_check: SmokeCheck::default(),
@@ -199,6 +288,7 @@ impl Service {
             unsatisfied_binds: HashSet::new(),
             binding_mode: spec.binding_mode,
             spec_ident: spec.ident,
+            desired_state: spec.desired_state,
             spec_file: spec_file,
             topology: spec.topology,
             update_strategy: spec.update_strategy,
@@ -258,6 +348,7 @@ impl Service {
         } else {
             self.needs_reload = false;
             self.needs_reconfiguration = false;
+            self.needs_restart = false;
         }
     }
 
@@ -268,28 +359,46 @@ impl Service {
         }
     }
 
+    /// Request that the service's process be restarted on the next tick, regardless of whether
+    /// a reload hook is present.
+    pub fn request_restart(&mut self) {
+        self.needs_restart = true;
+    }
+
+    /// Send the given signal to the service's process.
+    #[cfg(unix)]
+    pub fn signal(&self, signal: Signal) -> Result<()> {
+        self.supervisor.signal(signal)
+    }
+
+    fn restart(&mut self, launcher: &LauncherCli) {
+        self.needs_restart = false;
+        if let Some(err) = self.supervisor
+            .restart(
+                &self.pkg,
+                &self.service_group,
+                launcher,
+                self.svc_encrypted_password.as_ref(),
+            )
+            .err()
+        {
+            outputln!(preamble self.service_group, "Service restart failed: {}", err);
+        }
+    }
+
     /// Runs the reconfigure hook if present, otherwise restarts the service.
     fn reload(&mut self, launcher: &LauncherCli) {
         self.needs_reload = false;
         if self.process_down() || self.hooks.reload.is_none() {
-            if let Some(err) = self.supervisor
-                .restart(
-                    &self.pkg,
-                    &self.service_group,
-                    launcher,
-                    self.svc_encrypted_password.as_ref(),
-                )
-                .err()
-            {
-                outputln!(preamble self.service_group, "Service restart failed: {}", err);
-            }
+            self.restart(launcher);
         } else {
-            let hook = self.hooks.reload.as_ref().unwrap();
-            hook.run(
+            let run = self.hooks.reload.as_ref().unwrap().execute(
                 &self.service_group,
                 &self.pkg,
                 self.svc_encrypted_password.as_ref(),
+                &self.hook_timeouts,
             );
+            self.record_hook_run(ReloadHook::file_name(), &run);
         }
     }
 
@@ -297,6 +406,39 @@ impl Service {
         self.supervisor.state_entered
     }
 
+    /// Result of the last health check of the service.
+    pub fn health_check(&self) -> HealthCheck {
+        self.health_check
+    }
+
+    /// Publish the current state of the service's process, its resources and health to the
+    /// Supervisor's metrics.
+    pub fn observe_metrics(&self) {
+        let uptime = (time::get_time() - self.supervisor.state_entered).num_seconds();
+        metrics::observe_service(
+            &self.service_group,
+            self.supervisor.state,
+            uptime,
+            self.supervisor.restart_count(),
+            self.health_check,
+            self.supervisor.stats(),
+            self.supervisor.cgroup_usage(),
+        );
+        if let Some(cpu_usec) = self.supervisor.cpu_usec_total() {
+            metrics::observe_cpu(&self.service_group, cpu_usec);
+        }
+    }
+
+    /// Returns a reader for the output of this service which the Launcher records to disk.
+    pub fn output_log(&self) -> ServiceLog {
+        ServiceLog::new(fs::svc_output_log(self.service_group.service()))
+    }
+
+    /// Returns the recorded history of the runs of this service's hooks.
+    pub fn hook_history(&self) -> HookHistory {
+        HookHistory::new(fs::svc_logs_path(self.service_group.service()))
+    }
+
     /// Performs updates and executes hooks.
     ///
     /// Returns `true` if the service was updated.
@@ -336,6 +478,7 @@ impl Service {
                 let census_group = census_ring
                     .census_group_for(&self.service_group)
                     .expect("Service Group's census entry missing from list!");
+                self.election_role = election_role(census_group);
                 match census_group.election_status {
                     ElectionStatus::None => {
                         if self.last_election_status != census_group.election_status {
@@ -397,6 +540,16 @@ impl Service {
         if let Some(ref password) = self.svc_encrypted_password {
             spec.svc_encrypted_password = Some(password.clone())
         }
+        spec.health_check = self.health_check_overrides;
+        spec.health_probe = self.health_probe_override.clone();
+        spec.hook_timeouts = self.hook_timeouts;
+        spec.shutdown_signal = self.shutdown_overrides.signal.clone();
+        spec.shutdown_timeout = self.shutdown_overrides.timeout;
+        spec.restart = self.supervisor.restart;
+        spec.isolation = self.supervisor.isolation;
+        spec.resources = self.resource_overrides;
+        spec.rlimits = self.rlimit_overrides;
+        spec.ambient_capabilities = self.capabilities_override.clone();
         spec
     }
 
@@ -531,8 +684,8 @@ impl Service {
     }
 
     /// Updates the process state of the service's supervisor
-    fn check_process(&mut self) -> bool {
-        self.supervisor.check_process()
+    fn check_process(&mut self, launcher: &LauncherCli) -> bool {
+        self.supervisor.check_process(launcher)
     }
 
     fn process_down(&self) -> bool {
@@ -585,9 +738,18 @@ impl Service {
     }
 
     /// Replace the package of the running service and restart its system process.
-    pub fn update_package(&mut self, package: PackageInstall, launcher: &LauncherCli) {
+    ///
+    /// Returns `false` if the service kept its current package, because its `pre-update` hook
+    /// failed or the updated package couldn't be loaded.
+    pub fn update_package(&mut self, package: PackageInstall, launcher: &LauncherCli) -> bool {
         match Pkg::from_install(package) {
             Ok(pkg) => {
+                if !self.pre_update(&pkg.ident) {
+                    outputln!(preamble self.service_group,
+                              "Update to {} aborted by the pre-update hook, keeping {}",
+                              pkg.ident, self.pkg.ident);
+                    return false;
+                }
                 outputln!(preamble self.service_group,
                             "Updating service {} to {}", self.pkg.ident, pkg.ident);
                 match CfgRenderer::new(&Self::config_root(&pkg, self.config_from.as_ref())) {
@@ -595,7 +757,7 @@ impl Service {
                     Err(e) => {
                         outputln!(preamble self.service_group,
                                   "Failed to load config templates after updating package, {}", e);
-                        return;
+                        return false;
                     }
                 }
                 self.hooks = HookTable::load(
@@ -603,18 +765,35 @@ impl Service {
                     &Self::hooks_root(&pkg, self.config_from.as_ref()),
                     fs::svc_hooks_path(self.service_group.service()),
                 );
+                self.health_check_settings = HealthCheckSettings::from_package(&pkg.path)
+                    .merge(&self.health_check_overrides);
+                self.health_probe = self.health_probe_override
+                    .clone()
+                    .or_else(|| HealthProbe::from_package(&pkg.path));
+                self.updated_from = Some(self.pkg.ident.clone());
                 self.pkg = pkg;
             }
             Err(err) => {
                 outputln!(preamble self.service_group,
                           "Unexpected error while updating package, {}", err);
-                return;
+                return false;
             }
         }
         if let Err(err) = self.supervisor.stop(launcher) {
             outputln!(preamble self.service_group,
                       "Error stopping process while updating package: {}", err);
         }
+        // The old process was stopped as its package asked, and the new one is stopped as the
+        // updated package asks.
+        self.supervisor.shutdown =
+            ShutdownConfig::from_package(&self.pkg.path).merge(&self.shutdown_overrides);
+        self.supervisor.resources =
+            ResourceLimits::from_package(&self.pkg.path).merge(&self.resource_overrides);
+        self.supervisor.rlimits =
+            Rlimits::from_package(&self.pkg.path).merge(&self.rlimit_overrides);
+        self.supervisor.ambient_capabilities = self.capabilities_override
+            .clone()
+            .unwrap_or_else(|| ambient_capabilities_from_package(&self.pkg.path));
 
         match self.cfg.update_defaults_from_package(&self.pkg) {
             Ok(maybe_updated) => {
@@ -627,6 +806,7 @@ impl Service {
         }
 
         self.initialized = false;
+        true
     }
 
     pub fn to_rumor(&self, incarnation: u64) -> ServiceRumor {
@@ -657,44 +837,114 @@ impl Service {
         }
         outputln!(preamble self.service_group, "Initializing");
         self.initialized = true;
-        if let Some(ref hook) = self.hooks.init {
-            self.initialized = hook.run(
+        let run = match self.hooks.init {
+            Some(ref hook) => hook.execute(
                 &self.service_group,
                 &self.pkg,
                 self.svc_encrypted_password.as_ref(),
-            )
-        }
+                &self.hook_timeouts,
+            ),
+            None => return,
+        };
+        self.record_hook_run(InitHook::file_name(), &run);
+        self.initialized = run.value;
     }
 
     /// Run reconfigure hook if present.
     fn reconfigure(&mut self) {
         self.needs_reconfiguration = false;
-        if let Some(ref hook) = self.hooks.reconfigure {
-            hook.run(
+        let run = match self.hooks.reconfigure {
+            Some(ref hook) => hook.execute(
                 &self.service_group,
                 &self.pkg,
                 self.svc_encrypted_password.as_ref(),
-            );
-        }
+                &self.hook_timeouts,
+            ),
+            None => return,
+        };
+        self.record_hook_run(ReconfigureHook::file_name(), &run);
     }
 
     fn post_run(&mut self) {
-        if let Some(ref hook) = self.hooks.post_run {
-            hook.run(
+        let run = match self.hooks.post_run {
+            Some(ref hook) => hook.execute(
                 &self.service_group,
                 &self.pkg,
                 self.svc_encrypted_password.as_ref(),
-            );
-        }
+                &self.hook_timeouts,
+            ),
+            None => return,
+        };
+        self.record_hook_run(PostRunHook::file_name(), &run);
     }
 
     fn post_stop(&mut self) {
-        if let Some(ref hook) = self.hooks.post_stop {
-            hook.run(
+        let run = match self.hooks.post_stop {
+            Some(ref hook) => hook.execute(
                 &self.service_group,
                 &self.pkg,
                 self.svc_encrypted_password.as_ref(),
-            );
+                &self.hook_timeouts,
+            ),
+            None => return,
+        };
+        self.record_hook_run(PostStopHook::file_name(), &run);
+    }
+
+    /// Run pre-update hook if present, before the service is updated to the given package.
+    ///
+    /// Returns `false` if the update should be aborted.
+    fn pre_update(&mut self, to: &PackageIdent) -> bool {
+        let pkg = self.update_hook_pkg(&self.pkg.ident, to);
+        let run = match self.hooks.pre_update {
+            Some(ref hook) => hook.execute(
+                &self.service_group,
+                &pkg,
+                self.svc_encrypted_password.as_ref(),
+                &self.hook_timeouts,
+            ),
+            None => return true,
+        };
+        self.record_hook_run(PreUpdateHook::file_name(), &run);
+        run.value
+    }
+
+    /// Run post-update hook if present, once the service has been started after an update.
+    fn post_update(&mut self) {
+        let from = match self.updated_from.take() {
+            Some(from) => from,
+            None => return,
+        };
+        let pkg = self.update_hook_pkg(&from, &self.pkg.ident);
+        let run = match self.hooks.post_update {
+            Some(ref hook) => hook.execute(
+                &self.service_group,
+                &pkg,
+                self.svc_encrypted_password.as_ref(),
+                &self.hook_timeouts,
+            ),
+            None => return,
+        };
+        self.record_hook_run(PostUpdateHook::file_name(), &run);
+    }
+
+    /// The service's package, with the environment of its update hooks.
+    fn update_hook_pkg(&self, from: &PackageIdent, to: &PackageIdent) -> Pkg {
+        let mut pkg = self.pkg.clone();
+        pkg.env.set(UPDATE_FROM_ENVVAR, from.to_string());
+        pkg.env.set(UPDATE_TO_ENVVAR, to.to_string());
+        pkg
+    }
+
+    /// Keep track of which hooks were killed on their last run for exceeding their timeout.
+    fn record_hook_run<T>(&mut self, hook: &'static str, run: &HookRun<T>) {
+        match run.timed_out_hook() {
+            Some(timed_out) => {
+                self.timed_out_hooks.insert(hook, timed_out);
+            }
+            None => {
+                self.timed_out_hooks.remove(hook);
+            }
         }
     }
 
@@ -703,11 +953,12 @@ impl Service {
             return None;
         }
         self.hooks.suitability.as_ref().and_then(|hook| {
-            hook.run(
+            hook.execute(
                 &self.service_group,
                 &self.pkg,
                 self.svc_encrypted_password.as_ref(),
-            )
+                &self.hook_timeouts,
+            ).value
         })
     }
 
@@ -803,7 +1054,7 @@ impl Service {
 
     fn execute_hooks(&mut self, launcher: &LauncherCli) {
         if !self.initialized {
-            if self.check_process() {
+            if self.check_process(launcher) {
                 outputln!("Reattached to {}", self.service_group);
                 self.initialized = true;
                 return;
@@ -812,18 +1063,30 @@ impl Service {
             if self.initialized {
                 self.start(launcher);
                 self.post_run();
+                self.post_update();
             }
         } else {
-            self.check_process();
+            self.check_process(launcher);
             match self.last_health_check {
                 Some(last_check) => {
-                    if Instant::now().duration_since(last_check) >= *HEALTH_CHECK_INTERVAL {
+                    let interval = self.health_check_settings.interval();
+                    if Instant::now().duration_since(last_check) >= interval {
                         self.run_health_check_hook();
                     }
                 }
                 None => self.run_health_check_hook(),
             }
 
+            if self.needs_restart {
+                outputln!(preamble self.service_group, "Restarting service as requested");
+                self.supervisor.reset_restarts();
+                self.restart(launcher);
+            }
+
+            if self.process_down() && !self.supervisor.may_restart(launcher) {
+                return;
+            }
+
             // NOTE: if you need reconfiguration and you DON'T have a
             // reload script, you're going to restart anyway.
             if self.needs_reload || self.process_down() || self.needs_reconfiguration {
@@ -837,17 +1100,21 @@ impl Service {
     }
 
     /// Run file_updated hook if present.
-    fn file_updated(&self) -> bool {
-        if self.initialized {
-            if let Some(ref hook) = self.hooks.file_updated {
-                return hook.run(
-                    &self.service_group,
-                    &self.pkg,
-                    self.svc_encrypted_password.as_ref(),
-                );
-            }
+    fn file_updated(&mut self) -> bool {
+        if !self.initialized {
+            return false;
         }
-        false
+        let run = match self.hooks.file_updated {
+            Some(ref hook) => hook.execute(
+                &self.service_group,
+                &self.pkg,
+                self.svc_encrypted_password.as_ref(),
+                &self.hook_timeouts,
+            ),
+            None => return false,
+        };
+        self.record_hook_run(FileUpdatedHook::file_name(), &run);
+        run.value
     }
 
     /// Write service files from gossip data to disk under
@@ -888,20 +1155,87 @@ impl Service {
     }
 
     fn run_health_check_hook(&mut self) {
-        let check_result = if let Some(ref hook) = self.hooks.health_check {
-            hook.run(
-                &self.service_group,
-                &self.pkg,
-                self.svc_encrypted_password.as_ref(),
-            )
-        } else {
-            match self.supervisor.status() {
+        if self.health_probe.is_some() {
+            return self.run_health_probe();
+        }
+        let run = match self.hooks.health_check {
+            Some(ref hook) => {
+                // The timeout of the health check settings wins over that of the hook timeouts.
+                let mut timeouts = self.hook_timeouts;
+                timeouts.health_check = self.health_check_settings
+                    .timeout
+                    .or(timeouts.health_check);
+                Some(hook.execute(
+                    &self.service_group,
+                    &self.pkg,
+                    self.svc_encrypted_password.as_ref(),
+                    &timeouts,
+                ))
+            }
+            None => None,
+        };
+        let check_result = match run {
+            Some(run) => {
+                self.record_hook_run(HealthCheckHook::file_name(), &run);
+                run.value
+            }
+            None => match self.supervisor.status() {
                 (true, _) => HealthCheck::Ok,
                 (false, _) => HealthCheck::Critical,
-            }
+            },
+        };
+        self.record_health_check(check_result);
+    }
+
+    /// Check the health of the service with its health probe, which replaces its `health_check`
+    /// hook. What the probe found is written where the output of the hook would be.
+    fn run_health_probe(&mut self) {
+        let timeout = Duration::from_secs(
+            self.health_check_settings
+                .timeout
+                .unwrap_or(DEFAULT_PROBE_TIMEOUT),
+        );
+        let result = match self.health_probe {
+            Some(ref probe) => probe.run(&self.pkg, self.svc_encrypted_password.as_ref(), timeout),
+            None => return,
         };
+        let outputs = vec![
+            (
+                hooks::stdout_log_path::<HealthCheckHook>(&self.service_group),
+                &result.stdout,
+            ),
+            (
+                hooks::stderr_log_path::<HealthCheckHook>(&self.service_group),
+                &result.stderr,
+            ),
+        ];
+        for (path, output) in outputs {
+            if let Err(err) = File::create(&path).and_then(|mut f| f.write_all(output.as_bytes())) {
+                warn!(
+                    "Couldn't write health probe output, {}, {}",
+                    self.service_group, err
+                );
+            }
+        }
+        self.record_health_check(result.health);
+    }
+
+    /// Update the health of the service with the result of its latest check.
+    fn record_health_check(&mut self, check_result: HealthCheck) {
         self.last_health_check = Some(Instant::now());
-        self.cache_health_check(check_result);
+        let status = self.health_check_settings.next_status(
+            self.health_check,
+            check_result,
+            &mut self.health_check_streak,
+        );
+        if status != check_result {
+            debug!(
+                "{}, health check returned {}, still reporting {} ({} in a row)",
+                self.service_group, check_result, status, self.health_check_streak
+            );
+        }
+        self.health_check = status;
+        self.cache_health_check(status);
     }
 
     // Returns `false` if the write fails.
@@ -975,3 +1309,77 @@ impl fmt::Display for Service {
         write!(f, "{} [{}]", self.service_group, self.pkg.ident)
     }
 }
+
+/// Determine the role of this Supervisor in the leader election of the given service group.
+fn election_role(census_group: &CensusGroup) -> ElectionRole {
+    match (census_group.election_status, census_group.me()) {
+        (ElectionStatus::ElectionFinished, Some(me))
+            if census_group.leader_id.as_ref() == Some(&me.member_id) =>
+        {
+            ElectionRole::Leader
+        }
+        (ElectionStatus::ElectionFinished, Some(_)) => ElectionRole::Follower,
+        _ => ElectionRole::Candidate,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use hcore::package::{PackageIdent, PackageInstall};
+    use protocol;
+    use serde_json;
+    use tempdir::TempDir;
+
+    use super::*;
+    use config::GossipListenAddr;
+    use http_gateway;
+
+    #[test]
+    fn service_matches_openapi_schema() {
+        let root = TempDir::new("service").expect("Could not create tempdir");
+        let ident = PackageIdent::new("core", "redis", Some("4.0.8"), Some("20180301000000"));
+        let service = new_service(&root, ident.clone(), ServiceSpec::default_for(ident));
+        http_gateway::test::assert_valid_schema("service", &service);
+    }
+
+    #[test]
+    fn stopped_service_status() {
+        let root = TempDir::new("service").expect("Could not create tempdir");
+        let ident = PackageIdent::new("core", "redis", Some("4.0.8"), Some("20180301000000"));
+        let mut spec = ServiceSpec::default_for(ident.clone());
+        spec.desired_state = DesiredState::Down;
+        let service = new_service(&root, ident, spec);
+
+        let data = serde_json::to_string(&service).expect("Could not serialize service");
+        let status: manager::ServiceStatus =
+            serde_json::from_str(&data).expect("Could not deserialize status");
+        assert_eq!(status.desired_state, DesiredState::Down);
+        let proto = protocol::types::ServiceStatus::from(status);
+        assert_eq!(
+            proto.desired_state,
+            Some(protocol::types::service_status::DesiredState::Down as i32)
+        );
+    }
+
+    fn new_service(root: &TempDir, ident: PackageIdent, spec: ServiceSpec) -> Service {
+        let install = PackageInstall::new_from_parts(
+            ident,
+            root.path().to_path_buf(),
+            root.path().to_path_buf(),
+            root.path().to_path_buf(),
+        );
+        let sys = Sys::new(
+            true,
+            GossipListenAddr::default(),
+            protocol::ctl::default_addr(),
+            http_gateway::ListenAddr::default(),
+        );
+        Service::new(
+            Arc::new(sys),
+            install,
+            spec,
+            Arc::new(manager::FsCfg::new(root.path())),
+            None,
+        ).expect("Could not create service")
+    }
+}
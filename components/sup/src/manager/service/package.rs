@@ -66,6 +66,15 @@ impl Env {
         Ok(Env(env))
     }
 
+    /// Set a variable of the environment, replacing any previous value.
+    pub fn set<K, V>(&mut self, key: K, value: V)
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.0.insert(key.into(), value.into());
+    }
+
     fn transform_path(path: Option<&String>) -> Result<String> {
         let mut paths: Vec<PathBuf> = match path {
             Some(path) => env::split_paths(&path).collect(),
@@ -0,0 +1,447 @@
+// Copyright (c) 2018 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Health probes which the Supervisor runs itself, in place of a `health_check` hook.
+//!
+//! A probe is declared by the `pkg_health_probe` of a plan, or by the `[health_probe]` table of
+//! a service spec which replaces it:
+//!
+//! ```toml
+//! [health_probe]
+//! type = "http"
+//! url = "http://localhost:8080/healthz"
+//! status = 200
+//! body = "ok"
+//! ```
+//!
+//! An HTTP probe passes when a GET of its `url` answers with its `status`, or any successful
+//! status if it has none, and a body containing its `body`, if any. A TCP probe passes when a
+//! connection to its `address` is accepted. An exec probe runs its `command` as the service's
+//! user, and maps the exit code of the command just like that of a `health_check` hook.
+
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::Path;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use hyper;
+use hyper::client::Client;
+use hyper::net::{HttpStream, HttpsConnector, NetworkConnector};
+use hyper_openssl::OpensslClient;
+
+use super::health::HealthCheck;
+use super::Pkg;
+use util::exec;
+
+static LOGKEY: &'static str = "PR";
+
+/// Name of the package metadata file holding the `pkg_health_probe` of a plan, written as
+/// `key=value` lines.
+const HEALTH_PROBE_METAFILE: &'static str = "HEALTH_PROBE";
+/// Seconds a probe may run for when the service's health check settings don't give a timeout.
+pub const DEFAULT_PROBE_TIMEOUT: u64 = 10;
+/// Number of bytes of an HTTP response body which are read and kept as the probe's output.
+const HTTP_BODY_LIMIT: u64 = 64 * 1024;
+
+/// A check of a service's health which the Supervisor runs without a hook.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum HealthProbe {
+    Http {
+        url: String,
+        status: Option<u16>,
+        body: Option<String>,
+    },
+    Tcp {
+        address: String,
+    },
+    Exec {
+        command: String,
+        #[serde(default)]
+        args: Vec<String>,
+    },
+}
+
+/// The result of a probe along with a description of what it found, which is kept in place of
+/// the output of a `health_check` hook.
+#[derive(Debug)]
+pub struct ProbeResult {
+    pub health: HealthCheck,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+impl ProbeResult {
+    fn ok(stdout: String) -> Self {
+        ProbeResult {
+            health: HealthCheck::Ok,
+            stdout: stdout,
+            stderr: String::new(),
+        }
+    }
+
+    fn critical(stderr: String) -> Self {
+        ProbeResult {
+            health: HealthCheck::Critical,
+            stdout: String::new(),
+            stderr: stderr,
+        }
+    }
+}
+
+impl HealthProbe {
+    /// Read the probe declared by the package installed at `pkg_path`, if any. A probe which
+    /// can't be parsed is ignored.
+    pub fn from_package<P>(pkg_path: P) -> Option<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let path = pkg_path.as_ref().join(HEALTH_PROBE_METAFILE);
+        match File::open(&path) {
+            Ok(file) => {
+                let probe = Self::from_metadata(BufReader::new(file));
+                if probe.is_none() {
+                    outputln!("Ignoring invalid health probe in {}", path.display());
+                }
+                probe
+            }
+            Err(ref err) if err.kind() == io::ErrorKind::NotFound => None,
+            Err(err) => {
+                outputln!("Couldn't read {}, {}", path.display(), err);
+                None
+            }
+        }
+    }
+
+    fn from_metadata<R>(reader: R) -> Option<Self>
+    where
+        R: BufRead,
+    {
+        let (mut kind, mut url, mut status, mut body, mut address, mut command) =
+            (None, None, None, None, None, None);
+        for line in reader.lines().filter_map(|l| l.ok()) {
+            let mut parts = line.splitn(2, '=');
+            let (key, value) = match (parts.next(), parts.next()) {
+                (Some(key), Some(value)) => (key.trim(), value.trim().to_string()),
+                _ => continue,
+            };
+            match key {
+                "type" => kind = Some(value),
+                "url" => url = Some(value),
+                "status" => match value.parse() {
+                    Ok(value) => status = Some(value),
+                    Err(_) => return None,
+                },
+                "body" => body = Some(value),
+                "address" => address = Some(value),
+                "command" => command = Some(value),
+                _ => outputln!("Ignoring unknown health probe setting, {}", line),
+            }
+        }
+        match kind.as_ref().map(String::as_str) {
+            Some("http") => url.map(|url| HealthProbe::Http {
+                url: url,
+                status: status,
+                body: body,
+            }),
+            Some("tcp") => address.map(|address| HealthProbe::Tcp { address: address }),
+            Some("exec") => {
+                let words: Vec<String> = command
+                    .unwrap_or_default()
+                    .split_whitespace()
+                    .map(|w| w.to_string())
+                    .collect();
+                if words.is_empty() {
+                    return None;
+                }
+                Some(HealthProbe::Exec {
+                    command: words[0].clone(),
+                    args: words[1..].to_vec(),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Probe the health of a service, giving up once `timeout` has passed.
+    pub fn run<T>(
+        &self,
+        pkg: &Pkg,
+        svc_encrypted_password: Option<T>,
+        timeout: Duration,
+    ) -> ProbeResult
+    where
+        T: ToString,
+    {
+        match *self {
+            HealthProbe::Http { .. } => self.http(timeout),
+            HealthProbe::Tcp { ref address } => tcp(address, timeout),
+            HealthProbe::Exec {
+                ref command,
+                ref args,
+            } => exec(pkg, command, args, svc_encrypted_password, timeout),
+        }
+    }
+
+    fn http(&self, timeout: Duration) -> ProbeResult {
+        let (url, status, body) = match *self {
+            HealthProbe::Http {
+                ref url,
+                ref status,
+                ref body,
+            } => (url, status, body),
+            _ => unreachable!(),
+        };
+        let mut client = match OpensslClient::new() {
+            Ok(ssl) => Client::with_connector(HttpsConnector::with_connector(
+                ssl,
+                TimeoutConnector(timeout),
+            )),
+            Err(err) => return ProbeResult::critical(format!("Couldn't set up TLS, {}", err)),
+        };
+        client.set_read_timeout(Some(timeout));
+        client.set_write_timeout(Some(timeout));
+        let response = match client.get(url.as_str()).send() {
+            Ok(response) => response,
+            Err(err) => return ProbeResult::critical(format!("GET {} failed, {}", url, err)),
+        };
+        let code = response.status.to_u16();
+        let status_ok = match *status {
+            Some(expected) => code == expected,
+            None => response.status.is_success(),
+        };
+        let mut content = String::new();
+        if let Err(err) = response.take(HTTP_BODY_LIMIT).read_to_string(&mut content) {
+            return ProbeResult::critical(format!("GET {} failed, {}", url, err));
+        }
+        let body_ok = body.as_ref().map_or(true, |b| content.contains(b.as_str()));
+        let summary = format!("GET {} returned {}", url, code);
+        if status_ok && body_ok {
+            ProbeResult::ok(format!("{}\n{}", summary, content))
+        } else if !status_ok {
+            ProbeResult::critical(format!("{}\n{}", summary, content))
+        } else {
+            ProbeResult::critical(format!(
+                "{} without {:?} in its body\n{}",
+                summary,
+                body.as_ref().unwrap(),
+                content
+            ))
+        }
+    }
+}
+
+impl fmt::Display for HealthProbe {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            HealthProbe::Http {
+                ref url,
+                ref status,
+                ref body,
+            } => {
+                write!(f, "http {}", url)?;
+                if let Some(status) = *status {
+                    write!(f, " status={}", status)?;
+                }
+                if let Some(ref body) = *body {
+                    write!(f, " body={:?}", body)?;
+                }
+                Ok(())
+            }
+            HealthProbe::Tcp { ref address } => write!(f, "tcp {}", address),
+            HealthProbe::Exec {
+                ref command,
+                ref args,
+            } => {
+                write!(f, "exec {}", command)?;
+                for arg in args {
+                    write!(f, " {}", arg)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Connects HTTP probes to their server, giving up on connecting and on reading or writing, which
+/// includes a TLS handshake, once the probe's timeout has passed.
+struct TimeoutConnector(Duration);
+
+impl NetworkConnector for TimeoutConnector {
+    type Stream = HttpStream;
+
+    fn connect(&self, host: &str, port: u16, _scheme: &str) -> hyper::Result<HttpStream> {
+        let mut last_err = None;
+        for addr in (host, port).to_socket_addrs()? {
+            match TcpStream::connect_timeout(&addr, self.0) {
+                Ok(stream) => {
+                    stream.set_read_timeout(Some(self.0))?;
+                    stream.set_write_timeout(Some(self.0))?;
+                    return Ok(HttpStream(stream));
+                }
+                Err(err) => last_err = Some(err),
+            }
+        }
+        let err = last_err.unwrap_or_else(|| {
+            io::Error::new(io::ErrorKind::AddrNotAvailable, format!("{} has no addresses", host))
+        });
+        Err(err.into())
+    }
+}
+
+fn tcp(address: &str, timeout: Duration) -> ProbeResult {
+    let addrs = match address.to_socket_addrs() {
+        Ok(addrs) => addrs,
+        Err(err) => return ProbeResult::critical(format!("Couldn't resolve {}, {}", address, err)),
+    };
+    let mut last_err = None;
+    for addr in addrs {
+        match TcpStream::connect_timeout(&addr, timeout) {
+            Ok(_) => return ProbeResult::ok(format!("Connected to {}", addr)),
+            Err(err) => last_err = Some(format!("Couldn't connect to {}, {}", addr, err)),
+        }
+    }
+    ProbeResult::critical(last_err.unwrap_or(format!("{} has no addresses", address)))
+}
+
+fn exec<T>(
+    pkg: &Pkg,
+    command: &str,
+    args: &[String],
+    svc_encrypted_password: Option<T>,
+    timeout: Duration,
+) -> ProbeResult
+where
+    T: ToString,
+{
+    // Relative commands are found in the package, such as `bin/check`.
+    let path = pkg.path.join(command);
+    let mut child = match exec::run_command(&path, args, pkg, svc_encrypted_password) {
+        Ok(child) => child,
+        Err(err) => {
+            return ProbeResult::critical(format!("Couldn't run {}, {}", path.display(), err))
+        }
+    };
+    let (stdout, stderr) = (child.stdout.take(), child.stderr.take());
+    let stdout = thread::spawn(move || read_all(stdout));
+    let stderr = thread::spawn(move || read_all(stderr));
+    let (tx, rx) = mpsc::channel();
+    let pid = child.id();
+    let waiter = thread::spawn(move || {
+        let _ = tx.send(child.wait());
+    });
+    let status = match rx.recv_timeout(timeout) {
+        Ok(status) => status,
+        Err(_) => {
+            if let Err(err) = exec::kill(pid) {
+                outputln!("Couldn't kill health probe {}, {}", pid, err);
+            }
+            let _ = waiter.join();
+            return ProbeResult::critical(format!(
+                "{} didn't exit within {}s and was killed",
+                path.display(),
+                timeout.as_secs()
+            ));
+        }
+    };
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+    let health = match status.map(|s| s.code()) {
+        Ok(Some(0)) => HealthCheck::Ok,
+        Ok(Some(1)) => HealthCheck::Warning,
+        Ok(Some(2)) => HealthCheck::Critical,
+        _ => HealthCheck::Unknown,
+    };
+    ProbeResult {
+        health: health,
+        stdout: stdout,
+        stderr: stderr,
+    }
+}
+
+fn read_all<R>(reader: Option<R>) -> String
+where
+    R: Read,
+{
+    let mut content = String::new();
+    if let Some(mut reader) = reader {
+        let _ = reader.read_to_string(&mut content);
+    }
+    content
+}
+
+#[cfg(test)]
+mod test {
+    use std::net::TcpListener;
+    use std::time::Instant;
+
+    use super::*;
+
+    #[test]
+    fn health_probe_from_metadata() {
+        let metadata = "type=http\nurl=http://localhost:8080/healthz\nstatus=204\n";
+        assert_eq!(
+            HealthProbe::from_metadata(metadata.as_bytes()),
+            Some(HealthProbe::Http {
+                url: "http://localhost:8080/healthz".to_string(),
+                status: Some(204),
+                body: None,
+            })
+        );
+        assert_eq!(
+            HealthProbe::from_metadata("type=exec\ncommand=bin/check --quick -v\n".as_bytes()),
+            Some(HealthProbe::Exec {
+                command: "bin/check".to_string(),
+                args: vec!["--quick".to_string(), "-v".to_string()],
+            })
+        );
+        assert!(HealthProbe::from_metadata("type=tcp\n".as_bytes()).is_none());
+        assert!(HealthProbe::from_metadata("type=http\nurl=x\nstatus=ok\n".as_bytes()).is_none());
+        assert!(HealthProbe::from_metadata("type=smoke\n".as_bytes()).is_none());
+    }
+
+    #[test]
+    fn tcp_probe_connects() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let result = tcp(&address, Duration::from_secs(1));
+        assert_eq!(result.health, HealthCheck::Ok);
+
+        drop(listener);
+        let result = tcp(&address, Duration::from_secs(1));
+        assert_eq!(result.health, HealthCheck::Critical);
+    }
+
+    #[test]
+    fn http_probe_gives_up_on_silent_server() {
+        // The listener accepts connections into its backlog but never answers them.
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        for scheme in &["http", "https"] {
+            let probe = HealthProbe::Http {
+                url: format!("{}://{}/healthz", scheme, address),
+                status: None,
+                body: None,
+            };
+            let started = Instant::now();
+            let result = probe.http(Duration::from_millis(200));
+            assert_eq!(result.health, HealthCheck::Critical);
+            assert!(started.elapsed() < Duration::from_secs(5));
+        }
+    }
+}
@@ -0,0 +1,401 @@
+// Copyright (c) 2018 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+use launcher_client;
+
+use error::{Error, Result};
+
+static LOGKEY: &'static str = "RL";
+
+/// Name of the package metadata file holding the `pkg_resources` limits of a plan, written as
+/// `key=value` lines.
+const RESOURCES_METAFILE: &'static str = "RESOURCES";
+/// Name of the package metadata file holding the `pkg_rlimits` of a plan, written as `key=value`
+/// lines.
+const RLIMITS_METAFILE: &'static str = "RLIMITS";
+/// Highest CPU and IO weight accepted by the kernel.
+const MAX_WEIGHT: u32 = 10_000;
+
+/// Resources a service's process may use, enforced by the Launcher through a cgroup on Linux.
+/// Each limit may be given by the `pkg_resources` of the service's plan and overridden in the
+/// service's spec; unset limits are left to the kernel.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(default)]
+pub struct ResourceLimits {
+    /// Bytes of memory the process may use.
+    pub memory_max: Option<u64>,
+    /// Share of CPU time relative to other services, from 1 to 10000.
+    pub cpu_weight: Option<u32>,
+    /// Percent of one CPU the process may use, such as 150 for one and a half CPUs.
+    pub cpu_quota: Option<u32>,
+    /// Number of processes and threads the service may run.
+    pub pids_max: Option<u64>,
+    /// Share of IO relative to other services, from 1 to 10000.
+    pub io_weight: Option<u32>,
+}
+
+impl ResourceLimits {
+    /// Read the limits from the metadata of the package installed at `pkg_path`. Limits which
+    /// can't be parsed are ignored.
+    pub fn from_package<P>(pkg_path: P) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        let path = pkg_path.as_ref().join(RESOURCES_METAFILE);
+        match File::open(&path) {
+            Ok(file) => Self::from_metadata(BufReader::new(file)),
+            Err(ref err) if err.kind() == io::ErrorKind::NotFound => Self::default(),
+            Err(err) => {
+                outputln!("Couldn't read {}, {}", path.display(), err);
+                Self::default()
+            }
+        }
+    }
+
+    fn from_metadata<R>(reader: R) -> Self
+    where
+        R: BufRead,
+    {
+        let mut limits = Self::default();
+        for line in reader.lines().filter_map(|l| l.ok()) {
+            let mut parts = line.splitn(2, '=');
+            let (key, value) = match (parts.next(), parts.next()) {
+                (Some(key), Some(value)) => (key.trim(), value.trim()),
+                _ => continue,
+            };
+            let valid = match key {
+                "memory_max" => value.parse().map(|v| limits.memory_max = Some(v)).is_ok(),
+                "cpu_weight" => value.parse().map(|v| limits.cpu_weight = Some(v)).is_ok(),
+                "cpu_quota" => value.parse().map(|v| limits.cpu_quota = Some(v)).is_ok(),
+                "pids_max" => value.parse().map(|v| limits.pids_max = Some(v)).is_ok(),
+                "io_weight" => value.parse().map(|v| limits.io_weight = Some(v)).is_ok(),
+                _ => false,
+            };
+            if !valid {
+                outputln!("Ignoring invalid resource limit, {}", line);
+            }
+        }
+        limits
+    }
+
+    /// Returns these limits with each limit given by `overrides` replaced.
+    pub fn merge(&self, overrides: &ResourceLimits) -> Self {
+        ResourceLimits {
+            memory_max: overrides.memory_max.or(self.memory_max),
+            cpu_weight: overrides.cpu_weight.or(self.cpu_weight),
+            cpu_quota: overrides.cpu_quota.or(self.cpu_quota),
+            pids_max: overrides.pids_max.or(self.pids_max),
+            io_weight: overrides.io_weight.or(self.io_weight),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Validates that each limit is within the range the kernel accepts.
+    pub fn validate(&self) -> Result<()> {
+        let weights = vec![("cpu_weight", self.cpu_weight), ("io_weight", self.io_weight)];
+        for (key, weight) in weights {
+            match weight {
+                Some(w) if w < 1 || w > MAX_WEIGHT => {
+                    return Err(sup_error!(Error::InvalidResourceLimit(format!(
+                        "{} must be from 1 to {}, not {}",
+                        key, MAX_WEIGHT, w
+                    ))))
+                }
+                _ => (),
+            }
+        }
+        if self.cpu_quota == Some(0) {
+            return Err(sup_error!(Error::InvalidResourceLimit(
+                "cpu_quota must be more than 0".to_string()
+            )));
+        }
+        Ok(())
+    }
+
+    /// The limits to send to the Launcher when spawning the process, unless none are set.
+    pub fn to_launcher(&self) -> Option<launcher_client::ResourceLimits> {
+        if self.is_empty() {
+            return None;
+        }
+        let mut limits = launcher_client::ResourceLimits::new();
+        if let Some(memory_max) = self.memory_max {
+            limits.set_memory_max(memory_max);
+        }
+        if let Some(cpu_weight) = self.cpu_weight {
+            limits.set_cpu_weight(cpu_weight);
+        }
+        if let Some(cpu_quota) = self.cpu_quota {
+            limits.set_cpu_quota(cpu_quota);
+        }
+        if let Some(pids_max) = self.pids_max {
+            limits.set_pids_max(pids_max);
+        }
+        if let Some(io_weight) = self.io_weight {
+            limits.set_io_weight(io_weight);
+        }
+        Some(limits)
+    }
+}
+
+impl fmt::Display for ResourceLimits {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let limits: Vec<String> = vec![
+            ("memory_max", self.memory_max),
+            ("cpu_weight", self.cpu_weight.map(u64::from)),
+            ("cpu_quota", self.cpu_quota.map(u64::from)),
+            ("pids_max", self.pids_max),
+            ("io_weight", self.io_weight.map(u64::from)),
+        ].into_iter()
+            .filter_map(|(key, value)| value.map(|v| format!("{}={}", key, v)))
+            .collect();
+        if limits.is_empty() {
+            write!(f, "none")
+        } else {
+            write!(f, "{}", limits.join(" "))
+        }
+    }
+}
+
+/// Resources used by the cgroup of a service's process, as reported by the Launcher. Resources
+/// which the Launcher couldn't read are unset.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct ResourceUsage {
+    /// Bytes of memory used by the cgroup.
+    pub memory_bytes: Option<u64>,
+    /// Microseconds of CPU time used by the cgroup.
+    pub cpu_usec: Option<u64>,
+    /// Number of processes and threads in the cgroup.
+    pub pids: Option<u64>,
+    /// Bytes read from block devices by the cgroup.
+    pub io_read_bytes: Option<u64>,
+    /// Bytes written to block devices by the cgroup.
+    pub io_write_bytes: Option<u64>,
+}
+
+impl ResourceUsage {
+    pub fn from_launcher(usage: &launcher_client::ResourceUsageOk) -> Self {
+        ResourceUsage {
+            memory_bytes: if usage.has_memory_current() {
+                Some(usage.get_memory_current())
+            } else {
+                None
+            },
+            cpu_usec: if usage.has_cpu_usage_usec() {
+                Some(usage.get_cpu_usage_usec())
+            } else {
+                None
+            },
+            pids: if usage.has_pids_current() {
+                Some(usage.get_pids_current())
+            } else {
+                None
+            },
+            io_read_bytes: if usage.has_io_read_bytes() {
+                Some(usage.get_io_read_bytes())
+            } else {
+                None
+            },
+            io_write_bytes: if usage.has_io_write_bytes() {
+                Some(usage.get_io_write_bytes())
+            } else {
+                None
+            },
+        }
+    }
+
+    /// Whether the Launcher reported no usage, as it doesn't for processes without a cgroup.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Limits set on a service's process with `setrlimit` before it's executed, as both its soft and
+/// hard limits. Each limit may be given by the `pkg_rlimits` of the service's plan and overridden
+/// in the service's spec; unset limits are inherited from the Launcher.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(default)]
+pub struct Rlimits {
+    /// Number of open file descriptors.
+    pub nofile: Option<u64>,
+    /// Number of processes of the service's user.
+    pub nproc: Option<u64>,
+    /// Bytes of core dumps.
+    pub core: Option<u64>,
+    /// Bytes of memory which may be locked.
+    pub memlock: Option<u64>,
+}
+
+impl Rlimits {
+    /// Read the limits from the metadata of the package installed at `pkg_path`. Limits which
+    /// can't be parsed are ignored.
+    pub fn from_package<P>(pkg_path: P) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        let path = pkg_path.as_ref().join(RLIMITS_METAFILE);
+        match File::open(&path) {
+            Ok(file) => Self::from_metadata(BufReader::new(file)),
+            Err(ref err) if err.kind() == io::ErrorKind::NotFound => Self::default(),
+            Err(err) => {
+                outputln!("Couldn't read {}, {}", path.display(), err);
+                Self::default()
+            }
+        }
+    }
+
+    fn from_metadata<R>(reader: R) -> Self
+    where
+        R: BufRead,
+    {
+        let mut limits = Self::default();
+        for line in reader.lines().filter_map(|l| l.ok()) {
+            let mut parts = line.splitn(2, '=');
+            let (key, value) = match (parts.next(), parts.next()) {
+                (Some(key), Some(value)) => (key.trim(), value.trim()),
+                _ => continue,
+            };
+            let valid = match key {
+                "nofile" => value.parse().map(|v| limits.nofile = Some(v)).is_ok(),
+                "nproc" => value.parse().map(|v| limits.nproc = Some(v)).is_ok(),
+                "core" => value.parse().map(|v| limits.core = Some(v)).is_ok(),
+                "memlock" => value.parse().map(|v| limits.memlock = Some(v)).is_ok(),
+                _ => false,
+            };
+            if !valid {
+                outputln!("Ignoring invalid rlimit, {}", line);
+            }
+        }
+        limits
+    }
+
+    /// Returns these limits with each limit given by `overrides` replaced.
+    pub fn merge(&self, overrides: &Rlimits) -> Self {
+        Rlimits {
+            nofile: overrides.nofile.or(self.nofile),
+            nproc: overrides.nproc.or(self.nproc),
+            core: overrides.core.or(self.core),
+            memlock: overrides.memlock.or(self.memlock),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The limits to send to the Launcher when spawning the process, unless none are set.
+    pub fn to_launcher(&self) -> Option<launcher_client::Rlimits> {
+        if self.is_empty() {
+            return None;
+        }
+        let mut limits = launcher_client::Rlimits::new();
+        if let Some(nofile) = self.nofile {
+            limits.set_nofile(nofile);
+        }
+        if let Some(nproc) = self.nproc {
+            limits.set_nproc(nproc);
+        }
+        if let Some(core) = self.core {
+            limits.set_core(core);
+        }
+        if let Some(memlock) = self.memlock {
+            limits.set_memlock(memlock);
+        }
+        Some(limits)
+    }
+}
+
+impl fmt::Display for Rlimits {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let limits: Vec<String> = vec![
+            ("nofile", self.nofile),
+            ("nproc", self.nproc),
+            ("core", self.core),
+            ("memlock", self.memlock),
+        ].into_iter()
+            .filter_map(|(key, value)| value.map(|v| format!("{}={}", key, v)))
+            .collect();
+        if limits.is_empty() {
+            write!(f, "none")
+        } else {
+            write!(f, "{}", limits.join(" "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn resource_limits_from_metadata() {
+        let metadata = "memory_max=536870912\ncpu_quota=150\npids_max=lots\n";
+        let limits = ResourceLimits::from_metadata(metadata.as_bytes());
+        assert_eq!(limits.memory_max, Some(536870912));
+        assert_eq!(limits.cpu_quota, Some(150));
+        assert_eq!(limits.pids_max, None);
+        assert_eq!(limits.to_string(), "memory_max=536870912 cpu_quota=150");
+    }
+
+    #[test]
+    fn resource_limits_merge_and_validate() {
+        let mut package = ResourceLimits::default();
+        package.memory_max = Some(1024);
+        package.cpu_weight = Some(100);
+        let mut overrides = ResourceLimits::default();
+        overrides.cpu_weight = Some(500);
+        let limits = package.merge(&overrides);
+        assert_eq!(limits.memory_max, Some(1024));
+        assert_eq!(limits.cpu_weight, Some(500));
+        assert!(limits.validate().is_ok());
+
+        overrides.io_weight = Some(20_000);
+        assert!(package.merge(&overrides).validate().is_err());
+        assert!(ResourceLimits::default().to_launcher().is_none());
+        assert!(limits.to_launcher().unwrap().has_memory_max());
+    }
+
+    #[test]
+    fn resource_usage_from_launcher() {
+        let mut reply = launcher_client::ResourceUsageOk::new();
+        assert!(ResourceUsage::from_launcher(&reply).is_empty());
+        reply.set_memory_current(1024);
+        reply.set_pids_current(3);
+        let usage = ResourceUsage::from_launcher(&reply);
+        assert_eq!(usage.memory_bytes, Some(1024));
+        assert_eq!(usage.pids, Some(3));
+        assert_eq!(usage.cpu_usec, None);
+    }
+
+    #[test]
+    fn rlimits_from_metadata_and_merge() {
+        let package = Rlimits::from_metadata("nofile=65536\ncore=0\nstack=8192\n".as_bytes());
+        assert_eq!(package.nofile, Some(65536));
+        assert_eq!(package.core, Some(0));
+
+        let mut overrides = Rlimits::default();
+        overrides.nofile = Some(1024);
+        let limits = package.merge(&overrides);
+        assert_eq!(limits.to_string(), "nofile=1024 core=0");
+        assert_eq!(limits.to_launcher().unwrap().get_nofile(), 1024);
+    }
+}
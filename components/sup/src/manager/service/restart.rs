@@ -0,0 +1,278 @@
+// Copyright (c) 2018 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! How a service's process is restarted after it exits on its own.
+//!
+//! Each restart is delayed by a backoff which starts at `backoff_base` seconds and doubles with
+//! each exit counted within the restart `window`, up to `backoff_max` seconds. A process which
+//! exits more than `max_restarts` times within the window is crash-looping, and is left down
+//! until an operator restarts the service.
+
+use std::cmp;
+use std::collections::VecDeque;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use protocol::types::RestartPolicy;
+
+/// Seconds to wait before restarting a process which exited, when no exits were counted before.
+pub const DEFAULT_RESTART_BACKOFF_BASE: u64 = 1;
+/// Maximum seconds to wait before restarting a process which keeps exiting.
+pub const DEFAULT_RESTART_BACKOFF_MAX: u64 = 60;
+/// Restarts allowed within the restart window before the service is crash-looping.
+pub const DEFAULT_MAX_RESTARTS: u32 = 10;
+/// Seconds over which restarts are counted.
+pub const DEFAULT_RESTART_WINDOW: u64 = 600;
+
+/// Whether and how fast the process of a service is restarted when it exits. The settings are
+/// given when the service is loaded; unset settings take their defaults.
+///
+/// ```toml
+/// [restart]
+/// policy = "on-failure"
+/// backoff_max = 30
+/// max_restarts = 5
+/// ```
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(default)]
+pub struct RestartSettings {
+    pub policy: Option<RestartPolicy>,
+    /// Seconds to wait before the first restart of a process which exited.
+    pub backoff_base: Option<u64>,
+    /// Maximum seconds to wait before restarting a process which keeps exiting.
+    pub backoff_max: Option<u64>,
+    /// Restarts allowed within `window` before the service is crash-looping. With 0, a service is
+    /// never considered crash-looping.
+    pub max_restarts: Option<u32>,
+    /// Seconds over which restarts are counted.
+    pub window: Option<u64>,
+}
+
+impl RestartSettings {
+    /// Returns these settings with each setting given by `overrides` replaced.
+    pub fn merge(&self, overrides: &RestartSettings) -> Self {
+        RestartSettings {
+            policy: overrides.policy.or(self.policy),
+            backoff_base: overrides.backoff_base.or(self.backoff_base),
+            backoff_max: overrides.backoff_max.or(self.backoff_max),
+            max_restarts: overrides.max_restarts.or(self.max_restarts),
+            window: overrides.window.or(self.window),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn policy(&self) -> RestartPolicy {
+        self.policy.unwrap_or_default()
+    }
+
+    pub fn max_restarts(&self) -> u32 {
+        self.max_restarts.unwrap_or(DEFAULT_MAX_RESTARTS)
+    }
+
+    pub fn window(&self) -> Duration {
+        Duration::from_secs(self.window.unwrap_or(DEFAULT_RESTART_WINDOW))
+    }
+
+    /// The delay before restarting a process which exited for the `exits`th time within the
+    /// window.
+    pub fn backoff(&self, exits: u32) -> Duration {
+        let base = self.backoff_base.unwrap_or(DEFAULT_RESTART_BACKOFF_BASE);
+        let max = self.backoff_max.unwrap_or(DEFAULT_RESTART_BACKOFF_MAX);
+        let factor = 1u64 << cmp::min(exits.saturating_sub(1), 32);
+        let secs = base.checked_mul(factor).unwrap_or(max);
+        Duration::from_secs(cmp::min(secs, max))
+    }
+}
+
+impl fmt::Display for RestartSettings {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut settings: Vec<String> = vec![];
+        if let Some(policy) = self.policy {
+            settings.push(format!("policy={}", policy));
+        }
+        settings.extend(
+            vec![
+                ("backoff_base", self.backoff_base),
+                ("backoff_max", self.backoff_max),
+                ("max_restarts", self.max_restarts.map(u64::from)),
+                ("window", self.window),
+            ].into_iter()
+                .filter_map(|(key, value)| value.map(|v| format!("{}={}", key, v))),
+        );
+        if settings.is_empty() {
+            write!(f, "default")
+        } else {
+            write!(f, "{}", settings.join(" "))
+        }
+    }
+}
+
+/// What is done about a process which exited.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Restart {
+    /// Restart the process once the given instant is reached.
+    At(Instant),
+    /// Leave the process down, as its restart policy asks.
+    Held,
+    /// Leave the process down, as it kept exiting.
+    CrashLooping,
+}
+
+/// The exits of a service's process which were counted within the restart window, and what is
+/// done about the last of them.
+#[derive(Debug, Default)]
+pub struct RestartTracker {
+    exits: VecDeque<Instant>,
+    pending: Option<Restart>,
+}
+
+impl RestartTracker {
+    /// Count an exit of the process at `now`, with the given exit code if it's known, and decide
+    /// what to do about it.
+    pub fn record_exit(
+        &mut self,
+        settings: &RestartSettings,
+        exit_code: Option<i32>,
+        now: Instant,
+    ) -> Restart {
+        let restart = match settings.policy() {
+            RestartPolicy::Never => Restart::Held,
+            RestartPolicy::OnFailure if exit_code == Some(0) => Restart::Held,
+            RestartPolicy::Always | RestartPolicy::OnFailure => {
+                let window = settings.window();
+                while self.exits
+                    .front()
+                    .map_or(false, |exit| now.duration_since(*exit) >= window)
+                {
+                    self.exits.pop_front();
+                }
+                self.exits.push_back(now);
+                let exits = self.exits.len() as u32;
+                let max_restarts = settings.max_restarts();
+                if max_restarts > 0 && exits > max_restarts {
+                    Restart::CrashLooping
+                } else {
+                    Restart::At(now + settings.backoff(exits))
+                }
+            }
+        };
+        self.pending = Some(restart);
+        restart
+    }
+
+    /// Whether a process which isn't running may be started at `now`.
+    pub fn ready(&self, now: Instant) -> bool {
+        match self.pending {
+            None => true,
+            Some(Restart::At(at)) => now >= at,
+            Some(Restart::Held) | Some(Restart::CrashLooping) => false,
+        }
+    }
+
+    pub fn crash_looping(&self) -> bool {
+        self.pending == Some(Restart::CrashLooping)
+    }
+
+    /// Forget the counted exits, as an operator asked for the service to be restarted.
+    pub fn reset(&mut self) {
+        self.exits.clear();
+        self.pending = None;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_up_to_max() {
+        let mut settings = RestartSettings::default();
+        assert_eq!(settings.backoff(1), Duration::from_secs(1));
+        assert_eq!(settings.backoff(4), Duration::from_secs(8));
+        assert_eq!(settings.backoff(100), Duration::from_secs(60));
+
+        settings.backoff_base = Some(5);
+        settings.backoff_max = Some(30);
+        assert_eq!(settings.backoff(2), Duration::from_secs(10));
+        assert_eq!(settings.backoff(3), Duration::from_secs(20));
+        assert_eq!(settings.backoff(4), Duration::from_secs(30));
+    }
+
+    #[test]
+    fn crash_loop_past_max_restarts_in_window() {
+        let mut settings = RestartSettings::default();
+        settings.max_restarts = Some(2);
+        settings.window = Some(60);
+        let mut tracker = RestartTracker::default();
+        let start = Instant::now();
+
+        let restart = tracker.record_exit(&settings, Some(1), start);
+        assert_eq!(restart, Restart::At(start + Duration::from_secs(1)));
+        assert!(!tracker.ready(start));
+        assert!(tracker.ready(start + Duration::from_secs(1)));
+
+        tracker.record_exit(&settings, Some(1), start + Duration::from_secs(10));
+        let restart = tracker.record_exit(&settings, Some(1), start + Duration::from_secs(20));
+        assert_eq!(restart, Restart::CrashLooping);
+        assert!(tracker.crash_looping());
+        assert!(!tracker.ready(start + Duration::from_secs(3600)));
+
+        tracker.reset();
+        assert!(tracker.ready(start));
+        assert!(!tracker.crash_looping());
+    }
+
+    #[test]
+    fn exits_outside_window_are_forgotten() {
+        let mut settings = RestartSettings::default();
+        settings.max_restarts = Some(1);
+        settings.window = Some(60);
+        let mut tracker = RestartTracker::default();
+        let start = Instant::now();
+
+        tracker.record_exit(&settings, None, start);
+        let later = start + Duration::from_secs(60);
+        assert_eq!(
+            tracker.record_exit(&settings, None, later),
+            Restart::At(later + Duration::from_secs(1))
+        );
+    }
+
+    #[test]
+    fn restart_policy_holds_process() {
+        let mut settings = RestartSettings::default();
+        settings.policy = Some(RestartPolicy::OnFailure);
+        let mut tracker = RestartTracker::default();
+        let now = Instant::now();
+        assert_eq!(tracker.record_exit(&settings, Some(0), now), Restart::Held);
+        assert!(!tracker.ready(now));
+        assert_ne!(tracker.record_exit(&settings, Some(3), now), Restart::Held);
+        assert_ne!(tracker.record_exit(&settings, None, now), Restart::Held);
+
+        settings.policy = Some(RestartPolicy::Never);
+        assert_eq!(tracker.record_exit(&settings, Some(1), now), Restart::Held);
+    }
+
+    #[test]
+    fn restart_settings_to_string() {
+        let mut settings = RestartSettings::default();
+        assert_eq!(settings.to_string(), "default");
+        settings.policy = Some(RestartPolicy::OnFailure);
+        settings.max_restarts = Some(5);
+        assert_eq!(settings.to_string(), "policy=on-failure max_restarts=5");
+    }
+}
@@ -33,8 +33,16 @@ use serde::{self, Deserialize};
 use toml;
 
 use super::composite_spec::CompositeSpec;
-use super::{BindingMode, Topology, UpdateStrategy};
+use super::health::HealthCheckSettings;
+use super::hooks::HookTimeouts;
+use super::probe::HealthProbe;
+use super::isolation::Isolation;
+use super::resources::{ResourceLimits, Rlimits};
+use super::restart::RestartSettings;
+use super::{BindingMode, RestartPolicy, Topology, UpdateStrategy};
 use error::{Error, Result, SupError};
+#[cfg(unix)]
+use manager;
 
 static LOGKEY: &'static str = "SS";
 static DEFAULT_GROUP: &'static str = "default";
@@ -153,6 +161,9 @@ impl IntoServiceSpec for protocol::ctl::SvcLoad {
         if let Some(ref svc_encrypted_password) = self.svc_encrypted_password {
             spec.svc_encrypted_password = Some(svc_encrypted_password.to_string());
         }
+        spec.health_check = spec.health_check.merge(&health_check_settings(self));
+        spec.restart = spec.restart.merge(&restart_settings(self));
+        set_shutdown(self, spec);
         spec.composite = None;
     }
 
@@ -238,6 +249,40 @@ impl IntoServiceSpec for protocol::ctl::SvcLoad {
             spec.binds = standard;
             set_composite_binds(spec, bind_map, &composite);
         }
+        spec.health_check = spec.health_check.merge(&health_check_settings(self));
+        spec.restart = spec.restart.merge(&restart_settings(self));
+        set_shutdown(self, spec);
+    }
+}
+
+/// Set the shutdown settings given when loading a service.
+fn set_shutdown(msg: &protocol::ctl::SvcLoad, spec: &mut ServiceSpec) {
+    if let Some(ref signal) = msg.shutdown_signal {
+        spec.shutdown_signal = Some(signal.to_string());
+    }
+    if let Some(timeout) = msg.shutdown_timeout {
+        spec.shutdown_timeout = Some(timeout);
+    }
+}
+
+/// The health check settings given when loading a service.
+fn health_check_settings(msg: &protocol::ctl::SvcLoad) -> HealthCheckSettings {
+    HealthCheckSettings {
+        interval: msg.health_check_interval,
+        timeout: msg.health_check_timeout,
+        success_threshold: msg.health_check_success_threshold,
+        failure_threshold: msg.health_check_failure_threshold,
+    }
+}
+
+/// The restart settings given when loading a service.
+fn restart_settings(msg: &protocol::ctl::SvcLoad) -> RestartSettings {
+    RestartSettings {
+        policy: msg.restart_policy.and_then(RestartPolicy::from_i32),
+        backoff_base: msg.restart_backoff_base,
+        backoff_max: msg.restart_backoff_max,
+        max_restarts: msg.max_restarts,
+        window: msg.restart_window,
     }
 }
 
@@ -270,6 +315,37 @@ pub struct ServiceSpec {
     pub svc_encrypted_password: Option<String>,
     // The name of the composite this service is a part of
     pub composite: Option<String>,
+    /// Name of the signal asking the service to stop, overriding that of its package.
+    pub shutdown_signal: Option<String>,
+    /// Seconds to wait for the service to stop before it's killed, overriding that of its
+    /// package.
+    pub shutdown_timeout: Option<u32>,
+    /// Linux capabilities the service keeps when running as its user, replacing those of its
+    /// package.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ambient_capabilities: Option<Vec<String>>,
+    /// Health check settings overriding those of the service's package.
+    #[serde(skip_serializing_if = "HealthCheckSettings::is_empty")]
+    pub health_check: HealthCheckSettings,
+    /// Health probe run by the Supervisor, replacing the probe of the service's package and its
+    /// `health_check` hook.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub health_probe: Option<HealthProbe>,
+    /// Seconds each hook may run for before it's killed.
+    #[serde(skip_serializing_if = "HookTimeouts::is_empty")]
+    pub hook_timeouts: HookTimeouts,
+    /// Whether and how fast the service's process is restarted when it exits.
+    #[serde(skip_serializing_if = "RestartSettings::is_empty")]
+    pub restart: RestartSettings,
+    /// Resource limits overriding those of the service's package.
+    #[serde(skip_serializing_if = "ResourceLimits::is_empty")]
+    pub resources: ResourceLimits,
+    /// Rlimits overriding those of the service's package.
+    #[serde(skip_serializing_if = "Rlimits::is_empty")]
+    pub rlimits: Rlimits,
+    /// Namespaces the Launcher isolates the service's process in on Linux.
+    #[serde(skip_serializing_if = "Isolation::is_empty")]
+    pub isolation: Isolation,
 }
 
 impl ServiceSpec {
@@ -327,8 +403,128 @@ impl ServiceSpec {
         format!("{}.{}", &self.ident.name, SPEC_FILE_EXT)
     }
 
+    /// Describe each field which may be set when loading a service that differs between this
+    /// spec and `other`, in the form `<field>: <this value> -> <other value>`.
+    pub fn diff(&self, other: &ServiceSpec) -> Vec<String> {
+        fn app_env(spec: &ServiceSpec) -> String {
+            spec.application_environment
+                .as_ref()
+                .map(ToString::to_string)
+                .unwrap_or("none".to_string())
+        }
+        fn binds(spec: &ServiceSpec) -> String {
+            if spec.binds.is_empty() {
+                return "none".to_string();
+            }
+            let binds: Vec<String> = spec.binds.iter().map(ToString::to_string).collect();
+            binds.join(" ")
+        }
+        fn capabilities(spec: &ServiceSpec) -> String {
+            match spec.ambient_capabilities {
+                Some(ref caps) if caps.is_empty() => "none".to_string(),
+                Some(ref caps) => caps.join(" "),
+                None => "package".to_string(),
+            }
+        }
+        fn or_default<T: ToString>(setting: Option<T>) -> String {
+            setting
+                .map(|s| s.to_string())
+                .unwrap_or("default".to_string())
+        }
+        let fields = vec![
+            ("ident", self.ident.to_string(), other.ident.to_string()),
+            ("group", self.group.clone(), other.group.clone()),
+            ("application_environment", app_env(self), app_env(other)),
+            ("bldr_url", self.bldr_url.clone(), other.bldr_url.clone()),
+            ("channel", self.channel.clone(), other.channel.clone()),
+            ("topology", self.topology.to_string(), other.topology.to_string()),
+            (
+                "update_strategy",
+                self.update_strategy.to_string(),
+                other.update_strategy.to_string(),
+            ),
+            ("binds", binds(self), binds(other)),
+            (
+                "binding_mode",
+                self.binding_mode.to_string(),
+                other.binding_mode.to_string(),
+            ),
+            (
+                "health_check",
+                self.health_check.to_string(),
+                other.health_check.to_string(),
+            ),
+            (
+                "health_probe",
+                self.health_probe
+                    .as_ref()
+                    .map_or("package".to_string(), |p| p.to_string()),
+                other
+                    .health_probe
+                    .as_ref()
+                    .map_or("package".to_string(), |p| p.to_string()),
+            ),
+            (
+                "hook_timeouts",
+                self.hook_timeouts.to_string(),
+                other.hook_timeouts.to_string(),
+            ),
+            ("restart", self.restart.to_string(), other.restart.to_string()),
+            (
+                "resources",
+                self.resources.to_string(),
+                other.resources.to_string(),
+            ),
+            ("rlimits", self.rlimits.to_string(), other.rlimits.to_string()),
+            (
+                "isolation",
+                self.isolation.to_string(),
+                other.isolation.to_string(),
+            ),
+            (
+                "ambient_capabilities",
+                capabilities(self),
+                capabilities(other),
+            ),
+            (
+                "shutdown_signal",
+                or_default(self.shutdown_signal.clone()),
+                or_default(other.shutdown_signal.clone()),
+            ),
+            (
+                "shutdown_timeout",
+                or_default(self.shutdown_timeout),
+                or_default(other.shutdown_timeout),
+            ),
+        ];
+        fields
+            .into_iter()
+            .filter(|&(_, ref this, ref other)| this != other)
+            .map(|(field, this, other)| format!("{}: {} -> {}", field, this, other))
+            .collect()
+    }
+
     pub fn validate(&self, package: &PackageInstall) -> Result<()> {
         self.validate_binds(package)?;
+        self.validate_shutdown_signal()?;
+        self.resources.validate()?;
+        Ok(())
+    }
+
+    /// Validates that the shutdown signal, if one is given, is one the Launcher can send.
+    #[cfg(unix)]
+    fn validate_shutdown_signal(&self) -> Result<()> {
+        match self.shutdown_signal {
+            Some(ref signal) if manager::signal_from_name(signal).is_none() => Err(sup_error!(
+                Error::InvalidShutdownSignal(signal.to_string())
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Signals aren't sent on Windows, so any shutdown signal is ignored.
+    #[cfg(windows)]
+    fn validate_shutdown_signal(&self) -> Result<()> {
         Ok(())
     }
 
@@ -392,6 +588,16 @@ impl Default for ServiceSpec {
             desired_state: DesiredState::default(),
             svc_encrypted_password: None,
             composite: None,
+            health_check: HealthCheckSettings::default(),
+            health_probe: None,
+            hook_timeouts: HookTimeouts::default(),
+            restart: RestartSettings::default(),
+            resources: ResourceLimits::default(),
+            rlimits: Rlimits::default(),
+            ambient_capabilities: None,
+            isolation: Isolation::default(),
+            shutdown_signal: None,
+            shutdown_timeout: None,
         }
     }
 }
@@ -590,8 +796,38 @@ mod test {
             update_strategy = "rolling"
             binds = ["cache:redis.cache@acmecorp", "db:postgres.app@acmecorp"]
             config_from = "/only/for/development"
+            shutdown_signal = "INT"
+            shutdown_timeout = 300
+            ambient_capabilities = ["CAP_NET_BIND_SERVICE"]
 
             extra_stuff = "should be ignored"
+
+            [health_check]
+            interval = 5
+            failure_threshold = 3
+
+            [health_probe]
+            type = "tcp"
+            address = "localhost:6379"
+
+            [hook_timeouts]
+            default = 60
+            post_stop = 0
+
+            [restart]
+            policy = "on-failure"
+            max_restarts = 5
+
+            [resources]
+            memory_max = 536870912
+            cpu_weight = 200
+
+            [rlimits]
+            nofile = 65536
+
+            [isolation]
+            mount = true
+            pid = true
             "#;
         let spec = ServiceSpec::from_str(toml).unwrap();
 
@@ -618,6 +854,32 @@ mod test {
             spec.config_from,
             Some(PathBuf::from("/only/for/development"))
         );
+        assert_eq!(spec.health_check.interval, Some(5));
+        assert_eq!(spec.health_check.failure_threshold, Some(3));
+        assert_eq!(spec.health_check.timeout, None);
+        assert_eq!(
+            spec.health_probe,
+            Some(HealthProbe::Tcp {
+                address: "localhost:6379".to_string(),
+            })
+        );
+        assert_eq!(spec.hook_timeouts.default, Some(60));
+        assert_eq!(spec.hook_timeouts.post_stop, Some(0));
+        assert_eq!(spec.hook_timeouts.init, None);
+        assert_eq!(spec.restart.policy, Some(RestartPolicy::OnFailure));
+        assert_eq!(spec.restart.max_restarts, Some(5));
+        assert_eq!(spec.restart.backoff_max, None);
+        assert_eq!(spec.resources.memory_max, Some(536870912));
+        assert_eq!(spec.resources.cpu_weight, Some(200));
+        assert_eq!(spec.resources.pids_max, None);
+        assert_eq!(spec.rlimits.nofile, Some(65536));
+        assert!(spec.isolation.mount && spec.isolation.pid && !spec.isolation.network);
+        assert_eq!(
+            spec.ambient_capabilities,
+            Some(vec!["CAP_NET_BIND_SERVICE".to_string()])
+        );
+        assert_eq!(spec.shutdown_signal, Some("INT".to_string()));
+        assert_eq!(spec.shutdown_timeout, Some(300));
     }
 
     #[test]
@@ -687,6 +949,18 @@ mod test {
             desired_state: DesiredState::Down,
             svc_encrypted_password: None,
             composite: None,
+            health_check: HealthCheckSettings::default(),
+            health_probe: Some(HealthProbe::Tcp {
+                address: "localhost:6379".to_string(),
+            }),
+            hook_timeouts: HookTimeouts::default(),
+            restart: RestartSettings::default(),
+            resources: ResourceLimits::default(),
+            rlimits: Rlimits::default(),
+            ambient_capabilities: None,
+            isolation: Isolation::default(),
+            shutdown_signal: None,
+            shutdown_timeout: None,
         };
         let toml = spec.to_toml_string().unwrap();
 
@@ -702,6 +976,8 @@ mod test {
         assert!(toml.contains(r#"desired_state = "down""#));
         assert!(toml.contains(r#"config_from = "/only/for/development""#));
         assert!(toml.contains(r#"binding_mode = "relaxed""#));
+        assert!(toml.contains("[health_probe]"));
+        assert!(toml.contains(r#"type = "tcp""#));
     }
 
     #[test]
@@ -719,6 +995,59 @@ mod test {
         }
     }
 
+    #[test]
+    fn service_spec_diff() {
+        let current = ServiceSpec::default_for(PackageIdent::from_str("core/redis").unwrap());
+        assert!(current.diff(&current.clone()).is_empty());
+
+        let mut desired = current.clone();
+        desired.group = String::from("prod");
+        desired.topology = Topology::Leader;
+        desired.binds = vec![ServiceBind::from_str("db:postgres.app").unwrap()];
+        desired.desired_state = DesiredState::Down;
+        desired.health_check.interval = Some(5);
+        desired.health_probe = Some(HealthProbe::Http {
+            url: "http://localhost:8080/healthz".to_string(),
+            status: Some(200),
+            body: None,
+        });
+        desired.hook_timeouts.init = Some(300);
+        desired.restart.policy = Some(RestartPolicy::Never);
+        desired.resources.pids_max = Some(64);
+        desired.rlimits.nofile = Some(4096);
+        desired.isolation.pid = true;
+        desired.ambient_capabilities = Some(vec!["CAP_NET_BIND_SERVICE".to_string()]);
+        desired.shutdown_timeout = Some(120);
+        assert_eq!(
+            current.diff(&desired),
+            vec![
+                "group: default -> prod",
+                "topology: standalone -> leader",
+                "binds: none -> db:postgres.app",
+                "health_check: default -> interval=5",
+                "health_probe: package -> http http://localhost:8080/healthz status=200",
+                "hook_timeouts: none -> init=300",
+                "restart: default -> policy=never",
+                "resources: none -> pids_max=64",
+                "rlimits: none -> nofile=4096",
+                "isolation: none -> pid",
+                "ambient_capabilities: package -> CAP_NET_BIND_SERVICE",
+                "shutdown_timeout: default -> 120",
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn service_spec_shutdown_signal() {
+        let mut spec = ServiceSpec::default_for(PackageIdent::from_str("core/redis").unwrap());
+        assert!(spec.validate_shutdown_signal().is_ok());
+        spec.shutdown_signal = Some("SIGQUIT".to_string());
+        assert!(spec.validate_shutdown_signal().is_ok());
+        spec.shutdown_signal = Some("BOGUS".to_string());
+        assert!(spec.validate_shutdown_signal().is_err());
+    }
+
     #[test]
     fn service_spec_from_file() {
         let tmpdir = TempDir::new("specs").unwrap();
@@ -837,6 +1166,16 @@ mod test {
             desired_state: DesiredState::Down,
             svc_encrypted_password: None,
             composite: None,
+            health_check: HealthCheckSettings::default(),
+            health_probe: None,
+            hook_timeouts: HookTimeouts::default(),
+            restart: RestartSettings::default(),
+            resources: ResourceLimits::default(),
+            rlimits: Rlimits::default(),
+            ambient_capabilities: None,
+            isolation: Isolation::default(),
+            shutdown_signal: None,
+            shutdown_timeout: None,
         };
         spec.to_file(&path).unwrap();
         let toml = string_from_file(path);
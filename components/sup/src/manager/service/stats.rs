@@ -0,0 +1,297 @@
+// Copyright (c) 2018 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Resources used by the tree of processes of a service, sampled from `/proc` on Linux.
+//!
+//! The service's process and all of its descendants are counted, so the resources of workers
+//! forked by a service, and of the processes running inside its PID namespace, are included.
+//! Descendants are found through the `children` files the kernel keeps for each thread, so that
+//! sampling a service only reads its own processes. Kernels without them have all of `/proc`
+//! scanned instead.
+
+use hcore::os::process::Pid;
+
+/// Resources used by a service's process and its descendants.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ProcessStats {
+    /// Microseconds of CPU time used, in user and kernel mode.
+    pub cpu_usec: u64,
+    /// Bytes of resident memory.
+    pub rss_bytes: u64,
+    /// Number of open file descriptors.
+    pub fds: u64,
+    /// Number of threads.
+    pub threads: u64,
+    /// Number of processes.
+    pub processes: u64,
+}
+
+impl ProcessStats {
+    /// Sample the resources used by the process `pid` and its descendants, unless it can't be
+    /// read.
+    #[cfg(target_os = "linux")]
+    pub fn sample(pid: Pid) -> Option<Self> {
+        imp::sample(pid)
+    }
+
+    /// Processes are only sampled on Linux.
+    #[cfg(not(target_os = "linux"))]
+    pub fn sample(_pid: Pid) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod imp {
+    use std::collections::HashMap;
+    use std::fs::{self, File};
+    use std::io::Read;
+    use std::path::Path;
+
+    use hcore::os::process::Pid;
+    use libc;
+
+    use super::ProcessStats;
+
+    const PROC_PATH: &'static str = "/proc";
+
+    /// The fields of `/proc/<pid>/stat` describing a process's resources.
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub struct Stat {
+        pub ppid: Pid,
+        /// Clock ticks spent in user and kernel mode.
+        pub cpu_ticks: u64,
+        pub threads: u64,
+        /// Pages of resident memory.
+        pub rss_pages: u64,
+    }
+
+    impl Stat {
+        /// Parse the content of `/proc/<pid>/stat`. The command name, in parentheses, may itself
+        /// hold spaces and parentheses, so fields are counted from the last closing parenthesis.
+        pub fn parse(content: &str) -> Option<Self> {
+            // Fields after the command name, starting with the state, field 3 in proc(5).
+            let fields: Vec<&str> = match content.rfind(')') {
+                Some(end) => content[end + 1..].split_whitespace().collect(),
+                None => return None,
+            };
+            if fields.len() < 22 {
+                return None;
+            }
+            match (
+                fields[1].parse(),
+                fields[11].parse::<u64>(),
+                fields[12].parse::<u64>(),
+                fields[17].parse(),
+                fields[21].parse(),
+            ) {
+                (Ok(ppid), Ok(utime), Ok(stime), Ok(threads), Ok(rss_pages)) => Some(Stat {
+                    ppid: ppid,
+                    cpu_ticks: utime + stime,
+                    threads: threads,
+                    rss_pages: rss_pages,
+                }),
+                _ => None,
+            }
+        }
+
+        fn read(path: &Path) -> Option<Self> {
+            let mut content = String::new();
+            match File::open(path).and_then(|mut f| f.read_to_string(&mut content)) {
+                Ok(_) => Self::parse(&content),
+                Err(_) => None,
+            }
+        }
+    }
+
+    pub fn sample(pid: Pid) -> Option<ProcessStats> {
+        let tree = match walk_children(pid).or_else(|| scan(pid)) {
+            Some(tree) => tree,
+            None => return None,
+        };
+        if tree.is_empty() {
+            return None;
+        }
+        let ticks_per_sec = unsafe { libc::sysconf(libc::_SC_CLK_TCK) }.max(1) as u64;
+        let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) }.max(0) as u64;
+        let mut stats = ProcessStats::default();
+        let mut cpu_ticks = 0;
+        for &(pid, stat) in &tree {
+            cpu_ticks += stat.cpu_ticks;
+            stats.rss_bytes += stat.rss_pages * page_size;
+            stats.threads += stat.threads;
+            stats.fds += fs::read_dir(format!("{}/{}/fd", PROC_PATH, pid))
+                .map(|fds| fds.count() as u64)
+                .unwrap_or(0);
+        }
+        stats.cpu_usec = cpu_ticks * 1_000_000 / ticks_per_sec;
+        stats.processes = tree.len() as u64;
+        Some(stats)
+    }
+
+    /// The process `root` followed by all of its descendants, found through the `children` files
+    /// of their threads, or `None` if the kernel doesn't provide them.
+    fn walk_children(root: Pid) -> Option<Vec<(Pid, Stat)>> {
+        let probe = format!("{}/{}/task/{}/children", PROC_PATH, root, root);
+        if !Path::new(&probe).exists() {
+            return None;
+        }
+        let mut tree = vec![];
+        let mut pending = vec![root];
+        while let Some(pid) = pending.pop() {
+            let dir = Path::new(PROC_PATH).join(pid.to_string());
+            // Processes may exit while they're read, and are then left out.
+            if let Some(stat) = Stat::read(&dir.join("stat")) {
+                tree.push((pid, stat));
+                pending.extend(children(&dir));
+            }
+        }
+        Some(tree)
+    }
+
+    /// The children of all threads of the process whose directory in `/proc` is `dir`.
+    fn children(dir: &Path) -> Vec<Pid> {
+        let tasks = match fs::read_dir(dir.join("task")) {
+            Ok(tasks) => tasks,
+            Err(_) => return vec![],
+        };
+        let mut pids = vec![];
+        for task in tasks.filter_map(|t| t.ok()) {
+            let mut content = String::new();
+            if File::open(task.path().join("children"))
+                .and_then(|mut f| f.read_to_string(&mut content))
+                .is_ok()
+            {
+                pids.extend(content.split_whitespace().filter_map(|p| p.parse().ok()));
+            }
+        }
+        pids
+    }
+
+    /// The process `root` followed by all of its descendants, found by reading every process in
+    /// `/proc`, or `None` if it can't be read.
+    fn scan(root: Pid) -> Option<Vec<(Pid, Stat)>> {
+        let entries = match fs::read_dir(PROC_PATH) {
+            Ok(entries) => entries,
+            Err(err) => {
+                debug!("Unable to read {}, {}", PROC_PATH, err);
+                return None;
+            }
+        };
+        let mut procs = HashMap::new();
+        for entry in entries.filter_map(|e| e.ok()) {
+            let entry_pid = match entry.file_name().to_str().and_then(|n| n.parse().ok()) {
+                Some(entry_pid) => entry_pid,
+                None => continue,
+            };
+            // Processes may exit while they're read, and are then left out.
+            if let Some(stat) = Stat::read(&entry.path().join("stat")) {
+                procs.insert(entry_pid, stat);
+            }
+        }
+        let tree = process_tree(root, &procs)
+            .into_iter()
+            .map(|pid| (pid, procs[&pid]))
+            .collect();
+        Some(tree)
+    }
+
+    /// The process `root` followed by all of its descendants in `procs`, or nothing if `root`
+    /// isn't in `procs`.
+    pub fn process_tree(root: Pid, procs: &HashMap<Pid, Stat>) -> Vec<Pid> {
+        if !procs.contains_key(&root) {
+            return vec![];
+        }
+        let mut children: HashMap<Pid, Vec<Pid>> = HashMap::new();
+        for (&pid, stat) in procs {
+            children.entry(stat.ppid).or_insert_with(Vec::new).push(pid);
+        }
+        let mut tree = vec![root];
+        let mut next = 0;
+        while next < tree.len() {
+            if let Some(pids) = children.get(&tree[next]) {
+                tree.extend(pids.iter().filter(|&&pid| pid != root));
+            }
+            next += 1;
+        }
+        tree
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod test {
+    use std::collections::HashMap;
+
+    use hcore::os::process::Pid;
+
+    use super::imp::{process_tree, Stat};
+
+    fn stat(ppid: Pid) -> Stat {
+        Stat {
+            ppid: ppid,
+            cpu_ticks: 0,
+            threads: 1,
+            rss_pages: 0,
+        }
+    }
+
+    #[test]
+    fn stat_parse() {
+        let content = "4242 (my (odd) svc) S 4200 4242 4242 0 -1 4194560 1000 0 0 0 150 50 0 0 \
+                       20 0 3 0 123456 104857600 2048 18446744073709551615 1 1 0 0 0 0 0 4096 \
+                       0 0 0 0 17 1 0 0 0 0 0";
+        assert_eq!(
+            Stat::parse(content),
+            Some(Stat {
+                ppid: 4200,
+                cpu_ticks: 200,
+                threads: 3,
+                rss_pages: 2048,
+            })
+        );
+        assert_eq!(Stat::parse("4242 (svc) S 4200"), None);
+    }
+
+    #[test]
+    fn process_tree_includes_descendants() {
+        let mut procs = HashMap::new();
+        procs.insert(1, stat(0));
+        procs.insert(10, stat(1));
+        procs.insert(11, stat(10));
+        procs.insert(12, stat(11));
+        procs.insert(20, stat(1));
+        let mut tree = process_tree(10, &procs);
+        tree.sort();
+        assert_eq!(tree, vec![10, 11, 12]);
+        assert!(process_tree(30, &procs).is_empty());
+    }
+
+    #[test]
+    fn sample_own_process() {
+        let stats = super::ProcessStats::sample(unsafe { ::libc::getpid() }).unwrap();
+        assert!(stats.processes >= 1);
+        assert!(stats.threads >= 1);
+        assert!(stats.fds >= 1);
+        assert!(stats.rss_bytes > 0);
+    }
+
+    #[test]
+    fn sample_includes_children() {
+        let mut child = ::std::process::Command::new("sleep").arg("10").spawn().unwrap();
+        let stats = super::ProcessStats::sample(unsafe { ::libc::getpid() }).unwrap();
+        child.kill().unwrap();
+        child.wait().unwrap();
+        assert!(stats.processes >= 2);
+    }
+}
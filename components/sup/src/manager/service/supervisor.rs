@@ -23,25 +23,91 @@ use std::io::prelude::*;
 use std::io::BufReader;
 use std::path::{Path, PathBuf};
 use std::result;
+use std::time::{Duration, Instant};
 
 use hcore::os::process::{self, Pid};
 #[cfg(unix)]
+use hcore::os::process::Signal;
+#[cfg(unix)]
 use hcore::os::users;
 use hcore::service::ServiceGroup;
-use launcher_client::LauncherCli;
+use launcher_client::{self, LauncherCli};
 use serde::ser::SerializeStruct;
 use serde::{Serialize, Serializer};
 use time::{self, Timespec};
 
+use super::isolation::Isolation;
+use super::resources::{ResourceLimits, ResourceUsage, Rlimits};
+use super::restart::{Restart, RestartSettings, RestartTracker};
+use super::spec::ServiceSpec;
+use super::stats::ProcessStats;
 use super::ProcessState;
 use error::{Error, Result};
 use fs;
 use manager::service::Pkg;
+use protocol::types::RestartPolicy;
 #[cfg(unix)]
 use sys::abilities;
 
 static LOGKEY: &'static str = "SV";
 
+const SHUTDOWN_SIGNAL_METAFILE: &'static str = "SHUTDOWN_SIGNAL";
+const SHUTDOWN_TIMEOUT_METAFILE: &'static str = "SHUTDOWN_TIMEOUT";
+const AMBIENT_CAPABILITIES_METAFILE: &'static str = "AMBIENT_CAPABILITIES";
+/// Seconds between samples of the resources used by the process.
+const STATS_INTERVAL: u64 = 10;
+
+/// Read the ambient capabilities declared by the package installed at `pkg_path`.
+pub fn ambient_capabilities_from_package(pkg_path: &Path) -> Vec<String> {
+    read_metafile(pkg_path, AMBIENT_CAPABILITIES_METAFILE)
+        .map(|caps| caps.split_whitespace().map(|c| c.to_string()).collect())
+        .unwrap_or_default()
+}
+
+/// How the Launcher stops a service's process: the signal asking it to stop, and the seconds to
+/// wait for it to do so before it's killed. Settings which aren't given are left to the Launcher,
+/// which sends `TERM` and waits 8 seconds.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct ShutdownConfig {
+    pub signal: Option<String>,
+    pub timeout: Option<u32>,
+}
+
+impl ShutdownConfig {
+    /// Read the shutdown settings declared by the package installed at `pkg_path`.
+    pub fn from_package(pkg_path: &Path) -> Self {
+        let timeout = read_metafile(pkg_path, SHUTDOWN_TIMEOUT_METAFILE).and_then(|timeout| {
+            match timeout.parse() {
+                Ok(timeout) => Some(timeout),
+                Err(_) => {
+                    outputln!("Ignoring invalid shutdown timeout, {}", timeout);
+                    None
+                }
+            }
+        });
+        ShutdownConfig {
+            signal: read_metafile(pkg_path, SHUTDOWN_SIGNAL_METAFILE),
+            timeout: timeout,
+        }
+    }
+
+    /// The shutdown settings given by a service's spec.
+    pub fn from_spec(spec: &ServiceSpec) -> Self {
+        ShutdownConfig {
+            signal: spec.shutdown_signal.clone(),
+            timeout: spec.shutdown_timeout,
+        }
+    }
+
+    /// Returns these settings with each setting given by `overrides` replaced.
+    pub fn merge(&self, overrides: &ShutdownConfig) -> Self {
+        ShutdownConfig {
+            signal: overrides.signal.clone().or_else(|| self.signal.clone()),
+            timeout: overrides.timeout.or(self.timeout),
+        }
+    }
+}
+
 /// Bundles up information about the user and group that a supervised
 /// service should be run as. If the Supervisor itself is running with
 /// root-like permissions, then these will be for `SVC_USER` and
@@ -66,6 +132,37 @@ pub struct Supervisor {
     pub state_entered: Timespec,
     pid: Option<Pid>,
     pid_file: PathBuf,
+    /// Number of times the process has been restarted, whether on request or because it died.
+    restart_count: u32,
+    /// Exit code of the last process stopped by the Supervisor.
+    last_exit_code: Option<i32>,
+    /// How the Launcher stops the process.
+    pub shutdown: ShutdownConfig,
+    /// Whether and how fast the process is restarted when it exits on its own.
+    pub restart: RestartSettings,
+    /// Resources the process may use.
+    pub resources: ResourceLimits,
+    /// Limits set on the process before it's executed.
+    pub rlimits: Rlimits,
+    /// Linux capabilities the process keeps when running as the service's user.
+    pub ambient_capabilities: Vec<String>,
+    /// Namespaces the process is isolated in.
+    pub isolation: Isolation,
+    restarts: RestartTracker,
+    /// The process which exited on its own, until its exit is counted.
+    exited: Option<Pid>,
+    /// Resources last sampled from a running process, along with its pid and when they were
+    /// sampled.
+    stats: Option<(Pid, ProcessStats, Instant)>,
+    /// Microseconds of CPU time used by the processes of the service since it was loaded, once
+    /// they were sampled.
+    cpu_usec_total: Option<u64>,
+    /// Resources used by the cgroup of a running process, last read from the Launcher along with
+    /// its pid.
+    cgroup_usage: Option<(Pid, ResourceUsage)>,
+    /// Cleared once the Launcher didn't answer a request for resource usage, as older Launchers
+    /// don't, so that it isn't asked again.
+    launcher_reports_usage: bool,
 }
 
 impl Supervisor {
@@ -76,11 +173,30 @@ impl Supervisor {
             state_entered: time::get_time(),
             pid: None,
             pid_file: fs::svc_pid_file(service_group.service()),
+            restart_count: 0,
+            last_exit_code: None,
+            shutdown: ShutdownConfig::default(),
+            restart: RestartSettings::default(),
+            resources: ResourceLimits::default(),
+            rlimits: Rlimits::default(),
+            ambient_capabilities: vec![],
+            isolation: Isolation::default(),
+            restarts: RestartTracker::default(),
+            exited: None,
+            stats: None,
+            cpu_usec_total: None,
+            cgroup_usage: None,
+            launcher_reports_usage: true,
         }
     }
 
+    /// Number of times the process has been restarted since the service was loaded.
+    pub fn restart_count(&self) -> u32 {
+        self.restart_count
+    }
+
     /// Check if the child process is running
-    pub fn check_process(&mut self) -> bool {
+    pub fn check_process(&mut self, launcher: &LauncherCli) -> bool {
         let pid = match self.pid {
             Some(pid) => Some(pid),
             None => {
@@ -95,16 +211,162 @@ impl Supervisor {
             if process::is_alive(pid) {
                 self.change_state(ProcessState::Up);
                 self.pid = Some(pid);
+                self.sample_stats(pid, launcher);
                 return true;
             }
         }
         debug!("Could not find a live process with pid {:?}", self.pid);
+        if self.state == ProcessState::Up {
+            self.exited = self.pid;
+        }
         self.change_state(ProcessState::Down);
         self.cleanup_pidfile();
         self.pid = None;
+        self.stats = None;
+        self.cgroup_usage = None;
         false
     }
 
+    /// Resources last sampled from the running process, if it could be read.
+    pub fn stats(&self) -> Option<&ProcessStats> {
+        if self.state != ProcessState::Up {
+            return None;
+        }
+        match self.stats {
+            Some((pid, ref stats, _)) if self.pid == Some(pid) => Some(stats),
+            _ => None,
+        }
+    }
+
+    /// Microseconds of CPU time used by the processes of the service since it was loaded, across
+    /// restarts, or `None` if they were never sampled.
+    pub fn cpu_usec_total(&self) -> Option<u64> {
+        self.cpu_usec_total
+    }
+
+    /// Resources used by the cgroup of the running process, if it was spawned with resource
+    /// limits and the Launcher reported them.
+    pub fn cgroup_usage(&self) -> Option<&ResourceUsage> {
+        if self.state != ProcessState::Up {
+            return None;
+        }
+        match self.cgroup_usage {
+            Some((pid, ref usage)) if self.pid == Some(pid) => Some(usage),
+            _ => None,
+        }
+    }
+
+    /// Sample the resources used by the process, unless they were sampled from the same process
+    /// recently.
+    fn sample_stats(&mut self, pid: Pid, launcher: &LauncherCli) {
+        let now = Instant::now();
+        if let Some((sampled_pid, _, sampled)) = self.stats {
+            let interval = Duration::from_secs(STATS_INTERVAL);
+            if sampled_pid == pid && now.duration_since(sampled) < interval {
+                return;
+            }
+        }
+        let stats = ProcessStats::sample(pid);
+        if let Some(ref stats) = stats {
+            // The CPU time of a new process counts from its start, that of the same process from
+            // its last sample. Descendants which exited take their CPU time with them.
+            let used = match self.stats {
+                Some((sampled_pid, ref sampled, _)) if sampled_pid == pid => {
+                    stats.cpu_usec.saturating_sub(sampled.cpu_usec)
+                }
+                _ => stats.cpu_usec,
+            };
+            self.cpu_usec_total = Some(self.cpu_usec_total.unwrap_or(0) + used);
+        }
+        self.stats = stats.map(|stats| (pid, stats, now));
+        self.cgroup_usage = self.read_cgroup_usage(pid, launcher)
+            .map(|usage| (pid, usage));
+    }
+
+    /// Ask the Launcher for the resources used by the cgroup of the process, if it was spawned
+    /// with resource limits.
+    fn read_cgroup_usage(&mut self, pid: Pid, launcher: &LauncherCli) -> Option<ResourceUsage> {
+        if !self.launcher_reports_usage || self.resources.to_launcher().is_none() {
+            return None;
+        }
+        match launcher.resource_usage(pid) {
+            Ok(usage) => {
+                let usage = ResourceUsage::from_launcher(&usage);
+                if usage.is_empty() {
+                    None
+                } else {
+                    Some(usage)
+                }
+            }
+            Err(launcher_client::Error::Timeout) => {
+                debug!("Launcher doesn't report the resource usage of processes");
+                self.launcher_reports_usage = false;
+                None
+            }
+            Err(err) => {
+                debug!("Unable to get the resource usage of process {}, {}", pid, err);
+                None
+            }
+        }
+    }
+
+    /// Whether the process, which isn't running, may be started again now. The first time this
+    /// is asked after the process exited on its own, the exit is counted against the restart
+    /// settings, which decide when the process is restarted, if at all.
+    pub fn may_restart(&mut self, launcher: &LauncherCli) -> bool {
+        let now = Instant::now();
+        if let Some(pid) = self.exited.take() {
+            // Only ask for the exit code when it matters. Older Launchers don't answer, so the
+            // request times out and the exit code is left unknown.
+            let exit_code = if self.restart.policy() == RestartPolicy::OnFailure {
+                match launcher.exit_code(pid) {
+                    Ok(code) => code,
+                    Err(err) => {
+                        debug!("Unable to get the exit code of process {}, {}", pid, err);
+                        None
+                    }
+                }
+            } else {
+                None
+            };
+            if exit_code.is_some() {
+                self.last_exit_code = exit_code;
+            }
+            match self.restarts.record_exit(&self.restart, exit_code, now) {
+                Restart::At(at) => {
+                    outputln!(preamble self.preamble,
+                              "Process exited, restarting in {}s",
+                              at.duration_since(now).as_secs());
+                }
+                Restart::Held => {
+                    outputln!(preamble self.preamble,
+                              "Process exited, not restarting it with restart policy {}",
+                              self.restart.policy());
+                }
+                Restart::CrashLooping => {
+                    outputln!(preamble self.preamble,
+                              "Process exited more than {} times in {}s, leaving it down as \
+                               crash-looping until the service is restarted",
+                              self.restart.max_restarts(),
+                              self.restart.window().as_secs());
+                }
+            }
+        }
+        self.restarts.ready(now)
+    }
+
+    /// Forget the exits counted against the restart settings, as the service was asked to
+    /// restart.
+    pub fn reset_restarts(&mut self) {
+        self.exited = None;
+        self.restarts.reset();
+    }
+
+    /// Whether the process exited too often to be restarted.
+    pub fn crash_looping(&self) -> bool {
+        self.restarts.crash_looping()
+    }
+
     // NOTE: the &self argument is only used to get access to
     // self.preamble, and even then only for Linux :/
     #[cfg(unix)]
@@ -212,8 +474,16 @@ impl Supervisor {
             service_group_id, // Linux preferred
             svc_password,     // Windows optional
             (*pkg.env).clone(),
+            fs::svc_output_log(group.service()),
+            self.shutdown.signal.as_ref().map(|s| s.as_str()),
+            self.shutdown.timeout,
+            self.resources.to_launcher(),
+            self.rlimits.to_launcher(),
+            self.ambient_capabilities.clone(),
+            self.isolation.to_launcher(),
         )?;
         self.pid = Some(pid);
+        self.exited = None;
         self.create_pidfile()?;
         self.change_state(ProcessState::Up);
         Ok(())
@@ -237,7 +507,11 @@ impl Supervisor {
         if self.pid.is_none() {
             return Ok(());
         }
-        launcher.terminate(self.pid.unwrap())?;
+        self.last_exit_code = Some(launcher.terminate(
+            self.pid.unwrap(),
+            self.shutdown.signal.as_ref().map(|s| s.as_str()),
+            self.shutdown.timeout,
+        )?);
         self.cleanup_pidfile();
         self.change_state(ProcessState::Down);
         Ok(())
@@ -253,6 +527,7 @@ impl Supervisor {
     where
         T: ToString,
     {
+        self.restart_count += 1;
         match self.pid {
             Some(pid) => match launcher.restart(pid) {
                 Ok(pid) => {
@@ -271,6 +546,17 @@ impl Supervisor {
         }
     }
 
+    /// Send the given signal to the running service's process.
+    #[cfg(unix)]
+    pub fn signal(&self, signal: Signal) -> Result<()> {
+        match self.pid {
+            Some(pid) => {
+                process::signal(pid, signal).map_err(|_| sup_error!(Error::SignalFailed))
+            }
+            None => Err(sup_error!(Error::SignalFailed)),
+        }
+    }
+
     /// Create a PID file for a running service
     fn create_pidfile(&mut self) -> Result<()> {
         match self.pid {
@@ -315,14 +601,42 @@ impl Serialize for Supervisor {
     where
         S: Serializer,
     {
-        let mut strukt = serializer.serialize_struct("supervisor", 5)?;
+        let mut strukt = serializer.serialize_struct("supervisor", 14)?;
         strukt.serialize_field("pid", &self.pid)?;
         strukt.serialize_field("state", &self.state)?;
         strukt.serialize_field("state_entered", &self.state_entered.sec)?;
+        strukt.serialize_field("restart_count", &self.restart_count)?;
+        strukt.serialize_field("last_exit_code", &self.last_exit_code)?;
+        strukt.serialize_field("shutdown", &self.shutdown)?;
+        strukt.serialize_field("restart", &self.restart)?;
+        strukt.serialize_field("resources", &self.resources)?;
+        strukt.serialize_field("rlimits", &self.rlimits)?;
+        strukt.serialize_field("ambient_capabilities", &self.ambient_capabilities)?;
+        strukt.serialize_field("isolation", &self.isolation)?;
+        strukt.serialize_field("crash_looping", &self.crash_looping())?;
+        strukt.serialize_field("stats", &self.stats())?;
+        strukt.serialize_field("cgroup_usage", &self.cgroup_usage())?;
         strukt.end()
     }
 }
 
+/// Read the trimmed content of one of a package's metadata files, if it exists and isn't empty.
+fn read_metafile(pkg_path: &Path, name: &str) -> Option<String> {
+    let mut content = String::new();
+    if File::open(pkg_path.join(name))
+        .and_then(|mut file| file.read_to_string(&mut content))
+        .is_err()
+    {
+        return None;
+    }
+    let content = content.trim();
+    if content.is_empty() {
+        None
+    } else {
+        Some(content.to_string())
+    }
+}
+
 fn read_pid<T>(pid_file: T) -> Result<Pid>
 where
     T: AsRef<Path>,
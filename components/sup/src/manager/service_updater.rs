@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::mpsc::{sync_channel, Receiver, SyncSender, TryRecvError};
 use std::thread;
 
@@ -25,7 +25,8 @@ use launcher_client::LauncherCli;
 
 use census::CensusRing;
 use manager::periodic::Periodic;
-use manager::service::{Service, Topology, UpdateStrategy};
+use manager::service::{Service, Topology, UpdateState, UpdateStrategy};
+use metrics::{self, UpdateCheck};
 use util;
 
 static LOGKEY: &'static str = "SU";
@@ -68,6 +69,8 @@ enum FollowerState {
 pub struct ServiceUpdater {
     states: UpdaterStateList,
     butterfly: butterfly::Server,
+    /// Service groups whose last update was aborted, until one of their updates succeeds.
+    aborted: HashSet<ServiceGroup>,
 }
 
 impl ServiceUpdater {
@@ -75,6 +78,7 @@ impl ServiceUpdater {
         ServiceUpdater {
             states: UpdaterStateList::default(),
             butterfly: butterfly,
+            aborted: HashSet::new(),
         }
     }
 
@@ -102,6 +106,27 @@ impl ServiceUpdater {
         }
     }
 
+    /// The progress of the given service group through its update strategy.
+    pub fn update_state(&self, service_group: &ServiceGroup) -> UpdateState {
+        match self.states.get(service_group) {
+            None => UpdateState::None,
+            Some(_) if self.aborted.contains(service_group) => UpdateState::Aborted,
+            Some(&UpdaterState::AtOnce(_)) => UpdateState::Polling,
+            Some(&UpdaterState::Rolling(RollingState::AwaitingElection))
+            | Some(&UpdaterState::Rolling(RollingState::InElection)) => UpdateState::Electing,
+            Some(&UpdaterState::Rolling(RollingState::Leader(LeaderState::Polling(_)))) => {
+                UpdateState::Polling
+            }
+            Some(&UpdaterState::Rolling(RollingState::Leader(LeaderState::Waiting)))
+            | Some(&UpdaterState::Rolling(RollingState::Follower(FollowerState::Waiting))) => {
+                UpdateState::Waiting
+            }
+            Some(&UpdaterState::Rolling(RollingState::Follower(FollowerState::Updating(_)))) => {
+                UpdateState::Updating
+            }
+        }
+    }
+
     // TODO (CM): How do we remove something from the updater? e.g.,
     // when we stop or unload a service?
 
@@ -118,8 +143,13 @@ impl ServiceUpdater {
         match self.states.get_mut(&service.service_group) {
             Some(&mut UpdaterState::AtOnce(ref mut rx)) => match rx.try_recv() {
                 Ok(package) => {
-                    service.update_package(package, launcher);
-                    return true;
+                    if update_service(service, package, launcher, &mut self.aborted) {
+                        return true;
+                    }
+                    *rx = Worker::new(service)
+                        .retry()
+                        .start(&service.service_group, None);
+                    return false;
                 }
                 Err(TryRecvError::Empty) => return false,
                 Err(TryRecvError::Disconnected) => {
@@ -183,8 +213,13 @@ impl ServiceUpdater {
                     LeaderState::Polling(ref mut rx) => match rx.try_recv() {
                         Ok(package) => {
                             debug!("Rolling Update, polling found a new package");
-                            service.update_package(package, launcher);
-                            updated = true;
+                            if update_service(service, package, launcher, &mut self.aborted) {
+                                updated = true;
+                            } else {
+                                *rx = Worker::new(service)
+                                    .retry()
+                                    .start(&service.service_group, None);
+                            }
                         }
                         Err(TryRecvError::Empty) => return false,
                         Err(TryRecvError::Disconnected) => {
@@ -252,8 +287,14 @@ impl ServiceUpdater {
                     {
                         Some(census_group) => match rx.try_recv() {
                             Ok(package) => {
-                                service.update_package(package, launcher);
-                                updated = true
+                                let ident = package.ident().clone();
+                                if update_service(service, package, launcher, &mut self.aborted) {
+                                    updated = true
+                                } else {
+                                    *rx = Worker::new(service)
+                                        .retry()
+                                        .start(&service.service_group, Some(ident));
+                                }
                             }
                             Err(TryRecvError::Empty) => return false,
                             Err(TryRecvError::Disconnected) => {
@@ -278,11 +319,35 @@ impl ServiceUpdater {
     }
 }
 
+/// Update a service to the given package, recording whether the service was updated or aborted
+/// the update.
+///
+/// Returns `true` if the service was updated.
+fn update_service(
+    service: &mut Service,
+    package: PackageInstall,
+    launcher: &LauncherCli,
+    aborted: &mut HashSet<ServiceGroup>,
+) -> bool {
+    let updated = service.update_package(package, launcher);
+    if updated {
+        metrics::observe_update_check(&service.service_group, UpdateCheck::Updated);
+        aborted.remove(&service.service_group);
+    } else {
+        metrics::observe_update_check(&service.service_group, UpdateCheck::Aborted);
+        aborted.insert(service.service_group.clone());
+    }
+    updated
+}
+
 struct Worker {
+    service_group: ServiceGroup,
     current: PackageIdent,
     spec_ident: PackageIdent,
     builder_url: String,
     channel: String,
+    /// Whether to wait for a period before the first check.
+    retry: bool,
 }
 
 impl Periodic for Worker {
@@ -338,13 +403,22 @@ impl Periodic for Worker {
 impl Worker {
     fn new(service: &Service) -> Self {
         Worker {
+            service_group: service.service_group.clone(),
             current: service.pkg.ident.clone(),
             spec_ident: service.spec_ident.clone(),
             builder_url: service.bldr_url.clone(),
             channel: service.channel.clone(),
+            retry: false,
         }
     }
 
+    /// Wait for a period before the first check, so that an update which the service aborted is
+    /// retried at the pace of regular checks rather than on every tick.
+    fn retry(mut self) -> Self {
+        self.retry = true;
+        self
+    }
+
     /// Start a new update worker.
     ///
     /// Passing an optional package identifier will make the worker perform a run-once update to
@@ -354,9 +428,15 @@ impl Worker {
         let (tx, rx) = sync_channel(0);
         thread::Builder::new()
             .name(format!("service-updater-{}", sg))
-            .spawn(move || match ident {
-                Some(latest) => self.run_once(tx, latest),
-                None => self.run_poll(tx),
+            .spawn(move || {
+                if self.retry {
+                    let next_time = self.next_period_start();
+                    self.sleep_until(next_time);
+                }
+                match ident {
+                    Some(latest) => self.run_once(tx, latest),
+                    None => self.run_poll(tx),
+                }
             })
             .expect("unable to start service-updater thread");
         rx
@@ -405,7 +485,10 @@ impl Worker {
                     sender.send(package).expect("Main thread has gone away!");
                     break;
                 }
-                Err(e) => warn!("Failed to install updated package: {:?}", e),
+                Err(e) => {
+                    metrics::observe_update_check(&self.service_group, UpdateCheck::Failed);
+                    warn!("Failed to install updated package: {:?}", e)
+                }
             }
 
             self.sleep_until(next_time);
@@ -439,10 +522,14 @@ impl Worker {
                             .expect("Main thread has gone away!");
                         break;
                     } else {
+                        metrics::observe_update_check(&self.service_group, UpdateCheck::Current);
                         debug!("Package found is not newer than ours");
                     }
                 }
-                Err(e) => warn!("Updater failed to get latest package: {:?}", e),
+                Err(e) => {
+                    metrics::observe_update_check(&self.service_group, UpdateCheck::Failed);
+                    warn!("Updater failed to get latest package: {:?}", e)
+                }
             }
 
             self.sleep_until(next_time);
@@ -0,0 +1,384 @@
+// Copyright (c) 2018 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Metrics describing the Supervisor and the services it runs, served in the Prometheus text
+//! format by the HTTP gateway's `/metrics` endpoint.
+//!
+//! Metrics describing the current state of services and of the gossip ring, and the counters of
+//! service restarts and gossip rounds, are refreshed by the Manager on every tick, those describing
+//! the resources used by services' processes as often as the Supervisor samples them, as is the
+//! counter of the CPU time they used. Hook runs and update checks are recorded as they happen.
+
+use std::time::Duration;
+
+use butterfly;
+use butterfly::member::Health;
+use hcore::service::ServiceGroup;
+use prometheus::{self, Counter, CounterVec, Encoder, GaugeVec, HistogramVec, TextEncoder};
+
+use manager::service::{HealthCheck, ProcessState, ProcessStats, ResourceUsage};
+
+lazy_static! {
+    static ref SERVICE_UP: GaugeVec = register_gauge_vec!(
+        "hab_sup_service_up",
+        "Whether the process of the service is running (1) or not (0)",
+        &["service_group"]
+    ).unwrap();
+    static ref SERVICE_UPTIME: GaugeVec = register_gauge_vec!(
+        "hab_sup_service_uptime_seconds",
+        "Seconds since the process of the service last changed state",
+        &["service_group"]
+    ).unwrap();
+    static ref SERVICE_RESTARTS: CounterVec = register_counter_vec!(
+        "hab_sup_service_restarts_total",
+        "Number of times the process of the service has been restarted since it was loaded",
+        &["service_group"]
+    ).unwrap();
+    static ref SERVICE_HEALTH: GaugeVec = register_gauge_vec!(
+        "hab_sup_service_health_check",
+        "Last health check result: 0 ok, 1 warning, 2 critical, 3 unknown",
+        &["service_group"]
+    ).unwrap();
+    static ref SERVICE_CPU: CounterVec = register_counter_vec!(
+        "hab_sup_service_cpu_seconds_total",
+        "CPU time used by the processes of the service since it was loaded",
+        &["service_group"]
+    ).unwrap();
+    static ref SERVICE_MEMORY: GaugeVec = register_gauge_vec!(
+        "hab_sup_service_memory_rss_bytes",
+        "Resident memory of the process of the service and its descendants",
+        &["service_group"]
+    ).unwrap();
+    static ref SERVICE_FDS: GaugeVec = register_gauge_vec!(
+        "hab_sup_service_open_fds",
+        "Open file descriptors of the process of the service and its descendants",
+        &["service_group"]
+    ).unwrap();
+    static ref SERVICE_THREADS: GaugeVec = register_gauge_vec!(
+        "hab_sup_service_threads",
+        "Threads of the process of the service and its descendants",
+        &["service_group"]
+    ).unwrap();
+    static ref SERVICE_PROCESSES: GaugeVec = register_gauge_vec!(
+        "hab_sup_service_processes",
+        "Number of processes in the tree of the process of the service",
+        &["service_group"]
+    ).unwrap();
+    static ref CGROUP_MEMORY: GaugeVec = register_gauge_vec!(
+        "hab_sup_service_cgroup_memory_bytes",
+        "Memory charged to the cgroup of the service, as reported by the Launcher",
+        &["service_group"]
+    ).unwrap();
+    static ref CGROUP_PIDS: GaugeVec = register_gauge_vec!(
+        "hab_sup_service_cgroup_pids",
+        "Number of processes in the cgroup of the service, as reported by the Launcher",
+        &["service_group"]
+    ).unwrap();
+    static ref HOOK_DURATION: HistogramVec = register_histogram_vec!(
+        "hab_sup_hook_duration_seconds",
+        "Time taken by a hook to run",
+        &["service_group", "hook"]
+    ).unwrap();
+    static ref HOOK_EXIT_CODE: GaugeVec = register_gauge_vec!(
+        "hab_sup_hook_exit_code",
+        "Exit code of the last run of a hook",
+        &["service_group", "hook"]
+    ).unwrap();
+    static ref UPDATE_CHECKS: CounterVec = register_counter_vec!(
+        "hab_sup_update_checks_total",
+        "Number of checks for an updated package of a service, by result",
+        &["service_group", "result"]
+    ).unwrap();
+    static ref GOSSIP_ROUNDS: Counter = register_counter!(
+        "hab_sup_gossip_rounds_total",
+        "Number of gossip rounds completed by the Supervisor"
+    ).unwrap();
+    static ref SWIM_ROUNDS: Counter = register_counter!(
+        "hab_sup_swim_rounds_total",
+        "Number of SWIM rounds completed by the Supervisor"
+    ).unwrap();
+    static ref MEMBERS: GaugeVec = register_gauge_vec!(
+        "hab_sup_members",
+        "Number of members of the gossip ring, by health",
+        &["health"]
+    ).unwrap();
+    static ref RUMORS: GaugeVec = register_gauge_vec!(
+        "hab_sup_rumors",
+        "Number of rumors held by the Supervisor, by type",
+        &["type"]
+    ).unwrap();
+}
+
+/// Outcome of a check for an updated package.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UpdateCheck {
+    /// A newer package was found, installed and the service updated to it.
+    Updated,
+    /// The installed package is the latest one available.
+    Current,
+    /// The check failed, for example because Builder couldn't be reached.
+    Failed,
+    /// A newer package was installed, but the service's `pre-update` hook aborted the update.
+    Aborted,
+}
+
+impl UpdateCheck {
+    fn as_str(&self) -> &'static str {
+        match *self {
+            UpdateCheck::Updated => "updated",
+            UpdateCheck::Current => "current",
+            UpdateCheck::Failed => "failed",
+            UpdateCheck::Aborted => "aborted",
+        }
+    }
+}
+
+/// Encode all metrics in the Prometheus text format, returning the content type of the encoding
+/// along with the encoded metrics.
+pub fn encode() -> prometheus::Result<(String, Vec<u8>)> {
+    let encoder = TextEncoder::new();
+    let mut buffer = vec![];
+    encoder.encode(&prometheus::gather(), &mut buffer)?;
+    Ok((encoder.format_type().to_string(), buffer))
+}
+
+/// Record the current state of a running service. The resources used by its process are only
+/// reported while they're sampled, and those used by its cgroup while the Launcher reports them.
+pub fn observe_service(
+    service_group: &ServiceGroup,
+    state: ProcessState,
+    uptime: i64,
+    restarts: u32,
+    health: HealthCheck,
+    stats: Option<&ProcessStats>,
+    cgroup_usage: Option<&ResourceUsage>,
+) {
+    let labels = &[service_group.as_ref()];
+    let up = match state {
+        ProcessState::Up => 1.0,
+        ProcessState::Down => 0.0,
+    };
+    SERVICE_UP.with_label_values(labels).set(up);
+    SERVICE_UPTIME.with_label_values(labels).set(uptime as f64);
+    advance_counter_vec(&*SERVICE_RESTARTS, labels, restarts as f64);
+    SERVICE_HEALTH
+        .with_label_values(labels)
+        .set(health_value(health));
+    match stats {
+        Some(stats) => {
+            SERVICE_MEMORY
+                .with_label_values(labels)
+                .set(stats.rss_bytes as f64);
+            SERVICE_FDS.with_label_values(labels).set(stats.fds as f64);
+            SERVICE_THREADS
+                .with_label_values(labels)
+                .set(stats.threads as f64);
+            SERVICE_PROCESSES
+                .with_label_values(labels)
+                .set(stats.processes as f64);
+        }
+        None => remove_stats(labels),
+    }
+    observe_cgroup(labels, &*CGROUP_MEMORY, cgroup_usage.and_then(|u| u.memory_bytes));
+    observe_cgroup(labels, &*CGROUP_PIDS, cgroup_usage.and_then(|u| u.pids));
+}
+
+fn observe_cgroup(labels: &[&str], gauge: &GaugeVec, value: Option<u64>) {
+    match value {
+        Some(value) => gauge.with_label_values(labels).set(value as f64),
+        None => {
+            let _ = gauge.remove_label_values(labels);
+        }
+    }
+}
+
+/// Record the CPU time used by the processes of a service since it was loaded.
+pub fn observe_cpu(service_group: &ServiceGroup, cpu_usec_total: u64) {
+    let labels = &[service_group.as_ref()];
+    advance_counter_vec(&*SERVICE_CPU, labels, cpu_usec_total as f64 / 1e6);
+}
+
+/// Bring a counter up to `total`. Prometheus counters only go up, so the counter is increased by
+/// what was counted since it was last recorded.
+fn advance_counter(counter: &Counter, total: f64) {
+    let counted = counter.get();
+    if total > counted {
+        counter.inc_by(total - counted);
+    }
+}
+
+/// Bring the counter with the given labels up to `total`. A count which started over, as that of
+/// a service loaded again does, is reported as a reset of the counter.
+fn advance_counter_vec(counters: &CounterVec, labels: &[&str], total: f64) {
+    if total < counters.with_label_values(labels).get() {
+        let _ = counters.remove_label_values(labels);
+    }
+    advance_counter(&counters.with_label_values(labels), total);
+}
+
+/// Stop reporting the state of a service which has been unloaded.
+pub fn remove_service(service_group: &ServiceGroup) {
+    let labels = &[service_group.as_ref()];
+    for gauge in &[&*SERVICE_UP, &*SERVICE_UPTIME, &*SERVICE_HEALTH] {
+        let _ = gauge.remove_label_values(labels);
+    }
+    for counter in &[&*SERVICE_RESTARTS, &*SERVICE_CPU] {
+        let _ = counter.remove_label_values(labels);
+    }
+    remove_stats(labels);
+}
+
+fn remove_stats(labels: &[&str]) {
+    for gauge in &[
+        &*SERVICE_MEMORY,
+        &*SERVICE_FDS,
+        &*SERVICE_THREADS,
+        &*SERVICE_PROCESSES,
+        &*CGROUP_MEMORY,
+        &*CGROUP_PIDS,
+    ] {
+        let _ = gauge.remove_label_values(labels);
+    }
+}
+
+/// Record a completed run of a service's hook. Hooks which were terminated by a signal have no
+/// exit code.
+pub fn observe_hook(
+    service_group: &ServiceGroup,
+    hook: &str,
+    duration: Duration,
+    exit_code: Option<i32>,
+) {
+    let labels = &[service_group.as_ref(), hook];
+    let seconds = duration.as_secs() as f64 + duration.subsec_nanos() as f64 / 1e9;
+    HOOK_DURATION.with_label_values(labels).observe(seconds);
+    if let Some(code) = exit_code {
+        HOOK_EXIT_CODE.with_label_values(labels).set(code as f64);
+    }
+}
+
+/// Record a check for an updated package of a service.
+pub fn observe_update_check(service_group: &ServiceGroup, result: UpdateCheck) {
+    UPDATE_CHECKS
+        .with_label_values(&[service_group.as_ref(), result.as_str()])
+        .inc();
+}
+
+/// Record the current state of the gossip ring.
+pub fn observe_butterfly(server: &butterfly::Server) {
+    advance_counter(&*GOSSIP_ROUNDS, server.gossip_rounds() as f64);
+    advance_counter(&*SWIM_ROUNDS, server.swim_rounds() as f64);
+
+    let (mut alive, mut suspect, mut confirmed, mut departed) = (0, 0, 0, 0);
+    server.member_list.with_members(|member| {
+        match server.member_list.health_of(member) {
+            Some(Health::Alive) => alive += 1,
+            Some(Health::Suspect) => suspect += 1,
+            Some(Health::Confirmed) => confirmed += 1,
+            Some(Health::Departed) => departed += 1,
+            None => (),
+        }
+    });
+    MEMBERS.with_label_values(&["alive"]).set(alive as f64);
+    MEMBERS.with_label_values(&["suspect"]).set(suspect as f64);
+    MEMBERS.with_label_values(&["confirmed"]).set(confirmed as f64);
+    MEMBERS.with_label_values(&["departed"]).set(departed as f64);
+
+    let rumors = [
+        ("service", server.service_store.len()),
+        ("service_config", server.service_config_store.len()),
+        ("service_file", server.service_file_store.len()),
+        ("election", server.election_store.len()),
+        ("election_update", server.update_store.len()),
+        ("departure", server.departure_store.len()),
+    ];
+    for &(kind, len) in rumors.iter() {
+        RUMORS.with_label_values(&[kind]).set(len as f64);
+    }
+}
+
+fn health_value(health: HealthCheck) -> f64 {
+    match health {
+        HealthCheck::Ok => 0.0,
+        HealthCheck::Warning => 1.0,
+        HealthCheck::Critical => 2.0,
+        HealthCheck::Unknown => 3.0,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn encode_service_metrics() {
+        let sg = ServiceGroup::new(None, "metrics", "test", None).unwrap();
+        let stats = ProcessStats {
+            cpu_usec: 1_500_000,
+            rss_bytes: 4096,
+            fds: 12,
+            threads: 3,
+            processes: 2,
+        };
+        let usage = ResourceUsage {
+            memory_bytes: Some(8192),
+            pids: Some(3),
+            ..ResourceUsage::default()
+        };
+        observe_service(
+            &sg,
+            ProcessState::Up,
+            30,
+            2,
+            HealthCheck::Warning,
+            Some(&stats),
+            Some(&usage),
+        );
+        observe_cpu(&sg, 1_000_000);
+        observe_cpu(&sg, 1_500_000);
+        observe_hook(&sg, "health_check", Duration::from_millis(1500), Some(1));
+        observe_update_check(&sg, UpdateCheck::Current);
+
+        let (content_type, body) = encode().unwrap();
+        let body = String::from_utf8(body).unwrap();
+        assert!(content_type.starts_with("text/plain"));
+        assert!(body.contains("hab_sup_service_up{service_group=\"metrics.test\"} 1"));
+        assert!(body.contains(
+            "hab_sup_service_restarts_total{service_group=\"metrics.test\"} 2"
+        ));
+        assert!(body.contains("# TYPE hab_sup_service_restarts_total counter"));
+        assert!(body.contains("hab_sup_service_health_check{service_group=\"metrics.test\"} 1"));
+        assert!(body.contains(
+            "hab_sup_service_cpu_seconds_total{service_group=\"metrics.test\"} 1.5"
+        ));
+        assert!(body.contains("# TYPE hab_sup_service_cpu_seconds_total counter"));
+        assert!(body.contains("hab_sup_service_open_fds{service_group=\"metrics.test\"} 12"));
+        assert!(body.contains(
+            "hab_sup_service_cgroup_memory_bytes{service_group=\"metrics.test\"} 8192"
+        ));
+        assert!(body.contains(
+            "hab_sup_hook_exit_code{hook=\"health_check\",service_group=\"metrics.test\"} 1"
+        ));
+
+        remove_service(&sg);
+        let (_, body) = encode().unwrap();
+        let body = String::from_utf8(body).unwrap();
+        assert!(!body.contains("hab_sup_service_up{service_group=\"metrics.test\"}"));
+        assert!(!body.contains("hab_sup_service_threads{service_group=\"metrics.test\"}"));
+        assert!(!body.contains(
+            "hab_sup_service_cpu_seconds_total{service_group=\"metrics.test\"}"
+        ));
+        assert!(!body.contains("hab_sup_service_cgroup_pids{service_group=\"metrics.test\"}"));
+    }
+}
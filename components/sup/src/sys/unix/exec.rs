@@ -13,24 +13,41 @@
 // limitations under the License.
 
 use std::ffi::OsStr;
+use std::io;
 use std::os::unix::process::CommandExt;
 use std::process::{Child, Command, Stdio};
+use std::result;
+
+use libc;
 
 use sys::abilities;
 
 use hcore::os;
+use hcore::os::process::{self, Pid, Signal};
 
 use error::{Error, Result};
 use manager::service::Pkg;
 
 static LOGKEY: &'static str = "EX";
 
-pub fn run<T, S>(path: S, pkg: &Pkg, _: Option<T>) -> Result<Child>
+pub fn run<T, S>(path: S, pkg: &Pkg, svc_encrypted_password: Option<T>) -> Result<Child>
+where
+    T: ToString,
+    S: AsRef<OsStr>,
+{
+    run_command::<T, S, &OsStr>(path, &[], pkg, svc_encrypted_password)
+}
+
+/// Run a program with the given arguments, as the service's user if possible.
+pub fn run_command<T, S, A>(path: S, args: &[A], pkg: &Pkg, _: Option<T>) -> Result<Child>
 where
     T: ToString,
     S: AsRef<OsStr>,
+    A: AsRef<OsStr>,
 {
     let mut cmd = Command::new(path.as_ref());
+    cmd.args(args);
+    cmd.before_exec(owned_pgid);
     cmd.stdin(Stdio::null())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped());
@@ -61,3 +78,18 @@ where
 
     Ok(cmd.spawn()?)
 }
+
+/// Forcefully terminate the process with the given pid along with the rest of its process group,
+/// which holds any processes it started.
+pub fn kill(pid: u32) -> Result<()> {
+    process::signal(-(pid as Pid), Signal::KILL).map_err(|_| sup_error!(Error::SignalFailed))
+}
+
+// Hooks run in their own process group so that they can be killed along with any processes they
+// started, without also signalling the Supervisor.
+fn owned_pgid() -> result::Result<(), io::Error> {
+    unsafe {
+        libc::setpgid(0, 0);
+    }
+    Ok(())
+}
@@ -13,12 +13,15 @@
 // limitations under the License.
 
 use std::ffi::OsStr;
+use std::process::{Command, Stdio};
 
 use hcore::os::process::windows_child::Child;
 
-use error::Result;
+use error::{Error, Result};
 use manager::service::Pkg;
 
+static LOGKEY: &'static str = "EX";
+
 pub fn run<T, S>(path: S, pkg: &Pkg, svc_encrypted_password: Option<T>) -> Result<Child>
 where
     T: ToString,
@@ -34,3 +37,42 @@ where
         svc_encrypted_password,
     )?)
 }
+
+/// Run a program with the given arguments as the service's user.
+pub fn run_command<T, S, A>(
+    path: S,
+    args: &[A],
+    pkg: &Pkg,
+    svc_encrypted_password: Option<T>,
+) -> Result<Child>
+where
+    T: ToString,
+    S: AsRef<OsStr>,
+    A: AsRef<OsStr>,
+{
+    let args: Vec<String> = args
+        .iter()
+        .map(|a| a.as_ref().to_string_lossy().into_owned())
+        .collect();
+    Ok(Child::spawn(
+        &path.as_ref().to_string_lossy(),
+        args.iter().map(String::as_str).collect(),
+        &pkg.env,
+        &pkg.svc_user,
+        svc_encrypted_password,
+    )?)
+}
+
+/// Forcefully terminate the process with the given pid, along with any processes it started.
+pub fn kill(pid: u32) -> Result<()> {
+    let status = Command::new("taskkill.exe")
+        .args(&["/F", "/T", "/PID", &pid.to_string()])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(sup_error!(Error::SignalFailed))
+    }
+}